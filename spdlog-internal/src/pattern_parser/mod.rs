@@ -64,6 +64,8 @@ pub enum BuiltInFormatterInner {
     TzOffset,
     #[strum(serialize = "unix_timestamp")]
     UnixTimestamp,
+    #[strum(serialize = "rfc3339")]
+    Rfc3339,
     #[strum(serialize = "full")]
     Full,
     #[strum(serialize = "level")]
@@ -86,10 +88,24 @@ pub enum BuiltInFormatterInner {
     LoggerName,
     #[strum(serialize = "payload")]
     Payload,
+    #[strum(serialize = "code")]
+    ErrorCode,
+    #[strum(serialize = "bytes")]
+    BytesHex,
+    #[strum(serialize = "bytes_base64")]
+    BytesBase64,
+    #[strum(serialize = "seq")]
+    Sequence,
+    #[strum(serialize = "global_seq")]
+    GlobalSequence,
     #[strum(serialize = "pid")]
     ProcessId,
     #[strum(serialize = "tid")]
     ThreadId,
+    #[strum(serialize = "thread_name")]
+    ThreadName,
+    #[strum(serialize = "fields")]
+    Fields,
     #[strum(serialize = "eol")]
     Eol,
 }
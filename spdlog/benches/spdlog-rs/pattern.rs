@@ -172,6 +172,7 @@ bench_patterns! {
     (time, "{time}"),
     (tz_offset, "{tz_offset}"),
     (unix_timestamp, "{unix_timestamp}"),
+    (rfc3339, "{rfc3339}"),
     (full, "{full}"),
     (level, "{level}"),
     (level_short, "{level_short}"),
@@ -137,3 +137,15 @@ fn bench_5_level_off(bencher: &mut Bencher) {
 
     bencher.iter(|| info!(logger: logger, bench_log_message!()))
 }
+
+#[bench]
+fn bench_6_write_sink(bencher: &mut Bencher) {
+    // Writes to nowhere, so that formatting dominates the cost.
+    let sink: Arc<dyn Sink> = Arc::new(
+        WriteSink::builder()
+            .target(std::io::sink())
+            .build()
+            .unwrap(),
+    );
+    bench_any(bencher, Mode::Sync, sink);
+}
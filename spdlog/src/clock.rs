@@ -0,0 +1,214 @@
+//! Provides clocks used as the source of time.
+//!
+//! By default, the crate reads the system time. A [`Clock`] can be injected
+//! into a [`Logger`] (which stamps records, so it is also the time seen by
+//! formatters and time-based rotation), the periodic flusher of the logger,
+//! and [`RotatingFileSink`]. With a [`ManualClock`], time-dependent behavior
+//! can be tested deterministically without sleeping. With a
+//! [`MonotonicClock`], intervals between records stay correct even if the
+//! system time is adjusted.
+//!
+//! # Examples
+//!
+//! ```
+//! use std::{sync::Arc, time::Duration};
+//!
+//! use spdlog::{clock::ManualClock, prelude::*};
+//!
+//! # fn main() -> Result<(), spdlog::Error> {
+//! let clock = Arc::new(ManualClock::new(std::time::UNIX_EPOCH));
+//! let logger = Logger::builder().clock(clock.clone()).build()?;
+//!
+//! info!(logger: logger, "at the epoch");
+//! clock.advance(Duration::from_secs(60));
+//! info!(logger: logger, "one minute after the epoch");
+//! # Ok(()) }
+//! ```
+//!
+//! [`Logger`]: crate::Logger
+//! [`RotatingFileSink`]: crate::sink::RotatingFileSink
+
+use std::time::{Duration, Instant, SystemTime};
+
+use crate::sync::*;
+
+/// Represents a source of time.
+pub trait Clock: Send + Sync {
+    /// Gets the current time.
+    #[must_use]
+    fn now(&self) -> SystemTime;
+
+    /// Returns `true` if the clock advances along with real time.
+    ///
+    /// Periodic tasks driven by a clock that does not advance along with real
+    /// time poll it frequently, rather than sleeping for a whole period.
+    ///
+    /// The default implementation returns `true`.
+    #[must_use]
+    fn is_real_time(&self) -> bool {
+        true
+    }
+}
+
+/// A clock reading the system time.
+///
+/// This is the clock used if no other clock is specified.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> SystemTime {
+        SystemTime::now()
+    }
+}
+
+/// A clock advancing monotonically from the system time at which it was
+/// constructed.
+///
+/// The system time may jump backwards or forwards, e.g. when it is stepped by
+/// NTP or changed manually. This clock is anchored to the system time once,
+/// and then advances by a monotonic clock ([`Instant`]), so the difference
+/// between the times of two records is always the actual time elapsed between
+/// them.
+///
+/// The trade-off is that this clock drifts away from the system time if the
+/// system time is adjusted after the anchoring, as the adjustments are never
+/// observed. [`MonotonicClock::reanchor`] can be called to catch up.
+///
+/// # Examples
+///
+/// ```
+/// use std::sync::Arc;
+///
+/// use spdlog::{clock::MonotonicClock, prelude::*};
+///
+/// # fn main() -> Result<(), spdlog::Error> {
+/// let logger = Logger::builder()
+///     .clock(Arc::new(MonotonicClock::new()))
+///     .build()?;
+/// # Ok(()) }
+/// ```
+#[derive(Debug)]
+pub struct MonotonicClock {
+    anchor: RwLock<(SystemTime, Instant)>,
+}
+
+impl MonotonicClock {
+    /// Constructs a `MonotonicClock` anchored to the current system time.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            anchor: RwLock::new((SystemTime::now(), Instant::now())),
+        }
+    }
+
+    /// Gets the system time the clock is anchored to.
+    #[must_use]
+    pub fn anchor(&self) -> SystemTime {
+        self.anchor.read_expect().0
+    }
+
+    /// Anchors the clock to the current system time again.
+    ///
+    /// The time of the clock jumps along with any adjustment of the system
+    /// time made since the previous anchoring.
+    pub fn reanchor(&self) {
+        *self.anchor.write_expect() = (SystemTime::now(), Instant::now());
+    }
+}
+
+impl Default for MonotonicClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clock for MonotonicClock {
+    fn now(&self) -> SystemTime {
+        let (system_time, instant) = *self.anchor.read_expect();
+        system_time + instant.elapsed()
+    }
+}
+
+/// A clock whose time only changes when it is set or advanced manually.
+///
+/// This is intended for testing.
+#[derive(Debug)]
+pub struct ManualClock {
+    now: Mutex<SystemTime>,
+}
+
+impl ManualClock {
+    /// Constructs a `ManualClock` starting at the given time.
+    #[must_use]
+    pub fn new(now: SystemTime) -> Self {
+        Self {
+            now: Mutex::new(now),
+        }
+    }
+
+    /// Sets the current time.
+    pub fn set(&self, now: SystemTime) {
+        *self.now.lock_expect() = now;
+    }
+
+    /// Advances the current time by the given duration.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the resulting time overflows.
+    pub fn advance(&self, duration: Duration) {
+        let mut now = self.now.lock_expect();
+        *now = now
+            .checked_add(duration)
+            .expect("ManualClock: time overflowed");
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> SystemTime {
+        *self.now.lock_expect()
+    }
+
+    fn is_real_time(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::UNIX_EPOCH;
+
+    use super::*;
+
+    #[test]
+    fn manual_clock() {
+        let clock = ManualClock::new(UNIX_EPOCH);
+        assert_eq!(clock.now(), UNIX_EPOCH);
+        assert!(!clock.is_real_time());
+
+        clock.advance(Duration::from_secs(10));
+        assert_eq!(clock.now(), UNIX_EPOCH + Duration::from_secs(10));
+
+        clock.set(UNIX_EPOCH + Duration::from_secs(3));
+        assert_eq!(clock.now(), UNIX_EPOCH + Duration::from_secs(3));
+
+        assert!(SystemClock.is_real_time());
+    }
+
+    #[test]
+    fn monotonic_clock() {
+        let clock = MonotonicClock::new();
+        assert!(clock.is_real_time());
+
+        let first = clock.now();
+        assert!(first >= clock.anchor());
+        std::thread::sleep(Duration::from_millis(10));
+        let second = clock.now();
+        assert!(second.duration_since(first).unwrap() >= Duration::from_millis(10));
+
+        let anchor = clock.anchor();
+        clock.reanchor();
+        assert!(clock.anchor() >= anchor);
+    }
+}
@@ -0,0 +1,1089 @@
+//! Provides loading log4rs-style YAML configurations.
+//!
+//! This allows teams migrating from `log4rs` to reuse their configurations with
+//! minimal edits. The configuration is mapped as follows:
+//!
+//! - Appenders are mapped to sinks. Supported kinds are `console` (to
+//!   [`StdStreamSink`]), `file` (to [`FileSink`]) and `rolling_file` (to
+//!   [`RotatingFileSink`]), as well as kinds registered with [`register_sink`],
+//!   whose factories receive all fields of the appender as parameters. A
+//!   `threshold` filter is mapped to the level filter of the sink. Appender
+//!   names are sink names for [`init_env_sink_levels`], whose levels override
+//!   the thresholds.
+//!
+//! - Encoders are mapped to formatters. Supported kinds are `pattern` (to
+//!   [`PatternFormatter`] with a [`RuntimePattern`] translated from the log4rs
+//!   pattern syntax) and `json` (to [`JsonFormatter`], requires crate feature
+//!   `serde_json`), as well as kinds registered with [`register_formatter`]. An
+//!   encoder is either a map of parameters, whose `kind` defaults to `pattern`,
+//!   or just the name of a kind (e.g. `encoder: json`). If an appender has no
+//!   encoder, the default formatter of the sink is used.
+//!
+//! - `root` is mapped to the default logger, and each entry of `loggers` is
+//!   mapped to a named logger. A logger inherits the level of its nearest
+//!   configured ancestor (separated by `::`) if it doesn't specify one, and
+//!   also writes to the appenders of the ancestor unless `additive` is `false`.
+//!
+//! For rolling file appenders, the `size` and `time` triggers are supported,
+//! and the `count` of a `fixed_window` roller limits the number of archived
+//! files. The naming of archived files follows [`RotatingFileSink`], the
+//! `pattern` of rollers is ignored.
+//!
+//! Configurations are validated against the schema before any sink is built.
+//! Unknown keys, unknown values of enumerations (e.g. levels and kinds) and
+//! values of wrong types are reported as [`LoadConfigError::Schema`] with
+//! their lines and columns. Parameters of registered kinds are left to their
+//! factories.
+//!
+//! # Examples
+//!
+//! ```
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let loggers = spdlog::config::log4rs::from_yaml_str(
+//!     r#"
+//! appenders:
+//!   stdout:
+//!     kind: console
+//!     encoder:
+//!       pattern: "{d(%Y-%m-%d %H:%M:%S)} {h({l})} {t} - {m}{n}"
+//! root:
+//!   level: info
+//!   appenders:
+//!     - stdout
+//! loggers:
+//!   app::backend::db:
+//!     level: warn
+//! "#,
+//! )?;
+//!
+//! loggers.install();
+//! # Ok(()) }
+//! ```
+//!
+//! [`StdStreamSink`]: crate::sink::StdStreamSink
+//! [`FileSink`]: crate::sink::FileSink
+//! [`RotatingFileSink`]: crate::sink::RotatingFileSink
+//! [`PatternFormatter`]: crate::formatter::PatternFormatter
+//! [`RuntimePattern`]: crate::formatter::RuntimePattern
+//! [`JsonFormatter`]: crate::formatter::JsonFormatter
+//! [`register_sink`]: super::register_sink
+//! [`register_formatter`]: super::register_formatter
+//! [`init_env_sink_levels`]: crate::init_env_sink_levels
+//! [`LoadConfigError::Schema`]: crate::error::LoadConfigError::Schema
+
+use std::{
+    collections::HashMap,
+    fs,
+    iter::Peekable,
+    path::{Path, PathBuf},
+    result::Result as StdResult,
+    str::Chars,
+    time::Duration,
+};
+
+use serde::Deserialize;
+
+mod schema;
+
+use super::{registry, Loggers, Params};
+use crate::{
+    env_level,
+    error::LoadConfigError,
+    formatter::{Formatter, PatternFormatter, RuntimePattern},
+    sink::{FileSink, RotatingFileSink, RotationPolicy, Sink, StdStream, StdStreamSink},
+    sync::*,
+    Error, Level, LevelFilter, Logger, Result,
+};
+
+/// Builds loggers from a log4rs-style YAML configuration string.
+///
+/// See the [module level documentation](self) for the mapping details.
+pub fn from_yaml_str(yaml: &str) -> Result<Loggers> {
+    from_yaml(yaml, None)
+}
+
+/// Builds loggers from a log4rs-style YAML configuration file.
+///
+/// See the [module level documentation](self) for the mapping details.
+pub fn from_yaml_file(path: impl AsRef<Path>) -> Result<Loggers> {
+    let path = path.as_ref();
+    let yaml = fs::read_to_string(path).map_err(Error::OpenFile)?;
+    from_yaml(&yaml, Some(path))
+}
+
+fn from_yaml(yaml: &str, file: Option<&Path>) -> Result<Loggers> {
+    let value: serde_yaml::Value = serde_yaml::from_str(yaml)
+        .map_err(|err| Error::LoadConfig(LoadConfigError::Parse(err.to_string())))?;
+
+    serde_yaml::from_str::<schema::Config>(yaml).map_err(|err| super::schema_error(err, file))?;
+
+    let config: RawConfig = serde_yaml::from_value(value)
+        .map_err(|err| Error::LoadConfig(LoadConfigError::Parse(err.to_string())))?;
+    config.build()
+}
+
+#[derive(Deserialize)]
+struct RawConfig {
+    #[serde(default)]
+    appenders: HashMap<String, serde_yaml::Value>,
+    #[serde(default)]
+    root: RawRoot,
+    #[serde(default)]
+    loggers: HashMap<String, RawLogger>,
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+enum RawAppender {
+    Console {
+        #[serde(default)]
+        target: RawConsoleTarget,
+        encoder: Option<RawEncoder>,
+        #[serde(default)]
+        filters: Vec<RawFilter>,
+    },
+    File {
+        path: PathBuf,
+        #[serde(default = "default_true")]
+        append: bool,
+        encoder: Option<RawEncoder>,
+        #[serde(default)]
+        filters: Vec<RawFilter>,
+    },
+    RollingFile {
+        path: PathBuf,
+        #[serde(default = "default_true")]
+        append: bool,
+        encoder: Option<RawEncoder>,
+        #[serde(default)]
+        filters: Vec<RawFilter>,
+        policy: RawPolicy,
+    },
+}
+
+// Fields handled for all appenders, including registered kinds.
+#[derive(Deserialize)]
+struct RawAppenderCommon {
+    kind: String,
+    encoder: Option<RawEncoder>,
+    #[serde(default)]
+    filters: Vec<RawFilter>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum RawConsoleTarget {
+    Stdout,
+    Stderr,
+}
+
+impl Default for RawConsoleTarget {
+    fn default() -> Self {
+        Self::Stdout
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RawEncoder {
+    Kind(String),
+    Params(serde_yaml::Mapping),
+}
+
+#[derive(Deserialize)]
+struct RawPatternEncoder {
+    pattern: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct RawFilter {
+    kind: String,
+    level: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct RawPolicy {
+    kind: Option<String>,
+    trigger: RawTrigger,
+    roller: Option<RawRoller>,
+}
+
+#[derive(Deserialize)]
+struct RawTrigger {
+    kind: String,
+    limit: Option<RawSize>,
+    interval: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+pub(super) enum RawSize {
+    Bytes(u64),
+    Text(String),
+}
+
+#[derive(Deserialize)]
+struct RawRoller {
+    kind: String,
+    count: Option<usize>,
+}
+
+#[derive(Default, Deserialize)]
+struct RawRoot {
+    level: Option<String>,
+    #[serde(default)]
+    appenders: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct RawLogger {
+    level: Option<String>,
+    #[serde(default)]
+    appenders: Vec<String>,
+    #[serde(default = "default_true")]
+    additive: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn invalid(message: impl Into<String>) -> Error {
+    Error::LoadConfig(LoadConfigError::Invalid(message.into()))
+}
+
+impl RawConfig {
+    fn build(self) -> Result<Loggers> {
+        let sinks = self
+            .appenders
+            .into_iter()
+            .map(|(name, appender)| {
+                let sink = build_appender(appender)
+                    .map_err(|err| invalid(format!("appender '{}': {}", name, err)))?;
+                if let Some(level_filter) = env_level::sink_level(&name) {
+                    sink.set_level_filter(level_filter);
+                }
+                Ok((name, sink))
+            })
+            .collect::<Result<HashMap<_, _>>>()?;
+
+        let find_sinks = |appenders: &[String]| {
+            appenders
+                .iter()
+                .map(|name| {
+                    sinks
+                        .get(name)
+                        .cloned()
+                        .ok_or_else(|| invalid(format!("appender '{}' is not defined", name)))
+                })
+                .collect::<Result<Vec<_>>>()
+        };
+
+        // log4rs uses `debug` as the default level of the root logger.
+        let root_level = parse_level_filter(self.root.level.as_deref())?
+            .unwrap_or(LevelFilter::MoreSevereEqual(Level::Debug));
+        let root_sinks = find_sinks(&self.root.appenders)?;
+        let default_logger = Logger::builder()
+            .level_filter(root_level)
+            .sinks(root_sinks.clone())
+            .build()?;
+
+        // Resolve parents before children.
+        let mut loggers = self.loggers.into_iter().collect::<Vec<_>>();
+        loggers.sort_by(|(lhs, _), (rhs, _)| lhs.cmp(rhs));
+
+        let mut named: Vec<(String, Arc<Logger>)> = vec![];
+        for (name, logger) in loggers {
+            let (parent_level, parent_sinks) = named
+                .iter()
+                .filter(|(parent, _)| is_ancestor(parent, &name))
+                .max_by_key(|(parent, _)| parent.len())
+                .map(|(_, parent)| (parent.level_filter(), parent.sinks().to_vec()))
+                .unwrap_or_else(|| (root_level, root_sinks.clone()));
+
+            let level = parse_level_filter(logger.level.as_deref())
+                .map_err(|err| invalid(format!("logger '{}': {}", name, err)))?
+                .unwrap_or(parent_level);
+            let mut sinks = find_sinks(&logger.appenders)?;
+            if logger.additive {
+                sinks.extend(parent_sinks);
+            }
+
+            let built = Logger::builder()
+                .name(&name)
+                .level_filter(level)
+                .sinks(sinks)
+                .build()?;
+            named.push((name, Arc::new(built)));
+        }
+
+        Ok(Loggers::new(Arc::new(default_logger), named))
+    }
+}
+
+#[must_use]
+fn is_ancestor(parent: &str, child: &str) -> bool {
+    match child.strip_prefix(parent) {
+        Some(rest) => rest.starts_with("::"),
+        None => false,
+    }
+}
+
+fn parse_level_filter(level: Option<&str>) -> Result<Option<LevelFilter>> {
+    level
+        .map(|level| {
+            LevelFilter::from_str_for_env(level)
+                .ok_or_else(|| invalid(format!("unknown level '{}'", level)))
+        })
+        .transpose()
+}
+
+fn build_appender(appender: serde_yaml::Value) -> StdResult<Arc<dyn Sink>, String> {
+    let common: RawAppenderCommon =
+        serde_yaml::from_value(appender.clone()).map_err(|err| err.to_string())?;
+
+    let params = Params::new(appender);
+    match registry::build_sink(&common.kind, &params) {
+        Some(sink) => {
+            let sink = sink.map_err(factory_error)?;
+            sink.set_level_filter(build_level_filter(&common.filters)?);
+            if let Some(encoder) = &common.encoder {
+                sink.set_formatter(build_formatter(encoder)?);
+            }
+            Ok(sink)
+        }
+        None => serde_yaml::from_value::<RawAppender>(params.into_value())
+            .map_err(|err| err.to_string())?
+            .build(),
+    }
+}
+
+impl RawAppender {
+    fn build(self) -> StdResult<Arc<dyn Sink>, String> {
+        let (encoder, filters) = match &self {
+            Self::Console {
+                encoder, filters, ..
+            }
+            | Self::File {
+                encoder, filters, ..
+            }
+            | Self::RollingFile {
+                encoder, filters, ..
+            } => (encoder.as_ref().map(build_formatter).transpose()?, filters),
+        };
+        let level_filter = build_level_filter(filters)?;
+
+        let sink: Arc<dyn Sink> = match self {
+            Self::Console { target, .. } => {
+                let std_stream = match target {
+                    RawConsoleTarget::Stdout => StdStream::Stdout,
+                    RawConsoleTarget::Stderr => StdStream::Stderr,
+                };
+                Arc::new(
+                    StdStreamSink::builder()
+                        .std_stream(std_stream)
+                        .level_filter(level_filter)
+                        .build()
+                        .map_err(|err| err.to_string())?,
+                )
+            }
+            Self::File { path, append, .. } => Arc::new(
+                FileSink::builder()
+                    .path(path)
+                    .truncate(!append)
+                    .level_filter(level_filter)
+                    .build()
+                    .map_err(|err| err.to_string())?,
+            ),
+            Self::RollingFile {
+                path,
+                append,
+                policy,
+                ..
+            } => {
+                let (rotation_policy, max_files) = policy.build()?;
+                Arc::new(
+                    RotatingFileSink::builder()
+                        .base_path(path)
+                        .rotation_policy(rotation_policy)
+                        .max_files(max_files)
+                        .rotate_on_open(!append)
+                        .level_filter(level_filter)
+                        .build()
+                        .map_err(|err| err.to_string())?,
+                )
+            }
+        };
+
+        if let Some(formatter) = encoder {
+            sink.set_formatter(formatter);
+        }
+        Ok(sink)
+    }
+}
+
+fn build_level_filter(filters: &[RawFilter]) -> StdResult<LevelFilter, String> {
+    let mut level_filter = LevelFilter::All;
+    for filter in filters {
+        if filter.kind != "threshold" {
+            return Err(format!("unsupported filter kind '{}'", filter.kind));
+        }
+        let level = filter
+            .level
+            .as_deref()
+            .ok_or_else(|| "filter 'threshold' requires 'level'".to_string())?;
+        level_filter = LevelFilter::from_str_for_env(level)
+            .ok_or_else(|| format!("unknown level '{}'", level))?;
+    }
+    Ok(level_filter)
+}
+
+fn factory_error(err: Error) -> String {
+    match err {
+        Error::LoadConfig(LoadConfigError::Invalid(message)) => message,
+        err => err.to_string(),
+    }
+}
+
+fn build_formatter(encoder: &RawEncoder) -> StdResult<Box<dyn Formatter>, String> {
+    let (kind, params) = match encoder {
+        RawEncoder::Kind(kind) => (kind.as_str(), serde_yaml::Mapping::new()),
+        RawEncoder::Params(params) => {
+            let kind = match params.get(&serde_yaml::Value::String("kind".to_string())) {
+                None => "pattern",
+                Some(serde_yaml::Value::String(kind)) => kind.as_str(),
+                Some(_) => return Err("encoder kind must be a string".to_string()),
+            };
+            (kind, params.clone())
+        }
+    };
+    let params = Params::new(serde_yaml::Value::Mapping(params));
+
+    if let Some(formatter) = registry::build_formatter(kind, &params) {
+        return formatter.map_err(factory_error);
+    }
+
+    match kind {
+        "pattern" => {
+            let encoder: RawPatternEncoder =
+                serde_yaml::from_value(params.into_value()).map_err(|err| err.to_string())?;
+            // The default pattern of log4rs.
+            let pattern = encoder.pattern.as_deref().unwrap_or("{d} {l} {t} - {m}{n}");
+            let template = translate_pattern(pattern)?;
+            let pattern = RuntimePattern::new(&template).map_err(|err| err.to_string())?;
+            Ok(Box::new(PatternFormatter::new(pattern)))
+        }
+        "json" => {
+            cfg_if::cfg_if! {
+                if #[cfg(feature = "serde_json")] {
+                    Ok(Box::new(crate::formatter::JsonFormatter::new()))
+                } else {
+                    Err("encoder 'json' requires crate feature 'serde_json'".to_string())
+                }
+            }
+        }
+        kind => Err(format!("unsupported encoder kind '{}'", kind)),
+    }
+}
+
+impl RawPolicy {
+    fn build(self) -> StdResult<(RotationPolicy, usize), String> {
+        if let Some(kind) = self.kind.as_deref() {
+            if kind != "compound" {
+                return Err(format!("unsupported policy kind '{}'", kind));
+            }
+        }
+
+        let rotation_policy = match self.trigger.kind.as_str() {
+            "size" => {
+                let limit = self
+                    .trigger
+                    .limit
+                    .ok_or_else(|| "trigger 'size' requires 'limit'".to_string())?;
+                RotationPolicy::FileSize(parse_size(limit)?)
+            }
+            "time" => {
+                let interval = self.trigger.interval.as_deref().unwrap_or("1 day");
+                parse_interval(interval)?
+            }
+            kind => return Err(format!("unsupported trigger kind '{}'", kind)),
+        };
+
+        let max_files = match self.roller {
+            None => 0,
+            Some(roller) => match roller.kind.as_str() {
+                // `count` is the number of archived files, excluding the current one.
+                "fixed_window" => roller.count.map_or(0, |count| count + 1),
+                "delete" => 1,
+                kind => return Err(format!("unsupported roller kind '{}'", kind)),
+            },
+        };
+
+        Ok((rotation_policy, max_files))
+    }
+}
+
+pub(super) fn parse_size(size: RawSize) -> StdResult<u64, String> {
+    let text = match size {
+        RawSize::Bytes(bytes) => return Ok(bytes),
+        RawSize::Text(text) => text,
+    };
+
+    let (number, unit) = split_number_unit(&text);
+    let number = number
+        .parse::<u64>()
+        .map_err(|_| format!("invalid size '{}'", text))?;
+    let multiplier: u64 = match unit.to_ascii_lowercase().as_str() {
+        "" | "b" => 1,
+        "kb" | "kib" => 1024,
+        "mb" | "mib" => 1024 * 1024,
+        "gb" | "gib" => 1024 * 1024 * 1024,
+        "tb" | "tib" => 1024 * 1024 * 1024 * 1024,
+        _ => return Err(format!("invalid size unit in '{}'", text)),
+    };
+    number
+        .checked_mul(multiplier)
+        .ok_or_else(|| format!("invalid size '{}'", text))
+}
+
+pub(super) fn parse_interval(text: &str) -> StdResult<RotationPolicy, String> {
+    let (number, unit) = split_number_unit(text);
+    let number = number
+        .parse::<u64>()
+        .map_err(|_| format!("invalid interval '{}'", text))?;
+    let seconds_per_unit: u64 = match unit.to_ascii_lowercase().trim_end_matches('s') {
+        "second" => 1,
+        "minute" => 60,
+        "hour" => 60 * 60,
+        "day" => 24 * 60 * 60,
+        "week" => 7 * 24 * 60 * 60,
+        _ => return Err(format!("invalid interval unit in '{}'", text)),
+    };
+    let seconds = number
+        .checked_mul(seconds_per_unit)
+        .ok_or_else(|| format!("invalid interval '{}'", text))?;
+
+    Ok(match (number, seconds_per_unit) {
+        (1, 3600) => RotationPolicy::Hourly,
+        (1, 86400) => RotationPolicy::Daily { hour: 0, minute: 0 },
+        _ => RotationPolicy::Period(Duration::from_secs(seconds)),
+    })
+}
+
+#[must_use]
+fn split_number_unit(text: &str) -> (&str, &str) {
+    let text = text.trim();
+    let index = text
+        .find(|ch: char| !ch.is_ascii_digit())
+        .unwrap_or(text.len());
+    (&text[..index], text[index..].trim())
+}
+
+// Translates a log4rs pattern into a `spdlog-rs` runtime pattern template.
+fn translate_pattern(pattern: &str) -> StdResult<String, String> {
+    let mut output = String::new();
+    let mut chars = pattern.chars().peekable();
+
+    while let Some(ch) = chars.next() {
+        match ch {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                output.push_str("{{");
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                output.push_str("}}");
+            }
+            '{' => translate_formatter(&mut chars, &mut output)?,
+            '}' => return Err(format!("unmatched '}}' in pattern '{}'", pattern)),
+            '\\' => match chars.next() {
+                Some(escaped) => push_literal(&mut output, escaped),
+                None => return Err(format!("trailing '\\' in pattern '{}'", pattern)),
+            },
+            _ => output.push(ch),
+        }
+    }
+
+    Ok(output)
+}
+
+fn translate_formatter(
+    chars: &mut Peekable<Chars<'_>>,
+    output: &mut String,
+) -> StdResult<(), String> {
+    let mut name = String::new();
+    while let Some(&ch) = chars.peek() {
+        if ch.is_ascii_alphanumeric() || ch == '_' {
+            name.push(ch);
+            chars.next();
+        } else {
+            break;
+        }
+    }
+
+    let mut args = vec![];
+    while chars.peek() == Some(&'(') {
+        chars.next();
+        let mut arg = String::new();
+        let mut depth = 1;
+        loop {
+            let ch = chars
+                .next()
+                .ok_or_else(|| format!("unclosed '(' in formatter '{}'", name))?;
+            match ch {
+                '(' => depth += 1,
+                ')' => {
+                    depth -= 1;
+                    if depth == 0 {
+                        break;
+                    }
+                }
+                '\\' => {
+                    // Keep escapes as is, they are handled when translating the argument.
+                    arg.push(ch);
+                    match chars.next() {
+                        Some(escaped) => arg.push(escaped),
+                        None => return Err(format!("trailing '\\' in formatter '{}'", name)),
+                    }
+                    continue;
+                }
+                _ => {}
+            }
+            arg.push(ch);
+        }
+        args.push(arg);
+    }
+
+    // Format specs (e.g. `{l:<5}`) are not supported by runtime patterns, ignore
+    // them.
+    if chars.peek() == Some(&':') {
+        while chars.peek().map_or(false, |&ch| ch != '}') {
+            chars.next();
+        }
+    }
+    if chars.next() != Some('}') {
+        return Err(format!("unclosed '{{' in formatter '{}'", name));
+    }
+
+    let placeholder = match name.as_str() {
+        "d" | "date" => {
+            // The second argument of log4rs is the timezone, records are always formatted
+            // in local time here.
+            return match args.first() {
+                Some(format) => translate_date_format(format, output),
+                None => {
+                    output.push_str("{date}T{time}.{nanosecond}{tz_offset}");
+                    Ok(())
+                }
+            };
+        }
+        "h" | "highlight" => {
+            let inner = args
+                .first()
+                .ok_or_else(|| format!("formatter '{}' requires an argument", name))?;
+            output.push_str("{^");
+            output.push_str(&translate_pattern(inner)?);
+            output.push('}');
+            return Ok(());
+        }
+        "l" | "level" => "{level}",
+        "m" | "message" => "{payload}",
+        "n" => "{eol}",
+        "t" | "target" => "{logger}",
+        "M" | "module" => "{module_path}",
+        "f" | "file" => "{file}",
+        "L" | "line" => "{line}",
+        "I" | "thread_id" | "T" | "thread" => "{tid}",
+        "P" | "pid" => "{pid}",
+        _ => return Err(format!("unsupported formatter '{}'", name)),
+    };
+    output.push_str(placeholder);
+    Ok(())
+}
+
+// Translates a chrono format string into a `spdlog-rs` runtime pattern
+// template.
+fn translate_date_format(format: &str, output: &mut String) -> StdResult<(), String> {
+    let mut chars = format.chars().peekable();
+
+    while let Some(ch) = chars.next() {
+        if ch != '%' {
+            push_literal(output, ch);
+            continue;
+        }
+
+        let mut spec = String::new();
+        loop {
+            let ch = chars
+                .next()
+                .ok_or_else(|| format!("incomplete specifier in date format '{}'", format))?;
+            spec.push(ch);
+            if ch.is_ascii_alphabetic() || ch == '%' {
+                break;
+            }
+        }
+
+        let placeholder = match spec.as_str() {
+            "Y" => "{year}",
+            "y" => "{year_short}",
+            "m" => "{month}",
+            "d" => "{day}",
+            "H" => "{hour}",
+            "I" => "{hour_12}",
+            "M" => "{minute}",
+            "S" => "{second}",
+            "p" => "{am_pm}",
+            "b" | "h" => "{month_name}",
+            "B" => "{month_name_full}",
+            "a" => "{weekday_name}",
+            "A" => "{weekday_name_full}",
+            "D" => "{date_short}",
+            "F" => "{date}",
+            "T" => "{time}",
+            "R" => "{time_short}",
+            "r" => "{time_12}",
+            "z" | ":z" => "{tz_offset}",
+            "s" => "{unix_timestamp}",
+            "f" | "9f" => "{nanosecond}",
+            "3f" => "{millisecond}",
+            "6f" => "{microsecond}",
+            ".f" | ".9f" => ".{nanosecond}",
+            ".3f" => ".{millisecond}",
+            ".6f" => ".{microsecond}",
+            "%" => "%",
+            _ => {
+                return Err(format!(
+                    "unsupported specifier '%{}' in date format '{}'",
+                    spec, format
+                ))
+            }
+        };
+        output.push_str(placeholder);
+    }
+
+    Ok(())
+}
+
+fn push_literal(output: &mut String, ch: char) {
+    match ch {
+        '{' => output.push_str("{{"),
+        '}' => output.push_str("}}"),
+        _ => output.push(ch),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{info, test_sink::CaptureSink, test_utils::*, warn};
+
+    #[test]
+    fn translate() {
+        let cases = [
+            (
+                "{d} {l} {t} - {m}{n}",
+                "{date}T{time}.{nanosecond}{tz_offset} {level} {logger} - {payload}{eol}",
+            ),
+            (
+                "{d(%Y-%m-%d %H:%M:%S%.3f)}",
+                "{year}-{month}-{day} {hour}:{minute}:{second}.{millisecond}",
+            ),
+            (
+                "{d(%F %T)(utc)} [{h({l:<5})}]",
+                "{date} {time} [{^{level}}]",
+            ),
+            (
+                "[{M}:{f}:{L}] {{literal}} \\{ {P}/{T}",
+                "[{module_path}:{file}:{line}] {{literal}} {{ {pid}/{tid}",
+            ),
+            ("{highlight([{level}] {message})}", "{^[{level}] {payload}}"),
+        ];
+        for (pattern, expected) in cases {
+            assert_eq!(translate_pattern(pattern).unwrap(), expected);
+        }
+
+        assert!(translate_pattern("{X}").is_err());
+        assert!(translate_pattern("{d(%Q)}").is_err());
+        assert!(translate_pattern("{l").is_err());
+        assert!(translate_pattern("{d(%Y}").is_err());
+    }
+
+    #[test]
+    fn sizes_and_intervals() {
+        assert_eq!(parse_size(RawSize::Bytes(100)).unwrap(), 100);
+        assert_eq!(
+            parse_size(RawSize::Text("10 kb".into())).unwrap(),
+            10 * 1024
+        );
+        assert_eq!(
+            parse_size(RawSize::Text("2MB".into())).unwrap(),
+            2 * 1024 * 1024
+        );
+        assert!(parse_size(RawSize::Text("2 parsecs".into())).is_err());
+        assert!(parse_size(RawSize::Text("18446744073709551615 kb".into())).is_err());
+        assert!(parse_interval("18446744073709551615 weeks").is_err());
+
+        assert!(matches!(
+            parse_interval("1 day"),
+            Ok(RotationPolicy::Daily { hour: 0, minute: 0 })
+        ));
+        assert!(matches!(
+            parse_interval("1 hour"),
+            Ok(RotationPolicy::Hourly)
+        ));
+        assert!(matches!(
+            parse_interval("30 minutes"),
+            Ok(RotationPolicy::Period(period)) if period == Duration::from_secs(30 * 60)
+        ));
+    }
+
+    #[test]
+    fn load_yaml() {
+        let path = TEST_LOGS_PATH.join("config_log4rs");
+        _ = fs::remove_dir_all(&path);
+        fs::create_dir_all(&path).unwrap();
+
+        let yaml = format!(
+            r#"
+refresh_rate: 30 seconds
+appenders:
+  all:
+    kind: file
+    path: {all:?}
+    append: false
+    encoder:
+      pattern: "{{l}} {{t}} {{m}}{{n}}"
+  db:
+    kind: file
+    path: {db:?}
+    append: false
+    encoder:
+      pattern: "{{l}} {{m}}{{n}}"
+    filters:
+      - kind: threshold
+        level: warn
+root:
+  level: info
+  appenders:
+    - all
+loggers:
+  app::db:
+    level: debug
+    appenders:
+      - db
+  app::db::pool:
+    additive: false
+    appenders:
+      - db
+"#,
+            all = path.join("all.log"),
+            db = path.join("db.log"),
+        );
+        let loggers = from_yaml_str(&yaml).unwrap();
+
+        let root = loggers.default_logger();
+        let db = loggers.get("app::db").unwrap();
+        let pool = loggers.get("app::db::pool").unwrap();
+        assert!(loggers.get("app").is_none());
+        assert_eq!(loggers.iter().count(), 2);
+
+        assert_eq!(
+            root.level_filter(),
+            LevelFilter::MoreSevereEqual(Level::Info)
+        );
+        assert_eq!(
+            db.level_filter(),
+            LevelFilter::MoreSevereEqual(Level::Debug)
+        );
+        assert_eq!(
+            pool.level_filter(),
+            LevelFilter::MoreSevereEqual(Level::Debug)
+        );
+
+        crate::debug!(logger: root, "root debug");
+        crate::info!(logger: root, "root info");
+        crate::debug!(logger: db, "db debug");
+        crate::warn!(logger: db, "db warn");
+        crate::error!(logger: pool, "pool error");
+        for logger in [&root, &db, &pool] {
+            logger.flush();
+        }
+
+        let read = |name| fs::read_to_string(path.join(name)).unwrap();
+        let eol = crate::__EOL;
+        assert_eq!(
+            read("all.log"),
+            format!(
+                "info  root info{eol}debug app::db db debug{eol}warn app::db db warn{eol}",
+                eol = eol
+            )
+        );
+        assert_eq!(
+            read("db.log"),
+            format!("warn db warn{eol}error pool error{eol}", eol = eol)
+        );
+    }
+
+    #[test]
+    fn registered_formatter() {
+        #[derive(Deserialize)]
+        struct TagParams {
+            #[serde(default)]
+            tag: Option<String>,
+        }
+
+        super::super::register_formatter("log4rs_test_tag", |params: &Params| {
+            let params: TagParams = params.deserialize()?;
+            let template = format!("[{}] {{payload}}", params.tag.as_deref().unwrap_or("-"));
+            Ok(Box::new(PatternFormatter::new(
+                RuntimePattern::new(&template).unwrap(),
+            )))
+        });
+
+        let format = |encoder: &str| {
+            let encoder: RawEncoder = serde_yaml::from_str(encoder).unwrap();
+            let formatter = build_formatter(&encoder)?;
+            let sink = Arc::new(StringSink::new());
+            sink.set_formatter(formatter);
+            let logger = build_test_logger(|b| b.sink(sink.clone()));
+            info!(logger: logger, "hello");
+            Ok::<_, String>(sink.clone_string())
+        };
+
+        assert_eq!(format("log4rs_test_tag").unwrap(), "[-] hello");
+        assert_eq!(
+            format("{ kind: log4rs_test_tag, tag: app }").unwrap(),
+            "[app] hello"
+        );
+        assert_eq!(format("{ pattern: '{m}' }").unwrap(), "hello");
+        assert_eq!(
+            format("{ kind: log4rs_test_tag, tag: [1] }").unwrap_err(),
+            "invalid type: sequence, expected a string"
+        );
+        assert_eq!(
+            format("{ kind: 1 }").unwrap_err(),
+            "encoder kind must be a string"
+        );
+
+        assert!(super::super::unregister_formatter("log4rs_test_tag"));
+        assert_eq!(
+            format("log4rs_test_tag").unwrap_err(),
+            "unsupported encoder kind 'log4rs_test_tag'"
+        );
+    }
+
+    #[test]
+    fn registered_sink() {
+        #[derive(Deserialize)]
+        struct CaptureParams {
+            label: String,
+        }
+
+        let sink = Arc::new(CaptureSink::new());
+        let registered = sink.clone();
+        super::super::register_sink("log4rs_test_capture", move |params: &Params| {
+            let params: CaptureParams = params.deserialize()?;
+            assert_eq!(params.label, "captured");
+            Ok(registered.clone())
+        });
+
+        let loggers = from_yaml_str(
+            r#"
+appenders:
+  capture:
+    kind: log4rs_test_capture
+    label: captured
+    encoder:
+      pattern: "{m}"
+    filters:
+      - kind: threshold
+        level: warn
+root:
+  appenders:
+    - capture
+"#,
+        )
+        .unwrap();
+        let logger = loggers.default_logger();
+        info!(logger: logger, "filtered");
+        warn!(logger: logger, "kept");
+        assert_eq!(sink.payloads(), ["kept"]);
+
+        let err = match from_yaml_str("appenders:\n  capture:\n    kind: log4rs_test_capture\n") {
+            Err(Error::LoadConfig(LoadConfigError::Invalid(message))) => message,
+            _ => panic!("expected a config error"),
+        };
+        assert_eq!(err, "appender 'capture': missing field `label`");
+
+        assert!(super::super::unregister_sink("log4rs_test_capture"));
+        assert!(from_yaml_str("appenders:\n  capture:\n    kind: log4rs_test_capture\n").is_err());
+    }
+
+    #[test]
+    fn schema() {
+        let check = |yaml: &str| match from_yaml_str(yaml) {
+            Err(Error::LoadConfig(LoadConfigError::Schema(err))) => err.to_string(),
+            _ => panic!("expected a schema error"),
+        };
+
+        assert_eq!(
+            check("roots:\n  level: info\n"),
+            "1:1: unknown field `roots`, expected one of `refresh_rate`, `appenders`, `root`, `loggers`"
+        );
+        assert_eq!(
+            check("appenders:\n  a:\n    kind: console\n    tagret: stdout\n"),
+            "4:5: appenders.a: unknown field `tagret`, expected one of `kind`, `target`, `encoder`, `filters`"
+        );
+        assert_eq!(
+            check("appenders:\n  a:\n    kind: consol\n"),
+            "3:11: appenders.a.kind: unknown appender kind `consol`, expected one of `console`, `file`, `rolling_file` or a registered kind"
+        );
+        assert_eq!(
+            check("appenders:\n  a:\n    kind: console\n    target: stdot\n"),
+            "4:13: appenders.a.target: unknown variant `stdot`, expected `stdout` or `stderr`"
+        );
+        assert_eq!(
+            check("appenders:\n  a:\n    kind: console\n    filters:\n      - kind: threshold\n        level: loud\n"),
+            "6:16: appenders.a.filters[0].level: invalid value: string \"loud\", expected a level, e.g. `info` or `off`"
+        );
+        assert_eq!(
+            check("loggers:\n  app:\n    additive: maybe\n"),
+            "3:15: loggers.app.additive: invalid type: string \"maybe\", expected a boolean"
+        );
+
+        let path = TEST_LOGS_PATH.join("config_log4rs_schema.yaml");
+        fs::create_dir_all(TEST_LOGS_PATH.as_path()).unwrap();
+        fs::write(&path, "root:\n  level: loud\n").unwrap();
+        let err = match from_yaml_file(&path) {
+            Err(Error::LoadConfig(LoadConfigError::Schema(err))) => err,
+            _ => panic!("expected a schema error"),
+        };
+        assert_eq!(err.file(), Some(path.as_path()));
+        assert_eq!((err.line(), err.column()), (2, 10));
+        assert!(err
+            .to_string()
+            .starts_with(&format!("{}:2:10: root.level:", path.display())));
+    }
+
+    #[test]
+    fn invalid_config() {
+        let load = |yaml| match from_yaml_str(yaml) {
+            Err(Error::LoadConfig(err)) => err,
+            _ => panic!("expected a config error"),
+        };
+
+        assert!(matches!(load("root: ["), LoadConfigError::Parse(_)));
+        assert!(matches!(
+            load("root:\n  appenders:\n    - missing\n"),
+            LoadConfigError::Invalid(_)
+        ));
+        assert!(matches!(
+            load("root:\n  level: verbose\n"),
+            LoadConfigError::Schema(_)
+        ));
+        assert!(matches!(
+            load("appenders:\n  a:\n    kind: console\n    encoder:\n      pattern: \"{X}\"\n"),
+            LoadConfigError::Invalid(_)
+        ));
+    }
+}
@@ -0,0 +1,459 @@
+// Validates log4rs-style configurations against the schema before they are
+// built, so that unknown keys, unknown values of enumerations and values of
+// wrong types are reported with their locations in the document.
+//
+// The types are only deserialized from the document text, the locations are
+// lost once the document is converted into `serde_yaml::Value`. Values are
+// checked in visitors where possible, errors returned from them are located at
+// the values themselves rather than at the enclosing mappings.
+
+// Fields are deserialized for validation only.
+#![allow(dead_code)]
+
+use std::{collections::HashMap, fmt};
+
+use serde::{
+    de::{self, DeserializeSeed, Deserializer, IgnoredAny, MapAccess, Visitor},
+    Deserialize,
+};
+
+use crate::{config::registry, LevelFilter};
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(super) struct Config {
+    // Accepted for compatibility, reloading is not supported.
+    #[serde(default)]
+    refresh_rate: Option<IgnoredAny>,
+    #[serde(default)]
+    appenders: HashMap<String, Appender>,
+    #[serde(default)]
+    root: Option<Root>,
+    #[serde(default)]
+    loggers: HashMap<String, Logger>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Root {
+    #[serde(default)]
+    level: Option<Level>,
+    #[serde(default)]
+    appenders: Vec<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Logger {
+    #[serde(default)]
+    level: Option<Level>,
+    #[serde(default)]
+    appenders: Vec<String>,
+    #[serde(default)]
+    additive: Option<bool>,
+}
+
+struct Level;
+
+impl<'de> Deserialize<'de> for Level {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct LevelVisitor;
+
+        impl<'de> Visitor<'de> for LevelVisitor {
+            type Value = Level;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a level, e.g. `info` or `off`")
+            }
+
+            fn visit_str<E>(self, level: &str) -> Result<Level, E>
+            where
+                E: de::Error,
+            {
+                match LevelFilter::from_str_for_env(level) {
+                    Some(_) => Ok(Level),
+                    None => Err(E::invalid_value(de::Unexpected::Str(level), &self)),
+                }
+            }
+        }
+
+        deserializer.deserialize_str(LevelVisitor)
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum ConsoleTarget {
+    Stdout,
+    Stderr,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Filter {
+    kind: FilterKind,
+    level: Level,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum FilterKind {
+    Threshold,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Policy {
+    #[serde(default)]
+    kind: Option<PolicyKind>,
+    trigger: Trigger,
+    #[serde(default)]
+    roller: Option<Roller>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum PolicyKind {
+    Compound,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Trigger {
+    kind: TriggerKind,
+    #[serde(default)]
+    limit: Option<Size>,
+    #[serde(default)]
+    interval: Option<String>,
+    // Accepted for compatibility, ignored by the loader.
+    #[serde(default)]
+    modulate: Option<bool>,
+    #[serde(default)]
+    max_random_delay: Option<IgnoredAny>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum TriggerKind {
+    Size,
+    Time,
+}
+
+// The syntax of sizes is checked by the loader.
+struct Size;
+
+impl<'de> Deserialize<'de> for Size {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct SizeVisitor;
+
+        impl<'de> Visitor<'de> for SizeVisitor {
+            type Value = Size;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a number of bytes, or a size with a unit, e.g. `10 mb`")
+            }
+
+            fn visit_u64<E>(self, _: u64) -> Result<Size, E>
+            where
+                E: de::Error,
+            {
+                Ok(Size)
+            }
+
+            fn visit_str<E>(self, _: &str) -> Result<Size, E>
+            where
+                E: de::Error,
+            {
+                Ok(Size)
+            }
+        }
+
+        deserializer.deserialize_any(SizeVisitor)
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Roller {
+    kind: RollerKind,
+    #[serde(default)]
+    count: Option<usize>,
+    // Accepted for compatibility, ignored by the loader.
+    #[serde(default)]
+    pattern: Option<String>,
+    #[serde(default)]
+    base: Option<usize>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum RollerKind {
+    FixedWindow,
+    Delete,
+}
+
+// Describes a mapping whose `kind` key selects the allowed keys.
+struct Kinds {
+    what: &'static str,
+    // Kinds supported by the loader itself, with all their allowed keys.
+    builtin: &'static [(&'static str, &'static [&'static str])],
+    // Keys allowed for all kinds, including registered ones.
+    common: &'static [&'static str],
+    default_kind: Option<&'static str>,
+    is_registered: fn(&str) -> bool,
+}
+
+enum Kind {
+    Builtin(&'static [&'static str]),
+    Registered,
+}
+
+const APPENDER_KINDS: Kinds = Kinds {
+    what: "appender",
+    builtin: &[
+        ("console", &["kind", "target", "encoder", "filters"]),
+        ("file", &["kind", "path", "append", "encoder", "filters"]),
+        (
+            "rolling_file",
+            &["kind", "path", "append", "encoder", "filters", "policy"],
+        ),
+    ],
+    common: &["encoder", "filters"],
+    default_kind: None,
+    is_registered: registry::is_sink_registered,
+};
+
+const ENCODER_KINDS: Kinds = Kinds {
+    what: "encoder",
+    builtin: &[("pattern", &["kind", "pattern"]), ("json", &["kind"])],
+    common: &[],
+    default_kind: Some("pattern"),
+    is_registered: registry::is_formatter_registered,
+};
+
+impl Kinds {
+    fn kind<E: de::Error>(&self, name: &str) -> Result<Kind, E> {
+        // Registered kinds take precedence over the built-in ones.
+        if (self.is_registered)(name) {
+            return Ok(Kind::Registered);
+        }
+        match self.builtin.iter().find(|(kind, _)| *kind == name) {
+            Some((_, keys)) => Ok(Kind::Builtin(keys)),
+            None => {
+                let expected = self
+                    .builtin
+                    .iter()
+                    .map(|(kind, _)| format!("`{}`", kind))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                Err(E::custom(format_args!(
+                    "unknown {} kind `{}`, expected one of {} or a registered kind",
+                    self.what, name, expected
+                )))
+            }
+        }
+    }
+
+    fn visit_map<'de, A>(&self, mut map: A) -> Result<(), A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut kind = None;
+        // Keys before `kind`, validated once the kind is known.
+        let mut deferred = vec![];
+
+        while let Some(key) = map.next_key_seed(Key {
+            kinds: self,
+            kind: kind.as_ref(),
+        })? {
+            if key == "kind" {
+                kind = Some(map.next_value_seed(KindName(self))?);
+            } else if self.common.contains(&key.as_str()) {
+                map.next_value_seed(Field(&key))?;
+            } else {
+                match kind {
+                    Some(Kind::Builtin(_)) => map.next_value_seed(Field(&key))?,
+                    Some(Kind::Registered) => {
+                        map.next_value::<IgnoredAny>()?;
+                    }
+                    None => deferred.push((key, map.next_value::<serde_yaml::Value>()?)),
+                }
+            }
+        }
+
+        let kind = match (kind, self.default_kind) {
+            (Some(kind), _) => kind,
+            (None, Some(default_kind)) => self.kind(default_kind)?,
+            (None, None) => return Err(de::Error::missing_field("kind")),
+        };
+        if let Kind::Builtin(keys) = kind {
+            for (key, value) in deferred {
+                if !keys.contains(&key.as_str()) {
+                    return Err(de::Error::unknown_field(&key, keys));
+                }
+                Field(&key).deserialize(value).map_err(de::Error::custom)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+// Reads a key of a mapping described by `Kinds`, rejecting keys that are not
+// allowed for the kind if it is known.
+struct Key<'a> {
+    kinds: &'a Kinds,
+    kind: Option<&'a Kind>,
+}
+
+impl<'de> DeserializeSeed<'de> for Key<'_> {
+    type Value = String;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<String, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_str(self)
+    }
+}
+
+impl<'de> Visitor<'de> for Key<'_> {
+    type Value = String;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "a key of an {}", self.kinds.what)
+    }
+
+    fn visit_str<E>(self, key: &str) -> Result<String, E>
+    where
+        E: de::Error,
+    {
+        match self.kind {
+            Some(Kind::Builtin(keys))
+                if !keys.contains(&key) && !self.kinds.common.contains(&key) =>
+            {
+                Err(E::unknown_field(key, keys))
+            }
+            _ => Ok(key.to_string()),
+        }
+    }
+}
+
+// Reads the value of key `kind` of a mapping described by `Kinds`.
+struct KindName<'a>(&'a Kinds);
+
+impl<'de> DeserializeSeed<'de> for KindName<'_> {
+    type Value = Kind;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Kind, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_str(self)
+    }
+}
+
+impl<'de> Visitor<'de> for KindName<'_> {
+    type Value = Kind;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "the kind of an {}", self.0.what)
+    }
+
+    fn visit_str<E>(self, kind: &str) -> Result<Kind, E>
+    where
+        E: de::Error,
+    {
+        self.0.kind(kind)
+    }
+}
+
+struct Appender;
+
+impl<'de> Deserialize<'de> for Appender {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct AppenderVisitor;
+
+        impl<'de> Visitor<'de> for AppenderVisitor {
+            type Value = Appender;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("an appender")
+            }
+
+            fn visit_map<A>(self, map: A) -> Result<Appender, A::Error>
+            where
+                A: MapAccess<'de>,
+            {
+                APPENDER_KINDS.visit_map(map).map(|_| Appender)
+            }
+        }
+
+        deserializer.deserialize_map(AppenderVisitor)
+    }
+}
+
+struct Encoder;
+
+impl<'de> Deserialize<'de> for Encoder {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct EncoderVisitor;
+
+        impl<'de> Visitor<'de> for EncoderVisitor {
+            type Value = Encoder;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("the kind of an encoder, or an encoder")
+            }
+
+            fn visit_str<E>(self, kind: &str) -> Result<Encoder, E>
+            where
+                E: de::Error,
+            {
+                ENCODER_KINDS.kind(kind).map(|_| Encoder)
+            }
+
+            fn visit_map<A>(self, map: A) -> Result<Encoder, A::Error>
+            where
+                A: MapAccess<'de>,
+            {
+                ENCODER_KINDS.visit_map(map).map(|_| Encoder)
+            }
+        }
+
+        deserializer.deserialize_any(EncoderVisitor)
+    }
+}
+
+// Validates the value of a key of built-in kinds.
+struct Field<'a>(&'a str);
+
+impl<'de> DeserializeSeed<'de> for Field<'_> {
+    type Value = ();
+
+    fn deserialize<D>(self, deserializer: D) -> Result<(), D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        match self.0 {
+            "target" => ConsoleTarget::deserialize(deserializer).map(drop),
+            "path" | "pattern" => String::deserialize(deserializer).map(drop),
+            "append" => bool::deserialize(deserializer).map(drop),
+            "encoder" => Encoder::deserialize(deserializer).map(drop),
+            "filters" => Vec::<Filter>::deserialize(deserializer).map(drop),
+            "policy" => Policy::deserialize(deserializer).map(drop),
+            _ => IgnoredAny::deserialize(deserializer).map(drop),
+        }
+    }
+}
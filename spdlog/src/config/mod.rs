@@ -0,0 +1,108 @@
+//! Provides building loggers from configuration files.
+//!
+//! This module is only available if crate feature `config` is enabled.
+//!
+//! Supported configuration formats:
+//!
+//! - [`native`]: YAML configurations describing `spdlog-rs` sinks and loggers
+//!   directly.
+//! - [`log4rs`]: log4rs-style YAML configurations, for migrating from `log4rs`
+//!   with minimal edits.
+//!
+//! Custom sinks and formatters can be instantiated by name from configurations
+//! after registering their factories with [`register_sink`] and
+//! [`register_formatter`].
+//!
+//! Native configuration files can be applied to the global loggers whenever
+//! they change with a [`ConfigWatcher`].
+
+pub mod log4rs;
+pub mod native;
+mod registry;
+mod watcher;
+
+use std::path::Path;
+
+pub use registry::*;
+pub use watcher::*;
+
+use crate::{
+    error::{ConfigSchemaError, LoadConfigError},
+    sync::*,
+    Error, Logger,
+};
+
+/// Loggers built from a configuration.
+///
+/// The loggers can be fetched by name, or installed globally with
+/// [`Loggers::install`].
+pub struct Loggers {
+    default_logger: Arc<Logger>,
+    named: Vec<(String, Arc<Logger>)>,
+}
+
+impl Loggers {
+    #[must_use]
+    pub(crate) fn new(default_logger: Arc<Logger>, named: Vec<(String, Arc<Logger>)>) -> Self {
+        Self {
+            default_logger,
+            named,
+        }
+    }
+
+    /// Gets the logger configured as the default (root) logger.
+    #[must_use]
+    pub fn default_logger(&self) -> Arc<Logger> {
+        self.default_logger.clone()
+    }
+
+    /// Gets a named logger.
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<Arc<Logger>> {
+        self.named
+            .iter()
+            .find(|(logger_name, _)| logger_name == name)
+            .map(|(_, logger)| logger.clone())
+    }
+
+    /// Gets an iterator over names and loggers of all named loggers.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &Arc<Logger>)> {
+        self.named
+            .iter()
+            .map(|(name, logger)| (name.as_str(), logger))
+    }
+
+    /// Installs the loggers globally.
+    ///
+    /// The default logger is set as the global default logger. If crate
+    /// feature `log` is enabled, records from `log` crate are also routed to
+    /// the named loggers by treating logger names as target prefixes, see
+    /// [`LogCrateProxy::set_target_loggers`].
+    ///
+    /// [`LogCrateProxy::set_target_loggers`]: crate::LogCrateProxy::set_target_loggers
+    pub fn install(&self) {
+        crate::set_default_logger(self.default_logger.clone());
+
+        #[cfg(feature = "log")]
+        crate::log_crate_proxy().set_target_loggers(self.named.iter().cloned());
+    }
+}
+
+// Converts an error of deserializing a configuration from the document text
+// into a schema error located in the document.
+#[must_use]
+fn schema_error(err: serde_yaml::Error, file: Option<&Path>) -> Error {
+    let (line, column) = err
+        .location()
+        .map_or((0, 0), |location| (location.line(), location.column()));
+    let mut message = err.to_string();
+    if let Some(location) = message.rfind(" at line ") {
+        message.truncate(location);
+    }
+    Error::LoadConfig(LoadConfigError::Schema(ConfigSchemaError {
+        file: file.map(Path::to_path_buf),
+        line,
+        column,
+        message,
+    }))
+}
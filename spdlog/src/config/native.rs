@@ -0,0 +1,691 @@
+//! Provides loading `spdlog-rs` native YAML configurations.
+//!
+//! A configuration describes sinks and loggers:
+//!
+//! - `sinks` is a map from sink names to sinks. Each sink has a `kind`, and
+//!   optionally a `level` filter and either a `pattern` (a [`RuntimePattern`]
+//!   template) or a `formatter`. Sink names are also names for
+//!   [`init_env_sink_levels`], whose levels override the configured ones.
+//!
+//! - `default_logger` configures the default logger, and each entry of
+//!   `loggers` configures a named logger. A logger has a `level` filter, a
+//!   `flush_level` filter, and a list of `sinks` by name. Sinks can be shared
+//!   by multiple loggers.
+//!
+//! Supported sink kinds and their parameters:
+//!
+//! | Kind            | Parameters                                                 |
+//! |-----------------|------------------------------------------------------------|
+//! | `std_stream`    | `stream`: `stdout` (default) or `stderr`                   |
+//! | `file`          | `path` (required), `truncate`: `false` (default)           |
+//! | `rotating_file` | `path` (required), `rotation` (required), `max_files`: `0` (default), `rotate_on_open`: `false` (default) |
+//!
+//! Kinds registered with [`register_sink`] are also supported, their factories
+//! receive all fields of the sink as parameters.
+//!
+//! `rotation` is one of `file_size: <size>` (e.g. `10 MiB`), `daily: <hh:mm>`,
+//! `hourly` or `period: <interval>` (e.g. `30 minutes`), see
+//! [`RotationPolicy`].
+//!
+//! `formatter` is either the name of a kind, or a map of parameters with a
+//! `kind`. Supported kinds are `full` (to [`FullFormatter`]) and `json` (to
+//! [`JsonFormatter`], requires crate feature `serde_json`), as well as kinds
+//! registered with [`register_formatter`]. If neither `pattern` nor
+//! `formatter` is specified, the default formatter of the sink is used.
+//!
+//! Levels are the same as in [`init_env_level`], e.g. `info` means
+//! `LevelFilter::MoreSevereEqual(Level::Info)`, and `all` and `off` are also
+//! accepted.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let yaml = r#"
+//! sinks:
+//!   console:
+//!     kind: std_stream
+//!     stream: stderr
+//!     level: warn
+//!   app_file:
+//!     kind: rotating_file
+//!     path: logs/app.log
+//!     rotation:
+//!       file_size: 10 MiB
+//!     max_files: 5
+//!     pattern: "[{date} {time}] [{level}] {payload}{eol}"
+//! default_logger:
+//!   level: info
+//!   sinks: [console, app_file]
+//! loggers:
+//!   net:
+//!     level: trace
+//!     sinks: [app_file]
+//! "#;
+//! let loggers = spdlog::config::native::from_yaml_str(yaml)?;
+//!
+//! loggers.install();
+//! # Ok(()) }
+//! ```
+//!
+//! [`RuntimePattern`]: crate::formatter::RuntimePattern
+//! [`init_env_sink_levels`]: crate::init_env_sink_levels
+//! [`init_env_level`]: crate::init_env_level
+//! [`register_sink`]: super::register_sink
+//! [`register_formatter`]: super::register_formatter
+//! [`RotationPolicy`]: crate::sink::RotationPolicy
+//! [`FullFormatter`]: crate::formatter::FullFormatter
+//! [`JsonFormatter`]: crate::formatter::JsonFormatter
+
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+    result::Result as StdResult,
+};
+
+use serde::Deserialize;
+
+use super::{
+    log4rs::{parse_interval, parse_size, RawSize},
+    registry, Loggers, Params,
+};
+use crate::{
+    env_level,
+    error::LoadConfigError,
+    formatter::{Formatter, FullFormatter, PatternFormatter, RuntimePattern},
+    sink::{FileSink, RotatingFileSink, RotationPolicy, Sink, StdStream, StdStreamSink},
+    sync::*,
+    Error, LevelFilter, Logger, Result,
+};
+
+/// Builds loggers from a native YAML configuration string.
+///
+/// See the [module level documentation](self) for the format.
+pub fn from_yaml_str(yaml: &str) -> Result<Loggers> {
+    from_yaml(yaml, None)
+}
+
+/// Builds loggers from a native YAML configuration file.
+///
+/// See the [module level documentation](self) for the format.
+pub fn from_yaml_file(path: impl AsRef<Path>) -> Result<Loggers> {
+    let path = path.as_ref();
+    let yaml = fs::read_to_string(path).map_err(Error::OpenFile)?;
+    from_yaml(&yaml, Some(path))
+}
+
+fn from_yaml(yaml: &str, file: Option<&Path>) -> Result<Loggers> {
+    let config = parse(yaml, file)?;
+    let (sinks, _) = config.build_sinks(&BuiltSinks::new())?;
+    let (default_logger, named) = config.build_loggers(&sinks)?;
+    Ok(Loggers::new(
+        default_logger.unwrap_or_else(crate::default_logger),
+        named,
+    ))
+}
+
+pub(super) fn parse(yaml: &str, file: Option<&Path>) -> Result<RawConfig> {
+    serde_yaml::from_str::<serde_yaml::Value>(yaml)
+        .map_err(|err| Error::LoadConfig(LoadConfigError::Parse(err.to_string())))?;
+
+    serde_yaml::from_str(yaml).map_err(|err| super::schema_error(err, file))
+}
+
+// Sinks built from a configuration by name, along with their configurations.
+pub(super) type BuiltSinks = HashMap<String, (serde_yaml::Value, Arc<dyn Sink>)>;
+
+// Levels and formatters to update reused sinks in place with. They are applied
+// after the whole configuration is built, so that an invalid configuration
+// changes nothing.
+pub(super) struct SinkUpdate {
+    sink: Arc<dyn Sink>,
+    level_filter: LevelFilter,
+    formatter: Option<Box<dyn Formatter>>,
+}
+
+impl SinkUpdate {
+    pub(super) fn apply(self) {
+        self.sink.set_level_filter(self.level_filter);
+        if let Some(formatter) = self.formatter {
+            self.sink.set_formatter(formatter);
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(super) struct RawConfig {
+    #[serde(default)]
+    sinks: HashMap<String, serde_yaml::Value>,
+    #[serde(default)]
+    default_logger: Option<RawLogger>,
+    #[serde(default)]
+    loggers: HashMap<String, RawLogger>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawLogger {
+    level: Option<String>,
+    flush_level: Option<String>,
+    #[serde(default)]
+    sinks: Vec<String>,
+}
+
+// Fields handled for all sinks, including registered kinds.
+#[derive(Deserialize)]
+struct RawSinkCommon {
+    kind: String,
+    level: Option<String>,
+    pattern: Option<String>,
+    formatter: Option<RawFormatter>,
+}
+
+const COMMON_FIELDS: &[&str] = &["kind", "level", "pattern", "formatter"];
+
+#[derive(Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+enum RawSink {
+    StdStream {
+        #[serde(default)]
+        stream: RawStdStream,
+    },
+    File {
+        path: PathBuf,
+        #[serde(default)]
+        truncate: bool,
+    },
+    RotatingFile {
+        path: PathBuf,
+        rotation: RawRotation,
+        #[serde(default)]
+        max_files: usize,
+        #[serde(default)]
+        rotate_on_open: bool,
+    },
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum RawStdStream {
+    Stdout,
+    Stderr,
+}
+
+impl Default for RawStdStream {
+    fn default() -> Self {
+        Self::Stdout
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum RawRotation {
+    FileSize(RawSize),
+    Daily(String),
+    Hourly,
+    Period(String),
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RawFormatter {
+    Kind(String),
+    Params(serde_yaml::Mapping),
+}
+
+fn invalid(message: impl Into<String>) -> Error {
+    Error::LoadConfig(LoadConfigError::Invalid(message.into()))
+}
+
+impl RawConfig {
+    // Builds the configured sinks. Sinks in `previous` are reused if their
+    // configurations only differ in levels and formatters, the returned updates
+    // are to be applied to them.
+    pub(super) fn build_sinks(
+        &self,
+        previous: &BuiltSinks,
+    ) -> Result<(BuiltSinks, Vec<SinkUpdate>)> {
+        let mut sinks = BuiltSinks::new();
+        let mut updates = vec![];
+        for (name, config) in &self.sinks {
+            let context = |err| invalid(format!("sink '{}': {}", name, err));
+            let sink = match previous.get(name) {
+                Some((previous_config, sink)) if is_reusable(previous_config, config) => {
+                    let mut update = sink_update(sink.clone(), config).map_err(context)?;
+                    if let Some(level_filter) = env_level::sink_level(name) {
+                        update.level_filter = level_filter;
+                    }
+                    updates.push(update);
+                    sink.clone()
+                }
+                _ => {
+                    let sink = build_sink(config.clone()).map_err(context)?;
+                    if let Some(level_filter) = env_level::sink_level(name) {
+                        sink.set_level_filter(level_filter);
+                    }
+                    sink
+                }
+            };
+            sinks.insert(name.clone(), (config.clone(), sink));
+        }
+        Ok((sinks, updates))
+    }
+
+    // Builds the default logger if it is configured, and the named loggers
+    // sorted by name.
+    #[allow(clippy::type_complexity)]
+    pub(super) fn build_loggers(
+        &self,
+        sinks: &BuiltSinks,
+    ) -> Result<(Option<Arc<Logger>>, Vec<(String, Arc<Logger>)>)> {
+        let build_logger = |name: Option<&str>, logger: &RawLogger| {
+            let context = match name {
+                Some(name) => format!("logger '{}'", name),
+                None => "default logger".to_string(),
+            };
+            let mut builder = Logger::builder();
+            if let Some(name) = name {
+                builder.name(name);
+            }
+            if let Some(level_filter) = parse_level_filter(logger.level.as_deref())
+                .map_err(|err| invalid(format!("{}: {}", context, err)))?
+            {
+                builder.level_filter(level_filter);
+            }
+            if let Some(level_filter) = parse_level_filter(logger.flush_level.as_deref())
+                .map_err(|err| invalid(format!("{}: {}", context, err)))?
+            {
+                builder.flush_level_filter(level_filter);
+            }
+            for sink in &logger.sinks {
+                let (_, sink) = sinks.get(sink).ok_or_else(|| {
+                    invalid(format!("{}: sink '{}' is not defined", context, sink))
+                })?;
+                builder.sink(sink.clone());
+            }
+            builder.build().map(Arc::new)
+        };
+
+        let default_logger = self
+            .default_logger
+            .as_ref()
+            .map(|logger| build_logger(None, logger))
+            .transpose()?;
+
+        let mut loggers = self.loggers.iter().collect::<Vec<_>>();
+        loggers.sort_by_key(|(name, _)| *name);
+        let named = loggers
+            .into_iter()
+            .map(|(name, logger)| {
+                let logger = build_logger(Some(name), logger)?;
+                Ok((name.clone(), logger))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok((default_logger, named))
+    }
+}
+
+fn parse_level_filter(level: Option<&str>) -> StdResult<Option<LevelFilter>, String> {
+    level
+        .map(|level| {
+            LevelFilter::from_str_for_env(level).ok_or_else(|| format!("unknown level '{}'", level))
+        })
+        .transpose()
+}
+
+impl RawSinkCommon {
+    fn build_formatter(&self) -> StdResult<Option<Box<dyn Formatter>>, String> {
+        match (&self.pattern, &self.formatter) {
+            (Some(_), Some(_)) => {
+                Err("'pattern' and 'formatter' cannot be specified together".to_string())
+            }
+            (Some(pattern), None) => {
+                let pattern = RuntimePattern::new(pattern).map_err(|err| err.to_string())?;
+                Ok(Some(Box::new(PatternFormatter::new(pattern))))
+            }
+            (None, Some(formatter)) => build_formatter(formatter).map(Some),
+            (None, None) => Ok(None),
+        }
+    }
+}
+
+// Fields that can be updated in place for sinks of any kind.
+const UPDATABLE_FIELDS: &[&str] = &["level", "pattern", "formatter"];
+
+// Checks whether a sink built from the previous configuration can be updated in
+// place to the new configuration. A formatter can only be replaced, not reset
+// to the default formatter of the sink.
+#[must_use]
+fn is_reusable(previous: &serde_yaml::Value, new: &serde_yaml::Value) -> bool {
+    let (previous, new) = match (previous.as_mapping(), new.as_mapping()) {
+        (Some(previous), Some(new)) => (previous, new),
+        _ => return false,
+    };
+    let has_formatter = |mapping: &serde_yaml::Mapping| {
+        mapping.contains_key(&"pattern".into()) || mapping.contains_key(&"formatter".into())
+    };
+    let fixed_fields = |mapping: &serde_yaml::Mapping| {
+        mapping
+            .iter()
+            .filter(|(key, _)| {
+                !key.as_str()
+                    .map_or(false, |key| UPDATABLE_FIELDS.contains(&key))
+            })
+            .map(|(key, value)| (key.clone(), value.clone()))
+            .collect::<serde_yaml::Mapping>()
+    };
+    (has_formatter(new) || !has_formatter(previous)) && fixed_fields(previous) == fixed_fields(new)
+}
+
+fn sink_update(sink: Arc<dyn Sink>, config: &serde_yaml::Value) -> StdResult<SinkUpdate, String> {
+    let common: RawSinkCommon =
+        serde_yaml::from_value(config.clone()).map_err(|err| err.to_string())?;
+    let formatter = common.build_formatter()?;
+    let level_filter = parse_level_filter(common.level.as_deref())?;
+
+    Ok(SinkUpdate {
+        sink,
+        level_filter: level_filter.unwrap_or(LevelFilter::All),
+        formatter,
+    })
+}
+
+fn build_sink(sink: serde_yaml::Value) -> StdResult<Arc<dyn Sink>, String> {
+    let common: RawSinkCommon =
+        serde_yaml::from_value(sink.clone()).map_err(|err| err.to_string())?;
+    let formatter = common.build_formatter()?;
+    let level_filter = parse_level_filter(common.level.as_deref())?;
+
+    let params = Params::new(sink);
+    let sink = match registry::build_sink(&common.kind, &params) {
+        Some(sink) => sink.map_err(factory_error)?,
+        None => {
+            let sink = params.into_value();
+            check_fields(&common.kind, &sink)?;
+            serde_yaml::from_value::<RawSink>(sink)
+                .map_err(|err| err.to_string())?
+                .build()?
+        }
+    };
+
+    if let Some(level_filter) = level_filter {
+        sink.set_level_filter(level_filter);
+    }
+    if let Some(formatter) = formatter {
+        sink.set_formatter(formatter);
+    }
+    Ok(sink)
+}
+
+// Rejects unknown fields of built-in kinds, which are likely typos.
+fn check_fields(kind: &str, sink: &serde_yaml::Value) -> StdResult<(), String> {
+    let fields: &[&str] = match kind {
+        "std_stream" => &["stream"],
+        "file" => &["path", "truncate"],
+        "rotating_file" => &["path", "rotation", "max_files", "rotate_on_open"],
+        // Left to the deserialization of `RawSink`.
+        _ => return Ok(()),
+    };
+    let mapping = match sink.as_mapping() {
+        Some(mapping) => mapping,
+        None => return Ok(()),
+    };
+    for key in mapping.iter().filter_map(|(key, _)| key.as_str()) {
+        if !COMMON_FIELDS.contains(&key) && !fields.contains(&key) {
+            return Err(format!(
+                "unknown field `{}` for kind `{}`, expected one of {}",
+                key,
+                kind,
+                COMMON_FIELDS
+                    .iter()
+                    .chain(fields)
+                    .map(|field| format!("`{}`", field))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ));
+        }
+    }
+    Ok(())
+}
+
+impl RawSink {
+    fn build(self) -> StdResult<Arc<dyn Sink>, String> {
+        let sink: Arc<dyn Sink> = match self {
+            Self::StdStream { stream } => {
+                let std_stream = match stream {
+                    RawStdStream::Stdout => StdStream::Stdout,
+                    RawStdStream::Stderr => StdStream::Stderr,
+                };
+                Arc::new(
+                    StdStreamSink::builder()
+                        .std_stream(std_stream)
+                        .build()
+                        .map_err(|err| err.to_string())?,
+                )
+            }
+            Self::File { path, truncate } => Arc::new(
+                FileSink::builder()
+                    .path(path)
+                    .truncate(truncate)
+                    .build()
+                    .map_err(|err| err.to_string())?,
+            ),
+            Self::RotatingFile {
+                path,
+                rotation,
+                max_files,
+                rotate_on_open,
+            } => Arc::new(
+                RotatingFileSink::builder()
+                    .base_path(path)
+                    .rotation_policy(rotation.build()?)
+                    .max_files(max_files)
+                    .rotate_on_open(rotate_on_open)
+                    .build()
+                    .map_err(|err| err.to_string())?,
+            ),
+        };
+        Ok(sink)
+    }
+}
+
+impl RawRotation {
+    fn build(self) -> StdResult<RotationPolicy, String> {
+        match self {
+            Self::FileSize(size) => Ok(RotationPolicy::FileSize(parse_size(size)?)),
+            Self::Daily(time) => {
+                let parse = || {
+                    let (hour, minute) = time.split_once(':')?;
+                    Some((hour.trim().parse().ok()?, minute.trim().parse().ok()?))
+                };
+                let (hour, minute) =
+                    parse().ok_or_else(|| format!("invalid daily time '{}'", time))?;
+                Ok(RotationPolicy::Daily { hour, minute })
+            }
+            Self::Hourly => Ok(RotationPolicy::Hourly),
+            Self::Period(interval) => parse_interval(&interval),
+        }
+    }
+}
+
+fn build_formatter(formatter: &RawFormatter) -> StdResult<Box<dyn Formatter>, String> {
+    let (kind, params) = match formatter {
+        RawFormatter::Kind(kind) => (kind.as_str(), serde_yaml::Mapping::new()),
+        RawFormatter::Params(params) => {
+            let kind = match params.get(&serde_yaml::Value::String("kind".to_string())) {
+                Some(serde_yaml::Value::String(kind)) => kind.as_str(),
+                Some(_) => return Err("formatter kind must be a string".to_string()),
+                None => return Err("formatter requires 'kind'".to_string()),
+            };
+            (kind, params.clone())
+        }
+    };
+    let params = Params::new(serde_yaml::Value::Mapping(params));
+
+    if let Some(formatter) = registry::build_formatter(kind, &params) {
+        return formatter.map_err(factory_error);
+    }
+
+    match kind {
+        "full" => Ok(Box::new(FullFormatter::new())),
+        "json" => {
+            cfg_if::cfg_if! {
+                if #[cfg(feature = "serde_json")] {
+                    Ok(Box::new(crate::formatter::JsonFormatter::new()))
+                } else {
+                    Err("formatter 'json' requires crate feature 'serde_json'".to_string())
+                }
+            }
+        }
+        kind => Err(format!("unsupported formatter kind '{}'", kind)),
+    }
+}
+
+fn factory_error(err: Error) -> String {
+    match err {
+        Error::LoadConfig(LoadConfigError::Invalid(message)) => message,
+        err => err.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{info, test_sink::CaptureSink, test_utils::*, trace, warn, Level};
+
+    #[test]
+    fn build() {
+        let path = TEST_LOGS_PATH.join("config_native");
+        fs::create_dir_all(&path).unwrap();
+
+        let yaml = format!(
+            r#"
+sinks:
+  all:
+    kind: file
+    path: {all:?}
+    truncate: true
+    pattern: "{{level}} {{logger}} {{payload}}{{eol}}"
+  net:
+    kind: rotating_file
+    path: {net:?}
+    rotation:
+      file_size: 1 MiB
+    max_files: 2
+    rotate_on_open: true
+    level: debug
+    pattern: "{{level}} {{payload}}{{eol}}"
+default_logger:
+  level: warn
+  sinks: [all]
+loggers:
+  net:
+    level: all
+    flush_level: all
+    sinks: [all, net]
+"#,
+            all = path.join("all.log"),
+            net = path.join("net.log"),
+        );
+        let loggers = from_yaml_str(&yaml).unwrap();
+
+        let default = loggers.default_logger();
+        let net = loggers.get("net").unwrap();
+        assert_eq!(loggers.iter().count(), 1);
+        assert_eq!(
+            default.level_filter(),
+            LevelFilter::MoreSevereEqual(Level::Warn)
+        );
+        assert_eq!(net.level_filter(), LevelFilter::All);
+        assert_eq!(net.flush_level_filter(), LevelFilter::All);
+        assert!(Arc::ptr_eq(&default.sinks()[0], &net.sinks()[0]));
+
+        info!(logger: default, "default info");
+        warn!(logger: default, "default warn");
+        trace!(logger: net, "net trace");
+        info!(logger: net, "net info");
+        default.flush();
+
+        let eol = crate::__EOL;
+        assert_eq!(
+            fs::read_to_string(path.join("all.log")).unwrap(),
+            format!(
+                "warn  default warn{eol}trace net net trace{eol}info net net info{eol}",
+                eol = eol
+            )
+        );
+        assert_eq!(
+            fs::read_to_string(path.join("net.log")).unwrap(),
+            format!("info net info{eol}", eol = eol)
+        );
+    }
+
+    #[test]
+    fn registered_sink() {
+        let sink = Arc::new(CaptureSink::new());
+        let registered = sink.clone();
+        super::super::register_sink("native_test_capture", move |_: &Params| {
+            Ok(registered.clone())
+        });
+
+        let loggers = from_yaml_str(
+            r#"
+sinks:
+  capture:
+    kind: native_test_capture
+    level: warn
+default_logger:
+  level: all
+  sinks: [capture]
+"#,
+        )
+        .unwrap();
+        let logger = loggers.default_logger();
+        info!(logger: logger, "filtered");
+        warn!(logger: logger, "kept");
+        assert_eq!(sink.payloads(), ["kept"]);
+
+        assert!(super::super::unregister_sink("native_test_capture"));
+    }
+
+    #[test]
+    fn invalid_config() {
+        let load = |yaml| match from_yaml_str(yaml) {
+            Err(Error::LoadConfig(err)) => err,
+            _ => panic!("expected a config error"),
+        };
+        let invalid = |yaml| match load(yaml) {
+            LoadConfigError::Invalid(message) => message,
+            err => panic!("expected an invalid config error, got {}", err),
+        };
+
+        assert!(matches!(load("sinks: ["), LoadConfigError::Parse(_)));
+        assert!(matches!(
+            load("logers:\n  net: {}\n"),
+            LoadConfigError::Schema(_)
+        ));
+        assert_eq!(
+            invalid("default_logger:\n  sinks: [missing]\n"),
+            "default logger: sink 'missing' is not defined"
+        );
+        assert_eq!(
+            invalid("loggers:\n  net:\n    level: loud\n"),
+            "logger 'net': unknown level 'loud'"
+        );
+        assert_eq!(
+            invalid("sinks:\n  a:\n    kind: std_stream\n    steam: stderr\n"),
+            "sink 'a': unknown field `steam` for kind `std_stream`, expected one of `kind`, `level`, `pattern`, `formatter`, `stream`"
+        );
+        assert_eq!(
+            invalid("sinks:\n  a:\n    kind: std_stream\n    formatter: fancy\n"),
+            "sink 'a': unsupported formatter kind 'fancy'"
+        );
+        assert_eq!(
+            invalid("sinks:\n  a:\n    kind: rotating_file\n    path: a.log\n    rotation:\n      daily: noon\n"),
+            "sink 'a': invalid daily time 'noon'"
+        );
+    }
+}
@@ -0,0 +1,213 @@
+use std::collections::HashMap;
+
+use serde::de::DeserializeOwned;
+
+use crate::{error::LoadConfigError, formatter::Formatter, sink::Sink, sync::*, Error, Result};
+
+/// Parameters of a component in a configuration.
+///
+/// Factories registered by [`register_sink`] and [`register_formatter`] receive
+/// the parameters of the configured component, and usually deserialize them
+/// into their own parameter types with [`Params::deserialize`].
+#[derive(Clone, Debug)]
+pub struct Params {
+    value: serde_yaml::Value,
+}
+
+impl Params {
+    #[must_use]
+    pub(crate) fn new(value: serde_yaml::Value) -> Self {
+        Self { value }
+    }
+
+    #[must_use]
+    pub(crate) fn into_value(self) -> serde_yaml::Value {
+        self.value
+    }
+
+    /// Deserializes the parameters into a type.
+    ///
+    /// Parameters handled by the configuration loader itself (e.g. `kind`) are
+    /// also present, types should not deny unknown fields.
+    ///
+    /// # Errors
+    ///
+    /// If the parameters cannot be deserialized into the type,
+    /// [`Error::LoadConfig`] will be returned.
+    pub fn deserialize<T>(&self) -> Result<T>
+    where
+        T: DeserializeOwned,
+    {
+        serde_yaml::from_value(self.value.clone())
+            .map_err(|err| Error::LoadConfig(LoadConfigError::Invalid(err.to_string())))
+    }
+}
+
+type SinkFactory = dyn Fn(&Params) -> Result<Arc<dyn Sink>> + Send + Sync;
+
+type FormatterFactory = dyn Fn(&Params) -> Result<Box<dyn Formatter>> + Send + Sync;
+
+static SINK_FACTORIES: Lazy<RwLock<HashMap<String, Arc<SinkFactory>>>> =
+    Lazy::new(Default::default);
+
+static FORMATTER_FACTORIES: Lazy<RwLock<HashMap<String, Arc<FormatterFactory>>>> =
+    Lazy::new(Default::default);
+
+/// Registers a factory constructing sinks of the given kind from parameters.
+///
+/// Configuration loaders look up the registered factories by the kind of a
+/// configured sink, so custom sinks can be instantiated by name from
+/// configuration files. Registered kinds take precedence over the kinds
+/// supported by the loaders themselves, and registering a kind again replaces
+/// the previous factory.
+///
+/// # Examples
+///
+/// ```
+/// use std::sync::Arc;
+///
+/// use serde::Deserialize;
+/// use spdlog::{
+///     config::{self, Params},
+///     sink::{StdStream, StdStreamSink},
+/// };
+///
+/// #[derive(Deserialize)]
+/// struct MyStderrParams {
+///     name: String,
+/// }
+///
+/// config::register_sink("my_stderr", |params: &Params| {
+///     let params: MyStderrParams = params.deserialize()?;
+///     println!("constructing sink '{}'", params.name);
+///     let sink = StdStreamSink::builder()
+///         .std_stream(StdStream::Stderr)
+///         .build()?;
+///     Ok(Arc::new(sink))
+/// });
+///
+/// # fn main() -> Result<(), spdlog::Error> {
+/// let loggers = config::log4rs::from_yaml_str(
+///     r#"
+/// appenders:
+///   stderr:
+///     kind: my_stderr
+///     name: errors
+/// root:
+///   appenders:
+///     - stderr
+/// "#,
+/// )?;
+/// # Ok(()) }
+/// ```
+pub fn register_sink<F>(kind: impl Into<String>, factory: F)
+where
+    F: Fn(&Params) -> Result<Arc<dyn Sink>> + Send + Sync + 'static,
+{
+    SINK_FACTORIES
+        .write_expect()
+        .insert(kind.into(), Arc::new(factory));
+}
+
+/// Unregisters the sink factory of the given kind.
+///
+/// Returns `true` if a factory was registered for the kind.
+pub fn unregister_sink(kind: &str) -> bool {
+    SINK_FACTORIES.write_expect().remove(kind).is_some()
+}
+
+/// Constructs a sink with the registered factory, returns `None` if no factory
+/// is registered for the kind.
+pub(crate) fn build_sink(kind: &str, params: &Params) -> Option<Result<Arc<dyn Sink>>> {
+    // Clone the factory to avoid calling it while holding the lock, it may
+    // register other factories.
+    let factory = SINK_FACTORIES.read_expect().get(kind).cloned();
+    factory.map(|factory| factory(params))
+}
+
+#[must_use]
+pub(crate) fn is_sink_registered(kind: &str) -> bool {
+    SINK_FACTORIES.read_expect().contains_key(kind)
+}
+
+/// Registers a factory constructing formatters of the given kind from
+/// parameters.
+///
+/// Configuration loaders look up the registered factories by the kind of a
+/// configured formatter, so custom formatters can be referenced by name from
+/// configuration files, either by the name alone (e.g. `encoder: my_format`)
+/// or with parameters (e.g. `encoder: { kind: my_format, ... }`). Registered
+/// kinds take precedence over the kinds supported by the loaders themselves,
+/// and registering a kind again replaces the previous factory.
+///
+/// # Examples
+///
+/// ```
+/// use serde::Deserialize;
+/// use spdlog::{
+///     config::{self, Params},
+///     formatter::{pattern, PatternFormatter},
+/// };
+///
+/// #[derive(Deserialize)]
+/// struct BracketParams {
+///     #[serde(default)]
+///     with_level: bool,
+/// }
+///
+/// config::register_formatter("bracket", |params: &Params| {
+///     let params: BracketParams = params.deserialize()?;
+///     Ok(if params.with_level {
+///         Box::new(PatternFormatter::new(pattern!("[{level}] {payload}{eol}")))
+///     } else {
+///         Box::new(PatternFormatter::new(pattern!("[{payload}]{eol}")))
+///     })
+/// });
+///
+/// # fn main() -> Result<(), spdlog::Error> {
+/// let loggers = config::log4rs::from_yaml_str(
+///     r#"
+/// appenders:
+///   plain:
+///     kind: console
+///     encoder: bracket
+///   leveled:
+///     kind: console
+///     encoder:
+///       kind: bracket
+///       with_level: true
+/// root:
+///   appenders:
+///     - plain
+///     - leveled
+/// "#,
+/// )?;
+/// # Ok(()) }
+/// ```
+pub fn register_formatter<F>(kind: impl Into<String>, factory: F)
+where
+    F: Fn(&Params) -> Result<Box<dyn Formatter>> + Send + Sync + 'static,
+{
+    FORMATTER_FACTORIES
+        .write_expect()
+        .insert(kind.into(), Arc::new(factory));
+}
+
+/// Unregisters the formatter factory of the given kind.
+///
+/// Returns `true` if a factory was registered for the kind.
+pub fn unregister_formatter(kind: &str) -> bool {
+    FORMATTER_FACTORIES.write_expect().remove(kind).is_some()
+}
+
+/// Constructs a formatter with the registered factory, returns `None` if no
+/// factory is registered for the kind.
+pub(crate) fn build_formatter(kind: &str, params: &Params) -> Option<Result<Box<dyn Formatter>>> {
+    let factory = FORMATTER_FACTORIES.read_expect().get(kind).cloned();
+    factory.map(|factory| factory(params))
+}
+
+#[must_use]
+pub(crate) fn is_formatter_registered(kind: &str) -> bool {
+    FORMATTER_FACTORIES.read_expect().contains_key(kind)
+}
@@ -0,0 +1,467 @@
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
+
+use super::native::{self, BuiltSinks, SinkUpdate};
+use crate::{
+    formatter::Formatter,
+    periodic_worker::PeriodicWorker,
+    sink::{lifecycle, Sink, Sinks},
+    sync::*,
+    Error, ErrorHandler, LevelFilter, Logger, Record, Result,
+};
+
+/// Watches a [native configuration] file, and applies it to the global loggers
+/// whenever it changes.
+///
+/// The file is applied when the watcher is constructed, and then checked for
+/// changes of its modification time and size at every interval. Applying a
+/// configuration updates the global loggers without restarting the program:
+///
+/// - Sinks whose configurations only differ in `level`, `pattern` and
+///   `formatter` are kept, and updated in place. Other sinks are rebuilt. A
+///   removed `level` resets the level filter of the sink to `all`.
+///
+/// - Registered loggers (see [`register_logger`]) and the default logger
+///   configured in the file are replaced by loggers installed by the watcher
+///   when it first applies them. From then on, their level filters and sinks
+///   are swapped in place, so references to them obtained earlier, e.g. from
+///   [`get_logger`], see the changes as well.
+///
+/// The [`Logger::sinks`] of an installed logger is a single internal sink
+/// forwarding records to the configured sinks. If an installed logger is
+/// replaced by other code, the watcher installs a new one on the next change.
+///
+/// The whole configuration is validated and built before any logger or sink is
+/// updated, so an invalid configuration changes nothing. Errors of applying
+/// configurations in the background are passed to the error handler.
+///
+/// Dropping the watcher stops watching, the applied configuration is kept.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::time::Duration;
+///
+/// use spdlog::config::ConfigWatcher;
+///
+/// # fn main() -> Result<(), spdlog::Error> {
+/// let watcher = ConfigWatcher::new("spdlog.yaml", Duration::from_secs(5))?;
+///
+/// // Now, changing `level: info` to `level: debug` for a logger in the file
+/// // takes effect within 5 seconds.
+/// # Ok(()) }
+/// ```
+///
+/// [native configuration]: super::native
+/// [`register_logger`]: crate::register_logger
+/// [`get_logger`]: crate::get_logger
+pub struct ConfigWatcher {
+    inner: Arc<ConfigWatcherInner>,
+    _worker: PeriodicWorker,
+}
+
+struct ConfigWatcherInner {
+    path: PathBuf,
+    state: Mutex<ConfigWatcherState>,
+    error_handler: Atomic<Option<ErrorHandler>>,
+}
+
+struct ConfigWatcherState {
+    // Modification time and size of the file when it was last applied.
+    stamp: Option<(SystemTime, u64)>,
+    sinks: BuiltSinks,
+    // Installed loggers by name, `None` for the default logger.
+    loggers: HashMap<Option<String>, InstalledLogger>,
+}
+
+impl ConfigWatcher {
+    /// Constructs a `ConfigWatcher` applying the configuration file, and then
+    /// checking it for changes at every `interval`.
+    ///
+    /// # Errors
+    ///
+    /// If the file cannot be read, or the configuration is invalid, the error
+    /// is returned and no watcher is constructed.
+    pub fn new(path: impl Into<PathBuf>, interval: Duration) -> Result<Self> {
+        let inner = Arc::new(ConfigWatcherInner {
+            path: path.into(),
+            state: Mutex::new(ConfigWatcherState {
+                stamp: None,
+                sinks: BuiltSinks::new(),
+                loggers: HashMap::new(),
+            }),
+            error_handler: Atomic::new(None),
+        });
+        inner.apply(&mut inner.state.lock_expect())?;
+
+        let worker = {
+            let inner = inner.clone();
+            PeriodicWorker::new(
+                "spdlog-config-watcher",
+                move || {
+                    if let Err(err) = inner.check() {
+                        match inner.error_handler.load(Ordering::Relaxed) {
+                            Some(handler) => handler(err),
+                            None => crate::default_error_handler("ConfigWatcher", err),
+                        }
+                    }
+                    true
+                },
+                move |_| interval,
+                None,
+            )
+        };
+
+        Ok(Self {
+            inner,
+            _worker: worker,
+        })
+    }
+
+    /// Gets the path of the watched configuration file.
+    #[must_use]
+    pub fn path(&self) -> &Path {
+        &self.inner.path
+    }
+
+    /// Applies the configuration file immediately, whether it has changed or
+    /// not.
+    pub fn reload(&self) -> Result<()> {
+        self.inner.apply(&mut self.inner.state.lock_expect())
+    }
+
+    /// Sets a error handler for errors of applying configurations in the
+    /// background.
+    ///
+    /// If no handler is set, [default error handler] will be used.
+    ///
+    /// [default error handler]: ../error/index.html#default-error-handler
+    pub fn set_error_handler(&self, handler: Option<ErrorHandler>) {
+        self.inner.error_handler.store(handler, Ordering::Relaxed);
+    }
+}
+
+impl ConfigWatcherInner {
+    // Applies the file if it has changed since it was last applied.
+    fn check(&self) -> Result<()> {
+        let mut state = self.state.lock_expect();
+        // The file may be missing for a moment while editors save it, wait for it
+        // to be back.
+        match self.stamp() {
+            Ok(stamp) if state.stamp != Some(stamp) => self.apply(&mut state),
+            _ => Ok(()),
+        }
+    }
+
+    fn stamp(&self) -> Result<(SystemTime, u64)> {
+        let metadata = fs::metadata(&self.path).map_err(Error::QueryFileMetadata)?;
+        let modified = metadata.modified().map_err(Error::QueryFileMetadata)?;
+        Ok((modified, metadata.len()))
+    }
+
+    fn apply(&self, state: &mut ConfigWatcherState) -> Result<()> {
+        let stamp = self.stamp()?;
+        let yaml = fs::read_to_string(&self.path).map_err(Error::OpenFile)?;
+        // Do not retry the same content over and over if it is invalid.
+        state.stamp = Some(stamp);
+
+        // Build everything first. The built loggers keep the new sinks opened
+        // until they are swapped into the installed loggers.
+        let config = native::parse(&yaml, Some(&self.path))?;
+        let (sinks, sink_updates) = config.build_sinks(&state.sinks)?;
+        let (default_logger, named) = config.build_loggers(&sinks)?;
+
+        let mut loggers = HashMap::new();
+        let mut changes = vec![];
+        let built_loggers = default_logger
+            .map(|logger| (None, logger))
+            .into_iter()
+            .chain(named.into_iter().map(|(name, logger)| (Some(name), logger)));
+        for (name, built) in built_loggers {
+            let current = match &name {
+                Some(name) => crate::get_logger(name),
+                None => Some(crate::default_logger()),
+            };
+            let installed = match (state.loggers.get(&name), current) {
+                (Some(installed), Some(current)) if Arc::ptr_eq(&installed.logger, &current) => {
+                    changes.push(LoggerChange::Update(installed.clone(), built));
+                    installed.clone()
+                }
+                _ => {
+                    let installed = InstalledLogger::new(&built)?;
+                    changes.push(LoggerChange::Install(installed.clone()));
+                    installed
+                }
+            };
+            loggers.insert(name, installed);
+        }
+
+        // Then apply the changes, which do not fail.
+        sink_updates.into_iter().for_each(SinkUpdate::apply);
+        let mut result = Ok(());
+        for change in changes {
+            match change {
+                LoggerChange::Update(installed, built) => {
+                    result = Error::push_result(result, installed.update(&built));
+                }
+                LoggerChange::Install(installed) => match installed.logger.name() {
+                    Some(_) => {
+                        crate::register_logger(installed.logger)?;
+                    }
+                    None => crate::set_default_logger(installed.logger),
+                },
+            }
+        }
+
+        state.sinks = sinks;
+        state.loggers = loggers;
+        result
+    }
+}
+
+enum LoggerChange {
+    Update(InstalledLogger, Arc<Logger>),
+    Install(InstalledLogger),
+}
+
+// A logger installed by the watcher, whose sinks can be swapped in place.
+#[derive(Clone)]
+struct InstalledLogger {
+    logger: Arc<Logger>,
+    sinks: Arc<SwappableSinks>,
+}
+
+impl InstalledLogger {
+    // Constructs a logger with the same name, levels and sinks as the built one.
+    fn new(built: &Logger) -> Result<Self> {
+        let sinks = Arc::new(SwappableSinks::new(built.sinks().to_vec()));
+        let mut builder = Logger::builder();
+        if let Some(name) = built.name() {
+            builder.name(name);
+        }
+        let logger = builder
+            .level_filter(built.level_filter())
+            .flush_level_filter(built.flush_level_filter())
+            .sink(sinks.clone())
+            .build()?;
+        Ok(Self {
+            logger: Arc::new(logger),
+            sinks,
+        })
+    }
+
+    fn update(&self, built: &Logger) -> Result<()> {
+        self.logger.set_level_filter(built.level_filter());
+        self.logger
+            .set_flush_level_filter(built.flush_level_filter());
+        self.sinks.swap(built.sinks().to_vec())
+    }
+}
+
+// Forwards records to the configured sinks of an installed logger.
+struct SwappableSinks {
+    sinks: ArcSwap<Sinks>,
+    // Whether the sinks are counted as opened by this sink. Locked while
+    // swapping, so that opening and closing see the current sinks.
+    opened: Mutex<bool>,
+    level_filter: Atomic<LevelFilter>,
+}
+
+impl SwappableSinks {
+    #[must_use]
+    fn new(sinks: Sinks) -> Self {
+        Self {
+            sinks: ArcSwap::new(Arc::new(sinks)),
+            opened: Mutex::new(false),
+            level_filter: Atomic::new(LevelFilter::All),
+        }
+    }
+
+    // Replaces the sinks. The new sinks are expected to be opened by the built
+    // logger, so they are not opened again, and the old sinks are flushed and
+    // closed if no longer used by any logger.
+    fn swap(&self, sinks: Sinks) -> Result<()> {
+        let opened = self.opened.lock_expect();
+        if *opened {
+            lifecycle::acquire(&sinks)?;
+        }
+        let previous = self.sinks.swap(Arc::new(sinks));
+        let mut errors = previous
+            .iter()
+            .filter_map(|sink| sink.flush().err())
+            .collect::<Vec<_>>();
+        if *opened {
+            lifecycle::release(&previous, |err| errors.push(err));
+        }
+        errors.into_iter().fold(Ok(()), Error::push_err)
+    }
+
+    fn for_each(&self, mut f: impl FnMut(&Arc<dyn Sink>) -> Result<()>) -> Result<()> {
+        #[allow(clippy::manual_try_fold)] // https://github.com/rust-lang/rust-clippy/issues/11554
+        self.sinks
+            .load()
+            .iter()
+            .fold(Ok(()), |result, sink| Error::push_result(result, f(sink)))
+    }
+}
+
+impl Sink for SwappableSinks {
+    fn log(&self, record: &Record) -> Result<()> {
+        self.for_each(|sink| {
+            if sink.should_log(record.level()) {
+                sink.log(record)
+            } else {
+                Ok(())
+            }
+        })
+    }
+
+    fn flush(&self) -> Result<()> {
+        self.for_each(|sink| sink.flush())
+    }
+
+    fn sync(&self) -> Result<()> {
+        self.for_each(|sink| sink.sync())
+    }
+
+    #[cfg(feature = "log-async")]
+    fn poll_capacity(&self, cx: &mut std::task::Context<'_>) -> std::task::Poll<()> {
+        crate::sink::poll_capacity(self.sinks.load().iter(), cx)
+    }
+
+    fn health(&self) -> Result<()> {
+        self.for_each(|sink| sink.health())
+    }
+
+    fn open(&self) -> Result<()> {
+        let mut opened = self.opened.lock_expect();
+        if !*opened {
+            lifecycle::acquire(&self.sinks.load())?;
+            *opened = true;
+        }
+        Ok(())
+    }
+
+    fn close(&self) -> Result<()> {
+        let mut opened = self.opened.lock_expect();
+        let mut errors = vec![];
+        if *opened {
+            *opened = false;
+            lifecycle::release(&self.sinks.load(), |err| errors.push(err));
+        }
+        errors.into_iter().fold(Ok(()), Error::push_err)
+    }
+
+    fn level_filter(&self) -> LevelFilter {
+        self.level_filter.load(Ordering::Relaxed)
+    }
+
+    fn set_level_filter(&self, level_filter: LevelFilter) {
+        self.level_filter.store(level_filter, Ordering::Relaxed);
+    }
+
+    // Formatters are configured per sink in the configuration file.
+    fn set_formatter(&self, _formatter: Box<dyn Formatter>) {}
+
+    fn set_error_handler(&self, handler: Option<ErrorHandler>) {
+        self.sinks
+            .load()
+            .iter()
+            .for_each(|sink| sink.set_error_handler(handler));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{prelude::*, test_utils::*};
+
+    #[test]
+    fn reload() {
+        fs::create_dir_all(TEST_LOGS_PATH.as_path()).unwrap();
+        let config_path = TEST_LOGS_PATH.join("config_watcher.yaml");
+        let log_path = TEST_LOGS_PATH.join("config_watcher.log");
+        let other_log_path = TEST_LOGS_PATH.join("config_watcher_other.log");
+        let write_config = |level: &str, pattern: &str, sinks: &str| {
+            let yaml = format!(
+                "sinks:\n  file:\n    kind: file\n    path: {:?}\n    truncate: true\n    pattern: {:?}\n  other:\n    kind: file\n    path: {:?}\n    truncate: true\n    pattern: \"{{payload}}{{eol}}\"\nloggers:\n  config_watcher_test:\n    level: {}\n    sinks: [{}]\n",
+                log_path, pattern, other_log_path, level, sinks
+            );
+            fs::write(&config_path, yaml).unwrap();
+        };
+        let read_log = |path| fs::read_to_string(path).unwrap();
+
+        write_config("info", "{payload}{eol}", "file");
+        let watcher = ConfigWatcher::new(&config_path, Duration::from_secs(3600)).unwrap();
+        let logger = crate::get_logger("config_watcher_test").unwrap();
+        assert_eq!(
+            logger.level_filter(),
+            LevelFilter::MoreSevereEqual(Level::Info)
+        );
+
+        // Levels and patterns are updated in place.
+        write_config("debug", "[{level}] {payload}{eol}", "file");
+        watcher.reload().unwrap();
+        let reloaded = crate::get_logger("config_watcher_test").unwrap();
+        assert!(Arc::ptr_eq(&logger, &reloaded));
+        assert_eq!(
+            logger.level_filter(),
+            LevelFilter::MoreSevereEqual(Level::Debug)
+        );
+        debug!(logger: logger, "hello");
+        logger.flush();
+        assert_eq!(
+            read_log(&log_path),
+            format!("[debug] hello{}", crate::__EOL)
+        );
+
+        // Invalid configurations change nothing, not even the reused sinks.
+        write_config("loud", "{payload}{eol}", "other");
+        assert!(watcher.reload().is_err());
+        assert!(Arc::ptr_eq(
+            &logger,
+            &crate::get_logger("config_watcher_test").unwrap()
+        ));
+        info!(logger: logger, "unchanged");
+        logger.flush();
+        assert_eq!(
+            read_log(&log_path),
+            format!("[debug] hello{0}[info] unchanged{0}", crate::__EOL)
+        );
+
+        // Sinks are swapped in place.
+        write_config("warn", "{payload}{eol}", "file, other");
+        watcher.reload().unwrap();
+        assert!(Arc::ptr_eq(
+            &logger,
+            &crate::get_logger("config_watcher_test").unwrap()
+        ));
+        warn!(logger: logger, "both");
+        write_config("warn", "{payload}{eol}", "other");
+        watcher.reload().unwrap();
+        warn!(logger: logger, "other only");
+        logger.flush();
+        assert_eq!(
+            read_log(&log_path),
+            format!("[debug] hello{0}[info] unchanged{0}both{0}", crate::__EOL)
+        );
+        assert_eq!(
+            read_log(&other_log_path),
+            format!("both{0}other only{0}", crate::__EOL)
+        );
+
+        // Loggers replaced by others are installed again.
+        let replacement = Arc::new(build_test_logger(|b| b.name("config_watcher_test")));
+        crate::register_logger(replacement.clone()).unwrap();
+        watcher.reload().unwrap();
+        let installed = crate::get_logger("config_watcher_test").unwrap();
+        assert!(!Arc::ptr_eq(&installed, &replacement));
+        assert!(!Arc::ptr_eq(&installed, &logger));
+
+        crate::unregister_logger("config_watcher_test");
+    }
+}
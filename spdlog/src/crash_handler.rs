@@ -0,0 +1,261 @@
+use std::{
+    fmt, io, ptr,
+    sync::atomic::{AtomicBool, AtomicPtr},
+};
+
+use crate::{sink::EmergencySink, sync::*, Level, Logger, Record};
+
+/// Installs a handler flushing loggers when the program crashes.
+///
+/// On Unix, the handler is installed for signals `SIGSEGV`, `SIGBUS`,
+/// `SIGABRT`, `SIGILL` and `SIGFPE`. On Windows, it is installed as the
+/// unhandled exception filter, which catches structured exceptions such as
+/// access violations.
+///
+/// When the program crashes, the handler first writes a final [`Critical`]
+/// record marking the crash to the [emergency sink] if one is set, which is
+/// safe in a crashing program. Then it logs the record as well and flushes the
+/// [default logger] and all loggers in the [registry]. Then the previously
+/// installed handler, or the default one of the system, runs as if this handler
+/// were not installed, e.g. to terminate the program and produce a core dump.
+///
+/// Calling this function again has no effect.
+///
+/// # Caveats
+///
+/// Logging to loggers and flushing them are not async-signal-safe, they
+/// allocate memory and acquire locks. If the program crashes while a logger or
+/// sink is in an inconsistent state, e.g. in the middle of writing a record,
+/// the handler may deadlock or crash again instead of flushing. This function
+/// is a best-effort attempt to not lose the last logs, do not rely on it for
+/// anything else.
+///
+/// Records kept in the queue of an asynchronous sink are not written by the
+/// handler.
+///
+/// # Error
+///
+/// If the handler fails to install, the OS error will be returned.
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> std::io::Result<()> {
+/// spdlog::install_crash_handler()?;
+/// # Ok(()) }
+/// ```
+///
+/// [`Critical`]: crate::Level::Critical
+/// [emergency sink]: set_emergency_sink
+/// [default logger]: crate::default_logger
+/// [registry]: crate::register_logger
+pub fn install_crash_handler() -> io::Result<()> {
+    platform::install()
+}
+
+static EMERGENCY_SINK: AtomicPtr<EmergencySink> = AtomicPtr::new(ptr::null_mut());
+
+/// Sets the sink the [crash handler] and the panic hook installed by [`init`]
+/// write their final records to.
+///
+/// The sink is written to before anything else is attempted, so that the
+/// record marking a crash or panic is not lost even if logging to loggers
+/// fails afterward.
+///
+/// The sink is never dropped, even if it is replaced by setting another one,
+/// since a crash handler may still be writing to it.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::sync::Arc;
+///
+/// use spdlog::sink::EmergencySink;
+///
+/// # fn main() -> Result<(), spdlog::Error> {
+/// let sink = EmergencySink::builder().path("logs/crash.log").build()?;
+/// spdlog::set_emergency_sink(Arc::new(sink));
+/// # Ok(()) }
+/// ```
+///
+/// [crash handler]: install_crash_handler
+/// [`init`]: crate::init
+pub fn set_emergency_sink(sink: Arc<EmergencySink>) {
+    // Leaks the previous sink intentionally.
+    EMERGENCY_SINK.store(Arc::into_raw(sink) as *mut _, Ordering::SeqCst);
+}
+
+#[must_use]
+pub(crate) fn emergency_sink() -> Option<&'static EmergencySink> {
+    // SAFETY: The pointer comes from `Arc::into_raw` and is never released.
+    unsafe { EMERGENCY_SINK.load(Ordering::SeqCst).as_ref() }
+}
+
+static IS_CRASHING: AtomicBool = AtomicBool::new(false);
+
+fn flush_loggers_on_crash(description: fmt::Arguments) {
+    // A crash in the handler itself must not run it again.
+    if IS_CRASHING.swap(true, Ordering::SeqCst) {
+        return;
+    }
+
+    if let Some(sink) = emergency_sink() {
+        _ = sink.write_message(
+            Level::Critical,
+            format_args!("program crashed: {}", description),
+        );
+    }
+
+    // Background threads of asynchronous sinks may never run again.
+    crate::IS_TEARING_DOWN.store(true, Ordering::SeqCst);
+
+    let default_logger = crate::DEFAULT_LOGGER
+        .get()
+        .map(|default_logger| default_logger.load_full());
+    let mut loggers = crate::registry::try_registered_loggers();
+    loggers.extend(default_logger);
+    // A logger may be both registered and the default one.
+    let mut flushed: Vec<&Arc<Logger>> = vec![];
+
+    let payload = format!("program crashed: {}", description);
+    for logger in &loggers {
+        if flushed.iter().any(|flushed| Arc::ptr_eq(flushed, logger)) {
+            continue;
+        }
+        logger.log(&Record::new(
+            Level::Critical,
+            payload.as_str(),
+            None,
+            logger.name(),
+        ));
+        logger.flush();
+        flushed.push(logger);
+    }
+}
+
+#[cfg(unix)]
+mod platform {
+    use std::{
+        io, mem,
+        os::raw::{c_int, c_void},
+        ptr,
+    };
+
+    use crate::sync::*;
+
+    const SIGNALS: [(c_int, &str); 5] = [
+        (libc::SIGSEGV, "SIGSEGV"),
+        (libc::SIGBUS, "SIGBUS"),
+        (libc::SIGABRT, "SIGABRT"),
+        (libc::SIGILL, "SIGILL"),
+        (libc::SIGFPE, "SIGFPE"),
+    ];
+
+    type SignalHandler = extern "C" fn(c_int, *mut libc::siginfo_t, *mut c_void);
+
+    struct PreviousActions([libc::sigaction; SIGNALS.len()]);
+
+    // SAFETY: The actions are only read after they are stored.
+    unsafe impl Send for PreviousActions {}
+    unsafe impl Sync for PreviousActions {}
+
+    static PREVIOUS_ACTIONS: OnceCell<PreviousActions> = OnceCell::new();
+
+    pub(super) fn install() -> io::Result<()> {
+        PREVIOUS_ACTIONS.get_or_try_init(|| unsafe {
+            let mut action: libc::sigaction = mem::zeroed();
+            action.sa_sigaction = handle_signal as SignalHandler as usize;
+            // Run on the alternate stack set up by std if there is one, so that
+            // stack overflows can be handled as well.
+            action.sa_flags = libc::SA_SIGINFO | libc::SA_ONSTACK;
+            libc::sigemptyset(&mut action.sa_mask);
+
+            let mut previous: [libc::sigaction; SIGNALS.len()] = mem::zeroed();
+            for ((signal, _), previous) in SIGNALS.iter().zip(previous.iter_mut()) {
+                if libc::sigaction(*signal, &action, previous) != 0 {
+                    return Err(io::Error::last_os_error());
+                }
+            }
+            Ok(PreviousActions(previous))
+        })?;
+        Ok(())
+    }
+
+    extern "C" fn handle_signal(signal: c_int, info: *mut libc::siginfo_t, _: *mut c_void) {
+        let index = SIGNALS.iter().position(|(s, _)| *s == signal);
+
+        if let Some(index) = index {
+            super::flush_loggers_on_crash(format_args!("received signal {}", SIGNALS[index].1));
+        }
+
+        unsafe {
+            // The signal may arrive before the previous actions are stored.
+            match (index, PREVIOUS_ACTIONS.get()) {
+                (Some(index), Some(previous)) => {
+                    libc::sigaction(signal, &previous.0[index], ptr::null_mut());
+                }
+                _ => {
+                    libc::signal(signal, libc::SIG_DFL);
+                }
+            }
+
+            // A fault raised by an instruction will be raised again by the same
+            // instruction after returning. A signal sent by a process, e.g. by
+            // `abort` or `kill`, has to be raised again explicitly, it will be
+            // delivered after returning since it is blocked in the handler.
+            if info.is_null() || (*info).si_code <= 0 {
+                libc::raise(signal);
+            }
+        }
+    }
+}
+
+#[cfg(windows)]
+mod platform {
+    use std::io;
+
+    use winapi::{
+        shared::ntdef::LONG,
+        um::{
+            errhandlingapi::{SetUnhandledExceptionFilter, LPTOP_LEVEL_EXCEPTION_FILTER},
+            winnt::EXCEPTION_POINTERS,
+        },
+    };
+
+    use crate::sync::*;
+
+    const EXCEPTION_CONTINUE_SEARCH: LONG = 0;
+
+    static PREVIOUS_FILTER: OnceCell<LPTOP_LEVEL_EXCEPTION_FILTER> = OnceCell::new();
+
+    pub(super) fn install() -> io::Result<()> {
+        PREVIOUS_FILTER
+            .get_or_init(|| unsafe { SetUnhandledExceptionFilter(Some(handle_exception)) });
+        Ok(())
+    }
+
+    unsafe extern "system" fn handle_exception(info: *mut EXCEPTION_POINTERS) -> LONG {
+        let code = if info.is_null() || (*info).ExceptionRecord.is_null() {
+            0
+        } else {
+            (*(*info).ExceptionRecord).ExceptionCode
+        };
+        super::flush_loggers_on_crash(format_args!("unhandled exception {:#010x}", code));
+
+        match PREVIOUS_FILTER.get().copied().flatten() {
+            Some(previous) => previous(info),
+            None => EXCEPTION_CONTINUE_SEARCH,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn install_twice() {
+        install_crash_handler().unwrap();
+        install_crash_handler().unwrap();
+    }
+}
@@ -0,0 +1,160 @@
+//! Provides an opt-in channel for internal diagnostics of the crate.
+//!
+//! By default, errors occurring in sinks only surface through the error
+//! handlers of loggers and sinks, and other internal events are not reported
+//! at all. After setting a diagnostics logger via [`set_logger`], the crate
+//! also reports the following events to it:
+//!
+//! | Event                               | Level   |
+//! |-------------------------------------|---------|
+//! | Errors occurring in sinks           | `Error` |
+//! | Records dropped by async sinks      | `Warn`  |
+//! | Files rotated by rotating sinks     | `Info`  |
+//! | Old files removed by rotating sinks | `Info`  |
+//!
+//! Events are reported with the name of the reporting component as a prefix
+//! of the payload, e.g. `[Logger (app)] ...`.
+//!
+//! The diagnostics logger should use sinks that are unlikely to fail, such as
+//! [`StdStreamSink`]. Events occurring while reporting an event are not
+//! reported again, so a failing diagnostics logger does not cause infinite
+//! recursion.
+//!
+//! # Examples
+//!
+//! ```
+//! use std::sync::Arc;
+//!
+//! use spdlog::{diagnostics, prelude::*, sink::StdStreamSink};
+//!
+//! # fn main() -> Result<(), spdlog::Error> {
+//! let sink = Arc::new(
+//!     StdStreamSink::builder()
+//!         .std_stream(spdlog::sink::StdStream::Stderr)
+//!         .build()?,
+//! );
+//! let logger = Logger::builder()
+//!     .name("spdlog-diagnostics")
+//!     .sink(sink)
+//!     .level_filter(LevelFilter::All)
+//!     .build()?;
+//! diagnostics::set_logger(Some(Arc::new(logger)));
+//! # Ok(()) }
+//! ```
+//!
+//! [`StdStreamSink`]: crate::sink::StdStreamSink
+
+use std::{cell::Cell, fmt};
+
+use crate::{sync::*, Error, Level, Logger};
+
+static DIAGNOSTICS_LOGGER: Lazy<ArcSwapOption<Logger>> = Lazy::new(ArcSwapOption::empty);
+
+thread_local! {
+    static REPORTING: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Sets the diagnostics logger, returns the previous one.
+///
+/// Pass `None` to disable reporting diagnostics, which is the default.
+pub fn set_logger(logger: Option<Arc<Logger>>) -> Option<Arc<Logger>> {
+    DIAGNOSTICS_LOGGER.swap(logger)
+}
+
+/// Gets the diagnostics logger.
+#[must_use]
+pub fn logger() -> Option<Arc<Logger>> {
+    DIAGNOSTICS_LOGGER.load_full()
+}
+
+#[must_use]
+pub(crate) fn is_enabled() -> bool {
+    DIAGNOSTICS_LOGGER.load().is_some()
+}
+
+pub(crate) fn report(level: Level, from: &str, args: fmt::Arguments) {
+    let logger = DIAGNOSTICS_LOGGER.load();
+    let logger = match logger.as_ref() {
+        Some(logger) if logger.should_log(level) => logger,
+        _ => return,
+    };
+
+    REPORTING.with(|reporting| {
+        if reporting.replace(true) {
+            return;
+        }
+        crate::__log(
+            logger,
+            level,
+            None,
+            None,
+            format_args!("[{}] {}", from, args),
+        );
+        reporting.set(false);
+    });
+}
+
+pub(crate) fn report_error(from: &str, err: &Error) {
+    match err {
+        Error::Multiple(errs) => errs.iter().for_each(|err| report_error(from, err)),
+        #[cfg(feature = "multi-thread")]
+        Error::SendToChannel(_, crate::error::SendToChannelErrorDropped::Record(_)) => {
+            report(Level::Warn, from, format_args!("record dropped: {}", err))
+        }
+        _ => report(Level::Error, from, format_args!("sink error: {}", err)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{prelude::*, test_utils::*};
+
+    #[test]
+    fn report_events() {
+        const FROM: &str = "diagnostics test";
+
+        let diag_sink = Arc::new(TestSink::new());
+        let diag_logger = Arc::new(build_test_logger(|b| {
+            b.sink(diag_sink.clone()).level_filter(LevelFilter::All)
+        }));
+
+        set_logger(Some(diag_logger));
+
+        report_error(
+            FROM,
+            &Error::Multiple(vec![
+                Error::FlushBuffer(std::io::Error::new(std::io::ErrorKind::Other, "1")),
+                Error::FlushBuffer(std::io::Error::new(std::io::ErrorKind::Other, "2")),
+            ]),
+        );
+        report(Level::Trace, FROM, format_args!("{}", 3));
+
+        set_logger(None);
+        report(Level::Info, FROM, format_args!("disabled"));
+
+        // Other tests may report events concurrently.
+        let records = diag_sink
+            .records()
+            .into_iter()
+            .filter(|record| record.payload().starts_with("[diagnostics test]"))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            records
+                .iter()
+                .map(|record| (record.level(), record.payload()))
+                .collect::<Vec<_>>(),
+            [
+                (
+                    Level::Error,
+                    "[diagnostics test] sink error: flush buffer error: 1"
+                ),
+                (
+                    Level::Error,
+                    "[diagnostics test] sink error: flush buffer error: 2"
+                ),
+                (Level::Trace, "[diagnostics test] 3"),
+            ]
+        );
+    }
+}
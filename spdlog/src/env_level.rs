@@ -11,6 +11,10 @@ pub(crate) type EnvLevel = HashMap<EnvLevelLogger, LevelFilter>;
 
 static ENV_LEVEL: Lazy<RwLock<Option<EnvLevel>>> = Lazy::new(|| RwLock::new(None));
 
+pub(crate) type EnvSinkLevels = HashMap<String, LevelFilter>;
+
+static ENV_SINK_LEVELS: Lazy<RwLock<Option<EnvSinkLevels>>> = Lazy::new(|| RwLock::new(None));
+
 #[derive(Clone, Eq, PartialEq, Hash, Debug)]
 pub(crate) enum EnvLevelLogger {
     Default,
@@ -134,6 +138,60 @@ pub(crate) fn logger_level_inner(env_level: &EnvLevel, kind: LoggerKind) -> Opti
     Some(*level)
 }
 
+pub(crate) fn sinks_from_str(var: &str) -> Result<(), EnvLevelError> {
+    let env_sink_levels = sinks_from_str_inner(var)?;
+    *ENV_SINK_LEVELS.write_expect() = Some(env_sink_levels);
+    Ok(())
+}
+
+pub(crate) fn sinks_from_str_inner(var: &str) -> Result<EnvSinkLevels, EnvLevelError> {
+    (|| {
+        let mut env_sink_levels = EnvSinkLevels::new();
+
+        for kv_str in var.split(',').map(str::trim) {
+            if kv_str.is_empty() {
+                continue;
+            }
+
+            let mut kv = kv_str.split('=').map(str::trim);
+            let (sink_name, level) = match (kv.next(), kv.next(), kv.next()) {
+                (Some(sink_name), Some(level), None) if !sink_name.is_empty() => {
+                    match LevelFilter::from_str_for_env(level) {
+                        Some(level) => (sink_name, level),
+                        None => {
+                            return Err(format!(
+                                "cannot parse level for sink '{}': '{}'",
+                                sink_name, kv_str
+                            ))
+                        }
+                    }
+                }
+                _ => return Err(format!("invalid kv: '{}'", kv_str)),
+            };
+
+            match env_sink_levels.entry(sink_name.into()) {
+                Entry::Occupied(_) => {
+                    return Err(format!("specified level multiple times: '{}'", kv_str));
+                }
+                Entry::Vacant(entry) => entry.insert(level),
+            };
+        }
+
+        Ok(env_sink_levels)
+    })()
+    .map_err(EnvLevelError::ParseEnvVar)
+}
+
+#[must_use]
+pub(crate) fn sink_level(sink_name: &str) -> Option<LevelFilter> {
+    ENV_SINK_LEVELS
+        .read()
+        .unwrap()
+        .as_ref()?
+        .get(sink_name)
+        .copied()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -280,4 +338,23 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn sinks_validation() {
+        let mut env_sink_levels = HashMap::new();
+        env_sink_levels.insert(
+            "console".to_string(),
+            LevelFilter::MoreSevereEqual(Level::Warn),
+        );
+        env_sink_levels.insert("file".to_string(), LevelFilter::All);
+        assert_eq!(
+            sinks_from_str_inner(" console=wArn, file = all ,").unwrap(),
+            env_sink_levels
+        );
+
+        assert!(sinks_from_str_inner("warn").is_err());
+        assert!(sinks_from_str_inner("=warn").is_err());
+        assert!(sinks_from_str_inner("console=loud").is_err());
+        assert!(sinks_from_str_inner("console=warn,console=info").is_err());
+    }
 }
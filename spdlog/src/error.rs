@@ -72,6 +72,21 @@ pub enum Error {
     #[error("remove file error: {0}")]
     RemoveFile(io::Error),
 
+    /// Passed to error handlers of [`RotatingFileSink`] when an error occurs in
+    /// compressing a rotated file.
+    ///
+    /// [`RotatingFileSink`]: crate::sink::RotatingFileSink
+    #[cfg(feature = "gzip")]
+    #[error("compress file error: {0}")]
+    CompressFile(io::Error),
+
+    /// Returned by [`Sink`]s when an error occurs in connecting to a remote
+    /// target, including resolving its address.
+    ///
+    /// [`Sink`]: crate::sink::Sink
+    #[error("connect error: {0}")]
+    Connect(io::Error),
+
     /// Returned by [`from_str`] when the string doesn't match any of the log
     /// levels.
     ///
@@ -105,6 +120,47 @@ pub enum Error {
     #[error("failed to serialize log: {0}")]
     SerializeRecord(io::Error),
 
+    /// Returned by [`Receiver`] when an error occurs in receiving records, e.g.
+    /// accepting a connection or reading from a socket.
+    ///
+    /// [`Receiver`]: crate::receiver::Receiver
+    #[cfg(feature = "receiver")]
+    #[error("failed to receive record: {0}")]
+    ReceiveRecord(io::Error),
+
+    /// Returned by [`Receiver`] when the received data is not a valid record.
+    ///
+    /// [`Receiver`]: crate::receiver::Receiver
+    #[cfg(feature = "receiver")]
+    #[error("failed to decode record: {0}")]
+    DecodeRecord(serde_json::Error),
+
+    /// Returned by [`LogReader`] when an error occurs in reading log text.
+    ///
+    /// [`LogReader`]: crate::reader::LogReader
+    #[error("failed to read record: {0}")]
+    ReadRecord(io::Error),
+
+    /// Returned by [`LogReader`] when a line cannot be parsed as a record.
+    ///
+    /// [`LogReader`]: crate::reader::LogReader
+    #[error("failed to parse record: {0}")]
+    ParseRecord(String),
+
+    /// Passed to error handlers when a sink recovered from consecutive errors,
+    /// e.g. [`FileSink`] reopened its file. It is never returned.
+    ///
+    /// [`FileSink`]: crate::sink::FileSink
+    #[error("recovered from errors: {0}")]
+    Recovered(String),
+
+    /// Returned by [`config`] when an error occurs in loading a configuration.
+    ///
+    /// [`config`]: crate::config
+    #[cfg(feature = "config")]
+    #[error("failed to load config: {0}")]
+    LoadConfig(LoadConfigError),
+
     /// Returned when multiple errors occurred.
     #[error("{0:?}")]
     Multiple(Vec<Error>),
@@ -128,6 +184,13 @@ pub enum InvalidArgumentError {
     #[error("'logger name': {0}")]
     LoggerName(#[from] SetLoggerNameError),
 
+    /// A logger without a name was specified where a name is required, e.g.
+    /// when registering it (see [`register_logger`]).
+    ///
+    /// [`register_logger`]: crate::register_logger
+    #[error("'logger': the logger has no name")]
+    UnnamedLogger,
+
     /// Invalid [`RotationPolicy`].
     ///
     /// See the documentation of [`RotationPolicy`] for the input requirements.
@@ -139,6 +202,35 @@ pub enum InvalidArgumentError {
     /// Invalid thread pool capacity.
     #[error("'thread pool capacity': {0}")]
     ThreadPoolCapacity(String),
+
+    /// Invalid capacity.
+    #[error("'capacity': {0}")]
+    Capacity(String),
+
+    /// Invalid batch configuration.
+    ///
+    /// See the documentation of [`BatchConfig`] for the requirements.
+    ///
+    /// [`BatchConfig`]: crate::sink::BatchConfig
+    #[error("'batch config': {0}")]
+    BatchConfig(String),
+
+    /// Invalid journald field.
+    ///
+    /// See the documentation of [`JournaldSinkBuilder::field`] for the field
+    /// name requirements.
+    ///
+    /// [`JournaldSinkBuilder::field`]: crate::sink::JournaldSinkBuilder::field
+    #[error("'journald field': {0}")]
+    JournaldField(String),
+
+    /// Invalid time zone.
+    #[error("'time zone': {0}")]
+    TimeZone(String),
+
+    /// Invalid regular expression.
+    #[error("'regex': {0}")]
+    Regex(String),
 }
 
 /// Indicates that an invalid logger name was set.
@@ -177,9 +269,12 @@ impl Display for SetLoggerNameError {
 pub enum SendToChannelError {
     /// The channel is full.
     ///
-    /// The variant returned only when [`OverflowPolicy::DropIncoming`] is used.
+    /// The variant returned only when [`OverflowPolicy::DropIncoming`] is used,
+    /// or reported to error handlers when [`OverflowPolicy::DropOldest`] is
+    /// used.
     ///
     /// [`OverflowPolicy::DropIncoming`]: crate::sink::async_sink::OverflowPolicy::DropIncoming
+    /// [`OverflowPolicy::DropOldest`]: crate::sink::async_sink::OverflowPolicy::DropOldest
     #[error("the channel is full")]
     Full,
 
@@ -199,6 +294,8 @@ pub enum SendToChannelErrorDropped {
     Record(Box<RecordOwned>), // Boxed because `RecordOwned` is a bit large.
     /// A `flush` operation is dropped.
     Flush,
+    /// A `close` operation is dropped.
+    Close,
 }
 
 impl Error {
@@ -250,7 +347,12 @@ impl SendToChannelErrorDropped {
     pub(crate) fn from_task(task: Task) -> Self {
         match task {
             Task::Log { record, .. } => Self::Record(Box::new(record)),
-            Task::Flush { .. } => Self::Flush,
+            Task::Flush { .. } | Task::Sync { .. } => Self::Flush,
+            Task::Close { .. } => Self::Close,
+            // Sent with `ThreadPool::try_assign_task`, failures are not errors.
+            Task::LogPending { .. } => {
+                unreachable!("`LogPending` task is not assigned with errors")
+            }
         }
     }
 }
@@ -261,6 +363,81 @@ impl SendToChannelErrorDropped {
 #[error("{0}")]
 pub struct BuildPatternError(pub(crate) spdlog_internal::pattern_parser::Error);
 
+/// Indicates that an error occurred while loading a configuration.
+#[cfg(feature = "config")]
+#[derive(Error, Debug)]
+#[non_exhaustive]
+pub enum LoadConfigError {
+    /// The configuration cannot be deserialized.
+    #[error("parse error: {0}")]
+    Parse(String),
+
+    /// The configuration is deserialized, but contains invalid or unsupported
+    /// values.
+    #[error("invalid config: {0}")]
+    Invalid(String),
+
+    /// The configuration does not match the schema, e.g. it contains unknown
+    /// keys, unknown values of enumerations or values of wrong types.
+    #[error("schema error: {0}")]
+    Schema(ConfigSchemaError),
+}
+
+/// Indicates where and why a configuration does not match the schema.
+///
+/// It is displayed as `file:line:column: message`, or `line:column: message`
+/// if the configuration is not loaded from a file.
+#[cfg(feature = "config")]
+#[derive(Debug)]
+pub struct ConfigSchemaError {
+    pub(crate) file: Option<std::path::PathBuf>,
+    pub(crate) line: usize,
+    pub(crate) column: usize,
+    pub(crate) message: String,
+}
+
+#[cfg(feature = "config")]
+impl ConfigSchemaError {
+    /// Gets the path of the configuration file, or `None` if the
+    /// configuration is not loaded from a file.
+    #[must_use]
+    pub fn file(&self) -> Option<&std::path::Path> {
+        self.file.as_deref()
+    }
+
+    /// Gets the line number of the error, starting from `1`.
+    #[must_use]
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    /// Gets the column number of the error, starting from `1`.
+    #[must_use]
+    pub fn column(&self) -> usize {
+        self.column
+    }
+
+    /// Gets the description of the error, prefixed with the path of keys to
+    /// the erroneous value, e.g. `appenders.stdout: unknown field ...`.
+    #[must_use]
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+#[cfg(feature = "config")]
+impl Display for ConfigSchemaError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(file) = &self.file {
+            write!(f, "{}:", file.display())?;
+        }
+        write!(f, "{}:{}: {}", self.line, self.column, self.message)
+    }
+}
+
+#[cfg(feature = "config")]
+impl std::error::Error for ConfigSchemaError {}
+
 /// The result type of this crate.
 pub type Result<T> = result::Result<T, Error>;
 
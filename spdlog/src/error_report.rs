@@ -0,0 +1,194 @@
+use std::{error::Error as StdError, fmt};
+
+/// Renders an error with its chain of sources (and backtrace if available) as
+/// a single piece of text.
+///
+/// For errors implementing [`std::error::Error`], use [`ErrorReport::new`], or
+/// convert from an error trait object (e.g. `&*boxed_error`). The rendered
+/// text contains the error message followed by a `Caused by:`
+/// section listing the sources, in the same layout as `anyhow`.
+///
+/// With crate feature `anyhow` or `eyre` enabled, `ErrorReport` can also be
+/// converted from `&anyhow::Error` or `&eyre::Report`, in which case the
+/// report is rendered by the report type itself, including the backtrace if
+/// one was captured.
+///
+/// Usually this is used via the [`log_report!`] macro, so that the whole
+/// report ends up in a single record.
+///
+/// # Examples
+///
+/// ```
+/// use std::io;
+///
+/// use spdlog::{error, ErrorReport};
+///
+/// let err = io::Error::new(io::ErrorKind::Other, "disk unavailable");
+///
+/// error!("failed to save: {}", ErrorReport::new(&err));
+/// ```
+///
+/// [`log_report!`]: crate::log_report
+#[derive(Clone, Copy)]
+pub struct ErrorReport<'a> {
+    inner: ErrorReportInner<'a>,
+}
+
+#[derive(Clone, Copy)]
+enum ErrorReportInner<'a> {
+    Std(&'a (dyn StdError + 'static)),
+    #[cfg(feature = "anyhow")]
+    Anyhow(&'a anyhow::Error),
+    #[cfg(feature = "eyre")]
+    Eyre(&'a eyre::Report),
+}
+
+impl<'a> ErrorReport<'a> {
+    /// Constructs an `ErrorReport` for an error implementing
+    /// [`std::error::Error`].
+    #[must_use]
+    pub fn new(error: &'a (dyn StdError + 'static)) -> Self {
+        Self {
+            inner: ErrorReportInner::Std(error),
+        }
+    }
+}
+
+impl<'a> From<&'a (dyn StdError + 'static)> for ErrorReport<'a> {
+    fn from(error: &'a (dyn StdError + 'static)) -> Self {
+        Self::new(error)
+    }
+}
+
+impl<'a> From<&'a (dyn StdError + Send + Sync + 'static)> for ErrorReport<'a> {
+    fn from(error: &'a (dyn StdError + Send + Sync + 'static)) -> Self {
+        Self::new(error)
+    }
+}
+
+#[cfg(feature = "anyhow")]
+impl<'a> From<&'a anyhow::Error> for ErrorReport<'a> {
+    fn from(error: &'a anyhow::Error) -> Self {
+        Self {
+            inner: ErrorReportInner::Anyhow(error),
+        }
+    }
+}
+
+#[cfg(feature = "eyre")]
+impl<'a> From<&'a eyre::Report> for ErrorReport<'a> {
+    fn from(report: &'a eyre::Report) -> Self {
+        Self {
+            inner: ErrorReportInner::Eyre(report),
+        }
+    }
+}
+
+impl fmt::Display for ErrorReport<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.inner {
+            ErrorReportInner::Std(error) => fmt_std_error(error, f),
+            // The `Debug` implementations of report types render the chain and the
+            // backtrace, rather than the internal structure.
+            #[cfg(feature = "anyhow")]
+            ErrorReportInner::Anyhow(error) => write!(f, "{:?}", error),
+            #[cfg(feature = "eyre")]
+            ErrorReportInner::Eyre(report) => write!(f, "{:?}", report),
+        }
+    }
+}
+
+impl fmt::Debug for ErrorReport<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+fn fmt_std_error(error: &(dyn StdError + 'static), f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "{}", error)?;
+
+    let mut sources = vec![];
+    let mut next = error.source();
+    while let Some(source) = next {
+        sources.push(source);
+        next = source.source();
+    }
+    if sources.is_empty() {
+        return Ok(());
+    }
+
+    write!(f, "{eol}{eol}Caused by:", eol = crate::__EOL)?;
+    if sources.len() == 1 {
+        write!(f, "{}    {}", crate::__EOL, sources[0])?;
+    } else {
+        for (index, source) in sources.iter().enumerate() {
+            write!(f, "{}    {}: {}", crate::__EOL, index, source)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io;
+
+    use super::*;
+
+    #[derive(Debug)]
+    struct WrappedError(&'static str, Option<Box<dyn StdError + 'static>>);
+
+    impl fmt::Display for WrappedError {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            f.write_str(self.0)
+        }
+    }
+
+    impl StdError for WrappedError {
+        fn source(&self) -> Option<&(dyn StdError + 'static)> {
+            self.1.as_deref()
+        }
+    }
+
+    #[test]
+    fn std_error() {
+        let render = |error: &WrappedError| ErrorReport::new(error).to_string().replace('\r', "");
+
+        let root = || io::Error::new(io::ErrorKind::Other, "disk unavailable");
+        assert_eq!(render(&WrappedError("plain", None)), "plain");
+        assert_eq!(
+            render(&WrappedError("save failed", Some(Box::new(root())))),
+            "save failed\n\nCaused by:\n    disk unavailable"
+        );
+        assert_eq!(
+            render(&WrappedError(
+                "request failed",
+                Some(Box::new(WrappedError(
+                    "save failed",
+                    Some(Box::new(root()))
+                )))
+            )),
+            "request failed\n\nCaused by:\n    0: save failed\n    1: disk unavailable"
+        );
+    }
+
+    #[cfg(feature = "anyhow")]
+    #[test]
+    fn anyhow_error() {
+        let error = anyhow::anyhow!("disk unavailable").context("save failed");
+        let rendered = ErrorReport::from(&error).to_string();
+        assert!(rendered.starts_with("save failed\n\nCaused by:\n    disk unavailable"));
+    }
+
+    #[cfg(feature = "eyre")]
+    #[test]
+    fn eyre_report() {
+        use eyre::WrapErr;
+
+        let report = Err::<(), _>(eyre::eyre!("disk unavailable"))
+            .wrap_err("save failed")
+            .unwrap_err();
+        let rendered = ErrorReport::from(&report).to_string();
+        assert!(rendered.starts_with("save failed"));
+        assert!(rendered.contains("disk unavailable"));
+    }
+}
@@ -0,0 +1,440 @@
+//! Provides a C API for emitting logs from C/C++ code.
+//!
+//! This module is only available if crate feature `ffi` is enabled.
+//!
+//! All functions are exported with unmangled names, so they are visible to C
+//! code when the crate is linked into a `cdylib` or `staticlib`. A typical
+//! setup is a small wrapper crate with `crate-type = ["cdylib"]` that depends
+//! on `spdlog-rs` with feature `ffi` enabled and configures the loggers on the
+//! Rust side, so that C/C++ components emit into the same Rust-managed sinks.
+//!
+//! # Conventions
+//!
+//! - Levels are passed as `int`, see the `SPDLOG_RS_LEVEL_*` constants.
+//! - Functions that can fail return [`SPDLOG_RS_OK`] on success and
+//!   [`SPDLOG_RS_ERROR`] on failure, or a null pointer if they return a handle.
+//! - Handles returned by `*_new` / `spdlog_rs_sink_*` /
+//!   [`spdlog_rs_default_logger`] are owned by the caller and must be released
+//!   with the corresponding `*_free` function.
+//!
+//! # Examples
+//!
+//! ```c
+//! SpdlogRsSink *sink = spdlog_rs_sink_file("logs/app.log", false);
+//! const SpdlogRsSink *sinks[] = { sink };
+//! SpdlogRsLogger *logger = spdlog_rs_logger_new("app", sinks, 1);
+//! spdlog_rs_sink_free(sink);
+//!
+//! spdlog_rs_logger_log(logger, SPDLOG_RS_LEVEL_INFO, "hello from C", __FILE__, __LINE__);
+//! spdlog_rs_log(SPDLOG_RS_LEVEL_WARN, "to the default logger", __FILE__, __LINE__);
+//!
+//! spdlog_rs_logger_free(logger);
+//! ```
+
+use std::{
+    ffi::CStr,
+    os::raw::{c_char, c_int},
+    ptr,
+};
+
+use crate::{
+    default_logger,
+    sink::{FileSink, Sink, StdStream, StdStreamSink},
+    sync::*,
+    Level, LevelFilter, Logger, Record, SourceLocation,
+};
+
+/// Returned by functions on success.
+pub const SPDLOG_RS_OK: c_int = 0;
+/// Returned by functions on failure, e.g. invalid arguments.
+pub const SPDLOG_RS_ERROR: c_int = -1;
+
+/// Level value for [`Level::Critical`].
+pub const SPDLOG_RS_LEVEL_CRITICAL: c_int = 0;
+/// Level value for [`Level::Error`].
+pub const SPDLOG_RS_LEVEL_ERROR: c_int = 1;
+/// Level value for [`Level::Warn`].
+pub const SPDLOG_RS_LEVEL_WARN: c_int = 2;
+/// Level value for [`Level::Info`].
+pub const SPDLOG_RS_LEVEL_INFO: c_int = 3;
+/// Level value for [`Level::Debug`].
+pub const SPDLOG_RS_LEVEL_DEBUG: c_int = 4;
+/// Level value for [`Level::Trace`].
+pub const SPDLOG_RS_LEVEL_TRACE: c_int = 5;
+/// Level value for [`LevelFilter::Off`], only accepted by functions setting a
+/// level filter.
+pub const SPDLOG_RS_LEVEL_OFF: c_int = -1;
+
+/// An opaque handle to a [`Logger`].
+pub struct SpdlogRsLogger(Arc<Logger>);
+
+/// An opaque handle to a [`Sink`].
+pub struct SpdlogRsSink(Arc<dyn Sink>);
+
+/// Logs a message with the global default logger.
+///
+/// `file` and `line` are optional, pass a null pointer as `file` to log
+/// without a source location.
+///
+/// # Safety
+///
+/// - `msg` must be a valid nul-terminated string.
+/// - `file` must be null or a valid nul-terminated string that stays valid and
+///   unchanged for the rest of the process, such as `__FILE__`. It is borrowed
+///   by records rather than copied.
+#[no_mangle]
+pub unsafe extern "C" fn spdlog_rs_log(
+    level: c_int,
+    msg: *const c_char,
+    file: *const c_char,
+    line: u32,
+) -> c_int {
+    log_with(&default_logger(), level, msg, file, line)
+}
+
+/// Gets a handle to the current global default logger.
+///
+/// The returned handle must be released with [`spdlog_rs_logger_free`].
+#[no_mangle]
+pub extern "C" fn spdlog_rs_default_logger() -> *mut SpdlogRsLogger {
+    into_raw(SpdlogRsLogger(default_logger()))
+}
+
+/// Sets the given logger as the global default logger.
+///
+/// # Safety
+///
+/// `logger` must be a valid handle returned by this module.
+#[no_mangle]
+pub unsafe extern "C" fn spdlog_rs_set_default_logger(logger: *const SpdlogRsLogger) -> c_int {
+    match logger.as_ref() {
+        Some(logger) => {
+            crate::set_default_logger(logger.0.clone());
+            SPDLOG_RS_OK
+        }
+        None => SPDLOG_RS_ERROR,
+    }
+}
+
+/// Creates a logger with the given name and sinks.
+///
+/// `name` can be null for an unnamed logger. The sinks are shared with the
+/// logger, so the sink handles can be released right after this call.
+///
+/// Returns a null pointer if the arguments are invalid.
+///
+/// # Safety
+///
+/// - `name` must be null or a valid nul-terminated string.
+/// - `sinks` must point to `sinks_len` valid sink handles, or be null if
+///   `sinks_len` is 0.
+#[no_mangle]
+pub unsafe extern "C" fn spdlog_rs_logger_new(
+    name: *const c_char,
+    sinks: *const *const SpdlogRsSink,
+    sinks_len: usize,
+) -> *mut SpdlogRsLogger {
+    let mut builder = Logger::builder();
+
+    if !name.is_null() {
+        match CStr::from_ptr(name).to_str() {
+            Ok(name) => builder.name(name),
+            Err(_) => return ptr::null_mut(),
+        };
+    }
+
+    if sinks_len != 0 {
+        if sinks.is_null() {
+            return ptr::null_mut();
+        }
+        for sink in std::slice::from_raw_parts(sinks, sinks_len) {
+            match sink.as_ref() {
+                Some(sink) => builder.sink(sink.0.clone()),
+                None => return ptr::null_mut(),
+            };
+        }
+    }
+
+    match builder.build() {
+        Ok(logger) => into_raw(SpdlogRsLogger(Arc::new(logger))),
+        Err(_) => ptr::null_mut(),
+    }
+}
+
+/// Releases a logger handle.
+///
+/// Passing a null pointer is a no-op.
+///
+/// # Safety
+///
+/// `logger` must be null or a valid handle returned by this module, and must
+/// not be used after this call.
+#[no_mangle]
+pub unsafe extern "C" fn spdlog_rs_logger_free(logger: *mut SpdlogRsLogger) {
+    if !logger.is_null() {
+        drop(Box::from_raw(logger));
+    }
+}
+
+/// Logs a message with the given logger.
+///
+/// See [`spdlog_rs_log`] for the arguments.
+///
+/// # Safety
+///
+/// - `logger` must be a valid handle returned by this module.
+/// - `msg` must be a valid nul-terminated string.
+/// - `file` must be null or a valid nul-terminated string that stays valid and
+///   unchanged for the rest of the process, such as `__FILE__`. It is borrowed
+///   by records rather than copied.
+#[no_mangle]
+pub unsafe extern "C" fn spdlog_rs_logger_log(
+    logger: *const SpdlogRsLogger,
+    level: c_int,
+    msg: *const c_char,
+    file: *const c_char,
+    line: u32,
+) -> c_int {
+    match logger.as_ref() {
+        Some(logger) => log_with(&logger.0, level, msg, file, line),
+        None => SPDLOG_RS_ERROR,
+    }
+}
+
+/// Sets the level filter of the given logger.
+///
+/// Records with a level more severe or equal to `level` will be logged, pass
+/// [`SPDLOG_RS_LEVEL_OFF`] to disable all levels.
+///
+/// # Safety
+///
+/// `logger` must be a valid handle returned by this module.
+#[no_mangle]
+pub unsafe extern "C" fn spdlog_rs_logger_set_level(
+    logger: *const SpdlogRsLogger,
+    level: c_int,
+) -> c_int {
+    match (logger.as_ref(), level_filter_from_c(level)) {
+        (Some(logger), Some(level_filter)) => {
+            logger.0.set_level_filter(level_filter);
+            SPDLOG_RS_OK
+        }
+        _ => SPDLOG_RS_ERROR,
+    }
+}
+
+/// Flushes sinks of the given logger.
+///
+/// # Safety
+///
+/// `logger` must be a valid handle returned by this module.
+#[no_mangle]
+pub unsafe extern "C" fn spdlog_rs_logger_flush(logger: *const SpdlogRsLogger) -> c_int {
+    match logger.as_ref() {
+        Some(logger) => {
+            logger.0.flush();
+            SPDLOG_RS_OK
+        }
+        None => SPDLOG_RS_ERROR,
+    }
+}
+
+/// Creates a sink that writes to `stdout`.
+///
+/// The returned handle must be released with [`spdlog_rs_sink_free`].
+#[no_mangle]
+pub extern "C" fn spdlog_rs_sink_stdout() -> *mut SpdlogRsSink {
+    std_stream_sink(StdStream::Stdout)
+}
+
+/// Creates a sink that writes to `stderr`.
+///
+/// The returned handle must be released with [`spdlog_rs_sink_free`].
+#[no_mangle]
+pub extern "C" fn spdlog_rs_sink_stderr() -> *mut SpdlogRsSink {
+    std_stream_sink(StdStream::Stderr)
+}
+
+/// Creates a sink that writes to the given file.
+///
+/// Returns a null pointer if the file cannot be opened.
+///
+/// # Safety
+///
+/// `path` must be a valid nul-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn spdlog_rs_sink_file(
+    path: *const c_char,
+    truncate: bool,
+) -> *mut SpdlogRsSink {
+    let path = match str_from_c(path) {
+        Some(path) => path,
+        None => return ptr::null_mut(),
+    };
+
+    match FileSink::builder().path(path).truncate(truncate).build() {
+        Ok(sink) => into_raw(SpdlogRsSink(Arc::new(sink))),
+        Err(_) => ptr::null_mut(),
+    }
+}
+
+/// Sets the level filter of the given sink.
+///
+/// See [`spdlog_rs_logger_set_level`] for the meaning of `level`.
+///
+/// # Safety
+///
+/// `sink` must be a valid handle returned by this module.
+#[no_mangle]
+pub unsafe extern "C" fn spdlog_rs_sink_set_level(
+    sink: *const SpdlogRsSink,
+    level: c_int,
+) -> c_int {
+    match (sink.as_ref(), level_filter_from_c(level)) {
+        (Some(sink), Some(level_filter)) => {
+            sink.0.set_level_filter(level_filter);
+            SPDLOG_RS_OK
+        }
+        _ => SPDLOG_RS_ERROR,
+    }
+}
+
+/// Releases a sink handle.
+///
+/// Loggers that were created with this sink keep it alive. Passing a null
+/// pointer is a no-op.
+///
+/// # Safety
+///
+/// `sink` must be null or a valid handle returned by this module, and must not
+/// be used after this call.
+#[no_mangle]
+pub unsafe extern "C" fn spdlog_rs_sink_free(sink: *mut SpdlogRsSink) {
+    if !sink.is_null() {
+        drop(Box::from_raw(sink));
+    }
+}
+
+fn into_raw<T>(value: T) -> *mut T {
+    Box::into_raw(Box::new(value))
+}
+
+fn std_stream_sink(std_stream: StdStream) -> *mut SpdlogRsSink {
+    match StdStreamSink::builder().std_stream(std_stream).build() {
+        Ok(sink) => into_raw(SpdlogRsSink(Arc::new(sink))),
+        Err(_) => ptr::null_mut(),
+    }
+}
+
+#[must_use]
+fn level_from_c(level: c_int) -> Option<Level> {
+    usize::try_from(level).ok().and_then(Level::from_usize)
+}
+
+#[must_use]
+fn level_filter_from_c(level: c_int) -> Option<LevelFilter> {
+    if level == SPDLOG_RS_LEVEL_OFF {
+        Some(LevelFilter::Off)
+    } else {
+        level_from_c(level).map(LevelFilter::MoreSevereEqual)
+    }
+}
+
+#[must_use]
+unsafe fn str_from_c<'a>(s: *const c_char) -> Option<&'a str> {
+    if s.is_null() {
+        None
+    } else {
+        CStr::from_ptr(s).to_str().ok()
+    }
+}
+
+unsafe fn log_with(
+    logger: &Logger,
+    level: c_int,
+    msg: *const c_char,
+    file: *const c_char,
+    line: u32,
+) -> c_int {
+    let level = match level_from_c(level) {
+        Some(level) => level,
+        None => return SPDLOG_RS_ERROR,
+    };
+    if msg.is_null() {
+        return SPDLOG_RS_ERROR;
+    }
+    if !logger.should_log(level) {
+        return SPDLOG_RS_OK;
+    }
+
+    // The caller guarantees that `file` is valid for the rest of the process.
+    let srcloc = str_from_c::<'static>(file).map(|file| SourceLocation::__new("", file, line, 0));
+    let payload = CStr::from_ptr(msg).to_string_lossy();
+
+    logger.log(&Record::new(level, payload, srcloc, logger.name()));
+    SPDLOG_RS_OK
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{ffi::CString, fs};
+
+    use super::*;
+    use crate::test_utils::*;
+
+    #[test]
+    fn log_to_file_sink() {
+        let path = TEST_LOGS_PATH.join("ffi_file_sink.log");
+        let c_path = CString::new(path.to_str().unwrap()).unwrap();
+        let name = CString::new("ffi").unwrap();
+        // Like `__FILE__`, valid for the rest of the process.
+        static FILE: &[u8] = b"main.c\0";
+
+        unsafe {
+            let sink = spdlog_rs_sink_file(c_path.as_ptr(), true);
+            assert!(!sink.is_null());
+            let sinks = [sink as *const SpdlogRsSink];
+            let logger = spdlog_rs_logger_new(name.as_ptr(), sinks.as_ptr(), sinks.len());
+            spdlog_rs_sink_free(sink);
+            assert!(!logger.is_null());
+
+            for (level, msg) in [
+                (SPDLOG_RS_LEVEL_INFO, "hello"),
+                (SPDLOG_RS_LEVEL_TRACE, "filtered"),
+                (SPDLOG_RS_LEVEL_ERROR, "world"),
+            ] {
+                let msg = CString::new(msg).unwrap();
+                assert_eq!(
+                    spdlog_rs_logger_log(logger, level, msg.as_ptr(), FILE.as_ptr().cast(), 42),
+                    SPDLOG_RS_OK
+                );
+            }
+            let msg = CString::new("invalid").unwrap();
+            assert_eq!(
+                spdlog_rs_logger_log(logger, 6, msg.as_ptr(), ptr::null(), 0),
+                SPDLOG_RS_ERROR
+            );
+            assert_eq!(spdlog_rs_logger_set_level(logger, 100), SPDLOG_RS_ERROR);
+            assert_eq!(spdlog_rs_logger_flush(logger), SPDLOG_RS_OK);
+            spdlog_rs_logger_free(logger);
+        }
+
+        let content = fs::read_to_string(&path).unwrap();
+        let lines = content.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].ends_with("[ffi] [info] [main.c:42] hello"));
+        assert!(lines[1].ends_with("[ffi] [error] [main.c:42] world"));
+    }
+
+    #[test]
+    fn invalid_arguments() {
+        let name = CString::new("invalid=name").unwrap();
+        unsafe {
+            assert!(spdlog_rs_logger_new(name.as_ptr(), ptr::null(), 0).is_null());
+            assert!(spdlog_rs_logger_new(ptr::null(), ptr::null(), 1).is_null());
+            assert!(spdlog_rs_sink_file(ptr::null(), false).is_null());
+            assert_eq!(spdlog_rs_logger_flush(ptr::null()), SPDLOG_RS_ERROR);
+            spdlog_rs_logger_free(ptr::null_mut());
+        }
+    }
+}
@@ -0,0 +1,211 @@
+use crate::{sync::*, Record};
+
+/// The decision of a [`Filter`] on a record.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum FilterResult {
+    /// The record is kept, the remaining filters in the chain are skipped.
+    Accept,
+    /// The filter has no opinion, the record is passed to the next filter in
+    /// the chain, or kept if this is the last filter.
+    Neutral,
+    /// The record is dropped, the remaining filters in the chain are skipped.
+    Reject,
+}
+
+/// Decides whether records are kept, beyond their levels.
+///
+/// Filters are attached to a logger via [`LoggerBuilder::filter`], or to sinks
+/// via [`FilterSink`]. Multiple filters form a chain, they are evaluated in the
+/// order they were added until one of them accepts or rejects the record. A
+/// record no filter decides on is kept.
+///
+/// Filters only see records that passed the level filters, so they cannot make
+/// records of filtered out levels be logged.
+///
+/// Closures taking a [`Record`] and returning a [`FilterResult`] are filters.
+///
+/// # Examples
+///
+/// ```
+/// use spdlog::{prelude::*, FilterResult, ModulePathFilter, Record};
+///
+/// # fn main() -> Result<(), spdlog::Error> {
+/// let logger = Logger::builder()
+///     // Keeps all records from the `my_app::net` module and its submodules.
+///     .filter(ModulePathFilter::new("my_app::net"))
+///     // Drops the other records mentioning passwords.
+///     .filter(|record: &Record| {
+///         if record.payload().contains("password") {
+///             FilterResult::Reject
+///         } else {
+///             FilterResult::Neutral
+///         }
+///     })
+///     .build()?;
+/// # Ok(()) }
+/// ```
+///
+/// [`LoggerBuilder::filter`]: crate::LoggerBuilder::filter
+/// [`FilterSink`]: crate::sink::FilterSink
+pub trait Filter: Send + Sync {
+    /// Decides whether the record is kept.
+    fn filter(&self, record: &Record) -> FilterResult;
+}
+
+impl<F> Filter for F
+where
+    F: Fn(&Record) -> FilterResult + Send + Sync,
+{
+    fn filter(&self, record: &Record) -> FilterResult {
+        self(record)
+    }
+}
+
+/// Container type for [`Filter`]s.
+pub type Filters = Vec<Arc<dyn Filter>>;
+
+// Evaluates a filter chain, returns `true` if the record is kept.
+#[must_use]
+pub(crate) fn filter_chain(filters: &[Arc<dyn Filter>], record: &Record) -> bool {
+    filters
+        .iter()
+        .map(|filter| filter.filter(record))
+        .find(|result| *result != FilterResult::Neutral)
+        != Some(FilterResult::Reject)
+}
+
+/// Filters records by their module paths, see [`Record::module_path`].
+///
+/// A record matches if its module path is the prefix or one of its
+/// submodules, e.g. prefix `my_app::net` matches `my_app::net` and
+/// `my_app::net::http`, but not `my_app::network`.
+///
+/// By default, matched records are accepted and other records are passed to
+/// the next filter, the results are configurable with
+/// [`ModulePathFilter::on_match`] and [`ModulePathFilter::on_mismatch`].
+/// Records without module paths never match.
+///
+/// # Examples
+///
+/// ```
+/// use spdlog::{prelude::*, FilterResult, ModulePathFilter};
+///
+/// # fn main() -> Result<(), spdlog::Error> {
+/// // Only keeps records from `my_app`.
+/// let logger = Logger::builder()
+///     .filter(ModulePathFilter::new("my_app").on_mismatch(FilterResult::Reject))
+///     .build()?;
+///
+/// // Drops records from `hyper`.
+/// let logger = Logger::builder()
+///     .filter(ModulePathFilter::new("hyper").on_match(FilterResult::Reject))
+///     .build()?;
+/// # Ok(()) }
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct ModulePathFilter {
+    prefix: String,
+    on_match: FilterResult,
+    on_mismatch: FilterResult,
+}
+
+impl ModulePathFilter {
+    /// Constructs a `ModulePathFilter` matching the module path prefix.
+    #[must_use]
+    pub fn new<S>(prefix: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self {
+            prefix: prefix.into(),
+            on_match: FilterResult::Accept,
+            on_mismatch: FilterResult::Neutral,
+        }
+    }
+
+    /// Specifies the result for matched records.
+    #[must_use]
+    pub fn on_match(mut self, result: FilterResult) -> Self {
+        self.on_match = result;
+        self
+    }
+
+    /// Specifies the result for records not matched.
+    #[must_use]
+    pub fn on_mismatch(mut self, result: FilterResult) -> Self {
+        self.on_mismatch = result;
+        self
+    }
+}
+
+// Returns `true` if the module path is the prefix or one of its submodules.
+#[must_use]
+pub(crate) fn is_module_prefix(prefix: &str, module_path: &str) -> bool {
+    match module_path.strip_prefix(prefix) {
+        Some(rest) => rest.is_empty() || rest.starts_with("::"),
+        None => false,
+    }
+}
+
+impl Filter for ModulePathFilter {
+    fn filter(&self, record: &Record) -> FilterResult {
+        let module_path = record.module_path();
+        if module_path.map_or(false, |module_path| {
+            is_module_prefix(&self.prefix, module_path)
+        }) {
+            self.on_match
+        } else {
+            self.on_mismatch
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{prelude::*, test_utils::*, SourceLocation};
+
+    #[test]
+    fn chain() {
+        let record = |module_path, payload| {
+            Record::new(
+                Level::Info,
+                payload,
+                Some(SourceLocation::__new(module_path, "lib.rs", 1, 1)),
+                None,
+            )
+        };
+        let filters: Filters = vec![
+            Arc::new(ModulePathFilter::new("app::net")),
+            Arc::new(|record: &Record| {
+                if record.payload().contains("secret") {
+                    FilterResult::Reject
+                } else {
+                    FilterResult::Neutral
+                }
+            }),
+        ];
+
+        assert!(filter_chain(&filters, &record("app::net", "a secret")));
+        assert!(filter_chain(
+            &filters,
+            &record("app::net::http", "a secret")
+        ));
+        assert!(!filter_chain(&filters, &record("app::network", "a secret")));
+        assert!(filter_chain(&filters, &record("app::network", "hello")));
+        assert!(filter_chain(&[], &record("app", "hello")));
+
+        let test_sink = Arc::new(TestSink::new());
+        let test = build_test_logger(|b| {
+            b.sink(test_sink.clone())
+                .filter(|record: &Record| match record.level() {
+                    Level::Warn => FilterResult::Reject,
+                    _ => FilterResult::Neutral,
+                })
+        });
+        info!(logger: test, "info");
+        warn!(logger: test, "warn");
+        error!(logger: test, "error");
+        assert_eq!(test_sink.payloads(), ["info", "error"]);
+    }
+}
@@ -0,0 +1,164 @@
+use std::fmt::{self, Write};
+
+/// Represents how formatters render the raw bytes attached to a record.
+///
+/// See [`Record::bytes`] for attaching raw bytes.
+///
+/// [`Record::bytes`]: crate::Record::bytes
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[non_exhaustive]
+pub enum BytesEncoding {
+    /// Lowercase hexadecimal, e.g. `00ff10`.
+    Hex,
+    /// Base64 with the standard alphabet and padding (RFC 4648), e.g. `AP8Q`.
+    Base64,
+}
+
+impl BytesEncoding {
+    /// Writes the bytes encoded into `dest`.
+    pub(crate) fn write(&self, bytes: &[u8], dest: &mut impl Write) -> fmt::Result {
+        match self {
+            BytesEncoding::Hex => write_hex(bytes, dest),
+            BytesEncoding::Base64 => write_base64(bytes, dest),
+        }
+    }
+
+    /// Encodes the bytes into a string.
+    #[must_use]
+    pub fn encode(&self, bytes: &[u8]) -> String {
+        let mut encoded = String::new();
+        self.write(bytes, &mut encoded).unwrap();
+        encoded
+    }
+
+    /// Decodes the encoded string into bytes.
+    ///
+    /// Returns `None` if the string is not validly encoded.
+    #[must_use]
+    pub fn decode(&self, encoded: &str) -> Option<Vec<u8>> {
+        match self {
+            BytesEncoding::Hex => decode_hex(encoded),
+            BytesEncoding::Base64 => decode_base64(encoded),
+        }
+    }
+}
+
+fn write_hex(bytes: &[u8], dest: &mut impl Write) -> fmt::Result {
+    const DIGITS: &[u8; 16] = b"0123456789abcdef";
+
+    for byte in bytes {
+        dest.write_char(DIGITS[(byte >> 4) as usize] as char)?;
+        dest.write_char(DIGITS[(byte & 0xf) as usize] as char)?;
+    }
+    Ok(())
+}
+
+fn write_base64(bytes: &[u8], dest: &mut impl Write) -> fmt::Result {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    for chunk in bytes.chunks(3) {
+        let n = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |n, (i, byte)| n | (*byte as u32) << (16 - 8 * i));
+        for i in 0..4 {
+            if i <= chunk.len() {
+                dest.write_char(ALPHABET[(n >> (18 - 6 * i) & 0x3f) as usize] as char)?;
+            } else {
+                dest.write_char('=')?;
+            }
+        }
+    }
+    Ok(())
+}
+
+#[must_use]
+fn decode_hex(encoded: &str) -> Option<Vec<u8>> {
+    if encoded.len() % 2 != 0 {
+        return None;
+    }
+    encoded
+        .as_bytes()
+        .chunks(2)
+        .map(|pair| {
+            let digit = |byte: u8| (byte as char).to_digit(16);
+            Some((digit(pair[0])? << 4 | digit(pair[1])?) as u8)
+        })
+        .collect()
+}
+
+#[must_use]
+fn decode_base64(encoded: &str) -> Option<Vec<u8>> {
+    let sextet = |byte: u8| match byte {
+        b'A'..=b'Z' => Some(byte - b'A'),
+        b'a'..=b'z' => Some(byte - b'a' + 26),
+        b'0'..=b'9' => Some(byte - b'0' + 52),
+        b'+' => Some(62),
+        b'/' => Some(63),
+        _ => None,
+    };
+
+    if encoded.len() % 4 != 0 {
+        return None;
+    }
+    let mut decoded = Vec::with_capacity(encoded.len() / 4 * 3);
+    let chunks = encoded.as_bytes().chunks(4);
+    let last = chunks.len().saturating_sub(1);
+    for (i, chunk) in chunks.enumerate() {
+        let padding = chunk.iter().rev().take_while(|byte| **byte == b'=').count();
+        if padding > 2 || (padding > 0 && i != last) {
+            return None;
+        }
+        let n = chunk[..4 - padding]
+            .iter()
+            .enumerate()
+            .try_fold(0u32, |n, (i, byte)| {
+                Some(n | (sextet(*byte)? as u32) << (18 - 6 * i))
+            })?;
+        decoded.extend((0..3 - padding).map(|i| (n >> (16 - 8 * i)) as u8));
+    }
+    Some(decoded)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encode() {
+        assert_eq!(BytesEncoding::Hex.encode(&[]), "");
+        assert_eq!(BytesEncoding::Hex.encode(&[0x00, 0xff, 0x10]), "00ff10");
+
+        assert_eq!(BytesEncoding::Base64.encode(b""), "");
+        assert_eq!(BytesEncoding::Base64.encode(b"f"), "Zg==");
+        assert_eq!(BytesEncoding::Base64.encode(b"fo"), "Zm8=");
+        assert_eq!(BytesEncoding::Base64.encode(b"foo"), "Zm9v");
+        assert_eq!(BytesEncoding::Base64.encode(b"foobar"), "Zm9vYmFy");
+        assert_eq!(BytesEncoding::Base64.encode(&[0x00, 0xff, 0x10]), "AP8Q");
+    }
+
+    #[test]
+    fn decode() {
+        for encoding in [BytesEncoding::Hex, BytesEncoding::Base64] {
+            for bytes in [
+                &b""[..],
+                b"f",
+                b"fo",
+                b"foo",
+                b"foobar",
+                &[0x00, 0xff, 0x10],
+            ] {
+                assert_eq!(
+                    encoding.decode(&encoding.encode(bytes)).as_deref(),
+                    Some(bytes)
+                );
+            }
+        }
+
+        assert_eq!(BytesEncoding::Hex.decode("0"), None);
+        assert_eq!(BytesEncoding::Hex.decode("0g"), None);
+        assert_eq!(BytesEncoding::Base64.decode("Zg="), None);
+        assert_eq!(BytesEncoding::Base64.decode("Zg==Zg=="), None);
+        assert_eq!(BytesEncoding::Base64.decode("Z!=="), None);
+    }
+}
@@ -0,0 +1,236 @@
+use std::{
+    borrow::Cow,
+    fmt::{self, Write},
+};
+
+use crate::StringBuf;
+
+/// Specifies how text formatters render key-value fields.
+///
+/// [`FullFormatter`] and [`PatternFormatter`] render fields, e.g. the
+/// [static fields], according to this style. The default style renders fields
+/// in the order they are set as `key=value` pairs separated by spaces, quoting
+/// values only if needed.
+///
+/// # Examples
+///
+/// ```
+/// use spdlog::formatter::{FieldOrder, FieldQuoting, FieldsStyle, FullFormatter};
+///
+/// // [2022-11-02 09:23:12.263] [info] hello, world! pod: "web-1", region: "eu-west-1"
+/// let style = FieldsStyle::new()
+///     .separator(", ")
+///     .key_value_separator(": ")
+///     .quoting(FieldQuoting::Always)
+///     .order(FieldOrder::ByKey)
+///     .exclude("secret");
+/// let formatter = FullFormatter::new().fields_style(style);
+/// ```
+///
+/// [`FullFormatter`]: crate::formatter::FullFormatter
+/// [`PatternFormatter`]: crate::formatter::PatternFormatter
+/// [static fields]: crate::formatter::StaticFields
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct FieldsStyle {
+    separator: Cow<'static, str>,
+    key_value_separator: Cow<'static, str>,
+    quoting: FieldQuoting,
+    order: FieldOrder,
+    excluded_keys: Vec<Cow<'static, str>>,
+}
+
+/// Specifies when values of fields are quoted.
+///
+/// Quoted values are enclosed in double quotes `"`, with double quotes,
+/// backslashes and control characters escaped as in Rust string literals.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+#[non_exhaustive]
+pub enum FieldQuoting {
+    /// Quotes values that are empty or contain whitespaces, `"` or the
+    /// key-value separator.
+    Auto,
+    /// Quotes all values.
+    Always,
+    /// Writes values as they are.
+    Never,
+}
+
+/// Specifies the order in which fields are rendered.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+#[non_exhaustive]
+pub enum FieldOrder {
+    /// Renders fields in the order they are set.
+    Insertion,
+    /// Renders fields sorted by their keys.
+    ByKey,
+}
+
+impl FieldsStyle {
+    /// Constructs a `FieldsStyle` with the default options:
+    ///
+    /// | Option                | Default Value             |
+    /// |-----------------------|---------------------------|
+    /// | [separator]           | `" "`                     |
+    /// | [key_value_separator] | `"="`                     |
+    /// | [quoting]             | [`FieldQuoting::Auto`]    |
+    /// | [order]               | [`FieldOrder::Insertion`] |
+    /// | [exclude]             | *none*                    |
+    ///
+    /// [separator]: FieldsStyle::separator
+    /// [key_value_separator]: FieldsStyle::key_value_separator
+    /// [quoting]: FieldsStyle::quoting
+    /// [order]: FieldsStyle::order
+    /// [exclude]: FieldsStyle::exclude
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            separator: Cow::Borrowed(" "),
+            key_value_separator: Cow::Borrowed("="),
+            quoting: FieldQuoting::Auto,
+            order: FieldOrder::Insertion,
+            excluded_keys: vec![],
+        }
+    }
+
+    /// Specifies the separator written between fields.
+    #[must_use]
+    pub fn separator<S>(mut self, separator: S) -> Self
+    where
+        S: Into<Cow<'static, str>>,
+    {
+        self.separator = separator.into();
+        self
+    }
+
+    /// Specifies the separator written between the key and the value of a
+    /// field, e.g. `"="` for `key=value` or `": "` for `key: value`.
+    #[must_use]
+    pub fn key_value_separator<S>(mut self, separator: S) -> Self
+    where
+        S: Into<Cow<'static, str>>,
+    {
+        self.key_value_separator = separator.into();
+        self
+    }
+
+    /// Specifies when values are quoted.
+    #[must_use]
+    pub fn quoting(mut self, quoting: FieldQuoting) -> Self {
+        self.quoting = quoting;
+        self
+    }
+
+    /// Specifies the order in which fields are rendered.
+    #[must_use]
+    pub fn order(mut self, order: FieldOrder) -> Self {
+        self.order = order;
+        self
+    }
+
+    /// Excludes fields with the given key from rendering.
+    ///
+    /// This can be called multiple times to exclude multiple keys.
+    #[must_use]
+    pub fn exclude<S>(mut self, key: S) -> Self
+    where
+        S: Into<Cow<'static, str>>,
+    {
+        self.excluded_keys.push(key.into());
+        self
+    }
+
+    pub(crate) fn write<'a, I>(&self, fields: I, dest: &mut StringBuf) -> fmt::Result
+    where
+        I: IntoIterator<Item = (&'a str, &'a str)>,
+    {
+        let fields = fields
+            .into_iter()
+            .filter(|(key, _)| !self.excluded_keys.iter().any(|k| k == key));
+
+        match self.order {
+            FieldOrder::Insertion => self.write_ordered(fields, dest),
+            FieldOrder::ByKey => {
+                let mut fields = fields.collect::<Vec<_>>();
+                fields.sort_by_key(|(key, _)| *key);
+                self.write_ordered(fields, dest)
+            }
+        }
+    }
+
+    fn write_ordered<'a, I>(&self, fields: I, dest: &mut StringBuf) -> fmt::Result
+    where
+        I: IntoIterator<Item = (&'a str, &'a str)>,
+    {
+        for (index, (key, value)) in fields.into_iter().enumerate() {
+            if index != 0 {
+                dest.write_str(&self.separator)?;
+            }
+            dest.write_str(key)?;
+            dest.write_str(&self.key_value_separator)?;
+            if self.needs_quote(value) {
+                write!(dest, "{:?}", value)?;
+            } else {
+                dest.write_str(value)?;
+            }
+        }
+        Ok(())
+    }
+
+    #[must_use]
+    fn needs_quote(&self, value: &str) -> bool {
+        match self.quoting {
+            FieldQuoting::Auto => {
+                value.is_empty()
+                    || value.chars().any(|ch| ch.is_whitespace() || ch == '"')
+                    || (!self.key_value_separator.is_empty()
+                        && value.contains(self.key_value_separator.as_ref()))
+            }
+            FieldQuoting::Always => true,
+            FieldQuoting::Never => false,
+        }
+    }
+}
+
+impl Default for FieldsStyle {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn render(style: FieldsStyle) -> String {
+        let fields = [("region", "eu"), ("note", "two words"), ("empty", "")];
+        let mut dest = StringBuf::new();
+        style.write(fields, &mut dest).unwrap();
+        dest.to_string()
+    }
+
+    #[test]
+    fn write() {
+        assert_eq!(
+            render(FieldsStyle::new()),
+            r#"region=eu note="two words" empty="""#
+        );
+        assert_eq!(
+            render(
+                FieldsStyle::new()
+                    .separator(", ")
+                    .key_value_separator(": ")
+                    .quoting(FieldQuoting::Always)
+                    .order(FieldOrder::ByKey)
+            ),
+            r#"empty: "", note: "two words", region: "eu""#
+        );
+        assert_eq!(
+            render(
+                FieldsStyle::new()
+                    .quoting(FieldQuoting::Never)
+                    .exclude("empty")
+            ),
+            "region=eu note=two words"
+        );
+    }
+}
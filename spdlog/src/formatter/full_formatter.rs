@@ -5,7 +5,10 @@ use std::fmt::{self, Write};
 use cfg_if::cfg_if;
 
 use crate::{
-    formatter::{fmt_with_time, Formatter, FormatterContext, TimeDate},
+    formatter::{
+        fmt_with_time, FieldsStyle, Formatter, FormatterContext, MultiLine, StaticFields, TimeDate,
+        TimeZone,
+    },
     Error, Record, StringBuf, __EOL,
 };
 
@@ -33,21 +36,104 @@ use crate::{
 ///    <pre>
 ///    [2022-11-02 09:23:12.263] [logger-name] [<font color="#0DBC79">info</font>] [mod::path, src/main.rs:4] hello, world!
 ///    </pre>
+///
+///  - If [static fields] are specified:
+///
+///    <pre>
+///    [2022-11-02 09:23:12.263] [<font color="#0DBC79">info</font>] hello, world! POD_NAME=web-1 REGION=eu-west-1
+///    </pre>
+///
+///  - If the record has [key-value fields], they are written before the static
+///    fields:
+///
+///    <pre>
+///    [2022-11-02 09:23:12.263] [<font color="#0DBC79">info</font>] login ok user_id=42 POD_NAME=web-1
+///    </pre>
+///
+/// [static fields]: FullFormatter::static_fields
+/// [key-value fields]: crate::Record::key_values
 #[derive(Clone)]
 pub struct FullFormatter {
     with_eol: bool,
+    time_zone: TimeZone,
+    multi_line: MultiLine,
+    static_fields: StaticFields,
+    fields_style: FieldsStyle,
 }
 
 impl FullFormatter {
     /// Constructs a `FullFormatter`.
     #[must_use]
     pub fn new() -> FullFormatter {
-        FullFormatter { with_eol: true }
+        FullFormatter {
+            with_eol: true,
+            time_zone: TimeZone::LOCAL,
+            multi_line: MultiLine::Raw,
+            static_fields: StaticFields::new(),
+            fields_style: FieldsStyle::new(),
+        }
     }
 
     #[must_use]
     pub(crate) fn without_eol() -> Self {
-        Self { with_eol: false }
+        Self {
+            with_eol: false,
+            time_zone: TimeZone::LOCAL,
+            multi_line: MultiLine::Raw,
+            static_fields: StaticFields::new(),
+            fields_style: FieldsStyle::new(),
+        }
+    }
+
+    /// Specifies the time zone in which the time of records is rendered.
+    ///
+    /// The default is [`TimeZone::LOCAL`].
+    #[must_use]
+    pub fn time_zone(mut self, time_zone: TimeZone) -> Self {
+        self.time_zone = time_zone;
+        self
+    }
+
+    /// Specifies how payloads spanning multiple lines are written.
+    ///
+    /// The default is [`MultiLine::Raw`].
+    #[must_use]
+    pub fn multi_line(mut self, multi_line: MultiLine) -> Self {
+        self.multi_line = multi_line;
+        self
+    }
+
+    /// Specifies the static fields written after the payload of each record.
+    ///
+    /// The default is empty.
+    #[must_use]
+    pub fn static_fields(mut self, static_fields: StaticFields) -> Self {
+        self.static_fields = static_fields;
+        self
+    }
+
+    /// Specifies how the static fields are rendered.
+    ///
+    /// The default is [`FieldsStyle::new`].
+    #[must_use]
+    pub fn fields_style(mut self, fields_style: FieldsStyle) -> Self {
+        self.fields_style = fields_style;
+        self
+    }
+
+    // Used by pattern `{full}`, which follows the options of the pattern
+    // formatter.
+    pub(crate) fn format_with_options(
+        &self,
+        record: &Record,
+        dest: &mut StringBuf,
+        ctx: &mut FormatterContext,
+        multi_line: &MultiLine,
+        static_fields: &StaticFields,
+        fields_style: &FieldsStyle,
+    ) -> crate::Result<()> {
+        self.format_impl(record, dest, ctx, multi_line, static_fields, fields_style)
+            .map_err(Error::FormatRecord)
     }
 
     fn format_impl(
@@ -55,6 +141,9 @@ impl FullFormatter {
         record: &Record,
         dest: &mut StringBuf,
         ctx: &mut FormatterContext,
+        multi_line: &MultiLine,
+        static_fields: &StaticFields,
+        fields_style: &FieldsStyle,
     ) -> Result<(), fmt::Error> {
         cfg_if! {
             if #[cfg(not(feature = "flexible-string"))] {
@@ -62,7 +151,7 @@ impl FullFormatter {
             }
         }
 
-        fmt_with_time(ctx, record, |mut time: TimeDate| {
+        fmt_with_time(ctx, record, self.time_zone, |mut time: TimeDate| {
             dest.write_str("[")?;
             dest.write_str(time.full_second_str())?;
             dest.write_str(".")?;
@@ -84,15 +173,23 @@ impl FullFormatter {
 
         if let Some(srcloc) = record.source_location() {
             dest.write_str("] [")?;
-            dest.write_str(srcloc.module_path())?;
-            dest.write_str(", ")?;
+            // Locations from C code have no module path.
+            if !srcloc.module_path().is_empty() {
+                dest.write_str(srcloc.module_path())?;
+                dest.write_str(", ")?;
+            }
             dest.write_str(srcloc.file())?;
             dest.write_str(":")?;
             write!(dest, "{}", srcloc.line())?;
         }
 
         dest.write_str("] ")?;
-        dest.write_str(record.payload())?;
+        multi_line.write_payload(record.payload(), dest)?;
+
+        if record.key_values().next().is_some() || !static_fields.is_empty() {
+            dest.write_str(" ")?;
+            fields_style.write(record.key_values().chain(static_fields.iter()), dest)?;
+        }
 
         if self.with_eol {
             dest.write_str(__EOL)?;
@@ -110,8 +207,14 @@ impl Formatter for FullFormatter {
         dest: &mut StringBuf,
         ctx: &mut FormatterContext,
     ) -> crate::Result<()> {
-        self.format_impl(record, dest, ctx)
-            .map_err(Error::FormatRecord)
+        self.format_with_options(
+            record,
+            dest,
+            ctx,
+            &self.multi_line,
+            &self.static_fields,
+            &self.fields_style,
+        )
     }
 }
 
@@ -126,7 +229,7 @@ mod tests {
     use chrono::prelude::*;
 
     use super::*;
-    use crate::{Level, __EOL};
+    use crate::{formatter::FieldOrder, Level, __EOL};
 
     #[test]
     fn format() {
@@ -148,4 +251,88 @@ mod tests {
         );
         assert_eq!(Some(27..31), ctx.style_range());
     }
+
+    #[test]
+    fn time_zone() {
+        let record = Record::new(Level::Warn, "test log content", None, None);
+        let mut buf = StringBuf::new();
+        let mut ctx = FormatterContext::new();
+        FullFormatter::new()
+            .time_zone(super::TimeZone::fixed_offset(-3600).unwrap())
+            .format(&record, &mut buf, &mut ctx)
+            .unwrap();
+
+        let time = DateTime::<Utc>::from(record.time()) - chrono::Duration::hours(1);
+        assert_eq!(
+            format!(
+                "[{}] [warn] test log content{}",
+                time.format("%Y-%m-%d %H:%M:%S.%3f"),
+                __EOL
+            ),
+            buf
+        );
+    }
+
+    #[test]
+    fn multi_line() {
+        let record = Record::new(Level::Error, "failed:\nat main.rs", None, None);
+        let mut buf = StringBuf::new();
+        let mut ctx = FormatterContext::new();
+        FullFormatter::new()
+            .multi_line(MultiLine::prefix(" | "))
+            .format(&record, &mut buf, &mut ctx)
+            .unwrap();
+
+        assert!(buf.ends_with(&format!("[error] failed:\n | at main.rs{}", __EOL)));
+    }
+
+    #[test]
+    fn static_fields() {
+        let record = Record::new(Level::Info, "test log content", None, None);
+        let mut buf = StringBuf::new();
+        let mut ctx = FormatterContext::new();
+        FullFormatter::new()
+            .static_fields(StaticFields::new().field("pod", "web-1").field("az", "b"))
+            .format(&record, &mut buf, &mut ctx)
+            .unwrap();
+
+        assert!(buf.ends_with(&format!("[info] test log content pod=web-1 az=b{}", __EOL)));
+
+        let mut buf = StringBuf::new();
+        FullFormatter::new()
+            .static_fields(StaticFields::new().field("pod", "web-1").field("az", "b"))
+            .fields_style(
+                FieldsStyle::new()
+                    .separator(", ")
+                    .key_value_separator(": ")
+                    .order(FieldOrder::ByKey),
+            )
+            .format(&record, &mut buf, &mut ctx)
+            .unwrap();
+
+        assert!(buf.ends_with(&format!(
+            "[info] test log content az: b, pod: web-1{}",
+            __EOL
+        )));
+    }
+
+    #[test]
+    fn key_values() {
+        let mut record = Record::new(Level::Info, "login ok", None, None);
+        record.set_key_values(vec![
+            ("user_id".into(), "42".into()),
+            ("agent".into(), "curl 8.0".into()),
+        ]);
+        let mut buf = StringBuf::new();
+        let mut ctx = FormatterContext::new();
+        FullFormatter::new()
+            .static_fields(StaticFields::new().field("pod", "web-1"))
+            .format(&record, &mut buf, &mut ctx)
+            .unwrap();
+
+        assert!(buf.ends_with(&format!(
+            r#"[info] login ok user_id=42 agent="curl 8.0" pod=web-1{}"#,
+            __EOL
+        )));
+    }
 }
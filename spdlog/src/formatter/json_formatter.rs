@@ -1,6 +1,5 @@
 use std::{
     fmt::{self, Write},
-    marker::PhantomData,
     time::SystemTime,
 };
 
@@ -8,7 +7,7 @@ use cfg_if::cfg_if;
 use serde::{ser::SerializeStruct, Serialize};
 
 use crate::{
-    formatter::{Formatter, FormatterContext},
+    formatter::{BytesEncoding, Formatter, FormatterContext, StaticFields},
     Error, Record, StringBuf, __EOL,
 };
 
@@ -16,15 +15,30 @@ fn opt_to_num<T>(opt: Option<T>) -> usize {
     opt.map_or(0, |_| 1)
 }
 
-struct JsonRecord<'a>(&'a Record<'a>);
+struct JsonRecord<'a>(&'a Record<'a>, BytesEncoding, &'a StaticFields);
+
+struct JsonFields<'a>(&'a Record<'a>, &'a StaticFields);
+
+impl Serialize for JsonFields<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.collect_map(self.0.key_values().chain(self.1.iter()))
+    }
+}
 
 impl Serialize for JsonRecord<'_> {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: serde::Serializer,
     {
-        let fields_len =
-            4 + opt_to_num(self.0.logger_name()) + opt_to_num(self.0.source_location());
+        let fields_len = 4
+            + opt_to_num(self.0.logger_name())
+            + opt_to_num(self.0.code())
+            + opt_to_num(self.0.bytes())
+            + opt_to_num(self.0.source_location())
+            + usize::from(self.has_fields());
         let mut record = serializer.serialize_struct("JsonRecord", fields_len)?;
 
         record.serialize_field("level", &self.0.level())?;
@@ -43,18 +57,28 @@ impl Serialize for JsonRecord<'_> {
         if let Some(logger_name) = self.0.logger_name() {
             record.serialize_field("logger", logger_name)?;
         }
+        if let Some(code) = self.0.code() {
+            record.serialize_field("code", code)?;
+        }
+        if let Some(bytes) = self.0.bytes() {
+            record.serialize_field("bytes", &self.1.encode(bytes))?;
+        }
         record.serialize_field("tid", &self.0.tid())?;
         if let Some(src_loc) = self.0.source_location() {
             record.serialize_field("source", src_loc)?;
         }
+        if self.has_fields() {
+            record.serialize_field("fields", &JsonFields(self.0, self.2))?;
+        }
 
         record.end()
     }
 }
 
-impl<'a> From<&'a Record<'a>> for JsonRecord<'a> {
-    fn from(value: &'a Record<'a>) -> Self {
-        JsonRecord(value)
+impl JsonRecord<'_> {
+    #[must_use]
+    fn has_fields(&self) -> bool {
+        self.0.key_values().next().is_some() || !self.2.is_empty()
     }
 }
 
@@ -88,6 +112,8 @@ impl From<JsonFormatterError> for crate::Error {
 /// JSON logs formatter.
 /// 
 /// Each log will be serialized into a single line of JSON object with the following schema.
+/// Payloads spanning multiple lines are kept in a single line as well, since line feeds are
+/// escaped in JSON strings.
 /// 
 /// ## Schema
 /// 
@@ -97,8 +123,11 @@ impl From<JsonFormatterError> for crate::Error {
 /// | `timestamp` | Integer(u64) | The timestamp when the log was generated, in milliseconds since January 1, 1970 00:00:00 UTC.                                  |
 /// | `payload`   | String       | The contents of the log.                                                                                                       |
 /// | `logger`    | String/Null  | The name of the logger. Null if the logger has no name.                                                                        |
+/// | `code`      | String/Null  | The error code of the log. Null if the log has no error code. See [`Record::code`].                                            |
+/// | `bytes`     | String/Null  | The raw bytes of the log, encoded as configured by [`JsonFormatter::bytes_encoding`]. Null if the log has no raw bytes.        |
 /// | `tid`       | Integer(u64) | The thread ID when the log was generated.                                                                                      |
 /// | `source`    | Object/Null  | The source location of the log. See [`SourceLocation`] for its schema. Null if crate feature `source-location` is not enabled. |
+/// | `fields`    | Object/Null  | The key-value fields of the log followed by the static fields as string values, see [`Record::key_values`] and [`JsonFormatter::static_fields`]. Null if there are none. |
 /// 
 /// <div class="warning">
 /// 
@@ -134,15 +163,43 @@ impl From<JsonFormatterError> for crate::Error {
 ///    ```
 /// 
 /// [`Level::as_str`]: crate::Level::as_str
+/// [`Record::code`]: crate::Record::code
+/// [`Record::key_values`]: crate::Record::key_values
 /// [`SourceLocation`]: crate::SourceLocation
 #[derive(Clone)]
-pub struct JsonFormatter(PhantomData<()>);
+pub struct JsonFormatter {
+    bytes_encoding: BytesEncoding,
+    static_fields: StaticFields,
+}
 
 impl JsonFormatter {
     /// Constructs a `JsonFormatter`.
     #[must_use]
     pub fn new() -> JsonFormatter {
-        JsonFormatter(PhantomData)
+        JsonFormatter {
+            bytes_encoding: BytesEncoding::Base64,
+            static_fields: StaticFields::new(),
+        }
+    }
+
+    /// Specifies how raw bytes attached to records are encoded into the `bytes`
+    /// field.
+    ///
+    /// The default is [`BytesEncoding::Base64`].
+    #[must_use]
+    pub fn bytes_encoding(mut self, bytes_encoding: BytesEncoding) -> Self {
+        self.bytes_encoding = bytes_encoding;
+        self
+    }
+
+    /// Specifies the static fields written into the `fields` object of each
+    /// record.
+    ///
+    /// The default is empty.
+    #[must_use]
+    pub fn static_fields(mut self, static_fields: StaticFields) -> Self {
+        self.static_fields = static_fields;
+        self
     }
 
     fn format_impl(
@@ -157,7 +214,7 @@ impl JsonFormatter {
             }
         }
 
-        let json_record: JsonRecord = record.into();
+        let json_record = JsonRecord(record, self.bytes_encoding, &self.static_fields);
 
         // TODO: https://github.com/serde-rs/json/issues/863
         //
@@ -218,6 +275,46 @@ mod tests {
         );
     }
 
+    #[test]
+    fn should_format_multi_line_json_in_single_line() {
+        let mut dest = StringBuf::new();
+        let formatter = JsonFormatter::new();
+        let record = Record::new(Level::Error, "failed:\n  at main.rs", None, None);
+        formatter
+            .format(&record, &mut dest, &mut FormatterContext::new())
+            .unwrap();
+
+        assert_eq!(dest.lines().count(), 1);
+        assert!(dest.contains(r#""payload":"failed:\n  at main.rs""#));
+    }
+
+    #[test]
+    fn should_format_json_with_static_fields() {
+        let mut dest = StringBuf::new();
+        let formatter = JsonFormatter::new()
+            .static_fields(StaticFields::new().field("pod", "web-1").field("az", "b"));
+        let record = Record::new(Level::Info, "payload", None, None);
+        formatter
+            .format(&record, &mut dest, &mut FormatterContext::new())
+            .unwrap();
+
+        assert!(dest.contains(r#""fields":{"pod":"web-1","az":"b"}"#));
+    }
+
+    #[test]
+    fn should_format_json_with_key_values() {
+        let mut dest = StringBuf::new();
+        let formatter =
+            JsonFormatter::new().static_fields(StaticFields::new().field("pod", "web-1"));
+        let mut record = Record::new(Level::Info, "payload", None, None);
+        record.set_key_values(vec![("user_id".into(), "42".into())]);
+        formatter
+            .format(&record, &mut dest, &mut FormatterContext::new())
+            .unwrap();
+
+        assert!(dest.contains(r#""fields":{"user_id":"42","pod":"web-1"}"#));
+    }
+
     #[test]
     fn should_format_json_with_logger_name() {
         let mut dest = StringBuf::new();
@@ -241,6 +338,59 @@ mod tests {
         );
     }
 
+    #[test]
+    fn should_format_json_with_code() {
+        let mut dest = StringBuf::new();
+        let formatter = JsonFormatter::new();
+        let mut record = Record::new(Level::Error, "payload", None, None);
+        record.set_code(Some("E1042"));
+        let mut ctx = FormatterContext::new();
+        formatter.format(&record, &mut dest, &mut ctx).unwrap();
+
+        let local_time: DateTime<Local> = record.time().into();
+
+        assert_eq!(
+            dest.to_string(),
+            format!(
+                r#"{{"level":"error","timestamp":{},"payload":"{}","code":"E1042","tid":{}}}{}"#,
+                local_time.timestamp_millis(),
+                "payload",
+                record.tid(),
+                __EOL
+            )
+        );
+    }
+
+    #[test]
+    fn should_format_json_with_bytes() {
+        let mut record = Record::new(Level::Info, "payload", None, None);
+        record.set_bytes(Some(&[0x7e, 0xff, 0x00]));
+
+        for (formatter, expected) in [
+            (JsonFormatter::new(), "fv8A"),
+            (
+                JsonFormatter::new().bytes_encoding(BytesEncoding::Hex),
+                "7eff00",
+            ),
+        ] {
+            let mut dest = StringBuf::new();
+            let mut ctx = FormatterContext::new();
+            formatter.format(&record, &mut dest, &mut ctx).unwrap();
+
+            let local_time: DateTime<Local> = record.time().into();
+            assert_eq!(
+                dest.to_string(),
+                format!(
+                    r#"{{"level":"info","timestamp":{},"payload":"payload","bytes":"{}","tid":{}}}{}"#,
+                    local_time.timestamp_millis(),
+                    expected,
+                    record.tid(),
+                    __EOL
+                )
+            );
+        }
+    }
+
     #[test]
     fn should_format_json_with_src_loc() {
         let mut dest = StringBuf::new();
@@ -1,27 +1,36 @@
 use std::{fmt, time::SystemTime};
 
-use chrono::prelude::*;
 use once_cell::sync::Lazy;
 
-use crate::{formatter::FormatterContext, sync::*, Record};
+use crate::{
+    formatter::{FormatterContext, TimeZone},
+    sync::*,
+    time_backend::DateTime,
+    Record,
+};
 
 static LOCAL_TIME_CACHER: Lazy<SpinMutex<LocalTimeCacher>> =
     Lazy::new(|| SpinMutex::new(LocalTimeCacher::new()));
 
-pub(crate) fn fmt_with_time<R, F>(ctx: &mut FormatterContext, record: &Record, mut callback: F) -> R
+pub(crate) fn fmt_with_time<R, F>(
+    ctx: &mut FormatterContext,
+    record: &Record,
+    time_zone: TimeZone,
+    mut callback: F,
+) -> R
 where
     F: FnMut(TimeDate) -> R,
 {
     if let Some(time_date) = ctx.locked_time_date.as_mut() {
         callback(time_date.get())
     } else {
-        callback(LOCAL_TIME_CACHER.lock().get(record.time()))
+        callback(LOCAL_TIME_CACHER.lock().get(record.time(), time_zone))
     }
 }
 
 #[derive(Clone)]
 pub(crate) struct LocalTimeCacher {
-    stored_key: u64,
+    stored_key: (u64, TimeZone),
     cache_values: Option<CacheValues>,
 }
 
@@ -33,7 +42,7 @@ pub(crate) struct TimeDate<'a> {
 
 #[derive(Clone, Eq, PartialEq)]
 struct CacheValues {
-    local_time: DateTime<Local>,
+    local_time: DateTime,
     full_second_str: Option<String>,
     year: Option<i32>,
     year_str: Option<String>,
@@ -67,20 +76,20 @@ impl LocalTimeCacher {
     #[must_use]
     fn new() -> LocalTimeCacher {
         LocalTimeCacher {
-            stored_key: 0,
+            stored_key: (0, TimeZone::LOCAL),
             cache_values: None,
         }
     }
 
     #[must_use]
-    pub(crate) fn get(&mut self, system_time: SystemTime) -> TimeDate {
+    pub(crate) fn get(&mut self, system_time: SystemTime, time_zone: TimeZone) -> TimeDate<'_> {
         let since_epoch = system_time.duration_since(SystemTime::UNIX_EPOCH).unwrap();
         let nanosecond = since_epoch.subsec_nanos();
         let millisecond = nanosecond / 1_000_000;
 
-        let cache_key = since_epoch.as_secs(); // Unix timestamp
+        let cache_key = (since_epoch.as_secs(), time_zone); // Unix timestamp and time zone
         if self.cache_values.is_none() || self.stored_key != cache_key {
-            self.cache_values = Some(CacheValues::new(system_time));
+            self.cache_values = Some(CacheValues::new(system_time, time_zone));
             self.stored_key = cache_key;
         }
 
@@ -176,7 +185,7 @@ impl TimeDate<'_> {
                     ];
 
                     let weekday_from_monday_0 =
-                        self.cached.local_time.weekday().num_days_from_monday() as usize;
+                        self.cached.local_time.weekday_from_monday() as usize;
 
                     MultiName {
                         short: SHORT[weekday_from_monday_0],
@@ -269,7 +278,7 @@ impl TimeDate<'_> {
     pub(crate) fn tz_offset_str(&mut self) -> &str {
         if self.cached.tz_offset_str.is_none() {
             self.cached.tz_offset_str = {
-                let offset_secs = self.cached.local_time.offset().local_minus_utc();
+                let offset_secs = self.cached.local_time.offset();
                 let offset_secs_abs = offset_secs.abs();
 
                 let sign_str = if offset_secs >= 0 { "+" } else { "-" };
@@ -288,9 +297,9 @@ impl TimeDate<'_> {
 
 impl CacheValues {
     #[must_use]
-    fn new(system_time: SystemTime) -> Self {
+    fn new(system_time: SystemTime, time_zone: TimeZone) -> Self {
         CacheValues {
-            local_time: system_time.into(),
+            local_time: time_zone.date_time(system_time),
             full_second_str: None,
             year: None,
             year_str: None,
@@ -324,20 +333,25 @@ struct TimeDateLocked<'a> {
 
 pub(crate) struct TimeDateLazyLocked<'a> {
     time: SystemTime,
+    time_zone: TimeZone,
     locked: Option<TimeDateLocked<'a>>,
 }
 
 impl TimeDateLazyLocked<'_> {
     #[must_use]
-    pub(crate) fn new(time: SystemTime) -> Self {
-        Self { time, locked: None }
+    pub(crate) fn new(time: SystemTime, time_zone: TimeZone) -> Self {
+        Self {
+            time,
+            time_zone,
+            locked: None,
+        }
     }
 
     #[must_use]
     pub(crate) fn get(&mut self) -> TimeDate<'_> {
         let locked = self.locked.get_or_insert_with(|| {
             let mut cached = LOCAL_TIME_CACHER.lock();
-            let time_date = cached.get(self.time);
+            let time_date = cached.get(self.time, self.time_zone);
             let (nanosecond, millisecond) = (time_date.nanosecond, time_date.millisecond);
             TimeDateLocked {
                 cached,
@@ -358,12 +372,15 @@ impl fmt::Debug for TimeDateLazyLocked<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("TimeDateLazyLocked")
             .field("time", &self.time)
+            .field("time_zone", &self.time_zone)
             .finish()
     }
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "chrono"))]
 mod tests {
+    use chrono::{Local, Timelike};
+
     use super::*;
 
     #[test]
@@ -376,12 +393,20 @@ mod tests {
             if now.duration_since(begin).unwrap().as_secs() >= 3 {
                 break;
             }
-            let from_cache = cacher.get(now);
-            let from_chrono = DateTime::<Local>::from(now);
+            let mut from_cache = cacher.get(now, TimeZone::LOCAL);
+            let from_chrono = chrono::DateTime::<Local>::from(now);
 
             assert_eq!(
-                from_cache.cached.local_time.with_nanosecond(0),
-                from_chrono.with_nanosecond(0)
+                from_cache.full_second_str(),
+                from_chrono.format("%Y-%m-%d %H:%M:%S").to_string()
+            );
+            assert_eq!(
+                from_cache.tz_offset_str(),
+                from_chrono.format("%:z").to_string()
+            );
+            assert_eq!(
+                from_cache.weekday_name().short,
+                from_chrono.format("%a").to_string()
             );
             assert_eq!(from_cache.nanosecond, from_chrono.nanosecond());
             assert_eq!(from_cache.millisecond, from_chrono.nanosecond() / 1_000_000);
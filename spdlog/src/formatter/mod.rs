@@ -48,6 +48,8 @@
 //! [`Sink::set_formatter`]: crate::sink::Sink::set_formatter
 //! [./examples]: https://github.com/SpriteOvO/spdlog-rs/tree/main/spdlog/examples
 
+mod bytes_encoding;
+mod fields_style;
 mod full_formatter;
 #[cfg(any(
     all(target_os = "linux", feature = "native", feature = "libsystemd"),
@@ -57,11 +59,16 @@ mod journald_formatter;
 #[cfg(feature = "serde_json")]
 mod json_formatter;
 mod local_time_cacher;
+mod multi_line;
 mod pattern_formatter;
+mod static_fields;
+mod time_zone;
 
 use std::ops::Range;
 
+pub use bytes_encoding::*;
 use dyn_clone::*;
+pub use fields_style::*;
 pub use full_formatter::*;
 #[cfg(any(
     all(target_os = "linux", feature = "native", feature = "libsystemd"),
@@ -71,7 +78,10 @@ pub(crate) use journald_formatter::*;
 #[cfg(feature = "serde_json")]
 pub use json_formatter::*;
 pub(crate) use local_time_cacher::*;
+pub use multi_line::*;
 pub use pattern_formatter::*;
+pub use static_fields::*;
+pub use time_zone::*;
 
 use crate::{Record, Result, StringBuf};
 
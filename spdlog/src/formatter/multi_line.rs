@@ -0,0 +1,161 @@
+use std::{
+    borrow::Cow,
+    fmt::{self, Write},
+};
+
+use crate::StringBuf;
+
+/// Specifies how formatters write payloads spanning multiple lines.
+///
+/// A payload spans multiple lines if it contains line feeds `\n`, e.g. a stack
+/// trace or a pretty-printed struct (see [`Record::is_multi_line`]). Written as
+/// they are, the continuation lines look like separate records to tools that
+/// split logs by lines, and they may even be mistaken for records themselves.
+///
+/// [`FullFormatter`] and [`PatternFormatter`] write payloads according to this
+/// option. [`JsonFormatter`] always keeps a record as a single line, since line
+/// feeds are escaped in JSON strings.
+///
+/// # Examples
+///
+/// ```
+/// use spdlog::formatter::{FullFormatter, MultiLine};
+///
+/// // [2022-11-02 09:23:12.263] [error] failed to connect:
+/// //   | connection refused
+/// //   | retried 3 times
+/// let formatter = FullFormatter::new().multi_line(MultiLine::prefix("  | "));
+/// ```
+///
+/// [`Record::is_multi_line`]: crate::Record::is_multi_line
+/// [`FullFormatter`]: crate::formatter::FullFormatter
+/// [`PatternFormatter`]: crate::formatter::PatternFormatter
+/// [`JsonFormatter`]: crate::formatter::JsonFormatter
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+#[non_exhaustive]
+pub enum MultiLine {
+    /// Writes payloads as they are.
+    Raw,
+    /// Writes the given marker at the beginning of each continuation line, so
+    /// that they can be told apart from the lines of records.
+    Prefix(Cow<'static, str>),
+    /// Escapes line feeds `\n` and carriage returns `\r` as `\n` and `\r`, and
+    /// backslashes `\` as `\\`, so that each record is written on a single
+    /// line.
+    Escape,
+}
+
+impl MultiLine {
+    /// Constructs a [`MultiLine::Prefix`] with the given marker.
+    #[must_use]
+    pub fn prefix<S>(marker: S) -> Self
+    where
+        S: Into<Cow<'static, str>>,
+    {
+        Self::Prefix(marker.into())
+    }
+
+    pub(crate) fn write_payload(&self, payload: &str, dest: &mut StringBuf) -> fmt::Result {
+        match self {
+            Self::Raw => dest.write_str(payload),
+            Self::Prefix(marker) => {
+                let mut lines = payload.split('\n');
+                dest.write_str(lines.next().unwrap_or_default())?;
+                for line in lines {
+                    dest.write_char('\n')?;
+                    dest.write_str(marker)?;
+                    dest.write_str(line)?;
+                }
+                Ok(())
+            }
+            Self::Escape => {
+                let mut rest = payload;
+                while let Some(pos) = rest.find(['\\', '\n', '\r'].as_ref()) {
+                    dest.write_str(&rest[..pos])?;
+                    dest.write_str(match rest.as_bytes()[pos] {
+                        b'\\' => "\\\\",
+                        b'\n' => "\\n",
+                        _ => "\\r",
+                    })?;
+                    rest = &rest[pos + 1..];
+                }
+                dest.write_str(rest)
+            }
+        }
+    }
+
+    // Reverses `write_payload`, for reading formatted records back.
+    #[must_use]
+    pub(crate) fn read_payload<'a>(&self, payload: &'a str) -> Cow<'a, str> {
+        match self {
+            Self::Raw => Cow::Borrowed(payload),
+            Self::Prefix(marker) => {
+                if !payload.contains('\n') || marker.is_empty() {
+                    return Cow::Borrowed(payload);
+                }
+                let mut lines = payload.split('\n');
+                let mut result = String::with_capacity(payload.len());
+                result.push_str(lines.next().unwrap_or_default());
+                for line in lines {
+                    result.push('\n');
+                    result.push_str(line.strip_prefix(marker.as_ref()).unwrap_or(line));
+                }
+                Cow::Owned(result)
+            }
+            Self::Escape => {
+                if !payload.contains('\\') {
+                    return Cow::Borrowed(payload);
+                }
+                let mut result = String::with_capacity(payload.len());
+                let mut chars = payload.chars();
+                while let Some(ch) = chars.next() {
+                    if ch != '\\' {
+                        result.push(ch);
+                        continue;
+                    }
+                    match chars.next() {
+                        Some('n') => result.push('\n'),
+                        Some('r') => result.push('\r'),
+                        Some(ch) => result.push(ch),
+                        None => result.push('\\'),
+                    }
+                }
+                Cow::Owned(result)
+            }
+        }
+    }
+}
+
+impl Default for MultiLine {
+    fn default() -> Self {
+        Self::Raw
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn write_and_read() {
+        let payload = "failed:\n  at main.rs:1\r\n  at C:\\lib.rs:2";
+        let cases = [
+            (MultiLine::Raw, payload.to_string()),
+            (
+                MultiLine::prefix("  | "),
+                "failed:\n  |   at main.rs:1\r\n  |   at C:\\lib.rs:2".to_string(),
+            ),
+            (
+                MultiLine::Escape,
+                "failed:\\n  at main.rs:1\\r\\n  at C:\\\\lib.rs:2".to_string(),
+            ),
+        ];
+
+        for (multi_line, expected) in cases {
+            let mut dest = StringBuf::new();
+            multi_line.write_payload(payload, &mut dest).unwrap();
+            assert_eq!(dest.as_str(), expected);
+            assert_eq!(multi_line.read_payload(dest.as_str()), payload);
+        }
+    }
+}
@@ -0,0 +1,482 @@
+use std::{
+    fmt::Write,
+    iter::Peekable,
+    str::Chars,
+    time::{Duration, SystemTime},
+};
+
+use super::{Pattern, PatternContext, RuntimePattern, __pattern as pattern};
+use crate::{sync::*, Error, Level, Record, StringBuf};
+
+// The same limit as C++ spdlog.
+const MAX_PADDING_WIDTH: usize = 64;
+
+impl RuntimePattern {
+    /// Builds a pattern from a [C++ spdlog] pattern string.
+    ///
+    /// All `%` flags of C++ spdlog are supported with the same semantics, so
+    /// that services written in both languages produce the same log layout
+    /// from one pattern string. This includes:
+    ///
+    /// - `%^` and `%$` to mark the color range. If `%$` is missing, the color
+    ///   range ends at the end of the pattern.
+    /// - Padding specs, e.g. `%8l` (align right), `%-8l` (align left), `%=8l`
+    ///   (align center), and `%8!l` (truncate if longer).
+    /// - `%l` writes the C++ level names, e.g. `warning` instead of `warn`.
+    /// - `%+` writes the default layout of C++ spdlog.
+    /// - `%o`, `%i`, `%u` and `%O` write the elapsed time since the previous
+    ///   record formatted by this pattern.
+    ///
+    /// Unknown flags are written as is. Like C++ spdlog, the line ending is
+    /// appended to the end of the pattern automatically.
+    ///
+    /// Since Rust has no function names in source locations, `%!` writes the
+    /// module path instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use spdlog::formatter::{PatternFormatter, RuntimePattern};
+    ///
+    /// let formatter = PatternFormatter::new(RuntimePattern::from_cpp_pattern(
+    ///     "[%Y-%m-%d %H:%M:%S.%e] [%n] [%^%-8l%$] %v",
+    /// ));
+    /// ```
+    ///
+    /// [C++ spdlog]: https://github.com/gabime/spdlog/wiki/3.-Custom-formatting
+    #[must_use]
+    pub fn from_cpp_pattern(pattern: &str) -> Self {
+        let mut patterns = vec![];
+        let mut style_range: Option<Vec<Box<dyn Pattern>>> = None;
+        let mut literal = String::new();
+        let mut chars = pattern.chars().peekable();
+
+        macro_rules! dest {
+            () => {
+                style_range.as_mut().unwrap_or(&mut patterns)
+            };
+        }
+
+        while let Some(ch) = chars.next() {
+            if ch != '%' {
+                literal.push(ch);
+                continue;
+            }
+
+            let padding = parse_padding(&mut chars);
+            let flag = match chars.next() {
+                Some(flag) => flag,
+                // A trailing `%` is ignored, like C++ spdlog.
+                None => break,
+            };
+
+            match flag {
+                '%' => {
+                    literal.push('%');
+                    continue;
+                }
+                '^' | '$' => {
+                    if !literal.is_empty() {
+                        dest!().push(Box::new(std::mem::take(&mut literal)));
+                    }
+                    if flag == '^' {
+                        if style_range.is_none() {
+                            style_range = Some(vec![]);
+                        }
+                    } else if let Some(body) = style_range.take() {
+                        patterns.push(Box::new(pattern::StyleRange::new(body)));
+                    }
+                    continue;
+                }
+                _ => {}
+            }
+
+            let flag_pattern = match build_flag_pattern(flag) {
+                Some(flag_pattern) => flag_pattern,
+                None => {
+                    // Unknown flags are written as is.
+                    literal.push('%');
+                    literal.push(flag);
+                    continue;
+                }
+            };
+
+            if !literal.is_empty() {
+                dest!().push(Box::new(std::mem::take(&mut literal)));
+            }
+            let flag_pattern = match padding {
+                Some(padding) => Box::new(Padded {
+                    inner: flag_pattern,
+                    padding,
+                }),
+                None => flag_pattern,
+            };
+            dest!().push(flag_pattern);
+        }
+
+        if !literal.is_empty() {
+            dest!().push(Box::new(literal));
+        }
+        if let Some(body) = style_range.take() {
+            patterns.push(Box::new(pattern::StyleRange::new(body)));
+        }
+        patterns.push(Box::<pattern::Eol>::default());
+
+        Self::from_patterns(patterns)
+    }
+}
+
+#[must_use]
+fn build_flag_pattern(flag: char) -> Option<Box<dyn Pattern>> {
+    macro_rules! boxed {
+        ($name:ident) => {
+            Box::<pattern::$name>::default()
+        };
+    }
+
+    let flag_pattern: Box<dyn Pattern> = match flag {
+        'v' => boxed!(Payload),
+        't' => boxed!(ThreadId),
+        'P' => boxed!(ProcessId),
+        'n' => boxed!(LoggerName),
+        'l' => Box::new(CppLevel),
+        'L' => boxed!(ShortLevel),
+        'a' => boxed!(AbbrWeekdayName),
+        'A' => boxed!(WeekdayName),
+        'b' | 'h' => boxed!(AbbrMonthName),
+        'B' => boxed!(MonthName),
+        'c' => boxed!(FullDateTime),
+        'C' => boxed!(ShortYear),
+        'Y' => boxed!(Year),
+        'D' | 'x' => boxed!(ShortDate),
+        'm' => boxed!(Month),
+        'd' => boxed!(Day),
+        'H' => boxed!(Hour),
+        'I' => boxed!(Hour12),
+        'M' => boxed!(Minute),
+        'S' => boxed!(Second),
+        'e' => boxed!(Millisecond),
+        'f' => boxed!(Microsecond),
+        'F' => boxed!(Nanosecond),
+        'p' => boxed!(AmPm),
+        'r' => boxed!(Time12),
+        'R' => boxed!(ShortTime),
+        'T' | 'X' => boxed!(Time),
+        'z' => boxed!(TzOffset),
+        'E' => boxed!(UnixTimestamp),
+        '+' => Box::new(CppFull),
+        '@' => boxed!(Source),
+        's' => boxed!(SourceFilename),
+        'g' => boxed!(SourceFile),
+        '#' => boxed!(SourceLine),
+        '!' => boxed!(SourceModulePath),
+        'o' => Box::new(Elapsed::new(ElapsedUnit::Millis)),
+        'i' => Box::new(Elapsed::new(ElapsedUnit::Micros)),
+        'u' => Box::new(Elapsed::new(ElapsedUnit::Nanos)),
+        'O' => Box::new(Elapsed::new(ElapsedUnit::Secs)),
+        _ => return None,
+    };
+    Some(flag_pattern)
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Align {
+    Left,
+    Right,
+    Center,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+struct Padding {
+    width: usize,
+    align: Align,
+    truncate: bool,
+}
+
+#[must_use]
+fn parse_padding(chars: &mut Peekable<Chars<'_>>) -> Option<Padding> {
+    let align = match chars.peek() {
+        Some('-') => {
+            chars.next();
+            Align::Left
+        }
+        Some('=') => {
+            chars.next();
+            Align::Center
+        }
+        _ => Align::Right,
+    };
+
+    let mut width = None::<usize>;
+    while let Some(digit) = chars.peek().and_then(|ch| ch.to_digit(10)) {
+        chars.next();
+        let new_width = width.unwrap_or(0) * 10 + digit as usize;
+        width = Some(new_width.min(MAX_PADDING_WIDTH));
+    }
+    // Like C++ spdlog, an alignment without width is consumed and ignored.
+    let width = width?;
+
+    let truncate = chars.peek() == Some(&'!');
+    if truncate {
+        chars.next();
+    }
+
+    Some(Padding {
+        width,
+        align,
+        truncate,
+    })
+}
+
+#[derive(Clone)]
+struct Padded {
+    inner: Box<dyn Pattern>,
+    padding: Padding,
+}
+
+impl Pattern for Padded {
+    fn format(
+        &self,
+        record: &Record,
+        dest: &mut StringBuf,
+        ctx: &mut PatternContext,
+    ) -> crate::Result<()> {
+        let mut content = StringBuf::new();
+        self.inner.format(record, &mut content, ctx)?;
+
+        let len = content.chars().count();
+        let content = if self.padding.truncate && len > self.padding.width {
+            let end = content
+                .char_indices()
+                .nth(self.padding.width)
+                .map_or(content.len(), |(index, _)| index);
+            &content[..end]
+        } else {
+            &content[..]
+        };
+
+        let total_pad = self.padding.width.saturating_sub(len);
+        let (left_pad, right_pad) = match self.padding.align {
+            Align::Left => (0, total_pad),
+            Align::Right => (total_pad, 0),
+            Align::Center => (total_pad / 2, total_pad - total_pad / 2),
+        };
+
+        (|| {
+            (0..left_pad).try_for_each(|_| dest.write_char(' '))?;
+            dest.write_str(content)?;
+            (0..right_pad).try_for_each(|_| dest.write_char(' '))
+        })()
+        .map_err(Error::FormatRecord)
+    }
+}
+
+#[must_use]
+fn cpp_level_name(level: Level) -> &'static str {
+    match level {
+        Level::Critical => "critical",
+        Level::Error => "error",
+        Level::Warn => "warning",
+        Level::Info => "info",
+        Level::Debug => "debug",
+        Level::Trace => "trace",
+    }
+}
+
+// Writes the level names of C++ spdlog.
+#[derive(Clone, Default)]
+struct CppLevel;
+
+impl Pattern for CppLevel {
+    fn format(
+        &self,
+        record: &Record,
+        dest: &mut StringBuf,
+        _ctx: &mut PatternContext,
+    ) -> crate::Result<()> {
+        dest.write_str(cpp_level_name(record.level()))
+            .map_err(Error::FormatRecord)
+    }
+}
+
+// Writes the default layout of C++ spdlog, equivalent to
+// `[%Y-%m-%d %H:%M:%S.%e] [%n] [%^%l%$] [%s:%#] %v`, where the logger name and
+// the source location are omitted if they are absent.
+#[derive(Clone, Default)]
+struct CppFull;
+
+impl Pattern for CppFull {
+    fn format(
+        &self,
+        record: &Record,
+        dest: &mut StringBuf,
+        ctx: &mut PatternContext,
+    ) -> crate::Result<()> {
+        dest.write_char('[').map_err(Error::FormatRecord)?;
+        pattern::Date.format(record, dest, ctx)?;
+        dest.write_char(' ').map_err(Error::FormatRecord)?;
+        pattern::Time.format(record, dest, ctx)?;
+        dest.write_char('.').map_err(Error::FormatRecord)?;
+        pattern::Millisecond::default().format(record, dest, ctx)?;
+
+        (|| {
+            dest.write_str("] ")?;
+            if let Some(logger_name) = record.logger_name() {
+                write!(dest, "[{}] ", logger_name)?;
+            }
+            dest.write_char('[')
+        })()
+        .map_err(Error::FormatRecord)?;
+
+        pattern::StyleRange::new(CppLevel).format(record, dest, ctx)?;
+
+        (|| {
+            dest.write_str("] ")?;
+            if let Some(srcloc) = record.source_location() {
+                write!(dest, "[{}:{}] ", srcloc.file_name(), srcloc.line())?;
+            }
+            dest.write_str(record.payload())
+        })()
+        .map_err(Error::FormatRecord)
+    }
+}
+
+#[derive(Clone, Copy)]
+enum ElapsedUnit {
+    Secs,
+    Millis,
+    Micros,
+    Nanos,
+}
+
+// Writes the elapsed time since the previous record formatted by this pattern.
+struct Elapsed {
+    unit: ElapsedUnit,
+    last_time: Mutex<SystemTime>,
+}
+
+impl Elapsed {
+    #[must_use]
+    fn new(unit: ElapsedUnit) -> Self {
+        Self {
+            unit,
+            last_time: Mutex::new(SystemTime::now()),
+        }
+    }
+}
+
+impl Clone for Elapsed {
+    fn clone(&self) -> Self {
+        Self {
+            unit: self.unit,
+            last_time: Mutex::new(*self.last_time.lock_expect()),
+        }
+    }
+}
+
+impl Pattern for Elapsed {
+    fn format(
+        &self,
+        record: &Record,
+        dest: &mut StringBuf,
+        _ctx: &mut PatternContext,
+    ) -> crate::Result<()> {
+        let elapsed = {
+            let mut last_time = self.last_time.lock_expect();
+            let elapsed = record
+                .time()
+                .duration_since(*last_time)
+                .unwrap_or(Duration::ZERO);
+            *last_time = record.time();
+            elapsed
+        };
+
+        let count = match self.unit {
+            ElapsedUnit::Secs => elapsed.as_secs() as u128,
+            ElapsedUnit::Millis => elapsed.as_millis(),
+            ElapsedUnit::Micros => elapsed.as_micros(),
+            ElapsedUnit::Nanos => elapsed.as_nanos(),
+        };
+        write!(dest, "{}", count).map_err(Error::FormatRecord)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        formatter::{Formatter, FormatterContext, PatternFormatter},
+        SourceLocation,
+    };
+
+    fn format(pattern: &str, record: &Record) -> (String, Option<std::ops::Range<usize>>) {
+        let formatter = PatternFormatter::new(RuntimePattern::from_cpp_pattern(pattern));
+        let mut dest = StringBuf::new();
+        let mut ctx = FormatterContext::new();
+        formatter.format(record, &mut dest, &mut ctx).unwrap();
+        (
+            dest.strip_suffix(crate::__EOL).unwrap().to_string(),
+            ctx.style_range(),
+        )
+    }
+
+    #[test]
+    fn flags() {
+        let record = Record::new(
+            Level::Warn,
+            "payload",
+            Some(SourceLocation::__new("module", "dir/file.rs", 10, 20)),
+            Some("logger"),
+        );
+
+        assert_eq!(
+            format("[%n] [%^%l%$] %v", &record),
+            ("[logger] [warning] payload".to_string(), Some(10..17))
+        );
+        assert_eq!(
+            format("%L|%@|%s|%g|%#|%!|%%|%k|%", &record).0,
+            "W|dir/file.rs:10|file.rs|dir/file.rs|10|module|%|%k|"
+        );
+        assert_eq!(format("%^%v", &record), ("payload".to_string(), Some(0..7)));
+        assert_eq!(format("%v%$", &record), ("payload".to_string(), None));
+
+        let (full, style_range) = format("%+", &record);
+        assert!(full.starts_with('['));
+        assert!(full.ends_with("] [logger] [warning] [file.rs:10] payload"));
+        assert_eq!(style_range, Some(full.len() - 29..full.len() - 22));
+
+        let record = Record::new(Level::Info, "payload", None, None);
+        let (full, _) = format("%+", &record);
+        assert!(full.ends_with("] [info] payload"));
+        assert_eq!(format("%n|%@|%#", &record).0, "||");
+    }
+
+    #[test]
+    fn padding() {
+        let record = Record::new(Level::Info, "payload", None, Some("logger"));
+
+        assert_eq!(format("[%8l]", &record).0, "[    info]");
+        assert_eq!(format("[%-8l]", &record).0, "[info    ]");
+        assert_eq!(format("[%=9l]", &record).0, "[  info   ]");
+        assert_eq!(format("[%3l]", &record).0, "[info]");
+        assert_eq!(format("[%3!l]", &record).0, "[inf]");
+        assert_eq!(format("[%-l]", &record).0, "[info]");
+        assert_eq!(format("[%100L]", &record).0.len(), MAX_PADDING_WIDTH + 2);
+    }
+
+    #[test]
+    fn elapsed() {
+        let pattern = RuntimePattern::from_cpp_pattern("%u");
+        let formatter = PatternFormatter::new(pattern);
+        let mut record = Record::new(Level::Info, "payload", None, None);
+        let mut ctx = FormatterContext::new();
+
+        let mut dest = StringBuf::new();
+        formatter.format(&record, &mut dest, &mut ctx).unwrap();
+
+        record.set_time(record.time() + Duration::from_nanos(1500));
+        let mut dest = StringBuf::new();
+        formatter.format(&record, &mut dest, &mut ctx).unwrap();
+        assert_eq!(dest.strip_suffix(crate::__EOL).unwrap(), "1500");
+    }
+}
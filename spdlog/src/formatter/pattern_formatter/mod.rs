@@ -2,6 +2,8 @@
 #[path = "pattern/mod.rs"]
 pub mod __pattern;
 
+#[cfg(feature = "runtime-pattern")]
+mod cpp_pattern;
 #[cfg(feature = "runtime-pattern")]
 mod runtime;
 
@@ -12,7 +14,10 @@ use dyn_clone::*;
 pub use runtime::*;
 
 use crate::{
-    formatter::{Formatter, FormatterContext, TimeDate, TimeDateLazyLocked},
+    formatter::{
+        FieldsStyle, Formatter, FormatterContext, MultiLine, StaticFields, TimeDate,
+        TimeDateLazyLocked, TimeZone,
+    },
     Error, Record, StringBuf,
 };
 
@@ -294,8 +299,11 @@ use crate::{
 ///
 /// # Appendix: Full List of Built-in Patterns
 ///
+/// Date and time patterns are rendered in the time zone specified by
+/// [`PatternFormatter::time_zone`], local time by default.
+///
 /// | Placeholders          | Description                  | Example                                      |
-/// | --------------------- | ---------------------------- | -------------------------------------------- |
+/// |-----------------------|------------------------------|----------------------------------------------|
 /// | `{weekday_name}`      | Abbreviated weekday name     | `Mon`, `Tue`                                 |
 /// | `{weekday_name_full}` | Weekday name                 | `Monday`, `Tuesday`                          |
 /// | `{month_name}`        | Abbreviated month name       | `Jan`, `Feb`                                 |
@@ -320,6 +328,7 @@ use crate::{
 /// | `{time}`              | Time                         | `22:28:02`, `09:53:41`                       |
 /// | `{tz_offset}`         | Timezone offset              | `+08:00`, `+00:00`, `-06:00`                 |
 /// | `{unix_timestamp}`    | Unix timestamp               | `1528834770`                                 |
+/// | `{rfc3339}`           | RFC 3339 date time           | `2022-04-01T22:28:02.231+08:00`              |
 /// | `{full}`              | Full log message             | See [`FullFormatter`]                        |
 /// | `{level}`             | Log level                    | `critical`, `error`, `warn`                  |
 /// | `{level_short}`       | Short log level              | `C`, `E`, `W`                                |
@@ -331,15 +340,45 @@ use crate::{
 /// | `{module_path}`       | Source module path           | `mod::module` [^1]                           |
 /// | `{logger}`            | Logger name                  | `my-logger`                                  |
 /// | `{payload}`           | Log payload                  | `log message`                                |
+/// | `{code}`              | Error code                   | `E1042` [^2]                                 |
+/// | `{bytes}`             | Raw bytes in hexadecimal     | `7eff00` [^3]                                |
+/// | `{bytes_base64}`      | Raw bytes in base64          | `fv8A` [^3]                                  |
+/// | `{seq}`               | Sequence number in logger    | `42` [^4]                                    |
+/// | `{global_seq}`        | Sequence number in process   | `1042` [^4]                                  |
 /// | `{pid}`               | Process ID                   | `3824`                                       |
 /// | `{tid}`               | Thread ID                    | `3132`                                       |
+/// | `{thread_name}`       | Thread name                  | `worker-3` [^5]                              |
+/// | `{fields}`            | Key-value and static fields  | `user_id=42 POD_NAME=web-1` [^6]             |
 /// | `{eol}`               | End of line                  | `\n` (on non-Windows) or `\r\n` (on Windows) |
 /// 
 /// [^1]: Patterns related to source location require that feature
 ///       `source-location` is enabled, otherwise the output is empty.
 ///
+/// [^2]: The output is empty if the record has no error code, see
+///       [`Record::code`].
+///
+/// [^3]: The output is empty if the record has no raw bytes, see
+///       [`Record::bytes`].
+///
+/// [^4]: See [`Record::seq`] and [`Record::global_seq`].
+///
+/// [^5]: The output is empty if the thread has no name, see
+///       [`Record::thread_name`].
+///
+/// [^6]: The output is empty if the record has no key-value fields and no
+///       static fields are specified, see [`Record::key_values`] and
+///       [`PatternFormatter::static_fields`].
+///
 /// [`runtime_pattern!`]: crate::formatter::runtime_pattern
 /// [`FullFormatter`]: crate::formatter::FullFormatter
+/// [`PatternFormatter::time_zone`]: crate::formatter::PatternFormatter::time_zone
+/// [`Record::code`]: crate::Record::code
+/// [`Record::bytes`]: crate::Record::bytes
+/// [`Record::seq`]: crate::Record::seq
+/// [`Record::global_seq`]: crate::Record::global_seq
+/// [`Record::thread_name`]: crate::Record::thread_name
+/// [`Record::key_values`]: crate::Record::key_values
+/// [`PatternFormatter::static_fields`]: crate::formatter::PatternFormatter::static_fields
 pub use ::spdlog_macros::pattern;
 
 // Emit a compile error if the feature is not enabled.
@@ -350,6 +389,10 @@ pub use ::spdlog_macros::runtime_pattern_disabled as runtime_pattern;
 #[derive(Clone)]
 pub struct PatternFormatter<P> {
     pattern: P,
+    time_zone: TimeZone,
+    multi_line: MultiLine,
+    static_fields: StaticFields,
+    fields_style: FieldsStyle,
 }
 
 impl<P> PatternFormatter<P>
@@ -365,7 +408,50 @@ where
     /// - Macro [`runtime_pattern!`] to build a pattern at runtime.
     #[must_use]
     pub fn new(pattern: P) -> Self {
-        Self { pattern }
+        Self {
+            pattern,
+            time_zone: TimeZone::LOCAL,
+            multi_line: MultiLine::Raw,
+            static_fields: StaticFields::new(),
+            fields_style: FieldsStyle::new(),
+        }
+    }
+
+    /// Specifies the time zone in which date and time patterns are rendered.
+    ///
+    /// The default is [`TimeZone::LOCAL`].
+    #[must_use]
+    pub fn time_zone(mut self, time_zone: TimeZone) -> Self {
+        self.time_zone = time_zone;
+        self
+    }
+
+    /// Specifies how payloads spanning multiple lines are written by patterns
+    /// `{payload}` and `{full}`.
+    ///
+    /// The default is [`MultiLine::Raw`].
+    #[must_use]
+    pub fn multi_line(mut self, multi_line: MultiLine) -> Self {
+        self.multi_line = multi_line;
+        self
+    }
+
+    /// Specifies the static fields written by patterns `{fields}` and `{full}`.
+    ///
+    /// The default is empty.
+    #[must_use]
+    pub fn static_fields(mut self, static_fields: StaticFields) -> Self {
+        self.static_fields = static_fields;
+        self
+    }
+
+    /// Specifies how fields are rendered by patterns `{fields}` and `{full}`.
+    ///
+    /// The default is [`FieldsStyle::new`].
+    #[must_use]
+    pub fn fields_style(mut self, fields_style: FieldsStyle) -> Self {
+        self.fields_style = fields_style;
+        self
     }
 }
 
@@ -385,9 +471,14 @@ where
             }
         };
 
-        fmt_ctx.locked_time_date = Some(TimeDateLazyLocked::new(record.time()));
+        fmt_ctx.locked_time_date = Some(TimeDateLazyLocked::new(record.time(), self.time_zone));
         {
-            let mut pat_ctx = PatternContext { fmt_ctx };
+            let mut pat_ctx = PatternContext {
+                fmt_ctx,
+                multi_line: &self.multi_line,
+                static_fields: &self.static_fields,
+                fields_style: &self.fields_style,
+            };
             self.pattern.format(record, dest, &mut pat_ctx)?;
         }
         fmt_ctx.locked_time_date = None;
@@ -401,11 +492,14 @@ where
 #[derive(Debug)]
 pub struct PatternContext<'a, 'b> {
     fmt_ctx: &'a mut FormatterContext<'b>,
+    multi_line: &'a MultiLine,
+    static_fields: &'a StaticFields,
+    fields_style: &'a FieldsStyle,
 }
 
 impl PatternContext<'_, '_> {
     #[must_use]
-    fn time_date(&mut self) -> TimeDate {
+    fn time_date(&mut self) -> TimeDate<'_> {
         self.fmt_ctx.locked_time_date.as_mut().unwrap().get()
     }
 }
@@ -1230,9 +1324,12 @@ mod tests {
         let record = get_mock_record();
         let mut output = StringBuf::new();
         let mut fmt_ctx = FormatterContext::new();
-        fmt_ctx.locked_time_date = Some(TimeDateLazyLocked::new(record.time()));
+        fmt_ctx.locked_time_date = Some(TimeDateLazyLocked::new(record.time(), TimeZone::LOCAL));
         let mut pat_ctx = PatternContext {
             fmt_ctx: &mut fmt_ctx,
+            multi_line: &MultiLine::Raw,
+            static_fields: &StaticFields::new(),
+            fields_style: &FieldsStyle::new(),
         };
         pattern.format(&record, &mut output, &mut pat_ctx).unwrap();
         fmt_ctx.locked_time_date = None;
@@ -1336,4 +1433,27 @@ mod tests {
     fn test_unit_as_pattern() {
         test_pattern((), "", None);
     }
+
+    #[test]
+    fn test_rfc3339_in_time_zones() {
+        let mut record = get_mock_record();
+        // 2024-01-15 12:00:00.042 UTC
+        record.set_time(
+            std::time::SystemTime::UNIX_EPOCH + std::time::Duration::from_millis(1_705_320_000_042),
+        );
+        let format = |time_zone| {
+            let mut output = StringBuf::new();
+            PatternFormatter::new(__pattern::Rfc3339)
+                .time_zone(time_zone)
+                .format(&record, &mut output, &mut FormatterContext::new())
+                .unwrap();
+            output
+        };
+
+        assert_eq!(format(TimeZone::UTC), "2024-01-15T12:00:00.042+00:00");
+        assert_eq!(
+            format(TimeZone::fixed_offset(-(5 * 3600 + 30 * 60)).unwrap()),
+            "2024-01-15T06:30:00.042-05:30"
+        );
+    }
 }
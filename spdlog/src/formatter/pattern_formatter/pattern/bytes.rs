@@ -0,0 +1,54 @@
+use crate::{
+    formatter::{
+        pattern_formatter::{Pattern, PatternContext},
+        BytesEncoding,
+    },
+    Error, Record, StringBuf,
+};
+
+/// A pattern that writes the raw bytes attached to the record into the output
+/// in lowercase hexadecimal. Example: `7eff00`.
+///
+/// Nothing is written if the record has no raw bytes.
+#[derive(Clone, Default)]
+pub struct BytesHex;
+
+impl Pattern for BytesHex {
+    fn format(
+        &self,
+        record: &Record,
+        dest: &mut StringBuf,
+        _ctx: &mut PatternContext,
+    ) -> crate::Result<()> {
+        write_bytes(record, dest, BytesEncoding::Hex)
+    }
+}
+
+/// A pattern that writes the raw bytes attached to the record into the output
+/// in base64. Example: `fv8A`.
+///
+/// Nothing is written if the record has no raw bytes.
+#[derive(Clone, Default)]
+pub struct BytesBase64;
+
+impl Pattern for BytesBase64 {
+    fn format(
+        &self,
+        record: &Record,
+        dest: &mut StringBuf,
+        _ctx: &mut PatternContext,
+    ) -> crate::Result<()> {
+        write_bytes(record, dest, BytesEncoding::Base64)
+    }
+}
+
+fn write_bytes(
+    record: &Record,
+    dest: &mut StringBuf,
+    encoding: BytesEncoding,
+) -> crate::Result<()> {
+    match record.bytes() {
+        Some(bytes) => encoding.write(bytes, dest).map_err(Error::FormatRecord),
+        None => Ok(()),
+    }
+}
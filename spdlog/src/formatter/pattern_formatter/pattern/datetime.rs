@@ -458,3 +458,35 @@ impl Pattern for UnixTimestamp {
             .map_err(Error::FormatRecord)
     }
 }
+
+/// A pattern that writes the timestamp of log records in RFC 3339 format with
+/// milliseconds into the output. Examples: `2022-04-01T22:28:02.231+08:00`,
+/// `2021-12-31T09:53:41.004+00:00`.
+#[derive(Clone, Default)]
+pub struct Rfc3339;
+
+impl Pattern for Rfc3339 {
+    fn format(
+        &self,
+        _record: &Record,
+        dest: &mut StringBuf,
+        ctx: &mut PatternContext,
+    ) -> crate::Result<()> {
+        (|| {
+            dest.write_str(ctx.time_date().year_str())?;
+            dest.write_char('-')?;
+            dest.write_str(ctx.time_date().month_str())?;
+            dest.write_char('-')?;
+            dest.write_str(ctx.time_date().day_str())?;
+            dest.write_char('T')?;
+            dest.write_str(ctx.time_date().hour_str())?;
+            dest.write_char(':')?;
+            dest.write_str(ctx.time_date().minute_str())?;
+            dest.write_char(':')?;
+            dest.write_str(ctx.time_date().second_str())?;
+            write!(dest, ".{:03}", ctx.time_date().millisecond())?;
+            dest.write_str(ctx.time_date().tz_offset_str())
+        })()
+        .map_err(Error::FormatRecord)
+    }
+}
@@ -0,0 +1,25 @@
+use std::fmt::Write;
+
+use crate::{
+    formatter::pattern_formatter::{Pattern, PatternContext},
+    Error, Record, StringBuf,
+};
+
+/// A pattern that writes the error code of the record into the output.
+/// Example: `E1042`.
+///
+/// Nothing is written if the record has no error code.
+#[derive(Clone, Default)]
+pub struct ErrorCode;
+
+impl Pattern for ErrorCode {
+    fn format(
+        &self,
+        record: &Record,
+        dest: &mut StringBuf,
+        _ctx: &mut PatternContext,
+    ) -> crate::Result<()> {
+        dest.write_str(record.code().unwrap_or(""))
+            .map_err(Error::FormatRecord)
+    }
+}
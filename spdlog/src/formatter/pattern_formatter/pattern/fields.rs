@@ -0,0 +1,32 @@
+use crate::{
+    formatter::pattern_formatter::{Pattern, PatternContext},
+    Error, Record, StringBuf,
+};
+
+/// A pattern that writes the key-value fields of the log record and the static
+/// fields of the pattern formatter into the output. Example: `user_id=42
+/// POD_NAME=web-1`.
+///
+/// Nothing is written if the record has no key-value fields and no static
+/// fields are specified. See [`Record::key_values`] and
+/// [`PatternFormatter::static_fields`] for more details. Fields are rendered
+/// according to [`PatternFormatter::fields_style`].
+///
+/// [`Record::key_values`]: crate::Record::key_values
+/// [`PatternFormatter::static_fields`]: crate::formatter::PatternFormatter::static_fields
+/// [`PatternFormatter::fields_style`]: crate::formatter::PatternFormatter::fields_style
+#[derive(Clone, Default)]
+pub struct Fields;
+
+impl Pattern for Fields {
+    fn format(
+        &self,
+        record: &Record,
+        dest: &mut StringBuf,
+        ctx: &mut PatternContext,
+    ) -> crate::Result<()> {
+        ctx.fields_style
+            .write(record.key_values().chain(ctx.static_fields.iter()), dest)
+            .map_err(Error::FormatRecord)
+    }
+}
@@ -1,7 +1,7 @@
 use crate::{
     formatter::{
         pattern_formatter::{Pattern, PatternContext},
-        Formatter, FullFormatter,
+        FullFormatter,
     },
     Record, StringBuf,
 };
@@ -9,7 +9,13 @@ use crate::{
 /// A pattern that writes the fully formatted text of log records into the
 /// output.
 ///
-/// This pattern writes the same formatted text as [`FullFormatter`].
+/// This pattern writes the same formatted text as [`FullFormatter`], following
+/// the [`multi_line`], [`static_fields`] and [`fields_style`] options of the
+/// pattern formatter.
+///
+/// [`multi_line`]: crate::formatter::PatternFormatter::multi_line
+/// [`static_fields`]: crate::formatter::PatternFormatter::static_fields
+/// [`fields_style`]: crate::formatter::PatternFormatter::fields_style
 #[derive(Clone)]
 pub struct Full {
     full_formatter: FullFormatter,
@@ -32,7 +38,14 @@ impl Pattern for Full {
     ) -> crate::Result<()> {
         let saved_style_range = ctx.fmt_ctx.style_range.clone();
 
-        self.full_formatter.format(record, dest, ctx.fmt_ctx)?;
+        self.full_formatter.format_with_options(
+            record,
+            dest,
+            ctx.fmt_ctx,
+            ctx.multi_line,
+            ctx.static_fields,
+            ctx.fields_style,
+        )?;
 
         // TODO: Before we support multiple style ranges, if there is already a style
         // range set, we don't override it.
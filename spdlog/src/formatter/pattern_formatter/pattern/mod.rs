@@ -1,23 +1,33 @@
 //! This module provides all the built-in patterns.
 
+mod bytes;
 mod datetime;
 mod eol;
+mod error_code;
+mod fields;
 mod full;
 mod level;
 mod logger_name;
 mod payload;
 mod process_id;
+mod sequence;
 mod srcloc;
 mod style_range;
 mod thread_id;
+mod thread_name;
 
+pub use bytes::*;
 pub use datetime::*;
 pub use eol::*;
+pub use error_code::*;
+pub use fields::*;
 pub use full::*;
 pub use level::*;
 pub use logger_name::*;
 pub use payload::*;
 pub use process_id::*;
+pub use sequence::*;
 pub use srcloc::*;
 pub use style_range::*;
 pub use thread_id::*;
+pub use thread_name::*;
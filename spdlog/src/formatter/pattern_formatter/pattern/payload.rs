@@ -1,5 +1,3 @@
-use std::fmt::Write;
-
 use crate::{
     formatter::pattern_formatter::{Pattern, PatternContext},
     Error, Record, StringBuf,
@@ -7,6 +5,11 @@ use crate::{
 
 /// A pattern that writes the payload of a log record into output. Example: `log
 /// message`.
+///
+/// Payloads spanning multiple lines are written according to
+/// [`PatternFormatter::multi_line`].
+///
+/// [`PatternFormatter::multi_line`]: crate::formatter::PatternFormatter::multi_line
 #[derive(Clone, Default)]
 pub struct Payload;
 
@@ -15,9 +18,10 @@ impl Pattern for Payload {
         &self,
         record: &Record,
         dest: &mut StringBuf,
-        _ctx: &mut PatternContext,
+        ctx: &mut PatternContext,
     ) -> crate::Result<()> {
-        dest.write_str(record.payload())
+        ctx.multi_line
+            .write_payload(record.payload(), dest)
             .map_err(Error::FormatRecord)
     }
 }
@@ -0,0 +1,42 @@
+use std::fmt::Write;
+
+use crate::{
+    formatter::pattern_formatter::{Pattern, PatternContext},
+    Error, Record, StringBuf,
+};
+
+/// A pattern that writes the sequence number of the record within its logger
+/// into the output. Example: `42`.
+///
+/// See [`Record::seq`] for more details.
+#[derive(Clone, Default)]
+pub struct Sequence;
+
+impl Pattern for Sequence {
+    fn format(
+        &self,
+        record: &Record,
+        dest: &mut StringBuf,
+        _ctx: &mut PatternContext,
+    ) -> crate::Result<()> {
+        write!(dest, "{}", record.seq()).map_err(Error::FormatRecord)
+    }
+}
+
+/// A pattern that writes the sequence number of the record across all loggers
+/// into the output. Example: `1042`.
+///
+/// See [`Record::global_seq`] for more details.
+#[derive(Clone, Default)]
+pub struct GlobalSequence;
+
+impl Pattern for GlobalSequence {
+    fn format(
+        &self,
+        record: &Record,
+        dest: &mut StringBuf,
+        _ctx: &mut PatternContext,
+    ) -> crate::Result<()> {
+        write!(dest, "{}", record.global_seq()).map_err(Error::FormatRecord)
+    }
+}
@@ -0,0 +1,26 @@
+use std::fmt::Write;
+
+use crate::{
+    formatter::pattern_formatter::{Pattern, PatternContext},
+    Error, Record, StringBuf,
+};
+
+/// A pattern that writes the name of the thread into the output. Example:
+/// `worker-3`.
+///
+/// Nothing is written if the thread has no name. See [`Record::thread_name`]
+/// for more details.
+#[derive(Clone, Default)]
+pub struct ThreadName;
+
+impl Pattern for ThreadName {
+    fn format(
+        &self,
+        record: &Record,
+        dest: &mut StringBuf,
+        _ctx: &mut PatternContext,
+    ) -> crate::Result<()> {
+        dest.write_str(record.thread_name().unwrap_or(""))
+            .map_err(Error::FormatRecord)
+    }
+}
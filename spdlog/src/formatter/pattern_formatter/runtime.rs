@@ -132,6 +132,15 @@ pub use spdlog_macros::runtime_pattern;
 pub struct RuntimePattern(Patterns);
 
 impl RuntimePattern {
+    pub(crate) fn new(template: &str) -> Result<Self> {
+        Self::__with_custom_patterns(template, PatternRegistry::with_builtin())
+    }
+
+    #[must_use]
+    pub(super) fn from_patterns(patterns: Patterns) -> Self {
+        Self(patterns)
+    }
+
     // Private function, do not use in your code directly.
     #[doc(hidden)]
     pub fn __with_custom_patterns(template: &str, registry: PatternRegistry) -> Result<Self> {
@@ -242,6 +251,7 @@ fn build_builtin_pattern(builtin: &BuiltInFormatter) -> Box<dyn Pattern> {
         Time,
         TzOffset,
         UnixTimestamp,
+        Rfc3339,
         Full,
         Level,
         ShortLevel,
@@ -253,8 +263,15 @@ fn build_builtin_pattern(builtin: &BuiltInFormatter) -> Box<dyn Pattern> {
         SourceModulePath,
         LoggerName,
         Payload,
+        ErrorCode,
+        BytesHex,
+        BytesBase64,
+        Sequence,
+        GlobalSequence,
         ProcessId,
         ThreadId,
+        ThreadName,
+        Fields,
         Eol
     )
 }
@@ -0,0 +1,154 @@
+use std::env;
+
+/// A set of key-value fields attached to every record by a formatter.
+///
+/// Static fields carry metadata that is the same for all records of a process,
+/// e.g. the name of the pod and the region it is deployed in, so that the
+/// records can be told apart after they are collected from multiple processes.
+/// They are usually captured from environment variables at initialization with
+/// [`StaticFields::from_env`] or [`StaticFields::env`].
+///
+/// Keys are unique, setting a key that is already present replaces its value.
+/// Fields are rendered in the order they are first set.
+///
+/// Static fields are rendered by [`FullFormatter`] and pattern `{fields}` of
+/// [`PatternFormatter`] after the payload according to their [`FieldsStyle`],
+/// and by [`JsonFormatter`] as the `fields` object.
+///
+/// # Examples
+///
+/// ```
+/// use spdlog::formatter::{FullFormatter, StaticFields};
+///
+/// // [2022-11-02 09:23:12.263] [info] hello, world! POD_NAME=web-1 region=eu-west-1
+/// let fields = StaticFields::from_env(["POD_NAME"]).env("region", "REGION");
+/// let formatter = FullFormatter::new().static_fields(fields);
+/// ```
+///
+/// [`FullFormatter`]: crate::formatter::FullFormatter
+/// [`PatternFormatter`]: crate::formatter::PatternFormatter
+/// [`JsonFormatter`]: crate::formatter::JsonFormatter
+/// [`FieldsStyle`]: crate::formatter::FieldsStyle
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct StaticFields {
+    fields: Vec<(String, String)>,
+}
+
+impl StaticFields {
+    /// Constructs an empty `StaticFields`.
+    #[must_use]
+    pub fn new() -> Self {
+        Self { fields: vec![] }
+    }
+
+    /// Constructs a `StaticFields` from the current values of the given
+    /// environment variables, using their names as keys.
+    ///
+    /// Variables that are not present are skipped. Values that are not valid
+    /// Unicode are converted lossily.
+    #[must_use]
+    pub fn from_env<I, S>(names: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        names.into_iter().fold(Self::new(), |fields, name| {
+            let name = name.as_ref();
+            fields.env(name, name)
+        })
+    }
+
+    /// Sets a field with the given key and value.
+    #[must_use]
+    pub fn field<K, V>(mut self, key: K, value: V) -> Self
+    where
+        K: Into<String>,
+        V: Into<String>,
+    {
+        let (key, value) = (key.into(), value.into());
+        match self.fields.iter_mut().find(|(k, _)| *k == key) {
+            Some((_, v)) => *v = value,
+            None => self.fields.push((key, value)),
+        }
+        self
+    }
+
+    /// Sets a field with the given key to the current value of the given
+    /// environment variable.
+    ///
+    /// Nothing is set if the variable is not present. A value that is not
+    /// valid Unicode is converted lossily.
+    #[must_use]
+    pub fn env<K>(self, key: K, name: &str) -> Self
+    where
+        K: Into<String>,
+    {
+        match env::var_os(name) {
+            Some(value) => self.field(key, value.to_string_lossy()),
+            None => self,
+        }
+    }
+
+    /// Gets the value of the field with the given key.
+    #[must_use]
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.fields
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// Gets an iterator over the keys and values of the fields, in the order
+    /// they are first set.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.fields.iter().map(|(k, v)| (k.as_str(), v.as_str()))
+    }
+
+    /// Gets the number of fields.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.fields.len()
+    }
+
+    /// Returns `true` if there are no fields.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.fields.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_env() {
+        env::set_var("SPDLOG_TEST_STATIC_FIELDS_POD", "web-1");
+        env::remove_var("SPDLOG_TEST_STATIC_FIELDS_MISSING");
+
+        let fields = StaticFields::from_env([
+            "SPDLOG_TEST_STATIC_FIELDS_POD",
+            "SPDLOG_TEST_STATIC_FIELDS_MISSING",
+        ])
+        .env("pod", "SPDLOG_TEST_STATIC_FIELDS_POD")
+        .env("missing", "SPDLOG_TEST_STATIC_FIELDS_MISSING");
+
+        assert_eq!(
+            fields.iter().collect::<Vec<_>>(),
+            [("SPDLOG_TEST_STATIC_FIELDS_POD", "web-1"), ("pod", "web-1")]
+        );
+    }
+
+    #[test]
+    fn replace_field() {
+        let fields = StaticFields::new()
+            .field("region", "eu")
+            .field("pod", "web-1")
+            .field("region", "us");
+        assert_eq!(fields.get("region"), Some("us"));
+        assert_eq!(
+            fields.iter().collect::<Vec<_>>(),
+            [("region", "us"), ("pod", "web-1")]
+        );
+    }
+}
@@ -0,0 +1,206 @@
+use std::time::SystemTime;
+
+use crate::{
+    error::InvalidArgumentError,
+    time_backend::{self, DateTime},
+    Error, Result,
+};
+
+/// Represents the time zone in which formatters render the time of records.
+///
+/// By default, formatters render the time in the local time zone of the
+/// system. Formatters can be configured with a different time zone, so logs
+/// from machines in different time zones can be normalized to a single one.
+///
+/// The local time zone is looked up via the selected time backend (see [crate
+/// feature flags]). Without a backend providing it, the local time zone is
+/// treated as UTC.
+///
+/// # Examples
+///
+/// ```
+/// use spdlog::formatter::{FullFormatter, TimeZone};
+///
+/// # fn main() -> Result<(), spdlog::Error> {
+/// let utc = FullFormatter::new().time_zone(TimeZone::UTC);
+/// let utc_plus_8 = FullFormatter::new().time_zone(TimeZone::fixed_offset(8 * 3600)?);
+/// # #[cfg(feature = "chrono-tz")]
+/// let new_york = FullFormatter::new().time_zone(TimeZone::named("America/New_York")?);
+/// # Ok(()) }
+/// ```
+///
+/// [crate feature flags]: crate#crate-feature-flags
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct TimeZone(TimeZoneInner);
+
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+enum TimeZoneInner {
+    Local,
+    Utc,
+    FixedOffset(i32),
+    #[cfg(feature = "chrono-tz")]
+    Named(chrono_tz::Tz),
+}
+
+impl TimeZone {
+    /// The local time zone of the system.
+    ///
+    /// This is the default time zone.
+    pub const LOCAL: TimeZone = TimeZone(TimeZoneInner::Local);
+
+    /// Coordinated Universal Time.
+    pub const UTC: TimeZone = TimeZone(TimeZoneInner::Utc);
+
+    /// Constructs a time zone with a fixed offset from UTC, in seconds.
+    ///
+    /// The offset is positive for time zones east of UTC, e.g. `8 * 3600` for
+    /// `+08:00`.
+    ///
+    /// # Error
+    ///
+    /// If the offset is not within a day (exclusive),
+    /// [`Error::InvalidArgument`] will be returned.
+    pub fn fixed_offset(seconds: i32) -> Result<Self> {
+        if seconds.unsigned_abs() < 86_400 {
+            Ok(TimeZone(TimeZoneInner::FixedOffset(seconds)))
+        } else {
+            Err(Error::InvalidArgument(InvalidArgumentError::TimeZone(
+                format!("offset {} seconds is out of range", seconds),
+            )))
+        }
+    }
+
+    /// Constructs a named time zone from the IANA time zone database, e.g.
+    /// `America/New_York` or `Asia/Shanghai`.
+    ///
+    /// Daylight saving time is taken into account according to the database.
+    ///
+    /// This function is only available if crate feature `chrono-tz` is
+    /// enabled.
+    ///
+    /// # Error
+    ///
+    /// If the name is unknown, [`Error::InvalidArgument`] will be returned.
+    #[cfg(feature = "chrono-tz")]
+    pub fn named(name: &str) -> Result<Self> {
+        name.parse::<chrono_tz::Tz>()
+            .map(|tz| TimeZone(TimeZoneInner::Named(tz)))
+            .map_err(|err| Error::InvalidArgument(InvalidArgumentError::TimeZone(err.to_string())))
+    }
+
+    /// Gets the offset of this time zone from UTC at the given time, in
+    /// seconds.
+    #[must_use]
+    pub(crate) fn utc_offset(&self, time: SystemTime) -> i32 {
+        match &self.0 {
+            TimeZoneInner::Local => time_backend::local_offset(time),
+            TimeZoneInner::Utc => 0,
+            TimeZoneInner::FixedOffset(offset) => *offset,
+            #[cfg(feature = "chrono-tz")]
+            TimeZoneInner::Named(tz) => {
+                use chrono::{Offset, TimeZone as _, Utc};
+
+                let utc = chrono::DateTime::<Utc>::from(time).naive_utc();
+                tz.offset_from_utc_datetime(&utc).fix().local_minus_utc()
+            }
+        }
+    }
+
+    /// Converts the time into a date time in this time zone.
+    #[must_use]
+    pub(crate) fn date_time(&self, time: SystemTime) -> DateTime {
+        DateTime::new(time, self.utc_offset(time))
+    }
+
+    /// Gets the time at which the clocks in this time zone show the given time
+    /// of day, on the date of the given date time.
+    #[must_use]
+    pub(crate) fn time_of_day(
+        &self,
+        date_time: &DateTime,
+        hour: u32,
+        minute: u32,
+        second: u32,
+    ) -> SystemTime {
+        let local = date_time.local_timestamp_at(hour, minute, second);
+        // The offset may differ from the one of the date time, e.g. across a
+        // daylight saving time transition.
+        self.local_timestamp_to_time(local, date_time.offset())
+    }
+
+    /// Gets the time at which the clocks in this time zone show the given local
+    /// timestamp (see [`time_backend::local_timestamp`]), starting the search
+    /// from the given offset.
+    #[must_use]
+    pub(crate) fn local_timestamp_to_time(&self, local: i64, offset_guess: i32) -> SystemTime {
+        let guess = time_backend::from_unix_timestamp(local - offset_guess as i64);
+        time_backend::from_unix_timestamp(local - self.utc_offset(guess) as i64)
+    }
+}
+
+impl Default for TimeZone {
+    fn default() -> Self {
+        Self::LOCAL
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    #[test]
+    fn date_time() {
+        // 2024-01-15 12:00:00 UTC
+        let time = SystemTime::UNIX_EPOCH + Duration::from_secs(1_705_320_000);
+        let format = |tz: TimeZone| {
+            let date_time = tz.date_time(time);
+            (
+                date_time.day(),
+                date_time.hour(),
+                date_time.minute(),
+                date_time.offset(),
+            )
+        };
+
+        assert_eq!(format(TimeZone::UTC), (15, 12, 0, 0));
+        assert_eq!(
+            format(TimeZone::fixed_offset(-(5 * 3600 + 30 * 60)).unwrap()),
+            (15, 6, 30, -(5 * 3600 + 30 * 60))
+        );
+        #[cfg(feature = "chrono")]
+        assert_eq!(
+            TimeZone::LOCAL.date_time(time).offset(),
+            chrono::DateTime::<chrono::Local>::from(time)
+                .offset()
+                .local_minus_utc()
+        );
+        #[cfg(feature = "chrono-tz")]
+        {
+            assert_eq!(
+                format(TimeZone::named("America/New_York").unwrap()),
+                (15, 7, 0, -5 * 3600)
+            );
+            assert!(TimeZone::named("Mars/Olympus_Mons").is_err());
+        }
+
+        assert!(matches!(
+            TimeZone::fixed_offset(86_400),
+            Err(Error::InvalidArgument(InvalidArgumentError::TimeZone(_)))
+        ));
+    }
+
+    #[test]
+    fn time_of_day() {
+        // 2024-01-15 12:00:00 UTC
+        let time = SystemTime::UNIX_EPOCH + Duration::from_secs(1_705_320_000);
+        let tz = TimeZone::fixed_offset(8 * 3600).unwrap();
+
+        // 2024-01-15 20:00:00 +08:00 -> 2024-01-15 06:30:00 +08:00
+        assert_eq!(
+            tz.time_of_day(&tz.date_time(time), 6, 30, 0),
+            time - Duration::from_secs(13 * 3600 + 30 * 60)
+        );
+    }
+}
@@ -0,0 +1,97 @@
+use std::fmt::{self, Write};
+
+const BYTES_PER_LINE: usize = 16;
+
+/// Renders a byte slice as a canonical hex+ASCII dump.
+///
+/// The output has the same layout as `hexdump -C`: each line contains the
+/// offset, 16 bytes in hexadecimal (split into two groups of 8), and the
+/// printable ASCII characters of these bytes (non-printable bytes are shown as
+/// `.`). Lines are separated by the platform's line ending, without a trailing
+/// one, so the whole dump can be logged as a single record.
+///
+/// # Examples
+///
+/// ```
+/// use spdlog::{info, HexDump};
+///
+/// let packet = b"Hello, world!\n\x00\x01\x02";
+///
+/// info!("received packet:\n{}", HexDump::new(packet));
+/// # assert_eq!(
+/// #     HexDump::new(packet).to_string().replace("\r", ""),
+/// /* Output of the dump */
+/// "00000000  48 65 6c 6c 6f 2c 20 77  6f 72 6c 64 21 0a 00 01  |Hello, world!...|
+/// 00000010  02                                                |.|"
+/// # );
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct HexDump<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> HexDump<'a> {
+    /// Constructs a `HexDump` for the given bytes.
+    #[must_use]
+    pub fn new(data: &'a [u8]) -> Self {
+        Self { data }
+    }
+}
+
+impl fmt::Display for HexDump<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (index, line) in self.data.chunks(BYTES_PER_LINE).enumerate() {
+            if index != 0 {
+                f.write_str(crate::__EOL)?;
+            }
+
+            write!(f, "{:08x} ", index * BYTES_PER_LINE)?;
+            for i in 0..BYTES_PER_LINE {
+                if i == BYTES_PER_LINE / 2 {
+                    f.write_char(' ')?;
+                }
+                match line.get(i) {
+                    Some(byte) => write!(f, " {:02x}", byte)?,
+                    None => f.write_str("   ")?,
+                }
+            }
+
+            f.write_str("  |")?;
+            for byte in line {
+                let ch = if byte.is_ascii_graphic() || *byte == b' ' {
+                    *byte as char
+                } else {
+                    '.'
+                };
+                f.write_char(ch)?;
+            }
+            f.write_char('|')?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format() {
+        assert_eq!(HexDump::new(b"").to_string(), "");
+        assert_eq!(
+            HexDump::new(b"spdlog-rs").to_string(),
+            "00000000  73 70 64 6c 6f 67 2d 72  73                       |spdlog-rs|"
+        );
+
+        let data = (0..=0x20).collect::<Vec<u8>>();
+        assert_eq!(
+            HexDump::new(&data).to_string(),
+            [
+                "00000000  00 01 02 03 04 05 06 07  08 09 0a 0b 0c 0d 0e 0f  |................|",
+                "00000010  10 11 12 13 14 15 16 17  18 19 1a 1b 1c 1d 1e 1f  |................|",
+                "00000020  20                                                | |",
+            ]
+            .join(crate::__EOL)
+        );
+    }
+}
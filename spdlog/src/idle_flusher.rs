@@ -0,0 +1,115 @@
+use std::{thread, time::Duration};
+
+use crate::{sync::*, thread_options};
+
+// Calls the callback once no records have been logged for the idle period since
+// the last record, for flushing records that a bursty or interactive program
+// logged before going quiet.
+pub struct IdleFlusher {
+    idle: Duration,
+    shared: Arc<Shared>,
+    thread: Option<thread::JoinHandle<()>>,
+}
+
+struct Shared {
+    // Whether records have been logged since the callback was last called.
+    pending: AtomicBool,
+    // Incremented for each record, to tell whether the logger is still busy.
+    records: AtomicU64,
+    active: Mutex<bool>,
+    condvar: Condvar,
+}
+
+impl IdleFlusher {
+    // `callback` returns `false` to quit the worker thread.
+    #[allow(clippy::mutex_atomic)]
+    #[must_use]
+    pub fn new(idle: Duration, callback: impl Fn() -> bool + Send + 'static) -> Self {
+        let shared = Arc::new(Shared {
+            pending: AtomicBool::new(false),
+            records: AtomicU64::new(0),
+            active: Mutex::new(true),
+            condvar: Condvar::new(),
+        });
+
+        let thread = {
+            let shared = shared.clone();
+            thread_options::spawn_worker("spdlog-idle", move || shared.run(idle, callback))
+        };
+
+        Self {
+            idle,
+            shared,
+            thread: Some(thread),
+        }
+    }
+
+    #[must_use]
+    pub fn idle(&self) -> Duration {
+        self.idle
+    }
+
+    // Called for each logged record, it is cheap unless the logger was idle.
+    pub fn on_record(&self) {
+        self.shared.records.fetch_add(1, Ordering::Relaxed);
+        if !self.shared.pending.swap(true, Ordering::AcqRel) {
+            // Notify while holding the lock, so that the notification is not
+            // lost between the check and the wait of the worker thread.
+            let _active = self.shared.active.lock_expect();
+            self.shared.condvar.notify_all();
+        }
+    }
+}
+
+impl Shared {
+    #[allow(clippy::mutex_atomic)]
+    fn run(&self, idle: Duration, callback: impl Fn() -> bool) {
+        let mut active = self.active.lock_expect();
+        loop {
+            active = self
+                .condvar
+                .wait_while(active, |active| {
+                    *active && !self.pending.load(Ordering::Acquire)
+                })
+                .unwrap();
+
+            // Wait until no records are logged for a whole idle period.
+            loop {
+                if !*active {
+                    return;
+                }
+                let records = self.records.load(Ordering::Relaxed);
+                active = self
+                    .condvar
+                    .wait_timeout_while(active, idle, |active| *active)
+                    .unwrap()
+                    .0;
+                if *active && self.records.load(Ordering::Relaxed) == records {
+                    break;
+                }
+            }
+
+            // Records logged from now on will be flushed by the next round.
+            self.pending.store(false, Ordering::Release);
+            drop(active);
+            if !callback() {
+                return;
+            }
+            active = self.active.lock_expect();
+        }
+    }
+}
+
+impl Drop for IdleFlusher {
+    #[allow(clippy::mutex_atomic)]
+    fn drop(&mut self) {
+        *self.shared.active.lock_expect() = false;
+        self.shared.condvar.notify_all();
+
+        let thread = self.thread.take().unwrap();
+        // The owner may be dropped in the callback on the worker thread itself.
+        if thread.thread().id() != thread::current().id() {
+            thread.join().expect("IdleFlusher: worker thread panicked");
+        }
+    }
+}
@@ -77,6 +77,17 @@ impl serde::Serialize for Level {
     }
 }
 
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Level {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let level = <std::borrow::Cow<str>>::deserialize(deserializer)?;
+        level.parse().map_err(serde::de::Error::custom)
+    }
+}
+
 cfg_if! {
     if #[cfg(test)] {
         crate::utils::const_assert!(atomic::Atomic::<Level>::is_lock_free());
@@ -85,7 +96,7 @@ cfg_if! {
 
 impl Level {
     #[must_use]
-    fn from_usize(u: usize) -> Option<Level> {
+    pub(crate) fn from_usize(u: usize) -> Option<Level> {
         match u {
             0 => Some(Level::Critical),
             1 => Some(Level::Error),
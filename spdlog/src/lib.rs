@@ -53,6 +53,19 @@
 //! [2022-11-02 09:23:12.263] [<font color="#11A8CD">debug</font>] 3 + 2 = 5
 //! </pre>
 //!
+//! For the common case, [`init`] sets up a colored `stderr` logger, the [log
+//! crate] backend and a panic hook in one call:
+//!
+//! ```
+//! use spdlog::prelude::*;
+//!
+//! # fn main() -> spdlog::Result<()> {
+//! let _guard = spdlog::init()?;
+//!
+//! info!("hello, world!");
+//! # Ok(()) }
+//! ```
+//!
 //! The basic use is through these logging macros: [`trace!`], [`debug!`],
 //! [`info!`], [`warn!`], [`error!`], [`critical!`], where `critical!`
 //! represents the most severe logs and `trace!` the most verbose. Each of these
@@ -199,6 +212,10 @@
 //!
 //!  - `log` enables the compatibility with [log crate].
 //!
+//!  - `regex` makes the message filter of [`RustLogFilter`] a regular
+//!    expression instead of a substring, the same as `env_logger`, and enables
+//!    [`sink::RegexFilterSink`] and [`Redactor`].
+//!
 //!  - `native` enables platform-specific components, such as
 //!    [`sink::WinDebugSink`] for Windows, [`sink::JournaldSink`] for Linux,
 //!    etc. Note If the component requires additional system dependencies, then
@@ -207,8 +224,59 @@
 //!  - `runtime-pattern` enables the ability to build patterns with runtime
 //!    template string. See [`RuntimePattern`] for more details.
 //!
+//!  - `serde` implements [`serde::Serialize`] and [`serde::Deserialize`] for
+//!    [`RecordOwned`], so that records can be persisted, sent across processes,
+//!    and replayed into sinks.
+//!
 //!  - `serde_json` enables [`formatter::JsonFormatter`].
 //!
+//!  - `chrono` (enabled by default) and `time` select the date library used to
+//!    look up the local time zone, via [chrono crate] or [time crate]
+//!    respectively. If both are enabled, `chrono` takes precedence. If neither
+//!    is enabled, the local time zone is looked up via `localtime_r` of the C
+//!    library on Unix-like platforms and via the Windows API on Windows, and is
+//!    treated as UTC on other platforms. Note that [time crate] is unable to
+//!    look up the local time zone in multi-threaded processes on some
+//!    platforms, in which case UTC is used as well.
+//!
+//!  - `chrono-tz` enables named time zones from the IANA time zone database via
+//!    [`formatter::TimeZone::named`].
+//!
+//!  - `gzip` enables compressing rotated files of [`sink::RotatingFileSink`]
+//!    into gzip archives, see [`sink::RotatingFileSinkBuilder::compress`].
+//!
+//!  - `tracing` enables the compatibility with [tracing crate] via
+//!    [`TracingLayer`].
+//!
+//!  - `slog` enables the compatibility with [slog crate] via [`SlogDrain`].
+//!
+//!  - `anyhow` and `eyre` enable logging [anyhow crate] errors and [eyre crate]
+//!    reports with [`log_report!`] and [`ErrorReport`].
+//!
+//!  - `metrics` emits counters to [metrics crate] alongside normal sinking, so
+//!    that error-log rates can be monitored without a log pipeline. It requires
+//!    Rust 1.71.1 or later, see [Supported Rust versions]. The label `logger`
+//!    is the logger name, or an empty string for unnamed loggers.
+//!    - `spdlog_records_total` (labels `logger`, `level`) counts records that
+//!      passed the level filter, the filters and the sampler of a logger.
+//!    - `spdlog_sink_errors_total` (label `logger`) counts errors returned by
+//!      sinks when logging or flushing.
+//!    - `spdlog_records_dropped_total` (label `logger`) counts records dropped
+//!      by asynchronous sinks, e.g. because the channel is full.
+//!
+//!  - `log-async` enables [`Logger::log_async`] and [`log_async!`], which await
+//!    until sinks have capacity for a record, so that asynchronous applications
+//!    can apply backpressure to log producers.
+//!
+//!  - `ffi` enables a C API for emitting logs from C/C++ code. See [`ffi`] for
+//!    more details.
+//!
+//!  - `receiver` enables [`receiver::Receiver`], which receives records sent
+//!    over the network by other processes and logs them into a local logger.
+//!
+//!  - `config` enables building loggers from configuration files, such as
+//!    log4rs-style YAML configurations. See [`config`] for more details.
+//!
 //! # Supported Rust versions
 //!
 //! <!--
@@ -220,6 +288,9 @@
 //!
 //! The current minimum supported Rust version is 1.60.
 //!
+//! Crate feature `metrics` is an exception, it requires Rust 1.71.1 or later,
+//! which is the minimum supported version of [metrics crate].
+//!
 //! `spdlog-rs` is built against the latest Rust stable release, it is not
 //! guaranteed to build on Rust versions earlier than the minimum supported
 //! version.
@@ -234,10 +305,9 @@
 //! # Significant differences from C++ spdlog
 //!
 //! The significant differences between `spdlog-rs` and C++ `spdlog`[^1]:
-//!  - `spdlog-rs` does not have `registry`[^2]. You don't need to register for
-//!    loggers.
-//!
-//!  - `spdlog-rs` does not have `backtrace`[^2].
+//!  - In `spdlog-rs`, the `registry` is optional. You don't need to register
+//!    for loggers, but they can be registered with [`register_logger`] and
+//!    looked up by name with [`get_logger`] or [`get_or_create`].
 //!
 //!  - In `spdlog-rs`, [`LevelFilter`] is a more flexible and readable enum with
 //!    logical conditions.
@@ -258,8 +328,6 @@
 //!
 //! [^1]: At the time of writing this section, the latest version of C++ `spdlog` is v1.9.2.
 //!
-//! [^2]: C++ `spdlog` is also planned to remove it in v2.x.
-//!
 //! [spdlog]: https://github.com/gabime/spdlog
 //! [`FileSink`]: crate::sink::FileSink
 //! [`RotatingFileSink`]: crate::sink::RotatingFileSink
@@ -268,6 +336,14 @@
 //! [open a discussion]: https://github.com/SpriteOvO/spdlog-rs/discussions/new
 //! [open an issue]: https://github.com/SpriteOvO/spdlog-rs/issues/new/choose
 //! [log crate]: https://crates.io/crates/log
+//! [tracing crate]: https://crates.io/crates/tracing
+//! [slog crate]: https://crates.io/crates/slog
+//! [anyhow crate]: https://crates.io/crates/anyhow
+//! [eyre crate]: https://crates.io/crates/eyre
+//! [metrics crate]: https://crates.io/crates/metrics
+//! [Supported Rust versions]: #supported-rust-versions
+//! [chrono crate]: https://crates.io/crates/chrono
+//! [time crate]: https://crates.io/crates/time
 //! [`Formatter`]: crate::formatter::Formatter
 //! [`RuntimePattern`]: crate::formatter::RuntimePattern
 //! [`RotationPolicy::Daily`]: crate::sink::RotationPolicy::Daily
@@ -278,53 +354,106 @@
 #![cfg_attr(all(doc, CHANNEL_NIGHTLY), feature(doc_auto_cfg))]
 #![warn(missing_docs)]
 
+pub mod clock;
+#[cfg(feature = "config")]
+pub mod config;
+#[cfg(any(unix, windows))]
+mod crash_handler;
+pub mod diagnostics;
 mod env_level;
 pub mod error;
+mod error_report;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+mod filter;
 pub mod formatter;
+mod hex_dump;
+mod idle_flusher;
 mod level;
 #[cfg(feature = "log")]
 mod log_crate_proxy;
 mod log_macros;
+#[cfg(feature = "metrics")]
+mod log_metrics;
 mod logger;
 mod periodic_worker;
+mod quick_start;
 pub mod re_export;
+pub mod reader;
+#[cfg(feature = "receiver")]
+pub mod receiver;
 mod record;
+#[cfg(feature = "regex")]
+mod redactor;
+mod registry;
+mod rust_log;
+mod sampler;
 pub mod sink;
+#[cfg(feature = "slog")]
+mod slog_drain;
 mod source_location;
 #[doc(hidden)]
 pub mod string_buf;
 mod sync;
 pub mod terminal_style;
+pub mod test_sink;
 #[cfg(test)]
 mod test_utils;
+mod thread_options;
 #[cfg(feature = "multi-thread")]
 mod thread_pool;
+mod time_backend;
+mod timing;
+#[cfg(feature = "tracing")]
+mod tracing_layer;
 mod utils;
 
+#[cfg(any(unix, windows))]
+pub use crash_handler::*;
 pub use error::{Error, ErrorHandler, Result};
+pub use error_report::*;
+pub use filter::*;
+pub use hex_dump::*;
 pub use level::*;
 #[cfg(feature = "log")]
 pub use log_crate_proxy::*;
 pub use logger::*;
+pub use quick_start::*;
 pub use record::*;
+#[cfg(feature = "regex")]
+pub use redactor::*;
+pub use registry::*;
+pub use rust_log::*;
+pub use sampler::*;
+#[cfg(feature = "slog")]
+pub use slog_drain::*;
 pub use source_location::*;
 pub use string_buf::StringBuf;
+pub use thread_options::*;
 #[cfg(feature = "multi-thread")]
 pub use thread_pool::*;
+pub use timing::{LatencyStats, LoggerStats, SinkStats};
+#[cfg(feature = "tracing")]
+pub use tracing_layer::*;
 
 /// Contains all log macros and common types.
 pub mod prelude {
     pub use super::{
-        critical, debug, error, info, log, trace, warn, Level, LevelFilter, Logger, LoggerBuilder,
+        critical, debug, error, info, log, log_assert, log_dbg, log_debug_assert, log_report,
+        trace, warn, Level, LevelFilter, Logger, LoggerBuilder,
     };
 }
 
 use std::{
     borrow::Cow,
+    cell::RefCell,
     env::{self, VarError},
     ffi::OsStr,
-    fmt, panic,
+    fmt,
+    marker::PhantomData,
+    panic,
     result::Result as StdResult,
+    time::SystemTime,
 };
 
 use cfg_if::cfg_if;
@@ -432,7 +561,12 @@ fn default_logger_ref() -> &'static ArcSwap<Logger> {
 ///    ```
 ///
 /// Users can replace the default logger with [`set_default_logger`] or
-/// [`swap_default_logger`].
+/// [`swap_default_logger`]. The default logger can also be overridden for the
+/// current thread, temporarily with [`with_default`] or
+/// [`set_scoped_default_logger`], or permanently with
+/// [`set_thread_default_logger`], in which case this function returns the
+/// overriding logger. Temporary overrides take precedence over permanent
+/// ones.
 ///
 /// # Examples
 ///
@@ -454,7 +588,20 @@ fn default_logger_ref() -> &'static ArcSwap<Logger> {
 /// ```
 #[must_use]
 pub fn default_logger() -> Arc<Logger> {
-    default_logger_ref().load().clone()
+    // Thread-locals are unavailable while the thread is being torn down, e.g. if
+    // logging in destructors of other thread-locals, fall back to the global
+    // default logger then.
+    SCOPED_DEFAULT_LOGGER
+        .try_with(|scoped| scoped.borrow().clone())
+        .ok()
+        .flatten()
+        .or_else(|| {
+            THREAD_DEFAULT_LOGGER
+                .try_with(|thread| thread.borrow().clone())
+                .ok()
+                .flatten()
+        })
+        .unwrap_or_else(|| default_logger_ref().load().clone())
 }
 
 /// Sets the given logger as the new global default logger, and returns the old
@@ -494,6 +641,115 @@ pub fn set_default_logger(logger: Arc<Logger>) {
     swap_default_logger(logger);
 }
 
+thread_local! {
+    static THREAD_DEFAULT_LOGGER: RefCell<Option<Arc<Logger>>> = const { RefCell::new(None) };
+    static SCOPED_DEFAULT_LOGGER: RefCell<Option<Arc<Logger>>> = const { RefCell::new(None) };
+}
+
+/// Sets the given logger as the default logger of the current thread, and
+/// returns the old one.
+///
+/// See [`set_thread_default_logger`] for more details.
+pub fn swap_thread_default_logger(logger: Option<Arc<Logger>>) -> Option<Arc<Logger>> {
+    THREAD_DEFAULT_LOGGER.with(|thread| thread.replace(logger))
+}
+
+/// Sets the given logger as the default logger of the current thread.
+///
+/// Logging macros called on the current thread use the given logger, while
+/// other threads keep using their own default logger, or the global default
+/// logger if they have none. For example, a thread-per-tenant server can
+/// isolate the logs of each tenant without passing loggers everywhere.
+///
+/// Passing `None` makes the current thread use the global default logger
+/// again.
+///
+/// # Examples
+///
+/// ```
+/// use spdlog::prelude::*;
+///
+/// # let tenant_logger = spdlog::default_logger();
+/// std::thread::spawn(move || {
+///     spdlog::set_thread_default_logger(Some(tenant_logger));
+///     info!("this log will be handled by `tenant_logger`");
+/// });
+/// info!("this log will be handled by the global default logger");
+/// ```
+pub fn set_thread_default_logger(logger: Option<Arc<Logger>>) {
+    swap_thread_default_logger(logger);
+}
+
+/// Overrides the default logger for the current thread while calling the
+/// closure.
+///
+/// Logging macros called in the closure on the current thread use the given
+/// logger, while other threads are not affected. Unlike
+/// [`set_default_logger`], this does not race with other threads, so tests
+/// running in parallel and request handlers can redirect logs of their own.
+///
+/// The override is also reverted if the closure panics. Calls can be nested.
+///
+/// # Examples
+///
+/// ```
+/// use spdlog::prelude::*;
+///
+/// # let request_logger = spdlog::default_logger();
+/// spdlog::with_default(request_logger, || {
+///     info!("this log will be handled by `request_logger`");
+/// });
+/// info!("this log will be handled by the global default logger");
+/// ```
+pub fn with_default<R>(logger: Arc<Logger>, f: impl FnOnce() -> R) -> R {
+    let _guard = set_scoped_default_logger(logger);
+    f()
+}
+
+/// Overrides the default logger for the current thread until the returned
+/// guard is dropped.
+///
+/// This is the guard version of [`with_default`]. Guards should be dropped in
+/// the reverse order of their creation, otherwise the default logger restored
+/// by an earlier dropped guard is not the expected one.
+///
+/// # Examples
+///
+/// ```
+/// use spdlog::prelude::*;
+///
+/// # let test_logger = spdlog::default_logger();
+/// let _guard = spdlog::set_scoped_default_logger(test_logger);
+/// info!("this log will be handled by `test_logger`");
+/// ```
+#[must_use = "the override is reverted immediately if the guard is not held"]
+pub fn set_scoped_default_logger(logger: Arc<Logger>) -> DefaultLoggerGuard {
+    let previous = SCOPED_DEFAULT_LOGGER.with(|scoped| scoped.replace(Some(logger)));
+    DefaultLoggerGuard {
+        previous,
+        _not_send: PhantomData,
+    }
+}
+
+/// A guard reverting the override of the default logger for the current
+/// thread when dropped.
+///
+/// It is returned by [`set_scoped_default_logger`].
+pub struct DefaultLoggerGuard {
+    previous: Option<Arc<Logger>>,
+    // The override is thread-local, so the guard must be dropped on the same thread.
+    _not_send: PhantomData<*const ()>,
+}
+
+impl Drop for DefaultLoggerGuard {
+    fn drop(&mut self) {
+        let previous = self.previous.take();
+        // The overriding logger is dropped after the borrow ends, as dropping it
+        // may log to the default logger.
+        let _overriding = SCOPED_DEFAULT_LOGGER.try_with(|scoped| scoped.replace(previous));
+    }
+}
+
 /// Initializes environment variable level filters from environment variable
 /// `SPDLOG_RS_LEVEL`.
 ///
@@ -693,6 +949,80 @@ pub fn init_env_level_from<K: AsRef<OsStr>>(env_key: K) -> StdResult<bool, EnvLe
     Ok(true)
 }
 
+/// Initializes environment variable level filters of named sinks from
+/// environment variable `SPDLOG_RS_SINK_LEVELS`.
+///
+/// Returns whether the levels in the environment variable were applied if
+/// there are no errors.
+///
+/// The value is a comma-separated list of `sink-name=level` rules, e.g.
+/// `console=warn,file=trace`. The levels mean
+/// `LevelFilter::MoreSevereEqual(level)` and are not case-sensitive, as in
+/// [`init_env_level`]. The same sink name cannot be specified more than once.
+///
+/// Sinks are named when they are added to loggers with
+/// [`LoggerBuilder::named_sink`], or by the names of appenders when loggers are
+/// built from [configurations]. The level filter of a named sink is set to
+/// the level in the environment variable if there is a rule for its name, so
+/// that the verbosity of each destination can be changed in deployment.
+///
+/// Users should call this function early, sinks named before calling this
+/// function will not be configured by environment variable.
+///
+/// If you want to read from a custom environment variable, see
+/// [`init_env_sink_levels_from`].
+///
+/// # Examples
+///
+/// - `SPDLOG_RS_SINK_LEVELS="console=warn,file=trace"`:
+///
+///   ```
+///   use std::sync::Arc;
+///
+///   use spdlog::{
+///       prelude::*,
+///       sink::{Sink, StdStream, StdStreamSink},
+///   };
+///
+///   # fn main() -> Result<(), Box<dyn std::error::Error>> {
+///   # std::env::set_var("SPDLOG_RS_SINK_LEVELS", "console=warn,file=trace");
+///   assert_eq!(spdlog::init_env_sink_levels()?, true);
+///
+///   let console = Arc::new(
+///       StdStreamSink::builder()
+///           .std_stream(StdStream::Stdout)
+///           .build()?,
+///   );
+///   let logger = Logger::builder()
+///       .named_sink("console", console.clone())
+///       .build()?;
+///
+///   assert_eq!(
+///       console.level_filter(),
+///       LevelFilter::MoreSevereEqual(Level::Warn)
+///   );
+///   # Ok(()) }
+///   ```
+///
+/// [configurations]: crate::config
+pub fn init_env_sink_levels() -> StdResult<bool, EnvLevelError> {
+    init_env_sink_levels_from("SPDLOG_RS_SINK_LEVELS")
+}
+
+/// Initializes environment variable level filters of named sinks from a
+/// specified environment variable.
+///
+/// For more information, see [`init_env_sink_levels`].
+pub fn init_env_sink_levels_from<K: AsRef<OsStr>>(env_key: K) -> StdResult<bool, EnvLevelError> {
+    let var = match env::var(env_key.as_ref()) {
+        Err(VarError::NotPresent) => return Ok(false),
+        Err(err) => return Err(EnvLevelError::FetchEnvVar(err)),
+        Ok(var) => var,
+    };
+    env_level::sinks_from_str(&var)?;
+    Ok(true)
+}
+
 /// Initializes the log crate proxy.
 ///
 /// This function calls [`log::set_logger`] to set up a [`LogCrateProxy`] and
@@ -711,6 +1041,43 @@ pub fn init_log_crate_proxy() -> StdResult<(), re_export::log::SetLoggerError> {
     log::set_logger(log_crate_proxy())
 }
 
+/// Initializes the log crate proxy filter from environment variable
+/// `RUST_LOG`.
+///
+/// This function parses `RUST_LOG` with the same grammar as [env_logger
+/// crate] (see [`RustLogFilter`]), sets the filter to the log crate proxy, and
+/// calls [`log::set_max_level`] with the most verbose level that the filter
+/// enables, so that `spdlog-rs` can be used as a drop-in replacement of
+/// `env_logger`. The proxy itself still needs to be initialized by
+/// [`init_log_crate_proxy`].
+///
+/// Returns whether the environment variable is present. If it is not present,
+/// nothing is changed.
+///
+/// ## Examples
+///
+/// - `RUST_LOG="info,hyper=warn"`:
+///
+///   ```
+///   # fn main() -> Result<(), Box<dyn std::error::Error>> {
+///   # std::env::set_var("RUST_LOG", "info,hyper=warn");
+///   spdlog::init_log_crate_proxy().expect("failed to initialize the proxy");
+///   assert_eq!(spdlog::init_rust_log()?, true);
+///   # Ok(()) }
+///   ```
+///
+/// [env_logger crate]: https://crates.io/crates/env_logger
+#[cfg(feature = "log")]
+pub fn init_rust_log() -> StdResult<bool, EnvLevelError> {
+    let filter = match RustLogFilter::from_env()? {
+        Some(filter) => filter,
+        None => return Ok(false),
+    };
+    log::set_max_level(filter.log_crate_max_level());
+    log_crate_proxy().set_rust_log_filter(Some(filter));
+    Ok(true)
+}
+
 /// Returns the global instance of log crate proxy.
 #[cfg(feature = "log")]
 #[must_use]
@@ -734,6 +1101,15 @@ fn flush_default_logger_at_exit() {
         }
     }
 
+    // Sinks are only closed at the program exit, the program may continue
+    // running after a panic.
+    extern "C" fn exit_handler() {
+        IS_TEARING_DOWN.store(true, Ordering::SeqCst);
+        if let Some(default_logger) = DEFAULT_LOGGER.get() {
+            default_logger.load().close()
+        }
+    }
+
     #[must_use]
     fn try_atexit() -> bool {
         use std::os::raw::c_int;
@@ -742,7 +1118,7 @@ fn flush_default_logger_at_exit() {
             fn atexit(cb: extern "C" fn()) -> c_int;
         }
 
-        (unsafe { atexit(handler) }) == 0
+        (unsafe { atexit(exit_handler) }) == 0
     }
 
     fn hook_panic() {
@@ -766,9 +1142,20 @@ fn default_error_handler(from: impl AsRef<str>, error: Error) {
         return;
     }
 
-    let date = chrono::Local::now()
-        .format("%Y-%m-%d %H:%M:%S.%3f")
-        .to_string();
+    let now = SystemTime::now();
+    let local_time = formatter::TimeZone::LOCAL.date_time(now);
+    let date = format!(
+        "{}-{:02}-{:02} {:02}:{:02}:{:02}.{:03}",
+        local_time.year(),
+        local_time.month(),
+        local_time.day(),
+        local_time.hour(),
+        local_time.minute(),
+        local_time.second(),
+        now.duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap_or_default()
+            .subsec_millis()
+    );
 
     eprintln!(
         "[*** SPDLOG-RS UNHANDLED ERROR ***] [{}] [{}] {}",
@@ -784,6 +1171,7 @@ pub fn __log(
     logger: &Logger,
     level: Level,
     srcloc: Option<SourceLocation>,
+    module_path: Option<&'static str>,
     fmt_args: fmt::Arguments,
 ) {
     // Use `Cow` to avoid allocation as much as we can
@@ -791,10 +1179,132 @@ pub fn __log(
         .as_str()
         .map(Cow::Borrowed) // No format arguments, so it is a `&'static str`
         .unwrap_or_else(|| Cow::Owned(fmt_args.to_string()));
-    let record = Record::new(level, payload, srcloc, logger.name());
+    let mut record = Record::new(level, payload, srcloc, logger.name());
+    record.set_module_path(module_path);
+    logger.log(&record);
+}
+
+// Used at log macros with named parameters
+#[doc(hidden)]
+#[allow(clippy::too_many_arguments)]
+pub fn __log_ext(
+    logger: &Logger,
+    level: Level,
+    srcloc: Option<SourceLocation>,
+    module_path: Option<&'static str>,
+    code: Option<&str>,
+    bytes: Option<&[u8]>,
+    time: Option<SystemTime>,
+    destination: Option<&str>,
+    key_values: &[(&'static str, &dyn fmt::Display)],
+    fmt_args: fmt::Arguments,
+) {
+    let payload: Cow<str> = fmt_args
+        .as_str()
+        .map(Cow::Borrowed)
+        .unwrap_or_else(|| Cow::Owned(fmt_args.to_string()));
+    let mut record = Record::new(level, payload, srcloc, logger.name());
+    record.set_module_path(module_path);
+    record.set_code(code);
+    record.set_bytes(bytes);
+    record.set_destination(destination);
+    if !key_values.is_empty() {
+        record.set_key_values(
+            key_values
+                .iter()
+                .map(|(key, value)| (Cow::Borrowed(*key), Cow::Owned(value.to_string())))
+                .collect(),
+        );
+    }
+    if let Some(time) = time {
+        record.set_time(time);
+    }
     logger.log(&record);
 }
 
+// Used at `try_log!` macro
+#[doc(hidden)]
+pub fn __try_log(
+    logger: &Logger,
+    level: Level,
+    srcloc: Option<SourceLocation>,
+    module_path: Option<&'static str>,
+    fmt_args: fmt::Arguments,
+) -> Result<()> {
+    let payload: Cow<str> = fmt_args
+        .as_str()
+        .map(Cow::Borrowed)
+        .unwrap_or_else(|| Cow::Owned(fmt_args.to_string()));
+    let mut record = Record::new(level, payload, srcloc, logger.name());
+    record.set_module_path(module_path);
+    logger.try_log(&record)
+}
+
+// Used at `log_async!` macro
+#[cfg(feature = "log-async")]
+#[doc(hidden)]
+#[must_use]
+pub fn __payload(fmt_args: fmt::Arguments) -> Cow<'static, str> {
+    fmt_args
+        .as_str()
+        .map(Cow::Borrowed)
+        .unwrap_or_else(|| Cow::Owned(fmt_args.to_string()))
+}
+
+// Used at `log_async!` macro, `payload` is `None` if the record is filtered out
+#[cfg(feature = "log-async")]
+#[doc(hidden)]
+pub async fn __log_async(
+    logger: &Logger,
+    level: Level,
+    srcloc: Option<SourceLocation>,
+    module_path: Option<&'static str>,
+    payload: Option<Cow<'static, str>>,
+) {
+    if let Some(payload) = payload {
+        let mut record = Record::new(level, payload, srcloc, logger.name());
+        record.set_module_path(module_path);
+        logger.log_async(&record).await;
+    }
+}
+
+// Used at assertion macros
+#[doc(hidden)]
+#[cold]
+#[track_caller]
+pub fn __log_assert_failed(
+    logger: &Logger,
+    srcloc: Option<SourceLocation>,
+    module_path: Option<&'static str>,
+    cond: &'static str,
+    fmt_args: Option<fmt::Arguments>,
+) -> ! {
+    match fmt_args {
+        Some(fmt_args) => {
+            __log(
+                logger,
+                Level::Critical,
+                srcloc,
+                module_path,
+                format_args!("assertion failed: {}: {}", cond, fmt_args),
+            );
+            logger.flush();
+            panic!("assertion failed: {}: {}", cond, fmt_args)
+        }
+        None => {
+            __log(
+                logger,
+                Level::Critical,
+                srcloc,
+                module_path,
+                format_args!("assertion failed: {}", cond),
+            );
+            logger.flush();
+            panic!("assertion failed: {}", cond)
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use test_utils::*;
@@ -826,4 +1336,86 @@ mod tests {
             vec!["hello".to_string(), "rust".to_string()]
         );
     }
+
+    #[test]
+    fn test_scoped_default_logger() {
+        let outer_sink = Arc::new(TestSink::new());
+        let inner_sink = Arc::new(TestSink::new());
+        let outer_logger = Arc::new(build_test_logger(|b| b.sink(outer_sink.clone())));
+        let inner_logger = Arc::new(build_test_logger(|b| b.sink(inner_sink.clone())));
+
+        with_default(outer_logger.clone(), || {
+            info!("outer 1");
+            with_default(inner_logger, || info!("inner"));
+            info!("outer 2");
+
+            let cloned = outer_logger.clone();
+            std::thread::spawn(move || {
+                assert!(!Arc::ptr_eq(&default_logger(), &cloned));
+            })
+            .join()
+            .unwrap();
+        });
+        assert!(!Arc::ptr_eq(&default_logger(), &outer_logger));
+
+        let cloned = outer_logger.clone();
+        let panicked = panic::catch_unwind(panic::AssertUnwindSafe(|| {
+            with_default(cloned, || panic!("reverted anyway"));
+        }));
+        assert!(panicked.is_err());
+        assert!(!Arc::ptr_eq(&default_logger(), &outer_logger));
+
+        assert_eq!(outer_sink.payloads(), ["outer 1", "outer 2"]);
+        assert_eq!(inner_sink.payloads(), ["inner"]);
+    }
+
+    #[test]
+    fn test_thread_default_logger() {
+        let thread_sink = Arc::new(TestSink::new());
+        let scoped_sink = Arc::new(TestSink::new());
+        let thread_logger = Arc::new(build_test_logger(|b| b.sink(thread_sink.clone())));
+        let scoped_logger = Arc::new(build_test_logger(|b| b.sink(scoped_sink.clone())));
+
+        let cloned = thread_logger.clone();
+        std::thread::spawn(move || {
+            assert!(swap_thread_default_logger(Some(cloned)).is_none());
+            info!("thread 1");
+            with_default(scoped_logger, || info!("scoped"));
+            info!("thread 2");
+
+            set_thread_default_logger(None);
+            assert!(swap_thread_default_logger(None).is_none());
+        })
+        .join()
+        .unwrap();
+        assert!(!Arc::ptr_eq(&default_logger(), &thread_logger));
+
+        assert_eq!(thread_sink.payloads(), ["thread 1", "thread 2"]);
+        assert_eq!(scoped_sink.payloads(), ["scoped"]);
+    }
+
+    #[test]
+    fn test_default_logger_in_thread_local_destructor() {
+        struct GetOnDrop;
+
+        impl Drop for GetOnDrop {
+            fn drop(&mut self) {
+                assert!(Arc::ptr_eq(&default_logger(), &default_logger_ref().load()));
+            }
+        }
+
+        thread_local! {
+            static GET_ON_DROP: GetOnDrop = const { GetOnDrop };
+        }
+
+        std::thread::spawn(|| {
+            // Thread-locals are destroyed in the reverse order of their
+            // initialization, so the default logger slots go first.
+            GET_ON_DROP.with(|_| {});
+            set_thread_default_logger(None);
+            drop(set_scoped_default_logger(default_logger()));
+        })
+        .join()
+        .unwrap();
+    }
 }
@@ -1,6 +1,6 @@
-use std::time::SystemTime;
+use std::{cmp::Reverse, time::SystemTime};
 
-use crate::{default_logger, sync::*, Logger, Record};
+use crate::{default_logger, sync::*, Logger, Record, RustLogFilter};
 
 /// Proxy layer for compatible [log crate].
 ///
@@ -11,6 +11,10 @@ use crate::{default_logger, sync::*, Logger, Record};
 /// crate to the global default logger or the logger set by
 /// [`LogCrateProxy::set_logger`].
 ///
+/// Log messages can also be routed to different loggers based on their
+/// targets, see [`LogCrateProxy::set_target_logger`], and filtered with
+/// `RUST_LOG`-style directives, see [`LogCrateProxy::set_rust_log_filter`].
+///
 /// Note that the `log` crate uses a different log level filter and by default
 /// it rejects all log messages. To make `LogCrateProxy` able to receive log
 /// messages from `log` crate, you may need to call
@@ -39,6 +43,10 @@ use crate::{default_logger, sync::*, Logger, Record};
 #[derive(Default)]
 pub struct LogCrateProxy {
     logger: ArcSwapOption<Logger>,
+    // Sorted by the length of target prefixes in descending order, so that the
+    // longest matching prefix is found first.
+    target_loggers: SpinRwLock<Vec<(String, Arc<Logger>)>>,
+    rust_log_filter: ArcSwapOption<RustLogFilter>,
 }
 
 impl LogCrateProxy {
@@ -63,25 +71,155 @@ impl LogCrateProxy {
         self.swap_logger(logger);
     }
 
+    /// Routes log messages with the given target prefix to a logger, and
+    /// returns the logger previously set for the prefix.
+    ///
+    /// A target prefix matches a log message if the target of the log message
+    /// is equal to the prefix, or starts with the prefix followed by `::`. For
+    /// example, prefix `hyper` matches targets `hyper` and `hyper::client`,
+    /// but not `hyperlocal`. If multiple prefixes match, the longest one wins.
+    ///
+    /// Log messages that do not match any prefix are forwarded to the logger
+    /// set by [`LogCrateProxy::set_logger`].
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use spdlog::prelude::*;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let network = Arc::new(Logger::builder().name("network").build()?);
+    ///
+    /// spdlog::log_crate_proxy().set_target_logger("hyper", network);
+    /// # Ok(()) }
+    /// ```
+    pub fn set_target_logger<S>(&self, target_prefix: S, logger: Arc<Logger>) -> Option<Arc<Logger>>
+    where
+        S: Into<String>,
+    {
+        let target_prefix = target_prefix.into();
+        let mut target_loggers = self.target_loggers.write();
+
+        if let Some((_, existing)) = target_loggers
+            .iter_mut()
+            .find(|(prefix, _)| *prefix == target_prefix)
+        {
+            return Some(std::mem::replace(existing, logger));
+        }
+
+        let pos = target_loggers
+            .iter()
+            .position(|(prefix, _)| prefix.len() < target_prefix.len())
+            .unwrap_or(target_loggers.len());
+        target_loggers.insert(pos, (target_prefix, logger));
+        None
+    }
+
+    /// Removes the routing for the given target prefix, and returns the logger
+    /// previously set for the prefix.
+    pub fn remove_target_logger(&self, target_prefix: &str) -> Option<Arc<Logger>> {
+        let mut target_loggers = self.target_loggers.write();
+        let pos = target_loggers
+            .iter()
+            .position(|(prefix, _)| prefix == target_prefix)?;
+        Some(target_loggers.remove(pos).1)
+    }
+
+    /// Replaces all routings with the given map of target prefixes to loggers.
+    ///
+    /// See [`LogCrateProxy::set_target_logger`] for how target prefixes are
+    /// matched.
+    pub fn set_target_loggers<I, S>(&self, target_loggers: I)
+    where
+        I: IntoIterator<Item = (S, Arc<Logger>)>,
+        S: Into<String>,
+    {
+        let mut target_loggers = target_loggers
+            .into_iter()
+            .map(|(prefix, logger)| (prefix.into(), logger))
+            .collect::<Vec<_>>();
+        // Stable sort, so that the first one of duplicate prefixes is kept.
+        target_loggers.sort_by_key(|(prefix, _)| Reverse(prefix.len()));
+        target_loggers.dedup_by(|(lhs, _), (rhs, _)| lhs == rhs);
+
+        *self.target_loggers.write() = target_loggers;
+    }
+
+    /// Sets a `RUST_LOG`-style filter for log messages, and returns the old
+    /// one.
+    ///
+    /// Log messages rejected by the filter are dropped before they are
+    /// forwarded to loggers. If the argument `filter` is `None`, log messages
+    /// are only filtered by the level filters of loggers.
+    ///
+    /// See also [`init_rust_log`] for initializing the filter from environment
+    /// variable `RUST_LOG`.
+    ///
+    /// [`init_rust_log`]: crate::init_rust_log
+    pub fn set_rust_log_filter(&self, filter: Option<RustLogFilter>) -> Option<Arc<RustLogFilter>> {
+        self.rust_log_filter.swap(filter.map(Arc::new))
+    }
+
     #[must_use]
-    fn logger(&self) -> Arc<Logger> {
-        self.logger.load_full().unwrap_or_else(default_logger)
+    fn logger(&self, target: &str) -> Arc<Logger> {
+        let routed = self
+            .target_loggers
+            .read()
+            .iter()
+            .find(|(prefix, _)| is_target_matched(target, prefix))
+            .map(|(_, logger)| logger.clone());
+
+        routed.unwrap_or_else(|| self.logger.load_full().unwrap_or_else(default_logger))
+    }
+}
+
+#[must_use]
+fn is_target_matched(target: &str, prefix: &str) -> bool {
+    match target.strip_prefix(prefix) {
+        Some(rest) => rest.is_empty() || rest.starts_with("::"),
+        None => false,
     }
 }
 
 impl log::Log for LogCrateProxy {
     fn enabled(&self, metadata: &log::Metadata) -> bool {
-        self.logger().should_log(metadata.level().into())
+        let level = metadata.level().into();
+        if let Some(filter) = &*self.rust_log_filter.load() {
+            if !filter.enabled(metadata.target(), level) {
+                return false;
+            }
+        }
+        self.logger(metadata.target())
+            .enabled_in(level, Some(metadata.target()))
     }
 
     fn log(&self, record: &log::Record) {
-        let logger = self.logger();
+        if let Some(filter) = &*self.rust_log_filter.load() {
+            let level = record.level().into();
+            let passed = if filter.has_message_filter() {
+                filter.matches(record.target(), level, &record.args().to_string())
+            } else {
+                filter.enabled(record.target(), level)
+            };
+            if !passed {
+                return;
+            }
+        }
+
+        let logger = self.logger(record.target());
         let record = Record::from_log_crate_record(&logger, record, SystemTime::now());
         logger.log(&record)
     }
 
     fn flush(&self) {
-        self.logger().flush()
+        let target_loggers = self.target_loggers.read().clone();
+        target_loggers.iter().for_each(|(_, logger)| logger.flush());
+        self.logger
+            .load_full()
+            .unwrap_or_else(default_logger)
+            .flush()
     }
 }
 
@@ -110,4 +248,101 @@ mod tests {
             vec!["hello".to_string(), "world".to_string()]
         );
     }
+
+    #[test]
+    fn target_routing() {
+        use log::Log;
+
+        let proxy = LogCrateProxy::new();
+        let (fallback_sink, hyper_sink, hyper_client_sink) = (
+            Arc::new(TestSink::new()),
+            Arc::new(TestSink::new()),
+            Arc::new(TestSink::new()),
+        );
+        let build_logger =
+            |sink: &Arc<TestSink>| Arc::new(build_test_logger(|b| b.sink(sink.clone())));
+        proxy.set_logger(Some(build_logger(&fallback_sink)));
+        proxy.set_target_loggers([
+            ("hyper", build_logger(&hyper_sink)),
+            ("hyper::client", build_logger(&hyper_client_sink)),
+        ]);
+
+        let log = |target: &str, payload: &str| {
+            proxy.log(
+                &log::Record::builder()
+                    .target(target)
+                    .level(log::Level::Info)
+                    .args(format_args!("{}", payload))
+                    .build(),
+            )
+        };
+
+        log("hyper", "1");
+        log("hyper::server", "2");
+        log("hyper::client::pool", "3");
+        log("hyperlocal", "4");
+        log("app", "5");
+
+        assert_eq!(hyper_sink.payloads(), vec!["1", "2"]);
+        assert_eq!(hyper_client_sink.payloads(), vec!["3"]);
+        assert_eq!(fallback_sink.payloads(), vec!["4", "5"]);
+
+        assert!(proxy.remove_target_logger("hyper::client").is_some());
+        assert!(proxy.remove_target_logger("hyper::client").is_none());
+        log("hyper::client", "6");
+        assert_eq!(hyper_sink.payloads(), vec!["1", "2", "6"]);
+
+        let old = proxy.set_target_logger("hyper", build_logger(&hyper_client_sink));
+        assert!(old.is_some());
+        log("hyper", "7");
+        assert_eq!(hyper_client_sink.payloads(), vec!["3", "7"]);
+    }
+
+    #[test]
+    fn rust_log_filter() {
+        use log::Log;
+
+        let proxy = LogCrateProxy::new();
+        let sink = Arc::new(TestSink::new());
+        proxy.set_logger(Some(Arc::new(build_test_logger(|b| {
+            b.sink(sink.clone()).level_filter(crate::LevelFilter::All)
+        }))));
+        proxy.set_rust_log_filter(Some(
+            RustLogFilter::parse("warn,hyper=debug,hyper::client=off/ok").unwrap(),
+        ));
+
+        let log = |target: &str, level: log::Level, payload: &str| {
+            proxy.log(
+                &log::Record::builder()
+                    .target(target)
+                    .level(level)
+                    .args(format_args!("{}", payload))
+                    .build(),
+            )
+        };
+
+        log("app", log::Level::Info, "1 ok");
+        log("app", log::Level::Warn, "2 ok");
+        log("app", log::Level::Warn, "3");
+        log("hyper::server", log::Level::Debug, "4 ok");
+        log("hyper::client", log::Level::Error, "5 ok");
+
+        assert_eq!(sink.payloads(), vec!["2 ok", "4 ok"]);
+        assert!(!proxy.enabled(
+            &log::Metadata::builder()
+                .target("app")
+                .level(log::Level::Info)
+                .build()
+        ));
+        assert!(proxy.enabled(
+            &log::Metadata::builder()
+                .target("hyper")
+                .level(log::Level::Debug)
+                .build()
+        ));
+
+        assert!(proxy.set_rust_log_filter(None).is_some());
+        log("app", log::Level::Info, "6");
+        assert_eq!(sink.payloads(), vec!["2 ok", "4 ok", "6"]);
+    }
 }
@@ -2,11 +2,25 @@
 ///
 /// This macro will generically log with the specified [`Level`] and `format!`
 /// based argument list.
+///
+/// Optional named parameters can be specified before the level (or before the
+/// format string for level-specific macros such as [`error!`]):
+///
+/// | Name  | Type         | Description                                                                         |
+/// |-------|--------------|-------------------------------------------------------------------------------------|
+/// | code  | `&str`       | An error code attached to the record, see [`Record::code`].                         |
+/// | bytes | `&[u8]`      | Raw bytes attached to the record, see [`Record::bytes`].                            |
+/// | time  | `SystemTime` | The time of the record instead of the current time, see [`Record::time`].           |
+/// | sink  | `&str`       | The name of the destination the record is intended for, see [`Record::destination`]. |
+///
+/// Any other named parameter `key = value` attaches a structured key-value
+/// field to the record, with the value rendered by [`Display`], see
+/// [`Record::key_values`].
 #[doc = include_str!("./include/doc/log-macro-nameed-opt-params.md")]
 /// # Examples
 ///
 /// ```
-/// use spdlog::{log, Level};
+/// use spdlog::{error, log, Level};
 ///
 /// # let app_events = spdlog::default_logger();
 /// let data = (42, "Forty-two");
@@ -16,20 +30,207 @@
 ///
 /// // Or using the specified logger
 /// log!(logger: app_events, Level::Info, "Received data: {}, {}", data.0, data.1);
+///
+/// // With an error code and raw bytes
+/// let frame = [0x7e, 0xff, 0x00];
+/// log!(code = "E1042", Level::Error, "Failed to decode data: {}", data.0);
+/// error!(logger: app_events, code = "E1042", bytes = &frame, "Malformed frame");
+///
+/// // Re-emitting an event received from another system with its original time
+/// # let received_at = std::time::SystemTime::now();
+/// log!(time = received_at, Level::Info, "Received data: {}", data.1);
+///
+/// // Routing an event to the audit trail, see `RouterSink`
+/// log!(sink = "audit", Level::Info, "User {} signed in", data.0);
+///
+/// // With structured key-value fields
+/// let user_id = 42;
+/// log!(user_id = user_id, peer = "10.0.0.3", Level::Info, "Login ok");
+/// error!(logger: app_events, code = "E1042", frame_len = frame.len(), "Malformed frame");
 /// ```
 ///
 /// [`Level`]: crate::Level
+/// [`error!`]: macro@crate::error
+/// [`Record::code`]: crate::Record::code
+/// [`Record::bytes`]: crate::Record::bytes
+/// [`Record::time`]: crate::Record::time
+/// [`Record::destination`]: crate::Record::destination
+/// [`Display`]: std::fmt::Display
+/// [`Record::key_values`]: crate::Record::key_values
 #[macro_export]
 macro_rules! log {
+    (logger: $logger:expr, $($rest:tt)+) => (
+        $crate::__log_impl!(@pre ($logger, [], [], [], [], []) $($rest)+)
+    );
+    ($($rest:tt)+) => (
+        $crate::__log_impl!(@pre ($crate::default_logger(), [], [], [], [], []) $($rest)+)
+    )
+}
+
+// Parses the optional named parameters of log macros.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __log_impl {
+    // Named parameters before the level, used by `log!`.
+    (@pre ($logger:expr, [$($code:expr)?], [$($bytes:expr)?], [$($time:expr)?], [$($sink:expr)?], [$($kvs:tt)*]) code = $new:expr, $($rest:tt)+) => (
+        $crate::__log_impl!(@pre ($logger, [$new], [$($bytes)?], [$($time)?], [$($sink)?], [$($kvs)*]) $($rest)+)
+    );
+    (@pre ($logger:expr, [$($code:expr)?], [$($bytes:expr)?], [$($time:expr)?], [$($sink:expr)?], [$($kvs:tt)*]) bytes = $new:expr, $($rest:tt)+) => (
+        $crate::__log_impl!(@pre ($logger, [$($code)?], [$new], [$($time)?], [$($sink)?], [$($kvs)*]) $($rest)+)
+    );
+    (@pre ($logger:expr, [$($code:expr)?], [$($bytes:expr)?], [$($time:expr)?], [$($sink:expr)?], [$($kvs:tt)*]) time = $new:expr, $($rest:tt)+) => (
+        $crate::__log_impl!(@pre ($logger, [$($code)?], [$($bytes)?], [$new], [$($sink)?], [$($kvs)*]) $($rest)+)
+    );
+    (@pre ($logger:expr, [$($code:expr)?], [$($bytes:expr)?], [$($time:expr)?], [$($sink:expr)?], [$($kvs:tt)*]) sink = $new:expr, $($rest:tt)+) => (
+        $crate::__log_impl!(@pre ($logger, [$($code)?], [$($bytes)?], [$($time)?], [$new], [$($kvs)*]) $($rest)+)
+    );
+    // Other named parameters are key-value fields.
+    (@pre ($logger:expr, [$($code:expr)?], [$($bytes:expr)?], [$($time:expr)?], [$($sink:expr)?], [$($kvs:tt)*]) $key:ident = $value:expr, $($rest:tt)+) => (
+        $crate::__log_impl!(@pre ($logger, [$($code)?], [$($bytes)?], [$($time)?], [$($sink)?], [$($kvs)* ($key, $value)]) $($rest)+)
+    );
+    (@pre ($logger:expr, [$($code:expr)?], [$($bytes:expr)?], [$($time:expr)?], [$($sink:expr)?], [$($kvs:tt)*]) $level:expr, $($arg:tt)+) => (
+        $crate::__log_impl!(@log ($logger, $level, [$($code)?], [$($bytes)?], [$($time)?], [$($sink)?], [$($kvs)*]) $($arg)+)
+    );
+    // Named parameters before the format string, used by level-specific macros.
+    (@post ($logger:expr, $level:expr, [$($code:expr)?], [$($bytes:expr)?], [$($time:expr)?], [$($sink:expr)?], [$($kvs:tt)*]) code = $new:expr, $($rest:tt)+) => (
+        $crate::__log_impl!(@post ($logger, $level, [$new], [$($bytes)?], [$($time)?], [$($sink)?], [$($kvs)*]) $($rest)+)
+    );
+    (@post ($logger:expr, $level:expr, [$($code:expr)?], [$($bytes:expr)?], [$($time:expr)?], [$($sink:expr)?], [$($kvs:tt)*]) bytes = $new:expr, $($rest:tt)+) => (
+        $crate::__log_impl!(@post ($logger, $level, [$($code)?], [$new], [$($time)?], [$($sink)?], [$($kvs)*]) $($rest)+)
+    );
+    (@post ($logger:expr, $level:expr, [$($code:expr)?], [$($bytes:expr)?], [$($time:expr)?], [$($sink:expr)?], [$($kvs:tt)*]) time = $new:expr, $($rest:tt)+) => (
+        $crate::__log_impl!(@post ($logger, $level, [$($code)?], [$($bytes)?], [$new], [$($sink)?], [$($kvs)*]) $($rest)+)
+    );
+    (@post ($logger:expr, $level:expr, [$($code:expr)?], [$($bytes:expr)?], [$($time:expr)?], [$($sink:expr)?], [$($kvs:tt)*]) sink = $new:expr, $($rest:tt)+) => (
+        $crate::__log_impl!(@post ($logger, $level, [$($code)?], [$($bytes)?], [$($time)?], [$new], [$($kvs)*]) $($rest)+)
+    );
+    (@post ($logger:expr, $level:expr, [$($code:expr)?], [$($bytes:expr)?], [$($time:expr)?], [$($sink:expr)?], [$($kvs:tt)*]) $key:ident = $value:expr, $($rest:tt)+) => (
+        $crate::__log_impl!(@post ($logger, $level, [$($code)?], [$($bytes)?], [$($time)?], [$($sink)?], [$($kvs)* ($key, $value)]) $($rest)+)
+    );
+    (@post ($logger:expr, $level:expr, [$($code:expr)?], [$($bytes:expr)?], [$($time:expr)?], [$($sink:expr)?], [$($kvs:tt)*]) $($arg:tt)+) => (
+        $crate::__log_impl!(@log ($logger, $level, [$($code)?], [$($bytes)?], [$($time)?], [$($sink)?], [$($kvs)*]) $($arg)+)
+    );
+    (@log ($logger:expr, $level:expr, [], [], [], [], []) $($arg:tt)+) => ({
+        let logger = &$logger;
+        const LEVEL: $crate::Level = $level;
+        const SHOULD_LOG: bool = $crate::STATIC_LEVEL_FILTER.__test_const(LEVEL);
+        if SHOULD_LOG && logger.enabled_in(LEVEL, $crate::__private_module_path!()) {
+            $crate::__log(logger, LEVEL, $crate::source_location_current!(), $crate::__private_module_path!(), format_args!($($arg)+));
+        }
+    });
+    (@log ($logger:expr, $level:expr, [$($code:expr)?], [$($bytes:expr)?], [$($time:expr)?], [$($sink:expr)?], [$(($key:ident, $value:expr))*]) $($arg:tt)+) => ({
+        let logger = &$logger;
+        const LEVEL: $crate::Level = $level;
+        const SHOULD_LOG: bool = $crate::STATIC_LEVEL_FILTER.__test_const(LEVEL);
+        if SHOULD_LOG && logger.enabled_in(LEVEL, $crate::__private_module_path!()) {
+            $crate::__log_ext(
+                logger,
+                LEVEL,
+                $crate::source_location_current!(),
+                $crate::__private_module_path!(),
+                $crate::__log_impl!(@opt $($code)?),
+                $crate::__log_impl!(@opt $($bytes)?),
+                $crate::__log_impl!(@opt $($time)?),
+                $crate::__log_impl!(@opt $($sink)?),
+                &[$((::core::stringify!($key), &$value as &dyn ::core::fmt::Display)),*],
+                format_args!($($arg)+),
+            );
+        }
+    });
+    (@opt) => (::core::option::Option::None);
+    (@opt $value:expr) => (::core::option::Option::Some($value));
+}
+
+/// Logs a message at the specified level, and returns the errors that occur.
+///
+/// This macro works like [`log!`], but it evaluates to a [`Result<()>`]
+/// returned by [`Logger::try_log`] instead of passing errors to the error
+/// handler, so that records which must not be lost (e.g. audit records) can be
+/// checked by the caller. It evaluates to `Ok(())` if the record is filtered
+/// out.
+#[doc = include_str!("./include/doc/log-macro-nameed-opt-params.md")]
+/// # Examples
+///
+/// ```
+/// use spdlog::{try_log, Level};
+///
+/// # fn main() -> Result<(), spdlog::Error> {
+/// # let audit_logger = spdlog::default_logger();
+/// let user = "alice";
+///
+/// try_log!(logger: audit_logger, Level::Info, "user {} signed in", user)?;
+/// # Ok(()) }
+/// ```
+///
+/// [`Result<()>`]: crate::Result
+/// [`Logger::try_log`]: crate::Logger::try_log
+#[macro_export]
+macro_rules! try_log {
     (logger: $logger:expr, $level:expr, $($arg:tt)+) => ({
         let logger = &$logger;
         const LEVEL: $crate::Level = $level;
         const SHOULD_LOG: bool = $crate::STATIC_LEVEL_FILTER.__test_const(LEVEL);
-        if SHOULD_LOG && logger.should_log(LEVEL) {
-            $crate::__log(logger, LEVEL, $crate::source_location_current!(), format_args!($($arg)+));
+        if SHOULD_LOG && logger.enabled_in(LEVEL, $crate::__private_module_path!()) {
+            $crate::__try_log(logger, LEVEL, $crate::source_location_current!(), $crate::__private_module_path!(), format_args!($($arg)+))
+        } else {
+            ::core::result::Result::Ok(())
+        }
+    });
+    ($level:expr, $($arg:tt)+) => (
+        $crate::try_log!(logger: $crate::default_logger(), $level, $($arg)+)
+    );
+}
+
+/// Logs a message at the specified level, awaiting until sinks have capacity
+/// for it.
+///
+/// This macro works like [`log!`], but it evaluates to a future returned by
+/// [`Logger::log_async`], which must be awaited for the record to be logged.
+/// Arguments are formatted before the future is created.
+///
+/// This macro is only available if crate feature `log-async` is enabled.
+///
+/// # Examples
+///
+/// ```
+/// use spdlog::{log_async, Level};
+///
+/// # async fn handle() {
+/// # let app_events = spdlog::default_logger();
+/// let request_id = 42;
+///
+/// // Using the global default logger
+/// log_async!(Level::Info, "Handling request {}", request_id).await;
+///
+/// // Or using the specified logger
+/// log_async!(logger: app_events, Level::Info, "Handled request {}", request_id).await;
+/// # }
+/// ```
+///
+/// [`Logger::log_async`]: crate::Logger::log_async
+#[cfg(feature = "log-async")]
+#[macro_export]
+macro_rules! log_async {
+    (logger: $logger:expr, $level:expr, $($arg:tt)+) => ({
+        const LEVEL: $crate::Level = $level;
+        const SHOULD_LOG: bool = $crate::STATIC_LEVEL_FILTER.__test_const(LEVEL);
+        match &$logger {
+            logger => $crate::__log_async(
+                logger,
+                LEVEL,
+                $crate::source_location_current!(),
+                $crate::__private_module_path!(),
+                if SHOULD_LOG && logger.enabled_in(LEVEL, $crate::__private_module_path!()) {
+                    ::core::option::Option::Some($crate::__payload(format_args!($($arg)+)))
+                } else {
+                    ::core::option::Option::None
+                },
+            ),
         }
     });
-    ($level:expr, $($arg:tt)+) => ($crate::log!(logger: $crate::default_logger(), $level, $($arg)+))
+    ($level:expr, $($arg:tt)+) => (
+        $crate::log_async!(logger: $crate::default_logger(), $level, $($arg)+)
+    );
 }
 
 /// Logs a message at the critical level.
@@ -50,11 +251,11 @@ macro_rules! log {
 /// ```
 #[macro_export]
 macro_rules! critical {
-    (logger: $logger:expr, $($arg:tt)+) => (
-        $crate::log!(logger: $logger, $crate::Level::Critical, $($arg)+)
+    (logger: $logger:expr, $($rest:tt)+) => (
+        $crate::__log_impl!(@post ($logger, $crate::Level::Critical, [], [], [], [], []) $($rest)+)
     );
-    ($($arg:tt)+) => (
-        $crate::log!($crate::Level::Critical, $($arg)+)
+    ($($rest:tt)+) => (
+        $crate::__log_impl!(@post ($crate::default_logger(), $crate::Level::Critical, [], [], [], [], []) $($rest)+)
     )
 }
 
@@ -76,11 +277,11 @@ macro_rules! critical {
 /// ```
 #[macro_export]
 macro_rules! error {
-    (logger: $logger:expr, $($arg:tt)+) => (
-        $crate::log!(logger: $logger, $crate::Level::Error, $($arg)+)
+    (logger: $logger:expr, $($rest:tt)+) => (
+        $crate::__log_impl!(@post ($logger, $crate::Level::Error, [], [], [], [], []) $($rest)+)
     );
-    ($($arg:tt)+) => (
-        $crate::log!($crate::Level::Error, $($arg)+)
+    ($($rest:tt)+) => (
+        $crate::__log_impl!(@post ($crate::default_logger(), $crate::Level::Error, [], [], [], [], []) $($rest)+)
     )
 }
 
@@ -102,11 +303,11 @@ macro_rules! error {
 /// ```
 #[macro_export]
 macro_rules! warn {
-    (logger: $logger:expr, $($arg:tt)+) => (
-        $crate::log!(logger: $logger, $crate::Level::Warn, $($arg)+)
+    (logger: $logger:expr, $($rest:tt)+) => (
+        $crate::__log_impl!(@post ($logger, $crate::Level::Warn, [], [], [], [], []) $($rest)+)
     );
-    ($($arg:tt)+) => (
-        $crate::log!($crate::Level::Warn, $($arg)+)
+    ($($rest:tt)+) => (
+        $crate::__log_impl!(@post ($crate::default_logger(), $crate::Level::Warn, [], [], [], [], []) $($rest)+)
     )
 }
 
@@ -129,11 +330,11 @@ macro_rules! warn {
 /// ```
 #[macro_export]
 macro_rules! info {
-    (logger: $logger:expr, $($arg:tt)+) => (
-        $crate::log!(logger: $logger, $crate::Level::Info, $($arg)+)
+    (logger: $logger:expr, $($rest:tt)+) => (
+        $crate::__log_impl!(@post ($logger, $crate::Level::Info, [], [], [], [], []) $($rest)+)
     );
-    ($($arg:tt)+) => (
-        $crate::log!($crate::Level::Info, $($arg)+)
+    ($($rest:tt)+) => (
+        $crate::__log_impl!(@post ($crate::default_logger(), $crate::Level::Info, [], [], [], [], []) $($rest)+)
     )
 }
 
@@ -156,11 +357,11 @@ macro_rules! info {
 /// ```
 #[macro_export]
 macro_rules! debug {
-    (logger: $logger:expr, $($arg:tt)+) => (
-        $crate::log!(logger: $logger, $crate::Level::Debug, $($arg)+)
+    (logger: $logger:expr, $($rest:tt)+) => (
+        $crate::__log_impl!(@post ($logger, $crate::Level::Debug, [], [], [], [], []) $($rest)+)
     );
-    ($($arg:tt)+) => (
-        $crate::log!($crate::Level::Debug, $($arg)+)
+    ($($rest:tt)+) => (
+        $crate::__log_impl!(@post ($crate::default_logger(), $crate::Level::Debug, [], [], [], [], []) $($rest)+)
     )
 }
 
@@ -185,10 +386,383 @@ macro_rules! debug {
 /// ```
 #[macro_export]
 macro_rules! trace {
-    (logger: $logger:expr, $($arg:tt)+) => (
-        $crate::log!(logger: $logger, $crate::Level::Trace, $($arg)+)
+    (logger: $logger:expr, $($rest:tt)+) => (
+        $crate::__log_impl!(@post ($logger, $crate::Level::Trace, [], [], [], [], []) $($rest)+)
     );
-    ($($arg:tt)+) => (
-        $crate::log!($crate::Level::Trace, $($arg)+)
+    ($($rest:tt)+) => (
+        $crate::__log_impl!(@post ($crate::default_logger(), $crate::Level::Trace, [], [], [], [], []) $($rest)+)
     )
 }
+
+/// Logs the value of an expression at the specified level and returns it.
+///
+/// This macro works like [`std::dbg!`], but instead of printing to `stderr`,
+/// the record `<expr> = <value:?>` is sent through the logging pipeline, so
+/// temporary inspection points end up in the same sinks as other logs. The
+/// source location is attached to the record as usual.
+///
+/// If the level is omitted, [`Level::Debug`] is used. The value is moved into
+/// the macro and returned unchanged, so it can be used inline in an
+/// expression.
+#[doc = include_str!("./include/doc/log-macro-nameed-opt-params.md")]
+/// # Examples
+///
+/// ```
+/// use spdlog::{log_dbg, Level};
+///
+/// # let app_events = spdlog::default_logger();
+/// let a = 2;
+///
+/// // Using the global default logger at the debug level
+/// let b = log_dbg!(a * 2) + 1;
+/// assert_eq!(b, 5);
+///
+/// // Or using the specified logger and level
+/// let c = log_dbg!(logger: app_events, Level::Info, b * 2);
+/// assert_eq!(c, 10);
+/// ```
+///
+/// [`Level::Debug`]: crate::Level::Debug
+#[macro_export]
+macro_rules! log_dbg {
+    (logger: $logger:expr, $level:expr, $val:expr $(,)?) => (
+        // Use of `match` here is intentional, it extends the lifetime of
+        // temporaries, just like `std::dbg!` does.
+        match $val {
+            tmp => {
+                $crate::log!(
+                    logger: $logger,
+                    $level,
+                    "{} = {:?}",
+                    ::core::stringify!($val),
+                    &tmp
+                );
+                tmp
+            }
+        }
+    );
+    (logger: $logger:expr, $val:expr $(,)?) => (
+        $crate::log_dbg!(logger: $logger, $crate::Level::Debug, $val)
+    );
+    ($level:expr, $val:expr $(,)?) => (
+        $crate::log_dbg!(logger: $crate::default_logger(), $level, $val)
+    );
+    ($val:expr $(,)?) => (
+        $crate::log_dbg!(logger: $crate::default_logger(), $crate::Level::Debug, $val)
+    );
+}
+
+/// Logs an error with its chain of sources (and backtrace if available) as a
+/// single record.
+///
+/// The error is rendered by [`ErrorReport`], so it can be an error trait object
+/// reference (e.g. `&*boxed_error`), or `&anyhow::Error` / `&eyre::Report` if
+/// crate feature `anyhow` / `eyre` is enabled.
+///
+/// If the level is omitted, [`Level::Error`] is used.
+#[doc = include_str!("./include/doc/log-macro-nameed-opt-params.md")]
+/// # Examples
+///
+/// ```
+/// use std::{error::Error, io};
+///
+/// use spdlog::{log_report, Level};
+///
+/// # let app_events = spdlog::default_logger();
+/// let err: Box<dyn Error> = Box::new(io::Error::new(io::ErrorKind::Other, "disk unavailable"));
+///
+/// // Using the global default logger at the error level
+/// log_report!(&*err);
+///
+/// // Or using the specified logger and level
+/// log_report!(logger: app_events, Level::Warn, &*err);
+/// ```
+///
+/// [`ErrorReport`]: crate::ErrorReport
+/// [`Level::Error`]: crate::Level::Error
+#[macro_export]
+macro_rules! log_report {
+    (logger: $logger:expr, $level:expr, $report:expr $(,)?) => (
+        $crate::log!(logger: $logger, $level, "{}", $crate::ErrorReport::from($report))
+    );
+    (logger: $logger:expr, $report:expr $(,)?) => (
+        $crate::log_report!(logger: $logger, $crate::Level::Error, $report)
+    );
+    ($level:expr, $report:expr $(,)?) => (
+        $crate::log_report!(logger: $crate::default_logger(), $level, $report)
+    );
+    ($report:expr $(,)?) => (
+        $crate::log_report!(logger: $crate::default_logger(), $crate::Level::Error, $report)
+    );
+}
+
+/// Asserts that a boolean expression is `true` at runtime, logging the failure
+/// before panicking.
+///
+/// This macro works like [`std::assert!`], except that when the assertion
+/// fails, a record at the critical level containing the condition text and the
+/// optional message is logged, and the logger is flushed before the panic.
+/// This ensures the assertion context reaches the sinks (e.g. log files)
+/// before the process dies.
+///
+/// The failure record is subject to the level filter of the logger, like any
+/// other record.
+#[doc = include_str!("./include/doc/log-macro-nameed-opt-params.md")]
+/// # Examples
+///
+/// ```
+/// use spdlog::log_assert;
+///
+/// # let app_events = spdlog::default_logger();
+/// let (left, right) = (1, 1);
+///
+/// // Using the global default logger
+/// log_assert!(left == right);
+///
+/// // Or using the specified logger, with a custom message
+/// log_assert!(logger: app_events, left == right, "left: {}, right: {}", left, right);
+/// ```
+///
+/// See also [`log_debug_assert!`].
+///
+/// [`log_debug_assert!`]: crate::log_debug_assert
+#[macro_export]
+macro_rules! log_assert {
+    (logger: $logger:expr, $cond:expr $(,)?) => ({
+        if !$cond {
+            $crate::__log_assert_failed(
+                &$logger,
+                $crate::source_location_current!(),
+                $crate::__private_module_path!(),
+                ::core::stringify!($cond),
+                None,
+            );
+        }
+    });
+    (logger: $logger:expr, $cond:expr, $($arg:tt)+) => ({
+        if !$cond {
+            $crate::__log_assert_failed(
+                &$logger,
+                $crate::source_location_current!(),
+                $crate::__private_module_path!(),
+                ::core::stringify!($cond),
+                Some(format_args!($($arg)+)),
+            );
+        }
+    });
+    ($cond:expr $(,)?) => (
+        $crate::log_assert!(logger: $crate::default_logger(), $cond)
+    );
+    ($cond:expr, $($arg:tt)+) => (
+        $crate::log_assert!(logger: $crate::default_logger(), $cond, $($arg)+)
+    );
+}
+
+/// Asserts that a boolean expression is `true` at runtime in debug builds,
+/// logging the failure before panicking.
+///
+/// This macro is the [`std::debug_assert!`] counterpart of [`log_assert!`].
+/// It is only checked when `debug_assertions` are enabled, otherwise the
+/// condition is not evaluated.
+#[doc = include_str!("./include/doc/log-macro-nameed-opt-params.md")]
+/// # Examples
+///
+/// ```
+/// use spdlog::log_debug_assert;
+///
+/// # let app_events = spdlog::default_logger();
+/// let len = 3;
+///
+/// // Using the global default logger
+/// log_debug_assert!(len > 0);
+///
+/// // Or using the specified logger, with a custom message
+/// log_debug_assert!(logger: app_events, len < 10, "unexpected length: {}", len);
+/// ```
+///
+/// [`log_assert!`]: crate::log_assert
+#[macro_export]
+macro_rules! log_debug_assert {
+    ($($arg:tt)+) => (
+        if cfg!(debug_assertions) {
+            $crate::log_assert!($($arg)+);
+        }
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use std::panic::{self, AssertUnwindSafe};
+
+    use crate::{prelude::*, test_utils::*};
+
+    #[test]
+    fn code() {
+        let test_sink = std::sync::Arc::new(TestSink::new());
+        let test_logger = build_test_logger(|b| b.sink(test_sink.clone()));
+
+        let code = String::from("E2");
+        error!(logger: test_logger, code = "E1", "decode failed: {}", 42);
+        log!(logger: test_logger, code = &code, Level::Warn, "retrying");
+        info!(logger: test_logger, "done");
+
+        let records = test_sink.records();
+        assert_eq!(records[0].code(), Some("E1"));
+        assert_eq!(records[0].payload(), "decode failed: 42");
+        assert_eq!(records[1].code(), Some("E2"));
+        assert_eq!(records[1].level(), Level::Warn);
+        assert_eq!(records[2].code(), None);
+    }
+
+    #[test]
+    fn bytes() {
+        let test_sink = std::sync::Arc::new(TestSink::new());
+        let test_logger = build_test_logger(|b| b.sink(test_sink.clone()));
+
+        let frame = vec![0x7e, 0xff, 0x00];
+        warn!(logger: test_logger, bytes = &frame, "malformed frame");
+        log!(logger: test_logger, code = "E1", bytes = &frame[1..], Level::Error, "bad {}", 1);
+        error!(logger: test_logger, bytes = b"\xc3\x28", code = "E2", "invalid utf-8");
+
+        let records = test_sink.records();
+        assert_eq!(records[0].bytes(), Some(&[0x7e, 0xff, 0x00][..]));
+        assert_eq!(records[0].code(), None);
+        assert_eq!(records[1].bytes(), Some(&[0xff, 0x00][..]));
+        assert_eq!(records[1].code(), Some("E1"));
+        assert_eq!(records[1].payload(), "bad 1");
+        assert_eq!(records[2].bytes(), Some(&[0xc3, 0x28][..]));
+        assert_eq!(records[2].code(), Some("E2"));
+    }
+
+    #[test]
+    fn destination() {
+        let test_sink = std::sync::Arc::new(TestSink::new());
+        let test_logger = build_test_logger(|b| b.sink(test_sink.clone()));
+
+        info!(logger: test_logger, sink = "audit", "user {} signed in", 42);
+        log!(logger: test_logger, code = "S1", sink = "security", Level::Warn, "denied");
+        info!(logger: test_logger, "done");
+
+        let records = test_sink.records();
+        assert_eq!(records[0].destination(), Some("audit"));
+        assert_eq!(records[0].payload(), "user 42 signed in");
+        assert_eq!(records[1].destination(), Some("security"));
+        assert_eq!(records[1].code(), Some("S1"));
+        assert_eq!(records[2].destination(), None);
+    }
+
+    #[test]
+    fn key_values() {
+        let test_sink = std::sync::Arc::new(TestSink::new());
+        let test_logger = build_test_logger(|b| b.sink(test_sink.clone()));
+
+        let user_id = 42;
+        info!(logger: test_logger, user_id = user_id, peer = "10.0.0.3", "login ok");
+        log!(logger: test_logger, code = "E1", attempt = 3, Level::Warn, "retry {}", 1);
+        info!(logger: test_logger, "done");
+
+        let records = test_sink.records();
+        assert_eq!(
+            records[0].key_values().collect::<Vec<_>>(),
+            [("user_id", "42"), ("peer", "10.0.0.3")]
+        );
+        assert_eq!(records[0].payload(), "login ok");
+        assert_eq!(records[1].key_value("attempt"), Some("3"));
+        assert_eq!(records[1].code(), Some("E1"));
+        assert_eq!(records[1].payload(), "retry 1");
+        assert_eq!(records[2].key_values().count(), 0);
+    }
+
+    #[test]
+    fn try_log() {
+        let test_sink = std::sync::Arc::new(TestSink::new());
+        let test_logger = build_test_logger(|b| b.sink(test_sink.clone()));
+
+        try_log!(logger: test_logger, Level::Info, "signed in: {}", 42).unwrap();
+        try_log!(logger: test_logger, Level::Debug, "filtered").unwrap();
+
+        assert_eq!(test_sink.payloads(), ["signed in: 42"]);
+    }
+
+    #[test]
+    fn log_dbg() {
+        let test_sink = std::sync::Arc::new(TestSink::new());
+        let test_logger = build_test_logger(|b| b.sink(test_sink.clone()));
+        test_logger.set_level_filter(LevelFilter::All);
+
+        let value = 21;
+        let doubled = log_dbg!(logger: test_logger, value * 2);
+        assert_eq!(doubled, 42);
+
+        let owned = log_dbg!(logger: test_logger, Level::Warn, String::from("spdlog"));
+        assert_eq!(owned, "spdlog");
+
+        assert_eq!(
+            test_sink.payloads(),
+            vec![
+                "value * 2 = 42".to_string(),
+                "String::from(\"spdlog\") = \"spdlog\"".to_string()
+            ]
+        );
+        let records = test_sink.records();
+        assert_eq!(records[0].level(), Level::Debug);
+        assert_eq!(records[1].level(), Level::Warn);
+    }
+
+    #[test]
+    fn log_report() {
+        let test_sink = std::sync::Arc::new(TestSink::new());
+        let test_logger = build_test_logger(|b| b.sink(test_sink.clone()));
+
+        let err: Box<dyn std::error::Error> = Box::new(std::io::Error::new(
+            std::io::ErrorKind::Other,
+            "disk unavailable",
+        ));
+        log_report!(logger: test_logger, &*err);
+        log_report!(logger: test_logger, Level::Warn, &*err);
+
+        assert_eq!(
+            test_sink.payloads(),
+            vec![
+                "disk unavailable".to_string(),
+                "disk unavailable".to_string()
+            ]
+        );
+        let records = test_sink.records();
+        assert_eq!(records[0].level(), Level::Error);
+        assert_eq!(records[1].level(), Level::Warn);
+    }
+
+    #[test]
+    fn log_assert() {
+        let test_sink = std::sync::Arc::new(TestSink::new());
+        let test_logger = build_test_logger(|b| b.sink(test_sink.clone()));
+
+        let value = 1;
+        log_assert!(logger: test_logger, value == 1);
+        log_assert!(logger: test_logger, value == 1, "value is {}", value);
+        assert_eq!(test_sink.log_count(), 0);
+
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            log_assert!(logger: test_logger, value == 2, "value is {}", value);
+        }));
+        let panic_message = result.unwrap_err().downcast::<String>().unwrap();
+        assert_eq!(*panic_message, "assertion failed: value == 2: value is 1");
+
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            log_debug_assert!(logger: test_logger, value > 1);
+        }));
+        assert_eq!(result.is_err(), cfg!(debug_assertions));
+
+        let mut expected = vec!["assertion failed: value == 2: value is 1".to_string()];
+        if cfg!(debug_assertions) {
+            expected.push("assertion failed: value > 1".to_string());
+        }
+        assert_eq!(test_sink.payloads(), expected);
+        assert!(test_sink
+            .records()
+            .iter()
+            .all(|record| record.level() == Level::Critical));
+        assert_eq!(test_sink.flush_count(), expected.len());
+    }
+}
@@ -0,0 +1,150 @@
+use crate::{Error, Level};
+
+const RECORDS_TOTAL: &str = "spdlog_records_total";
+const SINK_ERRORS_TOTAL: &str = "spdlog_sink_errors_total";
+#[cfg(feature = "multi-thread")]
+const RECORDS_DROPPED_TOTAL: &str = "spdlog_records_dropped_total";
+
+pub(crate) fn on_record(logger_name: Option<&str>, level: Level) {
+    metrics::counter!(
+        RECORDS_TOTAL,
+        "logger" => logger_name.unwrap_or_default().to_string(),
+        "level" => level.as_str(),
+    )
+    .increment(1);
+}
+
+pub(crate) fn on_sink_error(logger_name: Option<&str>, err: &Error) {
+    if let Error::Multiple(errs) = err {
+        errs.iter().for_each(|err| on_sink_error(logger_name, err));
+        return;
+    }
+
+    let logger_name = logger_name.unwrap_or_default().to_string();
+    #[cfg(feature = "multi-thread")]
+    if let Error::SendToChannel(_, crate::error::SendToChannelErrorDropped::Record(_)) = err {
+        metrics::counter!(RECORDS_DROPPED_TOTAL, "logger" => logger_name.clone()).increment(1);
+    }
+    metrics::counter!(SINK_ERRORS_TOTAL, "logger" => logger_name).increment(1);
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        collections::HashMap,
+        sync::{atomic::AtomicU64, Mutex},
+    };
+
+    use metrics::{
+        Counter, Gauge, Histogram, Key, KeyName, Metadata, Recorder, SharedString, Unit,
+    };
+
+    use super::*;
+    use crate::{prelude::*, sync::*, test_utils::*, FilterResult, Record, Sampler};
+
+    #[derive(Default)]
+    struct CountingRecorder {
+        counters: Mutex<HashMap<String, Arc<AtomicU64>>>,
+    }
+
+    impl CountingRecorder {
+        #[must_use]
+        fn get(&self, key: &str) -> u64 {
+            self.counters
+                .lock()
+                .unwrap()
+                .get(key)
+                .map_or(0, |counter| counter.load(Ordering::Relaxed))
+        }
+    }
+
+    impl Recorder for CountingRecorder {
+        fn describe_counter(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+        fn describe_gauge(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+        fn describe_histogram(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+
+        fn register_counter(&self, key: &Key, _: &Metadata<'_>) -> Counter {
+            let labels = key
+                .labels()
+                .map(|label| format!("{}={}", label.key(), label.value()))
+                .collect::<Vec<_>>();
+            let key = format!("{}{{{}}}", key.name(), labels.join(","));
+            Counter::from_arc(
+                self.counters
+                    .lock()
+                    .unwrap()
+                    .entry(key)
+                    .or_default()
+                    .clone(),
+            )
+        }
+
+        fn register_gauge(&self, _: &Key, _: &Metadata<'_>) -> Gauge {
+            Gauge::noop()
+        }
+
+        fn register_histogram(&self, _: &Key, _: &Metadata<'_>) -> Histogram {
+            Histogram::noop()
+        }
+    }
+
+    #[test]
+    fn counters() {
+        let recorder = CountingRecorder::default();
+        let test_sink = Arc::new(TestSink::new());
+        let logger = build_test_logger(|b| {
+            b.name("app")
+                .sink(test_sink.clone())
+                .filter(|record: &Record| {
+                    if record.payload() == "rejected" {
+                        FilterResult::Reject
+                    } else {
+                        FilterResult::Neutral
+                    }
+                })
+                .sampler(Sampler::new().rate(Level::Debug, 0.0))
+        });
+        logger.set_error_handler(Some(|_| {}));
+
+        metrics::with_local_recorder(&recorder, || {
+            info!(logger: logger, "1");
+            info!(logger: logger, "2");
+            warn!(logger: logger, "3");
+            trace!(logger: logger, "filtered");
+            error!(logger: logger, "rejected");
+            logger.set_level_filter(LevelFilter::All);
+            debug!(logger: logger, "not sampled");
+
+            on_sink_error(
+                Some("app"),
+                &Error::Multiple(vec![
+                    Error::FlushBuffer(std::io::Error::new(std::io::ErrorKind::Other, "1")),
+                    Error::FlushBuffer(std::io::Error::new(std::io::ErrorKind::Other, "2")),
+                ]),
+            );
+        });
+
+        assert_eq!(
+            recorder.get("spdlog_records_total{logger=app,level=info}"),
+            2
+        );
+        assert_eq!(
+            recorder.get("spdlog_records_total{logger=app,level=warn}"),
+            1
+        );
+        assert_eq!(
+            recorder.get("spdlog_records_total{logger=app,level=trace}"),
+            0
+        );
+        assert_eq!(
+            recorder.get("spdlog_records_total{logger=app,level=error}"),
+            0
+        );
+        assert_eq!(
+            recorder.get("spdlog_records_total{logger=app,level=debug}"),
+            0
+        );
+        assert_eq!(recorder.get("spdlog_sink_errors_total{logger=app}"), 2);
+        assert_eq!(recorder.get("spdlog_records_dropped_total{logger=app}"), 0);
+    }
+}
@@ -1,12 +1,27 @@
-use std::{result::Result as StdResult, time::Duration};
+use std::{
+    cell::RefCell,
+    collections::VecDeque,
+    result::Result as StdResult,
+    time::{Duration, SystemTime},
+};
+#[cfg(feature = "log-async")]
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
 
 use crate::{
-    env_level,
+    clock::Clock,
+    diagnostics, env_level,
     error::{Error, ErrorHandler, InvalidArgumentError, SetLoggerNameError},
+    filter::{self, Filter, Filters},
+    idle_flusher::IdleFlusher,
     periodic_worker::PeriodicWorker,
-    sink::{Sink, Sinks},
+    sink::{lifecycle, LevelOverrideSink, Sink, Sinks},
     sync::*,
-    Level, LevelFilter, Record, Result,
+    timing::{LoggerStats, Timings},
+    Level, LevelFilter, Record, RecordOwned, Result, Sampler,
 };
 
 fn check_logger_name(name: impl AsRef<str>) -> StdResult<(), SetLoggerNameError> {
@@ -109,10 +124,84 @@ fn check_logger_name(name: impl AsRef<str>) -> StdResult<(), SetLoggerNameError>
 pub struct Logger {
     name: Option<String>,
     level_filter: Atomic<LevelFilter>,
+    // Sorted by the length of module path prefixes in descending order, so that
+    // the first match is the longest one.
+    module_level_filters: ArcSwapOption<Vec<(String, LevelFilter)>>,
     sinks: Sinks,
     flush_level_filter: Atomic<LevelFilter>,
     error_handler: SpinRwLock<Option<ErrorHandler>>,
-    periodic_flusher: Mutex<Option<(Duration, PeriodicWorker)>>,
+    periodic_flusher: Mutex<Option<(FlushPeriod, PeriodicWorker)>>,
+    idle_flusher: ArcSwapOption<IdleFlusher>,
+    backtrace: ArcSwapOption<Backtrace>,
+    clock: Option<Arc<dyn Clock>>,
+    audit: bool,
+    prefix: Option<String>,
+    filters: Filters,
+    sampler: Option<Sampler>,
+    timings: Option<Timings>,
+    seq: AtomicU64,
+    global_seq: bool,
+    #[cfg(feature = "regex")]
+    redactor: Option<Arc<crate::Redactor>>,
+    // Sinks counted as opened by this logger, released on close or drop.
+    opened_sinks: Mutex<Sinks>,
+}
+
+static GLOBAL_SEQ: AtomicU64 = AtomicU64::new(0);
+
+// Payloads longer than this are not kept for reuse, so that a single huge
+// record does not hold memory for the lifetime of the thread.
+const MAX_REUSED_PAYLOAD_LEN: usize = 64 * 1024;
+
+thread_local! {
+    static PAYLOAD_BUF: RefCell<String> = const { RefCell::new(String::new()) };
+}
+
+// Runs `f` with an empty buffer reused by the current thread, for building
+// payloads without allocating for each record. A new buffer is used instead if
+// the reused one is borrowed, e.g. a sink logs to another logger.
+fn with_payload_buf<R>(f: impl FnOnce(&mut String) -> R) -> R {
+    let mut f = Some(f);
+    let res = PAYLOAD_BUF.try_with(|payload| {
+        let mut payload = payload.try_borrow_mut().ok()?;
+        payload.clear();
+        let res = (f.take().unwrap())(&mut payload);
+        if payload.capacity() > MAX_REUSED_PAYLOAD_LEN {
+            *payload = String::new();
+        }
+        Some(res)
+    });
+    match res {
+        Ok(Some(res)) => res,
+        _ => (f.take().unwrap())(&mut String::new()),
+    }
+}
+
+// Keeps the latest records for `Logger::dump_backtrace`.
+struct Backtrace {
+    capacity: usize,
+    records: Mutex<VecDeque<RecordOwned>>,
+}
+
+impl Backtrace {
+    #[must_use]
+    fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            records: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    fn push(&self, record: &Record) {
+        if self.capacity == 0 {
+            return;
+        }
+        let mut records = self.records.lock_expect();
+        if records.len() == self.capacity {
+            records.pop_front();
+        }
+        records.push_back(record.to_owned());
+    }
 }
 
 impl Logger {
@@ -123,25 +212,53 @@ impl Logger {
     /// | [name]               | `None`                  |
     /// | [sinks]              | `[]`                    |
     /// | [level_filter]       | `MoreSevereEqual(Info)` |
+    /// | [module_level_filter] | `[]`                   |
     /// | [flush_level_filter] | `Off`                   |
     /// | [flush_period]       | `None`                  |
+    /// | [idle_flush]         | `None`                  |
     /// | [error_handler]      | [default error handler] |
+    /// | [clock]              | `None` (system time)    |
+    /// | [audit]              | `false`                 |
+    /// | [prefix]             | `None`                  |
+    /// | [filters]            | `[]`                    |
+    /// | [sampler]            | `None`                  |
+    /// | [timing]             | `false`                 |
+    /// | [redactor]           | `None`                  |
     ///
     /// [name]: LoggerBuilder::name
     /// [sinks]: LoggerBuilder::sink
     /// [level_filter]: LoggerBuilder::level_filter
+    /// [module_level_filter]: LoggerBuilder::module_level_filter
     /// [flush_level_filter]: LoggerBuilder::flush_level_filter
     /// [flush_period]: Logger::set_flush_period
+    /// [idle_flush]: Logger::set_idle_flush
     /// [error_handler]: LoggerBuilder::error_handler
     /// [default error handler]: error/index.html#default-error-handler
+    /// [clock]: LoggerBuilder::clock
+    /// [audit]: LoggerBuilder::audit
+    /// [prefix]: LoggerBuilder::prefix
+    /// [filters]: LoggerBuilder::filter
+    /// [sampler]: LoggerBuilder::sampler
+    /// [timing]: LoggerBuilder::timing
+    /// [redactor]: LoggerBuilder::redactor
     #[must_use]
     pub fn builder() -> LoggerBuilder {
         LoggerBuilder {
             name: None,
             level_filter: LevelFilter::MoreSevereEqual(Level::Info),
+            module_level_filters: vec![],
             sinks: vec![],
             flush_level_filter: LevelFilter::Off,
             error_handler: None,
+            clock: None,
+            audit: false,
+            prefix: None,
+            filters: vec![],
+            sampler: None,
+            timing: false,
+            global_seq: false,
+            #[cfg(feature = "regex")]
+            redactor: None,
         }
     }
 
@@ -197,6 +314,77 @@ impl Logger {
         self.level_filter().test(level)
     }
 
+    /// Determines if a log with the specified level would be written by any
+    /// sink.
+    ///
+    /// In addition to [`Logger::should_log`], it checks the level filters of
+    /// the sinks, so that the computation of log arguments can be skipped
+    /// when the logger accepts the level but all sinks would reject it. The
+    /// logging macros use this function.
+    ///
+    /// It is as cheap as loading the level filter of each sink, and always
+    /// reflects the current level filters, including those set on sinks
+    /// directly with [`Sink::set_level_filter`].
+    ///
+    /// While the [backtrace] is enabled, it returns `true` for all levels.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::sync::Arc;
+    /// use spdlog::{
+    ///     prelude::*,
+    ///     sink::{StdStream, StdStreamSink},
+    /// };
+    ///
+    /// # fn main() -> Result<(), spdlog::Error> {
+    /// let sink = Arc::new(
+    ///     StdStreamSink::builder()
+    ///         .std_stream(StdStream::Stderr)
+    ///         .level_filter(LevelFilter::MoreSevereEqual(Level::Warn))
+    ///         .build()?,
+    /// );
+    /// let logger = Logger::builder()
+    ///     .sink(sink)
+    ///     .level_filter(LevelFilter::All)
+    ///     .build()?;
+    ///
+    /// assert_eq!(logger.should_log(Level::Debug), true);
+    /// assert_eq!(logger.enabled(Level::Debug), false);
+    /// assert_eq!(logger.enabled(Level::Error), true);
+    /// # Ok(()) }
+    /// ```
+    ///
+    /// [backtrace]: Logger::enable_backtrace
+    #[must_use]
+    pub fn enabled(&self, level: Level) -> bool {
+        self.enabled_in(level, None)
+    }
+
+    /// Determines if a log with the specified level would be logged from the
+    /// module.
+    ///
+    /// Unlike [`Logger::should_log`], it checks the level filter in effect for
+    /// the module, see [`Logger::set_module_level_filter`]. If `module_path`
+    /// is `None`, the level filter of the logger is checked.
+    #[must_use]
+    pub fn should_log_in(&self, level: Level, module_path: Option<&str>) -> bool {
+        self.effective_level_filter(module_path).test(level)
+    }
+
+    /// Determines if a log with the specified level from the module would be
+    /// written by any sink.
+    ///
+    /// It is [`Logger::enabled`] with the level filter in effect for the
+    /// module, see [`Logger::should_log_in`]. The logging macros use this
+    /// function.
+    #[must_use]
+    pub fn enabled_in(&self, level: Level, module_path: Option<&str>) -> bool {
+        (self.should_log_in(level, module_path)
+            && self.sinks.iter().any(|sink| sink.should_log(level)))
+            || self.backtrace.load().is_some()
+    }
+
     /// Passes a log into sinks in sequence.
     ///
     /// It calls [`Sink::log`] method internally for each sink in sequence.
@@ -206,10 +394,144 @@ impl Logger {
     /// Users usually do not use this function directly, use logging macros
     /// instead.
     pub fn log(&self, record: &Record) {
-        if !self.should_log(record.level()) {
+        self.log_with(record, |err| self.handle_error(err));
+    }
+
+    /// Passes a log into sinks in sequence, and returns the errors that occur.
+    ///
+    /// Unlike [`Logger::log`], errors that occur in logging (and flushing if
+    /// an automatic flushing policy applies) are returned rather than passed
+    /// to the error handler, so that callers can react to records that failed
+    /// to be written. It returns `Ok(())` if the record is filtered out by the
+    /// level filter.
+    ///
+    /// In [audit mode], it returns after the record has been stored durably.
+    ///
+    /// Users usually use [`try_log!`] macro instead of calling this function
+    /// directly.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use spdlog::{prelude::*, try_log};
+    /// # use spdlog::sink::{StdStream, StdStreamSink};
+    ///
+    /// # fn main() -> Result<(), spdlog::Error> {
+    /// # let sink = std::sync::Arc::new(StdStreamSink::builder().std_stream(StdStream::Stdout).build()?);
+    /// let audit_logger = Logger::builder().sink(sink).audit(true).build()?;
+    ///
+    /// try_log!(logger: audit_logger, Level::Info, "user alice signed in")?;
+    /// # Ok(()) }
+    /// ```
+    ///
+    /// [audit mode]: LoggerBuilder::audit
+    /// [`try_log!`]: crate::try_log
+    pub fn try_log(&self, record: &Record) -> Result<()> {
+        let mut errors = vec![];
+        self.log_with(record, |err| errors.push(err));
+        errors.into_iter().fold(Ok(()), Error::push_err)
+    }
+
+    /// Passes a log into sinks in sequence, after awaiting until the sinks have
+    /// capacity for it.
+    ///
+    /// When an asynchronous sink (e.g. [`AsyncSinkAdapter`]) has a full queue,
+    /// [`Logger::log`] either blocks the thread or drops the record, depending
+    /// on the overflow policy of the sink. This function instead yields to the
+    /// executor until all sinks accepting the record have space (see
+    /// [`Sink::poll_capacity`]), so that asynchronous applications apply
+    /// backpressure to log producers naturally. Then the record is logged as
+    /// with [`Logger::log`].
+    ///
+    /// Since other producers may fill a queue in the meantime, the overflow
+    /// policy still applies if the queue is full again when the record is
+    /// passed to the sink.
+    ///
+    /// Users usually use [`log_async!`] macro instead of calling this function
+    /// directly.
+    ///
+    /// This function is only available if crate feature `log-async` is
+    /// enabled.
+    ///
+    /// [`AsyncSinkAdapter`]: crate::sink::AsyncSinkAdapter
+    /// [`log_async!`]: crate::log_async
+    #[cfg(feature = "log-async")]
+    pub async fn log_async(&self, record: &Record<'_>) {
+        if !self.should_log_record(record) {
+            // Records filtered out are still kept by the backtrace, as `log` does.
+            if let Some(backtrace) = self.backtrace.load().as_ref() {
+                backtrace.push(record);
+            }
+            return;
+        }
+        SinksCapacity {
+            sinks: &self.sinks,
+            level: record.level(),
+        }
+        .await;
+        self.log(record);
+    }
+
+    fn log_with(&self, record: &Record, on_error: impl FnMut(Error)) {
+        if let Some(backtrace) = self.backtrace.load().as_ref() {
+            backtrace.push(record);
+        }
+        if !self.should_log_record(record) {
+            return;
+        }
+        if !filter::filter_chain(&self.filters, record) {
             return;
         }
-        self.sink_record(record);
+        if let Some(sampler) = &self.sampler {
+            if !sampler.sample(record.level()) {
+                return;
+            }
+        }
+        #[cfg(feature = "metrics")]
+        crate::log_metrics::on_record(self.name(), record.level());
+        let stamped = record.stamped(
+            self.clock.as_ref().map(|clock| clock.now()),
+            self.seq.fetch_add(1, Ordering::Relaxed),
+            self.next_global_seq(),
+        );
+        self.sink_processed(&stamped, on_error)
+    }
+
+    #[must_use]
+    fn next_global_seq(&self) -> u64 {
+        if self.global_seq {
+            GLOBAL_SEQ.fetch_add(1, Ordering::Relaxed)
+        } else {
+            0
+        }
+    }
+
+    #[must_use]
+    fn should_log_record(&self, record: &Record) -> bool {
+        self.should_log_in(record.level(), record.module_path())
+    }
+
+    // Redacts and prefixes the payload of a stamped record, and passes it into
+    // sinks.
+    fn sink_processed(&self, record: &Record, on_error: impl FnMut(Error)) {
+        #[cfg(feature = "regex")]
+        let redacted;
+        #[cfg(feature = "regex")]
+        let record = match self.redactor.as_ref().map(|r| r.redact(record.payload())) {
+            Some(std::borrow::Cow::Owned(payload)) => {
+                redacted = record.replace_payload(payload);
+                &redacted
+            }
+            _ => record,
+        };
+        match &self.prefix {
+            Some(prefix) => with_payload_buf(|payload| {
+                payload.push_str(prefix);
+                payload.push_str(record.payload());
+                self.sink_record(&record.replace_payload(payload.as_str()), on_error)
+            }),
+            None => self.sink_record(record, on_error),
+        }
     }
 
     /// Flushes sinks explicitly.
@@ -217,13 +539,153 @@ impl Logger {
     /// It calls [`Sink::flush`] method internally for each sink in sequence.
     ///
     /// Users can call this function to flush explicitly and/or use automatic
-    /// flushing policies. See also [`Logger::flush_level_filter`] and
-    /// [`Logger::set_flush_period`].
+    /// flushing policies. See also [`Logger::flush_level_filter`],
+    /// [`Logger::set_flush_period`] and [`Logger::set_idle_flush`].
     ///
     /// Be aware that the method can be expensive, calling it frequently may
     /// affect performance.
     pub fn flush(&self) {
-        self.flush_sinks();
+        self.flush_sinks(|err| self.handle_error(err));
+    }
+
+    /// Flushes sinks explicitly, and returns the errors that occur.
+    ///
+    /// Unlike [`Logger::flush`], errors are returned rather than passed to the
+    /// error handler. In [audit mode], it calls [`Sink::sync`] instead of
+    /// [`Sink::flush`], so that an `Ok` result confirms that the records
+    /// logged so far have been stored durably.
+    ///
+    /// [audit mode]: LoggerBuilder::audit
+    pub fn try_flush(&self) -> Result<()> {
+        let mut errors = vec![];
+        self.flush_sinks(|err| errors.push(err));
+        errors.into_iter().fold(Ok(()), Error::push_err)
+    }
+
+    /// Whether the logger is in audit mode.
+    ///
+    /// See [`LoggerBuilder::audit`] for more details.
+    #[must_use]
+    pub fn is_audit(&self) -> bool {
+        self.audit
+    }
+
+    /// Gets the prefix of payloads.
+    ///
+    /// See [`LoggerBuilder::prefix`] for more details.
+    #[must_use]
+    pub fn prefix(&self) -> Option<&str> {
+        self.prefix.as_deref()
+    }
+
+    /// Gets the timing statistics of the pipeline, or `None` if timing is not
+    /// enabled.
+    ///
+    /// See [`LoggerBuilder::timing`] for more details.
+    #[must_use]
+    pub fn stats(&self) -> Option<LoggerStats> {
+        self.timings.as_ref().map(Timings::stats)
+    }
+
+    /// Flushes and closes sinks.
+    ///
+    /// It calls [`Sink::flush`] for each sink, and then [`Sink::close`] for
+    /// each sink no longer used by other loggers, so that connection-oriented
+    /// sinks can tear down resources deterministically at shutdown. Sinks
+    /// shared with other live loggers are kept open, see [`Sink::open`].
+    ///
+    /// Closing a logger more than once does nothing more than flushing.
+    ///
+    /// The default logger is closed automatically at the program exit.
+    pub fn close(&self) {
+        self.flush();
+        let opened_sinks = std::mem::take(&mut *self.opened_sinks.lock_expect());
+        lifecycle::release(&opened_sinks, |err| self.handle_error(err));
+    }
+
+    /// Checks the health of sinks.
+    ///
+    /// It calls [`Sink::health`] method internally for each sink, and returns
+    /// the errors of unhealthy sinks. This can be used in readiness probes to
+    /// report the health of the logging pipeline.
+    pub fn health(&self) -> Result<()> {
+        #[allow(clippy::manual_try_fold)] // https://github.com/rust-lang/rust-clippy/issues/11554
+        self.sinks.iter().fold(Ok(()), |result, sink| {
+            Error::push_result(result, sink.health())
+        })
+    }
+
+    /// Enables the backtrace with the given capacity.
+    ///
+    /// While the backtrace is enabled, the latest `capacity` records are kept
+    /// in memory, including those filtered out by the level filter of the
+    /// logger, so that they can be logged later by [`Logger::dump_backtrace`]
+    /// when something goes wrong. This keeps verbose logs cheap until they are
+    /// needed.
+    ///
+    /// Records kept before are discarded.
+    ///
+    /// Note that log arguments are always evaluated while the backtrace is
+    /// enabled, since records of all levels are kept.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use spdlog::prelude::*;
+    ///
+    /// # let logger = spdlog::default_logger();
+    /// logger.enable_backtrace(32);
+    ///
+    /// debug!(logger: logger, "connecting to peer 3"); // Filtered out, but kept
+    /// debug!(logger: logger, "handshake done");
+    ///
+    /// // Something went wrong, log the latest records.
+    /// logger.dump_backtrace();
+    /// # logger.disable_backtrace();
+    /// ```
+    pub fn enable_backtrace(&self, capacity: usize) {
+        self.backtrace
+            .store(Some(Arc::new(Backtrace::new(capacity))));
+    }
+
+    /// Disables the backtrace, and discards the kept records.
+    ///
+    /// See [`Logger::enable_backtrace`] for more details.
+    pub fn disable_backtrace(&self) {
+        self.backtrace.store(None);
+    }
+
+    /// Logs the records kept by the backtrace, and discards them.
+    ///
+    /// The records are passed into sinks regardless of the level filter of the
+    /// logger, between a start and an end marker record. It does nothing if
+    /// the backtrace is disabled or no records are kept.
+    ///
+    /// See [`Logger::enable_backtrace`] for more details.
+    pub fn dump_backtrace(&self) {
+        let records = match self.backtrace.load().as_ref() {
+            Some(backtrace) => std::mem::take(&mut *backtrace.records.lock_expect()),
+            None => return,
+        };
+        if records.is_empty() {
+            return;
+        }
+
+        let marker = |payload| {
+            let record = Record::new(Level::Info, payload, None, self.name());
+            self.sink_record(&record, |err| self.handle_error(err));
+        };
+        marker("****************** Backtrace Start ******************");
+        for record in &records {
+            let record = record.as_ref();
+            let stamped = record.stamped(
+                None,
+                self.seq.fetch_add(1, Ordering::Relaxed),
+                self.next_global_seq(),
+            );
+            self.sink_processed(&stamped, |err| self.handle_error(err));
+        }
+        marker("****************** Backtrace End ********************");
     }
 
     /// Gets the flush level filter.
@@ -276,6 +738,65 @@ impl Logger {
         self.level_filter.store(level_filter, Ordering::Relaxed);
     }
 
+    /// Gets the level filter in effect for the module.
+    ///
+    /// It is the module level filter with the longest prefix matching the
+    /// module path, or the level filter of the logger if there is no match or
+    /// `module_path` is `None`.
+    #[must_use]
+    pub fn effective_level_filter(&self, module_path: Option<&str>) -> LevelFilter {
+        if let Some(module_path) = module_path {
+            if let Some(filters) = &*self.module_level_filters.load() {
+                if let Some((_, level_filter)) = filters
+                    .iter()
+                    .find(|(prefix, _)| filter::is_module_prefix(prefix, module_path))
+                {
+                    return *level_filter;
+                }
+            }
+        }
+        self.level_filter()
+    }
+
+    /// Sets or removes the level filter for a module path prefix.
+    ///
+    /// A prefix matches the module and its submodules, e.g. prefix
+    /// `my_app::net` matches `my_app::net` and `my_app::net::http`, but not
+    /// `my_app::network`. Records from matched modules are filtered by the
+    /// level filter of the longest matching prefix instead of the level filter
+    /// of the logger, similar to `env_logger` directives such as
+    /// `warn,my_app::net=trace`.
+    ///
+    /// Module paths are taken from records, see [`Record::module_path`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use spdlog::prelude::*;
+    /// # let logger = spdlog::default_logger();
+    ///
+    /// logger.set_level_filter(LevelFilter::MoreSevereEqual(Level::Warn));
+    /// logger.set_module_level_filter("my_app::net", Some(LevelFilter::All));
+    ///
+    /// assert!(logger.should_log_in(Level::Trace, Some("my_app::net::http")));
+    /// assert!(!logger.should_log_in(Level::Trace, Some("my_app::db")));
+    /// ```
+    pub fn set_module_level_filter<S>(&self, prefix: S, level_filter: Option<LevelFilter>)
+    where
+        S: Into<String>,
+    {
+        let prefix = prefix.into();
+        self.module_level_filters.rcu(|filters| {
+            let mut filters = filters.as_deref().cloned().unwrap_or_default();
+            filters.retain(|(existing, _)| *existing != prefix);
+            if let Some(level_filter) = level_filter {
+                filters.push((prefix.clone(), level_filter));
+                filters.sort_by_key(|(prefix, _)| std::cmp::Reverse(prefix.len()));
+            }
+            (!filters.is_empty()).then(|| Arc::new(filters))
+        });
+    }
+
     /// Sets automatic periodic flushing.
     ///
     /// This function receives a `&Arc<Self>`. Calling it will spawn a new
@@ -284,6 +805,9 @@ impl Logger {
     /// This automatic flushing policy can work with
     /// [`Logger::set_flush_level_filter`] at the same time.
     ///
+    /// It is a shorthand for [`Logger::set_flush_period_with`] without jitter
+    /// and alignment.
+    ///
     /// # Panics
     ///
     ///  - Panics if `interval` is zero.
@@ -306,26 +830,118 @@ impl Logger {
     /// logger.set_flush_period(None);
     /// ```
     pub fn set_flush_period(self: &Arc<Self>, interval: Option<Duration>) {
+        self.set_flush_period_with(interval.map(FlushPeriod::new));
+    }
+
+    /// Sets automatic periodic flushing with jitter or wall-clock alignment.
+    ///
+    /// See [`FlushPeriod`] for the options, and [`Logger::set_flush_period`]
+    /// for the rest.
+    ///
+    /// # Panics
+    ///
+    /// Panics if this function is called with `Some` value and then clones the
+    /// `Logger` instead of the `Arc<Logger>`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// # use std::sync::Arc;
+    /// use spdlog::{prelude::*, FlushPeriod};
+    ///
+    /// # let logger: Arc<Logger> = spdlog::default_logger();
+    /// // Flush at :00 and :30 seconds of each minute, plus a random delay of up
+    /// // to 5 seconds, so that processes do not flush at the same moment.
+    /// logger.set_flush_period_with(Some(
+    ///     FlushPeriod::new(Duration::from_secs(30))
+    ///         .aligned(true)
+    ///         .jitter(Duration::from_secs(5)),
+    /// ));
+    /// # logger.set_flush_period(None);
+    /// ```
+    pub fn set_flush_period_with(self: &Arc<Self>, period: Option<FlushPeriod>) {
         let mut periodic_flusher = self.periodic_flusher.lock_expect();
 
         *periodic_flusher = None;
 
-        if let Some(interval) = interval {
+        if let Some(period) = period {
             let weak = Arc::downgrade(self);
             let callback = move || {
                 let strong = weak.upgrade();
                 if let Some(strong) = strong {
-                    strong.flush_sinks();
+                    strong.flush();
                     true
                 } else {
                     false // All `Arc`s are dropped, return `false` to quit the
                           // worker thread.
                 }
             };
-            *periodic_flusher = Some((interval, PeriodicWorker::new(callback, interval)));
+            *periodic_flusher = Some((
+                period,
+                PeriodicWorker::new(
+                    "spdlog-flush",
+                    callback,
+                    move |now| period.delay_from(now),
+                    self.clock.clone(),
+                ),
+            ));
         }
     }
 
+    /// Sets automatic flushing when the logger becomes idle.
+    ///
+    /// Once records are logged, the logger flushes after no more records have
+    /// been logged for the given idle period. Interactive programs see their
+    /// output promptly after a burst of records, while under a steady load no
+    /// flush is performed until the load stops, saving the cost of flushing
+    /// for each record.
+    ///
+    /// This function receives a `&Arc<Self>`. Calling it will spawn a new
+    /// thread internally.
+    ///
+    /// This automatic flushing policy can work with
+    /// [`Logger::set_flush_period`] at the same time, which bounds the delay
+    /// of records under a steady load, and [`Logger::set_flush_level_filter`].
+    ///
+    /// # Panics
+    ///
+    ///  - Panics if `idle` is zero.
+    ///
+    ///  - Panics if this function is called with `Some` value and then clones
+    ///    the `Logger` instead of the `Arc<Logger>`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// # use std::sync::Arc;
+    /// # use spdlog::prelude::*;
+    ///
+    /// # let logger: Arc<Logger> = spdlog::default_logger();
+    /// // Flush 50 ms after the last record of a burst, and at least every 5 seconds.
+    /// logger.set_idle_flush(Some(Duration::from_millis(50)));
+    /// logger.set_flush_period(Some(Duration::from_secs(5)));
+    /// # logger.set_idle_flush(None);
+    /// # logger.set_flush_period(None);
+    /// ```
+    pub fn set_idle_flush(self: &Arc<Self>, idle: Option<Duration>) {
+        let idle_flusher = idle.map(|idle| {
+            assert!(!idle.is_zero(), "idle period must not be zero");
+
+            let weak = Arc::downgrade(self);
+            Arc::new(IdleFlusher::new(idle, move || {
+                if let Some(strong) = weak.upgrade() {
+                    strong.flush();
+                    true
+                } else {
+                    false // All `Arc`s are dropped, quit the worker thread.
+                }
+            }))
+        });
+        self.idle_flusher.store(idle_flusher);
+    }
+
     /// Gets a reference to sinks in the logger.
     #[must_use]
     pub fn sinks(&self) -> &[Arc<dyn Sink>] {
@@ -395,13 +1011,17 @@ impl Logger {
         F: FnOnce(&mut Logger) -> Result<()>,
     {
         let flush_period = self.periodic_flusher.lock_expect().as_ref().map(|v| v.0);
+        let idle_flush = self.idle_flusher.load().as_ref().map(|v| v.idle());
 
         let mut new_logger = self.clone_lossy();
         modifier(&mut new_logger)?;
 
         let new_logger = Arc::new(new_logger);
-        if let Some(interval) = flush_period {
-            new_logger.set_flush_period(Some(interval));
+        if let Some(period) = flush_period {
+            new_logger.set_flush_period_with(Some(period));
+        }
+        if idle_flush.is_some() {
+            new_logger.set_idle_flush(idle_flush);
         }
 
         Ok(new_logger)
@@ -440,58 +1060,128 @@ impl Logger {
         })
     }
 
-    // This will lose the periodic flush property, if any.
+    // This will lose the periodic and idle flush properties, if any.
     #[must_use]
     fn clone_lossy(&self) -> Self {
         Logger {
             name: self.name.clone(),
             level_filter: Atomic::new(self.level_filter()),
+            module_level_filters: ArcSwapOption::new(self.module_level_filters.load_full()),
             sinks: self.sinks.clone(),
             flush_level_filter: Atomic::new(self.flush_level_filter()),
             periodic_flusher: Mutex::new(None),
+            idle_flusher: ArcSwapOption::empty(),
+            backtrace: ArcSwapOption::new(
+                self.backtrace
+                    .load()
+                    .as_ref()
+                    .map(|backtrace| Arc::new(Backtrace::new(backtrace.capacity))),
+            ),
             error_handler: SpinRwLock::new(*self.error_handler.read()),
+            clock: self.clock.clone(),
+            audit: self.audit,
+            prefix: self.prefix.clone(),
+            filters: self.filters.clone(),
+            sampler: self.sampler.clone(),
+            timings: self
+                .timings
+                .as_ref()
+                .map(|_| Timings::new(self.sinks.len())),
+            seq: AtomicU64::new(0),
+            global_seq: self.global_seq,
+            #[cfg(feature = "regex")]
+            redactor: self.redactor.clone(),
+            opened_sinks: Mutex::new(lifecycle::retain(&self.sinks)),
         }
     }
 
-    fn sink_record(&self, record: &Record) {
-        self.sinks.iter().for_each(|sink| {
+    fn sink_record(&self, record: &Record, mut on_error: impl FnMut(Error)) {
+        self.sinks.iter().enumerate().for_each(|(index, sink)| {
             if sink.should_log(record.level()) {
-                if let Err(err) = sink.log(record) {
-                    self.handle_error(err);
+                let result = match &self.timings {
+                    Some(timings) => timings.log(index, || sink.log(record)),
+                    None => sink.log(record),
+                };
+                if let Err(err) = result {
+                    on_error(err);
                 }
             }
         });
 
         if self.should_flush(record) {
-            self.flush();
+            self.flush_sinks(on_error);
+        } else if let Some(idle_flusher) = self.idle_flusher.load().as_ref() {
+            idle_flusher.on_record();
         }
     }
 
-    fn flush_sinks(&self) {
-        self.sinks.iter().for_each(|sink| {
-            if let Err(err) = sink.flush() {
-                self.handle_error(err);
+    fn flush_sinks(&self, mut on_error: impl FnMut(Error)) {
+        self.sinks.iter().enumerate().for_each(|(index, sink)| {
+            let flush = || {
+                if self.audit {
+                    sink.sync()
+                } else {
+                    sink.flush()
+                }
+            };
+            let result = match &self.timings {
+                Some(timings) => timings.flush(index, flush),
+                None => flush(),
+            };
+            if let Err(err) = result {
+                on_error(err);
             }
         });
     }
 
     fn handle_error(&self, err: Error) {
+        #[cfg(feature = "metrics")]
+        crate::log_metrics::on_sink_error(self.name(), &err);
+
+        let from = || {
+            format!(
+                "Logger ({})",
+                self.name.as_ref().map_or("*no name*", String::as_str)
+            )
+        };
+        if diagnostics::is_enabled() {
+            diagnostics::report_error(&from(), &err);
+        }
+
         if let Some(handler) = self.error_handler.read().as_ref() {
             handler(err)
         } else {
-            crate::default_error_handler(
-                format!(
-                    "Logger ({})",
-                    self.name.as_ref().map_or("*no name*", String::as_str)
-                ),
-                err,
-            );
+            crate::default_error_handler(from(), err);
         }
     }
 
     #[must_use]
     fn should_flush(&self, record: &Record) -> bool {
-        self.flush_level_filter().test(record.level())
+        self.audit || self.flush_level_filter().test(record.level())
+    }
+}
+
+// Resolves when all sinks accepting the level have capacity for a record.
+#[cfg(feature = "log-async")]
+struct SinksCapacity<'a> {
+    sinks: &'a [Arc<dyn Sink>],
+    level: Level,
+}
+
+#[cfg(feature = "log-async")]
+impl Future for SinksCapacity<'_> {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let level = self.level;
+        crate::sink::poll_capacity(self.sinks.iter().filter(|sink| sink.should_log(level)), cx)
+    }
+}
+
+impl Drop for Logger {
+    fn drop(&mut self) {
+        let opened_sinks = std::mem::take(&mut *self.opened_sinks.lock_expect());
+        lifecycle::release(&opened_sinks, |err| self.handle_error(err));
     }
 }
 
@@ -500,8 +1190,9 @@ impl Clone for Logger {
     ///
     /// # Panics
     ///
-    /// Panics if [`Logger::set_flush_period`] is called with `Some` value and
-    /// then clones the `Logger` instead of the `Arc<Logger>`.
+    /// Panics if [`Logger::set_flush_period`] or [`Logger::set_idle_flush`] is
+    /// called with `Some` value and then clones the `Logger` instead of the
+    /// `Arc<Logger>`.
     fn clone(&self) -> Self {
         if self.periodic_flusher.lock_expect().is_some() {
             panic!(
@@ -509,18 +1200,135 @@ impl Clone for Logger {
                  clone a `Arc<Logger>` instead."
             );
         }
+        if self.idle_flusher.load().is_some() {
+            panic!(
+                "you can't clone a `Logger` with an `idle_flush` value, \
+                 clone a `Arc<Logger>` instead."
+            );
+        }
         self.clone_lossy()
     }
 }
 
-#[allow(missing_docs)]
-#[derive(Clone)]
-pub struct LoggerBuilder {
-    name: Option<String>,
-    level_filter: LevelFilter,
-    sinks: Sinks,
-    flush_level_filter: LevelFilter,
-    error_handler: Option<ErrorHandler>,
+/// The schedule of automatic periodic flushing.
+///
+/// By default, a logger flushes at a fixed interval counted from when the
+/// periodic flushing is set. In a fleet of processes started together, they
+/// flush at about the same moments and may hammer a shared storage. Jitter
+/// spreads the flushes over time, and alignment makes the moments predictable.
+///
+/// Times are read from the [clock] of the logger.
+///
+/// See [`Logger::set_flush_period_with`].
+///
+/// [clock]: LoggerBuilder::clock
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub struct FlushPeriod {
+    interval: Duration,
+    jitter: Duration,
+    aligned: bool,
+}
+
+impl FlushPeriod {
+    /// Constructs a `FlushPeriod` flushing every `interval`, without jitter
+    /// and alignment.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `interval` is zero.
+    #[must_use]
+    pub fn new(interval: Duration) -> Self {
+        if interval.is_zero() {
+            panic!("FlushPeriod: the interval cannot be zero")
+        }
+        Self {
+            interval,
+            jitter: Duration::ZERO,
+            aligned: false,
+        }
+    }
+
+    /// Delays each flush by a random duration in `[0, jitter)`.
+    ///
+    /// The default is zero.
+    #[must_use]
+    pub fn jitter(mut self, jitter: Duration) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    /// Flushes at the wall-clock times that are multiples of the interval,
+    /// e.g. at :00 and :30 seconds of each minute for an interval of 30
+    /// seconds.
+    ///
+    /// Times are counted from the Unix epoch in UTC, so intervals that do not
+    /// divide a day evenly are not aligned to midnight. The default is `false`.
+    #[must_use]
+    pub fn aligned(mut self, aligned: bool) -> Self {
+        self.aligned = aligned;
+        self
+    }
+
+    /// Gets the interval.
+    #[must_use]
+    pub fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    #[must_use]
+    pub(crate) fn delay_from(&self, now: SystemTime) -> Duration {
+        let delay = if self.aligned {
+            let since_epoch = now
+                .duration_since(SystemTime::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_nanos();
+            let interval = self.interval.as_nanos();
+            duration_from_nanos(interval - since_epoch % interval)
+        } else {
+            self.interval
+        };
+
+        let jitter = self.jitter.as_nanos();
+        if jitter == 0 {
+            delay
+        } else {
+            delay + duration_from_nanos(crate::utils::random_u64() as u128 % jitter)
+        }
+    }
+}
+
+impl From<Duration> for FlushPeriod {
+    fn from(interval: Duration) -> Self {
+        Self::new(interval)
+    }
+}
+
+fn duration_from_nanos(nanos: u128) -> Duration {
+    const NANOS_PER_SEC: u128 = 1_000_000_000;
+    Duration::new(
+        (nanos / NANOS_PER_SEC) as u64,
+        (nanos % NANOS_PER_SEC) as u32,
+    )
+}
+
+#[allow(missing_docs)]
+#[derive(Clone)]
+pub struct LoggerBuilder {
+    name: Option<String>,
+    level_filter: LevelFilter,
+    module_level_filters: Vec<(String, LevelFilter)>,
+    sinks: Sinks,
+    flush_level_filter: LevelFilter,
+    error_handler: Option<ErrorHandler>,
+    clock: Option<Arc<dyn Clock>>,
+    audit: bool,
+    prefix: Option<String>,
+    filters: Filters,
+    sampler: Option<Sampler>,
+    timing: bool,
+    global_seq: bool,
+    #[cfg(feature = "regex")]
+    redactor: Option<Arc<crate::Redactor>>,
 }
 
 impl LoggerBuilder {
@@ -562,12 +1370,38 @@ impl LoggerBuilder {
         self
     }
 
+    /// Sets the log level filter for a module path prefix.
+    ///
+    /// This parameter is **optional**, and can be specified multiple times.
+    ///
+    /// See [`Logger::set_module_level_filter`] for details.
+    pub fn module_level_filter<S>(&mut self, prefix: S, level_filter: LevelFilter) -> &mut Self
+    where
+        S: Into<String>,
+    {
+        self.module_level_filters
+            .push((prefix.into(), level_filter));
+        self
+    }
+
     /// Add a [`Sink`].
     pub fn sink(&mut self, sink: Arc<dyn Sink>) -> &mut Self {
         self.sinks.push(sink);
         self
     }
 
+    /// Add a [`Sink`] with a level filter overriding its own one in this
+    /// logger.
+    ///
+    /// The sink is wrapped in a [`LevelOverrideSink`], so a single shared
+    /// sink instance can have different effective levels in different
+    /// loggers.
+    ///
+    /// [`LevelOverrideSink`]: crate::sink::LevelOverrideSink
+    pub fn sink_with_level(&mut self, sink: Arc<dyn Sink>, level_filter: LevelFilter) -> &mut Self {
+        self.sink(Arc::new(LevelOverrideSink::new(sink, level_filter)))
+    }
+
     /// Add multiple [`Sink`]s.
     pub fn sinks<I>(&mut self, sinks: I) -> &mut Self
     where
@@ -577,6 +1411,23 @@ impl LoggerBuilder {
         self
     }
 
+    /// Add a [`Sink`] with a name.
+    ///
+    /// The name is used to resolve the level filter of the sink from the
+    /// environment variable, see [`init_env_sink_levels`]. If there is a rule
+    /// for the name, the level filter of the sink is set immediately.
+    ///
+    /// [`init_env_sink_levels`]: crate::init_env_sink_levels
+    pub fn named_sink<S>(&mut self, name: S, sink: Arc<dyn Sink>) -> &mut Self
+    where
+        S: AsRef<str>,
+    {
+        if let Some(level_filter) = env_level::sink_level(name.as_ref()) {
+            sink.set_level_filter(level_filter);
+        }
+        self.sink(sink)
+    }
+
     /// Sets the flush level filter.
     ///
     /// This paramter is **optional**.
@@ -599,11 +1450,221 @@ impl LoggerBuilder {
         self
     }
 
+    /// Sets the clock.
+    ///
+    /// This parameter is **optional**.
+    ///
+    /// If specified, the time of records passed to the logger is replaced with
+    /// the time of the clock, and the periodic flusher (see
+    /// [`Logger::set_flush_period`]) is driven by the clock. See the
+    /// [`clock`] module for more details.
+    ///
+    /// [`clock`]: crate::clock
+    pub fn clock(&mut self, clock: Arc<dyn Clock>) -> &mut Self {
+        self.clock = Some(clock);
+        self
+    }
+
+    /// Enables or disables audit mode.
+    ///
+    /// This parameter is **optional**.
+    ///
+    /// A logger in audit mode never drops records silently, which is required
+    /// for compliance and audit logs:
+    ///
+    /// - Each record is followed by a [`Sink::sync`] of the sinks, so a record
+    ///   has been stored durably once logging it returns. Asynchronous sinks
+    ///   block until their queues have been processed.
+    ///
+    /// - [`Logger::flush`] and [`Logger::try_flush`] call [`Sink::sync`]
+    ///   instead of [`Sink::flush`].
+    ///
+    /// - Errors, including records dropped by an asynchronous sink with
+    ///   [`OverflowPolicy::DropIncoming`], are returned by [`Logger::try_log`]
+    ///   and [`Logger::try_flush`], or passed to the error handler if the
+    ///   records are logged with [`Logger::log`] (e.g. by logging macros).
+    ///
+    /// This costs a synchronization for each record, so audit records should
+    /// be logged to a dedicated logger.
+    ///
+    /// [`OverflowPolicy::DropIncoming`]: crate::sink::OverflowPolicy::DropIncoming
+    pub fn audit(&mut self, audit: bool) -> &mut Self {
+        self.audit = audit;
+        self
+    }
+
+    /// Sets the prefix of payloads.
+    ///
+    /// This parameter is **optional**.
+    ///
+    /// If specified, the prefix is prepended to payloads of records passed to
+    /// the logger before any sink formats them, e.g. a tenant or component tag.
+    /// This tells records of different loggers apart when they share sinks,
+    /// even if the formatters of the sinks do not write logger names. No
+    /// separator is inserted, so it should be included in the prefix.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use spdlog::prelude::*;
+    ///
+    /// # fn main() -> Result<(), spdlog::Error> {
+    /// let logger = Logger::builder().prefix("[tenant-a] ").build()?;
+    ///
+    /// // [2022-11-02 09:23:12.263] [info] [tenant-a] user signed in
+    /// info!(logger: logger, "user signed in");
+    /// # Ok(()) }
+    /// ```
+    pub fn prefix<S>(&mut self, prefix: S) -> &mut Self
+    where
+        S: Into<String>,
+    {
+        self.prefix = Some(prefix.into());
+        self
+    }
+
+    /// Add a [`Filter`] to the filter chain.
+    ///
+    /// This parameter is **optional**, and can be specified multiple times.
+    ///
+    /// Records that passed the level filter of the logger are passed through
+    /// the filter chain, and only kept records are passed into sinks. See
+    /// [`Filter`] for details.
+    pub fn filter<F>(&mut self, filter: F) -> &mut Self
+    where
+        F: Filter + 'static,
+    {
+        self.filters.push(Arc::new(filter));
+        self
+    }
+
+    /// Sets the sampler.
+    ///
+    /// This parameter is **optional**.
+    ///
+    /// If specified, records that passed the level filter of the logger are
+    /// sampled by the sampler, and only kept records are passed into sinks.
+    /// See [`Sampler`] for details.
+    pub fn sampler(&mut self, sampler: Sampler) -> &mut Self {
+        self.sampler = Some(sampler);
+        self
+    }
+
+    /// Enables or disables timing of the pipeline.
+    ///
+    /// If enabled, the logger measures the time each sink spends in formatting
+    /// and writing records, and in flushing. The statistics are retrieved via
+    /// [`Logger::stats`], which helps to find out which sink slows down the
+    /// logging calls.
+    ///
+    /// This costs reading the clock a few times for each record and sink, so
+    /// it is disabled by default.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use spdlog::prelude::*;
+    ///
+    /// # fn main() -> Result<(), spdlog::Error> {
+    /// # let sink = spdlog::default_logger().sinks()[0].clone();
+    /// let logger = Logger::builder().sink(sink).timing(true).build()?;
+    /// info!(logger: logger, "hello");
+    ///
+    /// for (index, stats) in logger.stats().unwrap().sinks().iter().enumerate() {
+    ///     println!(
+    ///         "sink {}: format {:?}, write {:?} on average",
+    ///         index,
+    ///         stats.format().mean(),
+    ///         stats.write().mean()
+    ///     );
+    /// }
+    /// # Ok(()) }
+    /// ```
+    pub fn timing(&mut self, timing: bool) -> &mut Self {
+        self.timing = timing;
+        self
+    }
+
+    /// Enables or disables numbering records across loggers.
+    ///
+    /// If enabled, the records of the logger are numbered with a counter
+    /// shared by all loggers that enable it, see [`Record::global_seq`].
+    /// Otherwise, the number is always 0.
+    ///
+    /// This costs an atomic operation on a counter shared across threads for
+    /// each record, so it is disabled by default.
+    pub fn global_seq(&mut self, global_seq: bool) -> &mut Self {
+        self.global_seq = global_seq;
+        self
+    }
+
+    /// Sets the redactor.
+    ///
+    /// This parameter is **optional**.
+    ///
+    /// If specified, payloads of records passed to the logger are scrubbed by
+    /// the redactor before any sink sees them. See [`Redactor`] for details.
+    ///
+    /// This method is only available if crate feature `regex` is enabled.
+    ///
+    /// [`Redactor`]: crate::Redactor
+    #[cfg(feature = "regex")]
+    pub fn redactor(&mut self, redactor: crate::Redactor) -> &mut Self {
+        self.redactor = Some(Arc::new(redactor));
+        self
+    }
+
     /// Builds a [`Logger`].
+    ///
+    /// [`Sink::open`] is called for each sink not opened by other loggers yet.
+    /// If it fails, the sinks opened before are closed, and the error is
+    /// returned.
     pub fn build(&mut self) -> Result<Logger> {
         self.build_inner(self.preset_level(false))
     }
 
+    /// Builds a [`Logger`] wrapped in an [`Arc`].
+    ///
+    /// This is a shorthand for `Arc::new(builder.build()?)`, since loggers are
+    /// usually shared, e.g. set as the default logger.
+    pub fn build_arc(&mut self) -> Result<Arc<Logger>> {
+        self.build().map(Arc::new)
+    }
+
+    /// Builds a [`Logger`] wrapped in an [`Arc`], and registers it in the
+    /// global registry.
+    ///
+    /// A logger previously registered under the same name is replaced. See
+    /// [`register_logger`] for more details.
+    ///
+    /// # Error
+    ///
+    /// If the logger does not have a name, [`Error::InvalidArgument`] will be
+    /// returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use spdlog::prelude::*;
+    ///
+    /// # fn main() -> Result<(), spdlog::Error> {
+    /// Logger::builder().name("network").build_registered()?;
+    ///
+    /// let logger = spdlog::get_logger("network").unwrap();
+    /// info!(logger: logger, "connected");
+    /// # Ok(()) }
+    /// ```
+    ///
+    /// [`register_logger`]: crate::register_logger
+    pub fn build_registered(&mut self) -> Result<Arc<Logger>> {
+        if self.name.is_none() {
+            return Err(Error::InvalidArgument(InvalidArgumentError::UnnamedLogger));
+        }
+        let logger = self.build_arc()?;
+        crate::register_logger(logger.clone())?;
+        Ok(logger)
+    }
+
     pub(crate) fn build_default(&mut self) -> Result<Logger> {
         self.build_inner(self.preset_level(true))
     }
@@ -621,16 +1682,34 @@ impl LoggerBuilder {
         if let Some(name) = &self.name {
             check_logger_name(name).map_err(InvalidArgumentError::from)?;
         }
+        lifecycle::acquire(&self.sinks)?;
 
         let logger = Logger {
             name: self.name.clone(),
             level_filter: Atomic::new(self.level_filter),
+            module_level_filters: ArcSwapOption::empty(),
             sinks: self.sinks.clone(),
             flush_level_filter: Atomic::new(self.flush_level_filter),
             error_handler: SpinRwLock::new(self.error_handler),
             periodic_flusher: Mutex::new(None),
+            idle_flusher: ArcSwapOption::empty(),
+            backtrace: ArcSwapOption::empty(),
+            clock: self.clock.clone(),
+            audit: self.audit,
+            prefix: self.prefix.clone(),
+            filters: self.filters.clone(),
+            sampler: self.sampler.clone(),
+            timings: self.timing.then(|| Timings::new(self.sinks.len())),
+            seq: AtomicU64::new(0),
+            global_seq: self.global_seq,
+            #[cfg(feature = "regex")]
+            redactor: self.redactor.clone(),
+            opened_sinks: Mutex::new(self.sinks.clone()),
         };
 
+        for (prefix, level_filter) in &self.module_level_filters {
+            logger.set_module_level_filter(prefix.clone(), Some(*level_filter));
+        }
         if let Some(preset_level) = preset_level {
             logger.set_level_filter(preset_level);
         }
@@ -659,10 +1738,14 @@ impl LoggerBuilder {
 
 #[cfg(test)]
 mod tests {
-    use std::{thread, time::Duration};
+    use std::{
+        sync::atomic::AtomicBool,
+        thread,
+        time::{Duration, UNIX_EPOCH},
+    };
 
     use super::*;
-    use crate::{prelude::*, test_utils::*};
+    use crate::{clock::ManualClock, prelude::*, test_utils::*};
 
     #[test]
     fn send_sync() {
@@ -725,6 +1808,379 @@ mod tests {
         assert_eq!(test_sink.flush_count(), 3);
     }
 
+    #[test]
+    fn idle_flush() {
+        let test_sink = Arc::new(TestSink::new());
+        let test_logger = Arc::new(Logger::builder().sink(test_sink.clone()).build().unwrap());
+
+        test_logger.set_idle_flush(Some(Duration::from_millis(200)));
+
+        thread::sleep(Duration::from_millis(400));
+        assert_eq!(test_sink.flush_count(), 0);
+
+        // Keeps the logger busy, no flush until it becomes idle.
+        for _ in 0..6 {
+            info!(logger: test_logger, "busy");
+            thread::sleep(Duration::from_millis(50));
+        }
+        assert_eq!(test_sink.flush_count(), 0);
+
+        thread::sleep(Duration::from_millis(400));
+        assert_eq!(test_sink.flush_count(), 1);
+
+        test_logger.set_idle_flush(None);
+        info!(logger: test_logger, "disabled");
+        thread::sleep(Duration::from_millis(400));
+        assert_eq!(test_sink.flush_count(), 1);
+    }
+
+    #[test]
+    fn audit() {
+        let test_sink = Arc::new(TestSink::new());
+        let test_logger = build_test_logger(|b| b.sink(test_sink.clone()).audit(true));
+        assert!(test_logger.is_audit());
+
+        info!(logger: test_logger, "hello");
+        info!(logger: test_logger, "world");
+        assert_eq!(test_sink.log_count(), 2);
+        assert_eq!(test_sink.flush_count(), 2);
+
+        struct BrokenWriter;
+
+        impl std::io::Write for BrokenWriter {
+            fn write(&mut self, _: &[u8]) -> std::io::Result<usize> {
+                Err(std::io::ErrorKind::BrokenPipe.into())
+            }
+
+            fn flush(&mut self) -> std::io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let broken_sink = Arc::new(
+            crate::sink::WriteSink::builder()
+                .target(BrokenWriter)
+                .build()
+                .unwrap(),
+        );
+        let test_logger = build_test_logger(|b| {
+            b.sink(broken_sink)
+                .audit(true)
+                .error_handler(|err| panic!("unexpected error: {}", err))
+        });
+        assert!(matches!(
+            crate::try_log!(logger: test_logger, Level::Info, "lost"),
+            Err(Error::WriteRecord(_))
+        ));
+        assert!(test_logger.try_flush().is_ok());
+    }
+
+    #[test]
+    fn prefix() {
+        let test_sink = Arc::new(TestSink::new());
+        let tenant_a = build_test_logger(|b| b.sink(test_sink.clone()).prefix("[tenant-a] "));
+        let tenant_b = build_test_logger(|b| b.sink(test_sink.clone()).prefix("[tenant-b] "));
+        assert_eq!(tenant_a.prefix(), Some("[tenant-a] "));
+
+        info!(logger: tenant_a, "hello");
+        info!(logger: tenant_b, "world");
+        assert_eq!(
+            test_sink.payloads(),
+            ["[tenant-a] hello", "[tenant-b] world"]
+        );
+
+        assert_eq!(tenant_a.clone().prefix(), Some("[tenant-a] "));
+    }
+
+    #[test]
+    fn global_seq() {
+        let test_sink = Arc::new(TestSink::new());
+        let numbered = build_test_logger(|b| b.sink(test_sink.clone()).global_seq(true));
+        let unnumbered = build_test_logger(|b| b.sink(test_sink.clone()));
+
+        info!(logger: numbered, "a");
+        info!(logger: unnumbered, "b");
+        info!(logger: numbered, "c");
+
+        let records = test_sink.records();
+        assert_eq!(
+            records[2].global_seq() - records[0].global_seq(),
+            1,
+            "other loggers must not take numbers"
+        );
+        assert_eq!(records[1].global_seq(), 0);
+        assert_eq!(records[1].seq(), 0);
+    }
+
+    #[test]
+    fn backtrace() {
+        let test_sink = Arc::new(TestSink::new());
+        let test_logger = build_test_logger(|b| {
+            b.sink(test_sink.clone())
+                .level_filter(LevelFilter::MoreSevereEqual(Level::Info))
+        });
+        assert!(!test_logger.enabled(Level::Debug));
+
+        test_logger.enable_backtrace(3);
+        assert!(test_logger.enabled(Level::Debug));
+        for i in 0..3 {
+            debug!(logger: test_logger, "debug {}", i);
+        }
+        info!(logger: test_logger, "info");
+        assert_eq!(test_sink.payloads(), ["info"]);
+
+        test_logger.dump_backtrace();
+        assert_eq!(
+            test_sink.payloads()[1..],
+            [
+                "****************** Backtrace Start ******************",
+                "debug 1",
+                "debug 2",
+                "info",
+                "****************** Backtrace End ********************",
+            ]
+        );
+
+        test_sink.reset();
+        test_logger.dump_backtrace();
+        test_logger.disable_backtrace();
+        debug!(logger: test_logger, "debug");
+        test_logger.dump_backtrace();
+        assert_eq!(test_sink.log_count(), 0);
+    }
+
+    #[test]
+    fn enabled() {
+        let info_sink = Arc::new(TestSink::new());
+        info_sink.set_level_filter(LevelFilter::MoreSevereEqual(Level::Info));
+        let warn_sink = Arc::new(TestSink::new());
+        warn_sink.set_level_filter(LevelFilter::MoreSevereEqual(Level::Warn));
+        let test_logger = build_test_logger(|b| {
+            b.sink(info_sink.clone())
+                .sink(warn_sink.clone())
+                .level_filter(LevelFilter::All)
+        });
+
+        assert!(test_logger.should_log(Level::Debug));
+        assert!(!test_logger.enabled(Level::Debug));
+        assert!(test_logger.enabled(Level::Info));
+
+        info_sink.set_level_filter(LevelFilter::Off);
+        assert!(!test_logger.enabled(Level::Info));
+        assert!(test_logger.enabled(Level::Warn));
+
+        let formatted = AtomicBool::new(false);
+        let mark_formatted = || {
+            formatted.store(true, Ordering::Relaxed);
+            ""
+        };
+        info!(logger: test_logger, "{}", mark_formatted());
+        assert!(!formatted.load(Ordering::Relaxed));
+        warn!(logger: test_logger, "{}", mark_formatted());
+        assert!(formatted.load(Ordering::Relaxed));
+
+        test_logger.set_level_filter(LevelFilter::Off);
+        assert!(!test_logger.enabled(Level::Warn));
+    }
+
+    #[test]
+    fn reconfigure_shared_sink() {
+        let sink = Arc::new(StringSink::new());
+        let shared: Arc<dyn Sink> = sink.clone();
+        let test_logger = build_test_logger(|b| b.sink(shared.clone()));
+
+        info!(logger: test_logger, "before");
+        shared.set_level_filter(LevelFilter::MoreSevereEqual(Level::Warn));
+        info!(logger: test_logger, "filtered out by the sink");
+        shared.set_formatter(Box::new(NoModFormatter::new()));
+        warn!(logger: test_logger, "after");
+
+        let output = sink.clone_string();
+        assert!(output.contains("before") && !output.contains("filtered out"));
+        assert!(output.ends_with("after") && !output.ends_with("] after"));
+    }
+
+    #[test]
+    fn flush_period_delay() {
+        let now = UNIX_EPOCH + Duration::from_millis(95_250);
+        let period = FlushPeriod::new(Duration::from_secs(30));
+        assert_eq!(period.delay_from(now), Duration::from_secs(30));
+
+        let aligned = period.aligned(true);
+        assert_eq!(aligned.delay_from(now), Duration::from_millis(24_750));
+        assert_eq!(
+            aligned.delay_from(UNIX_EPOCH + Duration::from_secs(90)),
+            Duration::from_secs(30)
+        );
+
+        let jittered = aligned.jitter(Duration::from_secs(5));
+        for _ in 0..100 {
+            let delay = jittered.delay_from(now);
+            assert!(delay >= Duration::from_millis(24_750));
+            assert!(delay < Duration::from_millis(29_750));
+        }
+    }
+
+    #[test]
+    fn aligned_periodic_flush() {
+        let clock = Arc::new(ManualClock::new(UNIX_EPOCH + Duration::from_secs(3590)));
+        let test_sink = Arc::new(TestSink::new());
+        let test_logger = Arc::new(build_test_logger(|b| {
+            b.sink(test_sink.clone()).clock(clock.clone())
+        }));
+        test_logger.set_flush_period_with(Some(
+            FlushPeriod::new(Duration::from_secs(3600)).aligned(true),
+        ));
+
+        thread::sleep(Duration::from_millis(100));
+        assert_eq!(test_sink.flush_count(), 0);
+
+        clock.advance(Duration::from_secs(10));
+        thread::sleep(Duration::from_millis(100));
+        assert_eq!(test_sink.flush_count(), 1);
+    }
+
+    #[test]
+    fn lifecycle() {
+        let test_sink = Arc::new(TestSink::new());
+        let test_logger = build_test_logger(|b| b.sink(test_sink.clone()));
+        assert_eq!(test_sink.open_count(), 1);
+        assert_eq!(test_sink.close_count(), 0);
+
+        test_logger.close();
+        assert_eq!(test_sink.flush_count(), 1);
+        assert_eq!(test_sink.close_count(), 1);
+
+        test_logger.close();
+        assert_eq!(test_sink.close_count(), 1);
+    }
+
+    #[test]
+    fn lifecycle_shared_sink() {
+        let test_sink = Arc::new(TestSink::new());
+        let logger_1 = build_test_logger(|b| b.sink(test_sink.clone()));
+        let logger_2 = build_test_logger(|b| b.sink(test_sink.clone()));
+        let logger_3 = logger_2.clone();
+        assert_eq!(test_sink.open_count(), 1);
+
+        logger_1.close();
+        assert_eq!(test_sink.close_count(), 0);
+        logger_2.close();
+        assert_eq!(test_sink.close_count(), 0);
+        drop(logger_3);
+        assert_eq!(test_sink.close_count(), 1);
+        drop((logger_1, logger_2));
+        assert_eq!(test_sink.close_count(), 1);
+
+        let logger = build_test_logger(|b| b.sink(test_sink.clone()));
+        assert_eq!(test_sink.open_count(), 2);
+        drop(logger);
+        assert_eq!(test_sink.flush_count(), 2);
+        assert_eq!(test_sink.close_count(), 2);
+    }
+
+    #[test]
+    fn lifecycle_open_failure() {
+        let opened_sink = Arc::new(TestSink::new());
+        let failing_sink = Arc::new(TestSink::new());
+        failing_sink.set_fail_open(true);
+
+        let result = Logger::builder()
+            .sink(opened_sink.clone())
+            .sink(failing_sink.clone())
+            .build();
+        assert!(matches!(result, Err(Error::Connect(_))));
+        assert_eq!(opened_sink.open_count(), 1);
+        assert_eq!(opened_sink.close_count(), 1);
+        assert_eq!(failing_sink.open_count(), 1);
+        assert_eq!(failing_sink.close_count(), 0);
+
+        failing_sink.set_fail_open(false);
+        let logger = build_test_logger(|b| b.sink(failing_sink.clone()));
+        assert_eq!(failing_sink.open_count(), 2);
+        logger.close();
+        assert_eq!(failing_sink.close_count(), 1);
+    }
+
+    #[test]
+    fn build_registered() {
+        let logger = Logger::builder()
+            .name("build-registered-test")
+            .build_registered()
+            .unwrap();
+        assert!(Arc::ptr_eq(
+            &crate::get_logger("build-registered-test").unwrap(),
+            &logger
+        ));
+
+        assert!(matches!(
+            Logger::builder().build_registered(),
+            Err(Error::InvalidArgument(InvalidArgumentError::UnnamedLogger))
+        ));
+    }
+
+    #[test]
+    fn explicit_time() {
+        let clock = Arc::new(ManualClock::new(UNIX_EPOCH + Duration::from_secs(60)));
+        let test_sink = Arc::new(TestSink::new());
+        let test_logger = build_test_logger(|b| b.sink(test_sink.clone()).clock(clock.clone()));
+
+        let received_at = UNIX_EPOCH + Duration::from_secs(1);
+        info!(logger: test_logger, time = received_at, "received");
+        log!(logger: test_logger, time = received_at, code = "E1", Level::Warn, "received");
+        info!(logger: test_logger, "now");
+
+        let records = test_sink.records();
+        assert_eq!(records[0].time(), received_at);
+        assert_eq!(records[1].time(), received_at);
+        assert_eq!(records[1].code(), Some("E1"));
+        assert_eq!(records[2].time(), clock.now());
+    }
+
+    #[test]
+    fn driven_by_clock() {
+        let clock = Arc::new(ManualClock::new(UNIX_EPOCH));
+        let test_sink = Arc::new(TestSink::new());
+        let test_logger = Arc::new(build_test_logger(|b| {
+            b.sink(test_sink.clone()).clock(clock.clone())
+        }));
+
+        info!(logger: test_logger, "");
+        clock.advance(Duration::from_secs(5));
+        info!(logger: test_logger, "");
+        let times = test_sink
+            .records()
+            .iter()
+            .map(|record| record.time())
+            .collect::<Vec<_>>();
+        assert_eq!(times, [UNIX_EPOCH, UNIX_EPOCH + Duration::from_secs(5)]);
+
+        test_logger.set_flush_period(Some(Duration::from_secs(3600)));
+
+        thread::sleep(Duration::from_millis(50));
+        assert_eq!(test_sink.flush_count(), 0);
+
+        let wait_flush_count = |expected| {
+            for _ in 0..1000 {
+                if test_sink.flush_count() == expected {
+                    return;
+                }
+                thread::sleep(Duration::from_millis(1));
+            }
+            assert_eq!(test_sink.flush_count(), expected);
+        };
+
+        clock.advance(Duration::from_secs(3600));
+        wait_flush_count(1);
+
+        clock.advance(Duration::from_secs(1800));
+        thread::sleep(Duration::from_millis(50));
+        assert_eq!(test_sink.flush_count(), 1);
+
+        clock.advance(Duration::from_secs(1800));
+        wait_flush_count(2);
+    }
+
     #[test]
     fn builder_name() {
         Logger::builder().name("hello-world");
@@ -928,4 +2384,38 @@ mod tests {
         assert_eq!(test_sink.1.log_count(), 0);
         assert_eq!(test_sink.1.flush_count(), 1);
     }
+
+    #[test]
+    fn module_level_filters() {
+        let test_sink = Arc::new(TestSink::new());
+        let test = build_test_logger(|b| {
+            b.sink(test_sink.clone())
+                .level_filter(LevelFilter::MoreSevereEqual(Level::Warn))
+                .module_level_filter("app::net", LevelFilter::All)
+                .module_level_filter("app::net::tls", LevelFilter::Off)
+        });
+
+        assert!(test.should_log_in(Level::Trace, Some("app::net")));
+        assert!(test.should_log_in(Level::Trace, Some("app::net::http")));
+        assert!(!test.should_log_in(Level::Error, Some("app::net::tls::handshake")));
+        assert!(!test.should_log_in(Level::Info, Some("app::network")));
+        assert!(!test.should_log_in(Level::Info, None));
+        assert!(test.should_log_in(Level::Warn, Some("app")));
+
+        test.set_module_level_filter("app::net::tls", None);
+        test.set_module_level_filter("app", Some(LevelFilter::MoreSevereEqual(Level::Info)));
+        assert_eq!(
+            test.effective_level_filter(Some("app::net::tls")),
+            LevelFilter::All
+        );
+        assert_eq!(
+            test.effective_level_filter(Some("app::db")),
+            LevelFilter::MoreSevereEqual(Level::Info)
+        );
+
+        test.set_module_level_filter(module_path!(), Some(LevelFilter::All));
+        trace!(logger: test, "trace");
+        assert_eq!(test_sink.payloads(), ["trace"]);
+        assert_eq!(test_sink.records()[0].module_path(), Some(module_path!()));
+    }
 }
@@ -1,6 +1,12 @@
-use std::{thread, time::Duration};
+use std::{
+    thread,
+    time::{Duration, SystemTime},
+};
 
-use crate::sync::*;
+use crate::{clock::Clock, sync::*, thread_options};
+
+// How often a clock not advancing along with real time is polled.
+const CLOCK_POLL_INTERVAL: Duration = Duration::from_millis(1);
 
 pub struct PeriodicWorker {
     thread: Option<thread::JoinHandle<()>>,
@@ -8,29 +14,63 @@ pub struct PeriodicWorker {
 }
 
 impl PeriodicWorker {
-    // Panic if the `interval.is_zero()` is `true`.
+    // `next_delay` returns the delay until the next call of `callback` from the
+    // given current time, it must not be zero. `name` is the default name of the
+    // worker thread.
     #[allow(clippy::mutex_atomic)]
     #[must_use]
-    pub fn new(callback: impl Fn() -> bool + Send + Sync + 'static, interval: Duration) -> Self {
-        if interval.is_zero() {
-            panic!("PeriodicWorker: the interval cannot be zero")
-        }
-
+    pub fn new(
+        name: &str,
+        callback: impl Fn() -> bool + Send + Sync + 'static,
+        next_delay: impl Fn(SystemTime) -> Duration + Send + 'static,
+        clock: Option<Arc<dyn Clock>>,
+    ) -> Self {
         let active = Arc::new((Mutex::new(true), Condvar::new()));
 
+        let thread = match clock.filter(|clock| !clock.is_real_time()) {
+            None => {
+                let active = active.clone();
+                thread_options::spawn_worker(name, move || loop {
+                    let delay = next_delay(SystemTime::now());
+                    let flag = active.0.lock_expect();
+                    let (flag, res) = active
+                        .1
+                        .wait_timeout_while(flag, delay, |flag| *flag)
+                        .unwrap();
+
+                    if !res.timed_out() || !*flag || !callback() {
+                        return;
+                    }
+                })
+            }
+            Some(clock) => {
+                let active = active.clone();
+                let now = clock.now();
+                let mut deadline = now + next_delay(now);
+                thread_options::spawn_worker(name, move || loop {
+                    let flag = active.0.lock_expect();
+                    let (flag, res) = active
+                        .1
+                        .wait_timeout_while(flag, CLOCK_POLL_INTERVAL, |flag| *flag)
+                        .unwrap();
+
+                    if !res.timed_out() || !*flag {
+                        return;
+                    }
+                    if clock.now() >= deadline {
+                        if !callback() {
+                            return;
+                        }
+                        let now = clock.now();
+                        deadline = now + next_delay(now);
+                    }
+                })
+            }
+        };
+
         Self {
-            active: active.clone(),
-            thread: Some(thread::spawn(move || loop {
-                let flag = active.0.lock_expect();
-                let (flag, res) = active
-                    .1
-                    .wait_timeout_while(flag, interval, |flag| *flag)
-                    .unwrap();
-
-                if !res.timed_out() || !*flag || !callback() {
-                    return;
-                }
-            })),
+            thread: Some(thread),
+            active,
         }
     }
 }
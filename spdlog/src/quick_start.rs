@@ -0,0 +1,179 @@
+use std::{panic, sync::Once};
+
+use crate::{
+    sink::{StdStream, StdStreamSink},
+    sync::*,
+    Level, LevelFilter, Logger, Result,
+};
+
+/// Initializes logging for the common case in one call, logging records on
+/// [`Level::Info`] and more severe levels.
+///
+/// This is equivalent to
+/// `init_with(LevelFilter::MoreSevereEqual(Level::Info))`, see [`init_with`]
+/// for details.
+///
+/// # Examples
+///
+/// ```
+/// use spdlog::prelude::*;
+///
+/// # fn main() -> Result<(), spdlog::Error> {
+/// let _guard = spdlog::init()?;
+///
+/// info!("hello, world!");
+/// # Ok(()) }
+/// ```
+pub fn init() -> Result<FlushGuard> {
+    init_with(LevelFilter::MoreSevereEqual(Level::Info))
+}
+
+/// Initializes logging for the common case in one call, logging records
+/// passing the given level filter.
+///
+/// This function:
+///
+///  - sets a new global default logger with the given level filter, writing
+///    records to `stderr` with the default formatter, colored if `stderr` is a
+///    terminal.
+///
+///  - initializes the log crate proxy (if crate feature `log` is enabled) and
+///    sets the max level of `log` crate accordingly, so that records from `log`
+///    crate are also written. If another `log` backend has already been set, it
+///    is kept.
+///
+///  - installs a panic hook (only once for the process) logging panics on
+///    [`Level::Critical`] and flushing the default logger, before calling the
+///    previous hook. Panics are also written to the emergency sink if one is
+///    set with [`set_emergency_sink`].
+///
+/// The returned [`FlushGuard`] flushes the logger when dropped, keep it alive
+/// until the end of `main`.
+///
+/// # Examples
+///
+/// ```
+/// use spdlog::prelude::*;
+///
+/// # fn main() -> Result<(), spdlog::Error> {
+/// let _guard = spdlog::init_with(LevelFilter::All)?;
+///
+/// debug!("debugging is enabled");
+/// # Ok(()) }
+/// ```
+///
+/// [`set_emergency_sink`]: crate::set_emergency_sink
+pub fn init_with(level_filter: LevelFilter) -> Result<FlushGuard> {
+    let sink = StdStreamSink::builder()
+        .std_stream(StdStream::Stderr)
+        .build()?;
+    let logger = Arc::new(
+        Logger::builder()
+            .sink(Arc::new(sink))
+            .level_filter(level_filter)
+            .build_default()?,
+    );
+    crate::set_default_logger(logger.clone());
+
+    #[cfg(feature = "log")]
+    if crate::init_log_crate_proxy().is_ok() {
+        log::set_max_level(log_crate_max_level(level_filter));
+    }
+
+    hook_panic();
+
+    Ok(FlushGuard { logger })
+}
+
+/// A guard flushing the logger set by [`init`] or [`init_with`] when dropped.
+#[must_use = "the logger is flushed when the guard is dropped, bind it to a variable"]
+pub struct FlushGuard {
+    logger: Arc<Logger>,
+}
+
+impl Drop for FlushGuard {
+    fn drop(&mut self) {
+        self.logger.flush();
+    }
+}
+
+#[cfg(feature = "log")]
+#[must_use]
+fn log_crate_max_level(level_filter: LevelFilter) -> log::LevelFilter {
+    [
+        (Level::Trace, log::LevelFilter::Trace),
+        (Level::Debug, log::LevelFilter::Debug),
+        (Level::Info, log::LevelFilter::Info),
+        (Level::Warn, log::LevelFilter::Warn),
+        (Level::Error, log::LevelFilter::Error),
+    ]
+    .into_iter()
+    .find(|(level, _)| level_filter.test(*level))
+    .map_or(log::LevelFilter::Off, |(_, max_level)| max_level)
+}
+
+fn hook_panic() {
+    static HOOKED: Once = Once::new();
+
+    HOOKED.call_once(|| {
+        let previous_hook = panic::take_hook();
+
+        panic::set_hook(Box::new(move |info| {
+            let logger = crate::default_logger();
+            let message = info
+                .payload()
+                .downcast_ref::<&str>()
+                .copied()
+                .or_else(|| info.payload().downcast_ref::<String>().map(String::as_str))
+                .unwrap_or("Box<dyn Any>");
+            #[cfg(any(unix, windows))]
+            if let Some(sink) = crate::crash_handler::emergency_sink() {
+                _ = match info.location() {
+                    Some(location) => sink.write_message(
+                        Level::Critical,
+                        format_args!("panicked at {}: {}", location, message),
+                    ),
+                    None => {
+                        sink.write_message(Level::Critical, format_args!("panicked: {}", message))
+                    }
+                };
+            }
+            match info.location() {
+                Some(location) => crate::critical!(
+                    logger: logger,
+                    "panicked at {}:{}:{}: {}",
+                    location.file(),
+                    location.line(),
+                    location.column(),
+                    message
+                ),
+                None => crate::critical!(logger: logger, "panicked: {}", message),
+            }
+            logger.flush();
+
+            previous_hook(info);
+        }));
+    });
+}
+
+#[cfg(all(test, feature = "log"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn log_crate_max_level_from_filter() {
+        assert_eq!(
+            log_crate_max_level(LevelFilter::MoreSevereEqual(Level::Info)),
+            log::LevelFilter::Info
+        );
+        assert_eq!(
+            log_crate_max_level(LevelFilter::All),
+            log::LevelFilter::Trace
+        );
+        assert_eq!(
+            log_crate_max_level(LevelFilter::Equal(Level::Critical)),
+            log::LevelFilter::Off
+        );
+        assert_eq!(log_crate_max_level(LevelFilter::Off), log::LevelFilter::Off);
+    }
+}
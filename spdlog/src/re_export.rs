@@ -29,3 +29,15 @@
 pub mod log {
     pub use log::{set_max_level, LevelFilter, SetLoggerError};
 }
+
+/// Items from [`tracing-subscriber` crate].
+///
+/// To forward events from `tracing` crate to `spdlog-rs`, register a
+/// [`spdlog::TracingLayer`] to a subscriber.
+///
+/// [`tracing-subscriber` crate]: https://docs.rs/tracing-subscriber
+/// [`spdlog::TracingLayer`]: crate::TracingLayer
+#[cfg(feature = "tracing")]
+pub mod tracing_subscriber {
+    pub use tracing_subscriber::{layer, prelude, registry, Layer, Registry};
+}
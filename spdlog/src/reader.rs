@@ -0,0 +1,842 @@
+//! Provides a reader parsing log files back into records.
+//!
+//! [`LogReader`] parses the text written by formatters of this crate back into
+//! [`RecordOwned`]s, so that tools for filtering, merging and replaying logs
+//! can be written in Rust with the same types used for logging.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use spdlog::{
+//!     prelude::*,
+//!     reader::{LogFormat, LogReader},
+//! };
+//!
+//! # fn main() -> Result<(), spdlog::Error> {
+//! for record in LogReader::open("logs/app.log", LogFormat::Full)? {
+//!     let record = record?;
+//!     if LevelFilter::MoreSevereEqual(Level::Error).test(record.level()) {
+//!         println!("{}", record.payload());
+//!     }
+//! }
+//! # Ok(()) }
+//! ```
+
+use std::{
+    borrow::Cow,
+    fs::File,
+    io::{BufRead, BufReader},
+    path::Path,
+    time::{Duration, SystemTime},
+};
+
+use crate::{
+    formatter::{BytesEncoding, MultiLine, TimeZone},
+    record::RecordFields,
+    source_location::intern,
+    time_backend, Error, Level, RecordOwned, Result, SourceLocation,
+};
+
+/// Represents the format of the log text to parse.
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub enum LogFormat {
+    /// The format written by [`FullFormatter`].
+    ///
+    /// [`FullFormatter`]: crate::formatter::FullFormatter
+    Full,
+    /// The format written by [`JsonFormatter`].
+    ///
+    /// The `fields` object is read into key-value fields in order, including
+    /// the static fields of the formatter.
+    ///
+    /// This variant is only available if crate feature `serde_json` is
+    /// enabled.
+    ///
+    /// [`JsonFormatter`]: crate::formatter::JsonFormatter
+    #[cfg(feature = "serde_json")]
+    Json,
+    /// The format written by [`PatternFormatter`] with the given pattern
+    /// template, in the syntax of [`pattern!`].
+    ///
+    /// Parsing is best-effort. The following placeholders are parsed into the
+    /// properties of records, others only match some text and are discarded:
+    ///
+    /// - `{date}`, `{time}`, `{year}`, `{month}`, `{day}`, `{hour}`,
+    ///   `{minute}`, `{second}`, `{millisecond}`, `{microsecond}`,
+    ///   `{nanosecond}` and `{unix_timestamp}`;
+    /// - `{level}`, `{level_short}`, `{logger}`, `{payload}` and `{code}`;
+    /// - `{module_path}`, `{file}`, `{line}` and `{column}`;
+    /// - `{tid}`, `{thread_name}`, `{seq}` and `{global_seq}`.
+    ///
+    /// A placeholder followed by a literal text matches until the first
+    /// occurrence of the literal, except that `{payload}` matches until the
+    /// last occurrence. So literals should not appear in the values of
+    /// placeholders other than `{payload}`. Records without `{level}` or
+    /// `{level_short}` are parsed on [`Level::Info`].
+    ///
+    /// [`PatternFormatter`]: crate::formatter::PatternFormatter
+    /// [`pattern!`]: crate::formatter::pattern
+    Pattern(String),
+}
+
+/// A reader parsing log text into records.
+///
+/// It is an iterator over the records. Lines that do not begin a record in the
+/// [`Full`] or [`Pattern`] format are treated as continuation lines of a
+/// multi-line payload, and appended to the payload of the previous record.
+/// Lines before the first record and invalid lines in the [`Json`] format
+/// yield [`Error::ParseRecord`].
+///
+/// The time zone in which the times in the text are rendered should be set
+/// with [`LogReader::time_zone`], it is the local time zone by default.
+/// Properties not present in the text, e.g. the thread ID in the [`Full`]
+/// format, are zero or empty. Times not present are the Unix epoch.
+///
+/// [`Full`]: LogFormat::Full
+/// [`Pattern`]: LogFormat::Pattern
+/// [`Json`]: LogFormat::Json
+pub struct LogReader<R> {
+    reader: R,
+    parser: Parser,
+    time_zone: TimeZone,
+    #[cfg_attr(not(feature = "serde_json"), allow(dead_code))]
+    bytes_encoding: BytesEncoding,
+    multi_line: MultiLine,
+    pending: Option<RecordFields>,
+    line: String,
+}
+
+impl<R: BufRead> LogReader<R> {
+    /// Constructs a `LogReader` reading from the given reader.
+    #[must_use]
+    pub fn new(reader: R, format: LogFormat) -> Self {
+        Self {
+            reader,
+            parser: Parser::new(format),
+            time_zone: TimeZone::LOCAL,
+            bytes_encoding: BytesEncoding::Base64,
+            multi_line: MultiLine::Raw,
+            pending: None,
+            line: String::new(),
+        }
+    }
+
+    /// Sets the time zone in which the times in the text are rendered.
+    ///
+    /// The default is [`TimeZone::LOCAL`]. Times in the [`Json`] format are
+    /// timestamps and not affected.
+    ///
+    /// [`Json`]: LogFormat::Json
+    #[must_use]
+    pub fn time_zone(mut self, time_zone: TimeZone) -> Self {
+        self.time_zone = time_zone;
+        self
+    }
+
+    /// Sets how raw bytes attached to records are encoded in the [`Json`]
+    /// format.
+    ///
+    /// The default is [`BytesEncoding::Base64`], the same as the default of
+    /// [`JsonFormatter`].
+    ///
+    /// [`Json`]: LogFormat::Json
+    /// [`JsonFormatter`]: crate::formatter::JsonFormatter
+    #[must_use]
+    pub fn bytes_encoding(mut self, bytes_encoding: BytesEncoding) -> Self {
+        self.bytes_encoding = bytes_encoding;
+        self
+    }
+
+    /// Sets how payloads spanning multiple lines are written in the text, so
+    /// that they are restored to their original contents.
+    ///
+    /// The default is [`MultiLine::Raw`]. With [`MultiLine::Prefix`], lines
+    /// starting with the marker are always taken as continuation lines. It has
+    /// no effect on the [`Json`] format.
+    ///
+    /// [`Json`]: LogFormat::Json
+    #[must_use]
+    pub fn multi_line(mut self, multi_line: MultiLine) -> Self {
+        self.multi_line = multi_line;
+        self
+    }
+
+    fn is_continuation(&self, line: &str) -> bool {
+        match &self.multi_line {
+            MultiLine::Prefix(marker) => {
+                self.pending.is_some() && !marker.is_empty() && line.starts_with(marker.as_ref())
+            }
+            _ => false,
+        }
+    }
+
+    fn finish(&self, mut fields: RecordFields) -> RecordOwned {
+        #[cfg(feature = "serde_json")]
+        if matches!(self.parser, Parser::Json) {
+            return fields.into();
+        }
+        if let Cow::Owned(payload) = self.multi_line.read_payload(&fields.payload) {
+            fields.payload = payload;
+        }
+        fields.into()
+    }
+
+    fn parse_line(&self, line: &str) -> Result<Option<RecordFields>> {
+        match &self.parser {
+            Parser::Full => Ok(parse_full(line, self.time_zone)),
+            #[cfg(feature = "serde_json")]
+            Parser::Json => parse_json(line, self.bytes_encoding).map(Some),
+            Parser::Pattern(tokens) => Ok(parse_pattern(tokens, line, self.time_zone)),
+        }
+    }
+}
+
+impl LogReader<BufReader<File>> {
+    /// Constructs a `LogReader` reading from the file at the given path.
+    ///
+    /// # Error
+    ///
+    /// If an error occurs opening the file, [`Error::OpenFile`] will be
+    /// returned.
+    pub fn open(path: impl AsRef<Path>, format: LogFormat) -> Result<Self> {
+        let file = File::open(path).map_err(Error::OpenFile)?;
+        Ok(Self::new(BufReader::new(file), format))
+    }
+}
+
+impl<R: BufRead> Iterator for LogReader<R> {
+    type Item = Result<RecordOwned>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            self.line.clear();
+            match self.reader.read_line(&mut self.line) {
+                Ok(0) => return self.pending.take().map(|fields| Ok(self.finish(fields))),
+                Ok(_) => {}
+                Err(err) => return Some(Err(Error::ReadRecord(err))),
+            }
+            let line = self.line.trim_end_matches(&['\r', '\n'][..]);
+            #[cfg(feature = "serde_json")]
+            if matches!(self.parser, Parser::Json) && line.trim().is_empty() {
+                continue;
+            }
+
+            let parsed = if self.is_continuation(line) {
+                None
+            } else {
+                match self.parse_line(line) {
+                    Ok(parsed) => parsed,
+                    Err(err) => return Some(Err(err)),
+                }
+            };
+            match (parsed, &mut self.pending) {
+                (Some(fields), _) => {
+                    if let Some(previous) = self.pending.replace(fields) {
+                        return Some(Ok(self.finish(previous)));
+                    }
+                }
+                (None, Some(pending)) => {
+                    pending.payload.push('\n');
+                    pending.payload.push_str(line);
+                }
+                (None, None) => {
+                    if !line.is_empty() {
+                        return Some(Err(Error::ParseRecord(line.to_string())));
+                    }
+                }
+            }
+        }
+    }
+}
+
+enum Parser {
+    Full,
+    #[cfg(feature = "serde_json")]
+    Json,
+    Pattern(Vec<Token>),
+}
+
+impl Parser {
+    #[must_use]
+    fn new(format: LogFormat) -> Self {
+        match format {
+            LogFormat::Full => Parser::Full,
+            #[cfg(feature = "serde_json")]
+            LogFormat::Json => Parser::Json,
+            LogFormat::Pattern(template) => Parser::Pattern(tokenize(&template)),
+        }
+    }
+}
+
+// The date and time of a record, as rendered in its time zone.
+#[derive(Default)]
+struct DateTime {
+    year: Option<i32>,
+    month: Option<u32>,
+    day: Option<u32>,
+    hour: Option<u32>,
+    minute: Option<u32>,
+    second: Option<u32>,
+    nanosecond: Option<u32>,
+    unix_timestamp: Option<i64>,
+}
+
+impl DateTime {
+    #[must_use]
+    fn parse_date(&mut self, date: &str) -> Option<()> {
+        let mut parts = date.split('-');
+        self.year = Some(parse_digits(parts.next()?, 4)? as i32);
+        self.month = Some(parse_digits(parts.next()?, 2)?);
+        self.day = Some(parse_digits(parts.next()?, 2)?);
+        parts.next().is_none().then(|| ())
+    }
+
+    #[must_use]
+    fn parse_time(&mut self, time: &str) -> Option<()> {
+        let mut parts = time.split(':');
+        self.hour = Some(parse_digits(parts.next()?, 2)?);
+        self.minute = Some(parse_digits(parts.next()?, 2)?);
+        self.second = Some(parse_digits(parts.next()?, 2)?);
+        parts.next().is_none().then(|| ())
+    }
+
+    #[must_use]
+    fn is_empty(&self) -> bool {
+        self.year.is_none()
+            && self.hour.is_none()
+            && self.nanosecond.is_none()
+            && self.unix_timestamp.is_none()
+    }
+
+    #[must_use]
+    fn to_time(&self, time_zone: TimeZone) -> SystemTime {
+        let secs = match self.unix_timestamp {
+            Some(timestamp) => time_backend::from_unix_timestamp(timestamp),
+            None => {
+                let local = time_backend::local_timestamp(
+                    self.year.unwrap_or(1970),
+                    self.month.unwrap_or(1),
+                    self.day.unwrap_or(1),
+                    self.hour.unwrap_or(0),
+                    self.minute.unwrap_or(0),
+                    self.second.unwrap_or(0),
+                );
+                time_zone.local_timestamp_to_time(local, 0)
+            }
+        };
+        secs + Duration::from_nanos(self.nanosecond.unwrap_or(0) as u64)
+    }
+}
+
+// Parses an unsigned number with exactly the given number of digits.
+#[must_use]
+fn parse_digits(digits: &str, len: usize) -> Option<u32> {
+    (digits.len() == len && digits.bytes().all(|byte| byte.is_ascii_digit()))
+        .then(|| digits.parse().ok())
+        .flatten()
+}
+
+#[must_use]
+fn parse_level_short(level: &str) -> Option<Level> {
+    Level::iter().find(|candidate| candidate.as_short_str() == level)
+}
+
+// Parses `[2022-11-02 09:23:12.263] [logger] [info] [module, file:line]
+// payload`, where the logger name and the source location are optional.
+#[must_use]
+fn parse_full(line: &str, time_zone: TimeZone) -> Option<RecordFields> {
+    let (date_time, rest) = line.strip_prefix('[')?.split_once("] [")?;
+    let (date, time) = date_time.split_once(' ')?;
+    let (time, millisecond) = time.split_once('.')?;
+    let mut parsed_time = DateTime::default();
+    parsed_time.parse_date(date)?;
+    parsed_time.parse_time(time)?;
+    parsed_time.nanosecond = Some(parse_digits(millisecond, 3)? * 1_000_000);
+
+    let (first, rest) = rest.split_once(']')?;
+    let (logger_name, level, rest) = match first.parse::<Level>() {
+        Ok(level) => (None, level, rest),
+        Err(_) => {
+            let (level, rest) = rest.strip_prefix(" [")?.split_once(']')?;
+            (Some(first), level.parse().ok()?, rest)
+        }
+    };
+
+    let source_location = rest
+        .strip_prefix(" [")
+        .and_then(|rest| rest.split_once("] "))
+        .and_then(|(source, rest)| {
+            let (module_path, file_line) = source.split_once(", ").unwrap_or(("", source));
+            let (file, line) = file_line.rsplit_once(':')?;
+            let line = line.parse().ok()?;
+            Some((
+                SourceLocation::__new(
+                    intern(module_path.to_string()),
+                    intern(file.to_string()),
+                    line,
+                    0,
+                ),
+                rest,
+            ))
+        });
+    let (source_location, payload) = match source_location {
+        Some((source_location, rest)) => (Some(source_location), rest),
+        None => (None, rest.strip_prefix(' ')?),
+    };
+
+    let mut fields = RecordFields::new(level, payload.to_string());
+    fields.logger_name = logger_name.map(str::to_string);
+    fields.source_location = source_location;
+    fields.time = parsed_time.to_time(time_zone);
+    Some(fields)
+}
+
+#[cfg(feature = "serde_json")]
+pub(crate) fn parse_json(line: &str, bytes_encoding: BytesEncoding) -> Result<RecordFields> {
+    #[derive(serde::Deserialize)]
+    struct JsonRecord {
+        level: Level,
+        timestamp: u64,
+        payload: String,
+        logger: Option<String>,
+        code: Option<String>,
+        bytes: Option<String>,
+        #[serde(default)]
+        tid: u64,
+        source: Option<SourceLocation>,
+        fields: Option<JsonFields>,
+    }
+
+    // Key-value fields in the order they are written, which a map would lose.
+    struct JsonFields(Vec<(String, String)>);
+
+    impl<'de> serde::Deserialize<'de> for JsonFields {
+        fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+        where
+            D: serde::Deserializer<'de>,
+        {
+            struct FieldsVisitor;
+
+            impl<'de> serde::de::Visitor<'de> for FieldsVisitor {
+                type Value = JsonFields;
+
+                fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+                    formatter.write_str("an object of string values")
+                }
+
+                fn visit_map<A>(self, mut map: A) -> std::result::Result<Self::Value, A::Error>
+                where
+                    A: serde::de::MapAccess<'de>,
+                {
+                    let mut fields = vec![];
+                    while let Some(field) = map.next_entry()? {
+                        fields.push(field);
+                    }
+                    Ok(JsonFields(fields))
+                }
+            }
+
+            deserializer.deserialize_map(FieldsVisitor)
+        }
+    }
+
+    let parse_err = || Error::ParseRecord(line.to_string());
+
+    let record: JsonRecord = serde_json::from_str(line).map_err(|_| parse_err())?;
+    let mut fields = RecordFields::new(record.level, record.payload);
+    fields.logger_name = record.logger;
+    fields.code = record.code;
+    fields.bytes = match record.bytes {
+        Some(bytes) => Some(bytes_encoding.decode(&bytes).ok_or_else(parse_err)?),
+        None => None,
+    };
+    fields.source_location = record.source;
+    fields.time = SystemTime::UNIX_EPOCH + Duration::from_millis(record.timestamp);
+    fields.tid = record.tid;
+    fields.key_values = record.fields.map_or_else(Vec::new, |fields| fields.0);
+    Ok(fields)
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+enum Token {
+    Literal(String),
+    Field(Field),
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+enum Field {
+    Date,
+    Time,
+    Year,
+    Month,
+    Day,
+    Hour,
+    Minute,
+    Second,
+    Millisecond,
+    Microsecond,
+    Nanosecond,
+    UnixTimestamp,
+    Level,
+    LevelShort,
+    Logger,
+    Payload,
+    Code,
+    ModulePath,
+    File,
+    Line,
+    Column,
+    Tid,
+    ThreadName,
+    Seq,
+    GlobalSeq,
+    Other,
+}
+
+impl Field {
+    #[must_use]
+    fn from_name(name: &str) -> Option<Self> {
+        let field = match name {
+            "date" => Field::Date,
+            "time" => Field::Time,
+            "year" => Field::Year,
+            "month" => Field::Month,
+            "day" => Field::Day,
+            "hour" => Field::Hour,
+            "minute" => Field::Minute,
+            "second" => Field::Second,
+            "millisecond" => Field::Millisecond,
+            "microsecond" => Field::Microsecond,
+            "nanosecond" => Field::Nanosecond,
+            "unix_timestamp" => Field::UnixTimestamp,
+            "level" => Field::Level,
+            "level_short" => Field::LevelShort,
+            "logger" => Field::Logger,
+            "payload" => Field::Payload,
+            "code" => Field::Code,
+            "module_path" => Field::ModulePath,
+            "file" => Field::File,
+            "line" => Field::Line,
+            "column" => Field::Column,
+            "tid" => Field::Tid,
+            "thread_name" => Field::ThreadName,
+            "seq" => Field::Seq,
+            "global_seq" => Field::GlobalSeq,
+            // Line endings are removed before parsing.
+            "eol" => return None,
+            _ => Field::Other,
+        };
+        Some(field)
+    }
+
+    // Gets the length of the text of fields rendered in a fixed length.
+    #[must_use]
+    fn fixed_len(&self) -> Option<usize> {
+        match self {
+            Field::Date => Some(10),
+            Field::Time => Some(8),
+            Field::Year => Some(4),
+            Field::Month | Field::Day | Field::Hour | Field::Minute | Field::Second => Some(2),
+            Field::Millisecond => Some(3),
+            Field::Microsecond => Some(6),
+            Field::Nanosecond => Some(9),
+            Field::LevelShort => Some(1),
+            _ => None,
+        }
+    }
+}
+
+// Splits a pattern template into literals and fields. Style range markers are
+// removed, and unbalanced braces are taken literally.
+#[must_use]
+fn tokenize(template: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut literal = String::new();
+    let mut rest = template;
+
+    while let Some(ch) = rest.chars().next() {
+        let len = if rest.starts_with("{{") {
+            literal.push('{');
+            2
+        } else if rest.starts_with("}}") {
+            literal.push('}');
+            2
+        } else if rest.starts_with("{^") {
+            2
+        } else if ch == '{' {
+            match rest[1..].find('}') {
+                Some(end)
+                    if rest[1..end + 1]
+                        .chars()
+                        .all(|c| c == '_' || c.is_alphanumeric()) =>
+                {
+                    if let Some(field) = Field::from_name(&rest[1..end + 1]) {
+                        if !literal.is_empty() {
+                            tokens.push(Token::Literal(std::mem::take(&mut literal)));
+                        }
+                        tokens.push(Token::Field(field));
+                    }
+                    end + 2
+                }
+                _ => {
+                    literal.push('{');
+                    1
+                }
+            }
+        } else if ch == '}' {
+            // The end of a style range.
+            1
+        } else {
+            literal.push(ch);
+            ch.len_utf8()
+        };
+        rest = &rest[len..];
+    }
+    if !literal.is_empty() {
+        tokens.push(Token::Literal(literal));
+    }
+    tokens
+}
+
+#[must_use]
+fn parse_pattern(tokens: &[Token], line: &str, time_zone: TimeZone) -> Option<RecordFields> {
+    let mut fields = RecordFields::new(Level::Info, String::new());
+    let mut time = DateTime::default();
+    let mut source = (None, None, None, None);
+    let mut rest = line;
+
+    for (index, token) in tokens.iter().enumerate() {
+        let field = match token {
+            Token::Literal(literal) => {
+                rest = rest.strip_prefix(literal.as_str())?;
+                continue;
+            }
+            Token::Field(field) => *field,
+        };
+
+        let len = match (field.fixed_len(), tokens.get(index + 1)) {
+            (Some(len), _) => rest.char_indices().nth(len).map_or(rest.len(), |(i, _)| i),
+            (None, Some(Token::Literal(literal))) if field == Field::Payload => {
+                rest.rfind(literal.as_str())?
+            }
+            (None, Some(Token::Literal(literal))) => rest.find(literal.as_str())?,
+            (None, Some(Token::Field(_))) => rest.find(' ').unwrap_or(rest.len()),
+            (None, None) => rest.len(),
+        };
+        let (value, remaining) = rest.split_at(len);
+        rest = remaining;
+
+        match field {
+            Field::Date => time.parse_date(value)?,
+            Field::Time => time.parse_time(value)?,
+            Field::Year => time.year = Some(parse_digits(value, 4)? as i32),
+            Field::Month => time.month = Some(parse_digits(value, 2)?),
+            Field::Day => time.day = Some(parse_digits(value, 2)?),
+            Field::Hour => time.hour = Some(parse_digits(value, 2)?),
+            Field::Minute => time.minute = Some(parse_digits(value, 2)?),
+            Field::Second => time.second = Some(parse_digits(value, 2)?),
+            Field::Millisecond => time.nanosecond = Some(parse_digits(value, 3)? * 1_000_000),
+            Field::Microsecond => time.nanosecond = Some(parse_digits(value, 6)? * 1_000),
+            Field::Nanosecond => time.nanosecond = Some(parse_digits(value, 9)?),
+            Field::UnixTimestamp => time.unix_timestamp = Some(value.parse().ok()?),
+            Field::Level => fields.level = value.parse().ok()?,
+            Field::LevelShort => fields.level = parse_level_short(value)?,
+            Field::Logger => fields.logger_name = Some(value.to_string()),
+            Field::Payload => fields.payload = value.to_string(),
+            Field::Code => fields.code = Some(value.to_string()),
+            Field::ModulePath => source.0 = Some(value),
+            Field::File => source.1 = Some(value),
+            Field::Line => source.2 = Some(value.parse().ok()?),
+            Field::Column => source.3 = Some(value.parse().ok()?),
+            Field::Tid => fields.tid = value.parse().ok()?,
+            Field::ThreadName => fields.thread_name = Some(value.to_string()),
+            Field::Seq => fields.seq = value.parse().ok()?,
+            Field::GlobalSeq => fields.global_seq = value.parse().ok()?,
+            Field::Other => {}
+        }
+    }
+    if !rest.is_empty() {
+        return None;
+    }
+
+    if let (Some(module_path), Some(file), Some(line)) = (source.0, source.1, source.2) {
+        fields.source_location = Some(SourceLocation::__new(
+            intern(module_path.to_string()),
+            intern(file.to_string()),
+            line,
+            source.3.unwrap_or(0),
+        ));
+    }
+    if !time.is_empty() {
+        fields.time = time.to_time(time_zone);
+    }
+    Some(fields)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+    use crate::{
+        formatter::{Formatter, FormatterContext, FullFormatter},
+        Record, StringBuf,
+    };
+
+    // 2024-01-15 12:34:56.789 UTC
+    fn test_time() -> SystemTime {
+        SystemTime::UNIX_EPOCH + Duration::from_millis(1_705_322_096_789)
+    }
+
+    fn format(formatter: &dyn Formatter, records: &[Record]) -> String {
+        let mut text = String::new();
+        for record in records {
+            let mut dest = StringBuf::new();
+            formatter
+                .format(record, &mut dest, &mut FormatterContext::new())
+                .unwrap();
+            text.push_str(&dest);
+        }
+        text
+    }
+
+    fn test_records() -> Vec<Record<'static>> {
+        let mut records = vec![
+            Record::new(Level::Warn, "disk almost full", None, Some("storage")),
+            Record::new(Level::Error, "multi\nline [payload]", None, None),
+        ];
+        records
+            .iter_mut()
+            .for_each(|record| record.set_time(test_time()));
+        records
+    }
+
+    fn read(text: &str, format: LogFormat) -> Vec<RecordOwned> {
+        LogReader::new(Cursor::new(text), format)
+            .time_zone(TimeZone::UTC)
+            .collect::<Result<_>>()
+            .unwrap()
+    }
+
+    fn assert_records(records: &[RecordOwned]) {
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].level(), Level::Warn);
+        assert_eq!(records[0].logger_name(), Some("storage"));
+        assert_eq!(records[0].payload(), "disk almost full");
+        assert_eq!(records[0].time(), test_time());
+        assert_eq!(records[1].level(), Level::Error);
+        assert_eq!(records[1].logger_name(), None);
+        assert_eq!(records[1].payload(), "multi\nline [payload]");
+    }
+
+    #[test]
+    fn full() {
+        let formatter = FullFormatter::new().time_zone(TimeZone::UTC);
+        let records = read(&format(&formatter, &test_records()), LogFormat::Full);
+        assert_records(&records);
+
+        let parsed = read(
+            "[2024-01-15 12:34:56.789] [info] [app::db, src/db.rs:42] connected\n",
+            LogFormat::Full,
+        );
+        let source_location = parsed[0].source_location().unwrap();
+        assert_eq!(source_location.module_path(), "app::db");
+        assert_eq!(source_location.file(), "src/db.rs");
+        assert_eq!(source_location.line(), 42);
+        assert_eq!(parsed[0].payload(), "connected");
+
+        let parsed = read(
+            "[2024-01-15 12:34:56.789] [info] [main.c:7] from C\n",
+            LogFormat::Full,
+        );
+        let source_location = parsed[0].source_location().unwrap();
+        assert_eq!(source_location.module_path(), "");
+        assert_eq!(source_location.file(), "main.c");
+        assert_eq!(parsed[0].payload(), "from C");
+
+        let mut reader = LogReader::new(Cursor::new("garbage\n"), LogFormat::Full);
+        assert!(matches!(reader.next(), Some(Err(Error::ParseRecord(_)))));
+        assert!(reader.next().is_none());
+    }
+
+    #[test]
+    fn multi_line() {
+        for multi_line in [MultiLine::prefix("  | "), MultiLine::Escape] {
+            let formatter = FullFormatter::new()
+                .time_zone(TimeZone::UTC)
+                .multi_line(multi_line.clone());
+            let mut records = test_records();
+            records.push(Record::new(
+                Level::Info,
+                "header\n[2024-01-15 12:34:56.789] [info] not a record",
+                None,
+                None,
+            ));
+
+            let parsed: Vec<_> =
+                LogReader::new(Cursor::new(format(&formatter, &records)), LogFormat::Full)
+                    .time_zone(TimeZone::UTC)
+                    .multi_line(multi_line)
+                    .collect::<Result<_>>()
+                    .unwrap();
+            assert_records(&parsed[..2]);
+            assert_eq!(parsed[2].payload(), records[2].payload());
+        }
+    }
+
+    #[cfg(feature = "serde_json")]
+    #[test]
+    fn json() {
+        let formatter = crate::formatter::JsonFormatter::new();
+        let mut records = test_records();
+        records[0].set_bytes(Some(b"\x00\xff"));
+        records[1].set_key_values(vec![
+            ("zone".into(), "b".into()),
+            ("attempt".into(), "2".into()),
+        ]);
+        let parsed = read(&format(&formatter, &records), LogFormat::Json);
+        assert_records(&parsed);
+        assert_eq!(parsed[0].bytes(), Some(&b"\x00\xff"[..]));
+        assert_eq!(parsed[0].key_values().count(), 0);
+        assert_eq!(
+            parsed[1].key_values().collect::<Vec<_>>(),
+            [("zone", "b"), ("attempt", "2")]
+        );
+        assert_eq!(parsed[0].tid(), records[0].tid());
+
+        let mut reader = LogReader::new(Cursor::new("{}\n"), LogFormat::Json);
+        assert!(matches!(reader.next(), Some(Err(Error::ParseRecord(_)))));
+    }
+
+    #[test]
+    fn pattern() {
+        let template =
+            "{^[{date} {time}.{millisecond}]} <{logger}> {level_short} {tid}: {payload}{eol}";
+        assert_eq!(
+            tokenize(template)[..3],
+            [
+                Token::Literal("[".to_string()),
+                Token::Field(Field::Date),
+                Token::Literal(" ".to_string()),
+            ]
+        );
+
+        let text = "[2024-01-15 12:34:56.789] <storage> W 7: disk almost full\n\
+                    [2024-01-15 12:34:56.789] <> E 8: multi\n\
+                    line [payload]\n";
+        let records = read(text, LogFormat::Pattern(template.to_string()));
+        assert_eq!(records[1].logger_name(), Some(""));
+        assert_eq!(records[1].tid(), 8);
+        assert_eq!(records[1].payload(), "multi\nline [payload]");
+        assert_eq!(records[0].logger_name(), Some("storage"));
+        assert_eq!(records[0].time(), test_time());
+
+        let records = read(
+            "{escaped} ts=1705322096 info\n",
+            LogFormat::Pattern("{{escaped}} ts={unix_timestamp} {level}".to_string()),
+        );
+        assert_eq!(records[0].level(), Level::Info);
+        assert_eq!(records[0].time(), test_time() - Duration::from_millis(789));
+    }
+}
@@ -0,0 +1,546 @@
+//! Provides a receiver of records sent over the network by other processes.
+//!
+//! A [`Receiver`] listens on a TCP, UDP or Unix domain socket, decodes records
+//! sent by other processes and logs them into a local [`Logger`], so that an
+//! aggregator process collecting logs from multiple machines can be built
+//! entirely on `spdlog-rs`.
+//!
+//! # Wire format
+//!
+//! Each record is encoded as a single-line JSON object in the schema of
+//! [`JsonFormatter`], which is what [`TcpSink`] and [`UdpSink`] send with a
+//! `JsonFormatter`. Objects in the serde schema of [`RecordOwned`] are accepted
+//! as well.
+//!
+//! On stream sockets (TCP and Unix stream sockets), records are delimited by
+//! line feeds `\n`. On datagram sockets (UDP and Unix datagram sockets), each
+//! datagram contains exactly one record, optionally followed by a line feed.
+//!
+//! # Examples
+//!
+//! Sending records from one process:
+//!
+//! ```no_run
+//! use std::sync::Arc;
+//!
+//! use spdlog::{formatter::JsonFormatter, prelude::*, sink::TcpSink};
+//!
+//! # fn main() -> Result<(), spdlog::Error> {
+//! let sink = TcpSink::builder()
+//!     .address("aggregator:9514")
+//!     .formatter(Box::new(JsonFormatter::new()))
+//!     .build()?;
+//! let logger = Logger::builder().sink(Arc::new(sink)).build()?;
+//! # Ok(()) }
+//! ```
+//!
+//! and receiving them in another one:
+//!
+//! ```no_run
+//! use std::{net::TcpListener, thread};
+//!
+//! use spdlog::receiver::Receiver;
+//!
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let receiver = Receiver::builder()
+//!     .logger(spdlog::default_logger())
+//!     .build()?;
+//!
+//! let listener = TcpListener::bind("0.0.0.0:9514")?;
+//! thread::spawn(move || receiver.serve_tcp(listener));
+//! # Ok(()) }
+//! ```
+//!
+//! This module is only available if crate feature `receiver` is enabled.
+//!
+//! [`JsonFormatter`]: crate::formatter::JsonFormatter
+//! [`TcpSink`]: crate::sink::TcpSink
+//! [`UdpSink`]: crate::sink::UdpSink
+
+#[cfg(unix)]
+use std::os::unix::net::{UnixDatagram, UnixListener};
+use std::{
+    convert::Infallible,
+    io::{self, BufRead, BufReader, Read},
+    net::{TcpListener, UdpSocket},
+    thread,
+};
+
+use crate::{
+    formatter::BytesEncoding, reader, sync::*, Error, ErrorHandler, Logger, RecordOwned, Result,
+};
+
+// The maximum size of a UDP datagram over IPv4.
+const MAX_DATAGRAM_LEN: usize = 65_507;
+
+const DEFAULT_MAX_RECORD_LEN: usize = 1024 * 1024;
+
+const DEFAULT_MAX_CONNECTIONS: usize = 64;
+
+/// Receives records sent over the network and logs them into a local logger.
+///
+/// Received records keep their original logger name, level, time and the
+/// other properties, except that sequence numbers are assigned by the local
+/// logger. The level filter and flushing policies of the local logger apply
+/// as usual.
+///
+/// Errors occurred in receiving or decoding records are reported to the error
+/// handler, and the receiver continues with the next record or connection.
+///
+/// Since the receiver is usually exposed to the network, the length of records
+/// on stream sockets and the number of connections served at the same time are
+/// limited, see [`ReceiverBuilder::max_record_len`] and
+/// [`ReceiverBuilder::max_connections`].
+///
+/// Cloning a `Receiver` is cheap, clones share the same logger.
+///
+/// See the [module level documentation] for the wire format.
+///
+/// [module level documentation]: crate::receiver
+#[derive(Clone)]
+pub struct Receiver {
+    logger: Arc<Logger>,
+    bytes_encoding: BytesEncoding,
+    max_record_len: usize,
+    max_connections: usize,
+    error_handler: Option<ErrorHandler>,
+}
+
+impl Receiver {
+    /// Gets a builder of `Receiver` with default parameters:
+    ///
+    /// | Parameter         | Default Value           |
+    /// |-------------------|-------------------------|
+    /// | [logger]          | *must be specified*     |
+    /// | [bytes_encoding]  | `Base64`                |
+    /// | [max_record_len]  | `1048576` (1 MiB)       |
+    /// | [max_connections] | `64`                    |
+    /// | [error_handler]   | [default error handler] |
+    ///
+    /// [logger]: ReceiverBuilder::logger
+    /// [bytes_encoding]: ReceiverBuilder::bytes_encoding
+    /// [max_record_len]: ReceiverBuilder::max_record_len
+    /// [max_connections]: ReceiverBuilder::max_connections
+    /// [error_handler]: ReceiverBuilder::error_handler
+    /// [default error handler]: ../error/index.html#default-error-handler
+    #[must_use]
+    pub fn builder() -> ReceiverBuilder<()> {
+        ReceiverBuilder {
+            logger: (),
+            bytes_encoding: BytesEncoding::Base64,
+            max_record_len: DEFAULT_MAX_RECORD_LEN,
+            max_connections: DEFAULT_MAX_CONNECTIONS,
+            error_handler: None,
+        }
+    }
+
+    /// Gets the logger that received records are logged into.
+    #[must_use]
+    pub fn logger(&self) -> &Arc<Logger> {
+        &self.logger
+    }
+
+    /// Decodes a record and logs it into the logger.
+    ///
+    /// This can be used to receive records from a custom transport. A trailing
+    /// line feed is ignored.
+    ///
+    /// # Error
+    ///
+    /// If the data is not a valid record in either schema, the error of
+    /// decoding it in the schema of [`RecordOwned`] will be returned as
+    /// [`Error::DecodeRecord`].
+    pub fn dispatch(&self, data: &[u8]) -> Result<()> {
+        let data = data.strip_suffix(b"\n").unwrap_or(data);
+        let data = data.strip_suffix(b"\r").unwrap_or(data);
+        let fields = std::str::from_utf8(data)
+            .ok()
+            .and_then(|line| reader::parse_json(line, self.bytes_encoding).ok());
+        let record: RecordOwned = match fields {
+            Some(fields) => fields.into(),
+            None => serde_json::from_slice(data).map_err(Error::DecodeRecord)?,
+        };
+        self.logger.log(&record.as_ref());
+        Ok(())
+    }
+
+    /// Accepts connections from the TCP listener, and receives records from
+    /// them.
+    ///
+    /// Each connection is served on a new thread, connections exceeding
+    /// [`ReceiverBuilder::max_connections`] are closed immediately. This
+    /// function blocks the current thread and never returns.
+    pub fn serve_tcp(&self, listener: TcpListener) -> ! {
+        self.serve_listener(|| listener.accept().map(|(stream, _)| stream))
+    }
+
+    /// Receives records from the UDP socket.
+    ///
+    /// This function blocks the current thread and never returns.
+    pub fn serve_udp(&self, socket: UdpSocket) -> ! {
+        self.serve_datagrams(|buf| socket.recv(buf))
+    }
+
+    /// Accepts connections from the Unix domain socket listener, and receives
+    /// records from them.
+    ///
+    /// Each connection is served on a new thread, connections exceeding
+    /// [`ReceiverBuilder::max_connections`] are closed immediately. This
+    /// function blocks the current thread and never returns.
+    ///
+    /// This function is only available on Unix.
+    #[cfg(unix)]
+    pub fn serve_unix(&self, listener: UnixListener) -> ! {
+        self.serve_listener(|| listener.accept().map(|(stream, _)| stream))
+    }
+
+    /// Receives records from the Unix domain datagram socket.
+    ///
+    /// This function blocks the current thread and never returns.
+    ///
+    /// This function is only available on Unix.
+    #[cfg(unix)]
+    pub fn serve_unix_datagram(&self, socket: UnixDatagram) -> ! {
+        self.serve_datagrams(|buf| socket.recv(buf))
+    }
+
+    fn serve_listener<S>(&self, accept: impl Fn() -> io::Result<S>) -> !
+    where
+        S: Read + Send + 'static,
+    {
+        let connections = Arc::new(AtomicUsize::new(0));
+        loop {
+            let stream = match accept() {
+                Ok(stream) => stream,
+                Err(err) => {
+                    self.handle_error(Error::ReceiveRecord(err));
+                    continue;
+                }
+            };
+            if connections.fetch_add(1, Ordering::Relaxed) >= self.max_connections {
+                connections.fetch_sub(1, Ordering::Relaxed);
+                self.handle_error(Error::ReceiveRecord(io::Error::new(
+                    io::ErrorKind::ConnectionRefused,
+                    "too many connections",
+                )));
+                continue;
+            }
+
+            let receiver = self.clone();
+            let connections = connections.clone();
+            thread::spawn(move || {
+                receiver.serve_stream(stream);
+                connections.fetch_sub(1, Ordering::Relaxed);
+            });
+        }
+    }
+
+    fn serve_stream(&self, stream: impl Read) {
+        let mut reader = BufReader::new(stream);
+        let mut line = Vec::new();
+        loop {
+            line.clear();
+            // Read one more byte to tell whether the limit is exceeded.
+            let limit = self.max_record_len as u64 + 1;
+            match reader.by_ref().take(limit).read_until(b'\n', &mut line) {
+                Ok(0) => return,
+                Ok(_) if line.len() > self.max_record_len => {
+                    // The rest of the record cannot be skipped without reading it, close
+                    // the connection.
+                    self.handle_error(Error::ReceiveRecord(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        "record exceeds the maximum length",
+                    )));
+                    return;
+                }
+                Ok(_) => {
+                    if line.iter().all(u8::is_ascii_whitespace) {
+                        continue;
+                    }
+                    if let Err(err) = self.dispatch(&line) {
+                        self.handle_error(err);
+                    }
+                }
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => {}
+                Err(err) => {
+                    self.handle_error(Error::ReceiveRecord(err));
+                    return;
+                }
+            }
+        }
+    }
+
+    fn serve_datagrams(&self, recv: impl Fn(&mut [u8]) -> io::Result<usize>) -> ! {
+        let mut buf = vec![0; MAX_DATAGRAM_LEN];
+        loop {
+            let result = recv(&mut buf)
+                .map_err(Error::ReceiveRecord)
+                .and_then(|len| self.dispatch(&buf[..len]));
+            if let Err(err) = result {
+                self.handle_error(err);
+            }
+        }
+    }
+
+    fn handle_error(&self, err: Error) {
+        match self.error_handler {
+            Some(handler) => handler(err),
+            None => crate::default_error_handler("Receiver", err),
+        }
+    }
+}
+
+/// #
+#[doc = include_str!("./include/doc/generic-builder-note.md")]
+pub struct ReceiverBuilder<ArgLogger> {
+    logger: ArgLogger,
+    bytes_encoding: BytesEncoding,
+    max_record_len: usize,
+    max_connections: usize,
+    error_handler: Option<ErrorHandler>,
+}
+
+impl<ArgLogger> ReceiverBuilder<ArgLogger> {
+    /// Specifies the logger that received records are logged into.
+    ///
+    /// This parameter is **required**.
+    #[must_use]
+    pub fn logger(self, logger: Arc<Logger>) -> ReceiverBuilder<Arc<Logger>> {
+        ReceiverBuilder {
+            logger,
+            bytes_encoding: self.bytes_encoding,
+            max_record_len: self.max_record_len,
+            max_connections: self.max_connections,
+            error_handler: self.error_handler,
+        }
+    }
+
+    /// Specifies how raw bytes of records in the schema of [`JsonFormatter`]
+    /// are encoded, it should be the same as [`JsonFormatter::bytes_encoding`]
+    /// of the senders.
+    ///
+    /// This parameter is **optional**.
+    ///
+    /// [`JsonFormatter`]: crate::formatter::JsonFormatter
+    /// [`JsonFormatter::bytes_encoding`]: crate::formatter::JsonFormatter::bytes_encoding
+    #[must_use]
+    pub fn bytes_encoding(mut self, bytes_encoding: BytesEncoding) -> Self {
+        self.bytes_encoding = bytes_encoding;
+        self
+    }
+
+    /// Specifies the maximum length of a record on stream sockets in bytes,
+    /// including the line feed.
+    ///
+    /// This parameter is **optional**.
+    ///
+    /// A connection sending a longer record is closed, since the rest of the
+    /// record would have to be read to find the next one.
+    #[must_use]
+    pub fn max_record_len(mut self, max_record_len: usize) -> Self {
+        self.max_record_len = max_record_len;
+        self
+    }
+
+    /// Specifies the maximum number of connections served at the same time by
+    /// each call to [`Receiver::serve_tcp`] or [`Receiver::serve_unix`].
+    ///
+    /// This parameter is **optional**.
+    ///
+    /// Connections beyond the limit are closed immediately, and reported to the
+    /// error handler.
+    #[must_use]
+    pub fn max_connections(mut self, max_connections: usize) -> Self {
+        self.max_connections = max_connections;
+        self
+    }
+
+    /// Specifies an error handler.
+    ///
+    /// This parameter is **optional**.
+    #[must_use]
+    pub fn error_handler(mut self, handler: ErrorHandler) -> Self {
+        self.error_handler = Some(handler);
+        self
+    }
+}
+
+impl ReceiverBuilder<()> {
+    #[doc(hidden)]
+    #[deprecated(note = "\n\n\
+        builder compile-time error:\n\
+        - missing required parameter `logger`\n\n\
+    ")]
+    pub fn build(self, _: Infallible) {}
+}
+
+impl ReceiverBuilder<Arc<Logger>> {
+    /// Builds a [`Receiver`].
+    pub fn build(self) -> Result<Receiver> {
+        Ok(Receiver {
+            logger: self.logger,
+            bytes_encoding: self.bytes_encoding,
+            max_record_len: self.max_record_len,
+            max_connections: self.max_connections,
+            error_handler: self.error_handler,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io::Write,
+        net::TcpStream,
+        time::{Duration, Instant},
+    };
+
+    use super::*;
+    use crate::{formatter::JsonFormatter, prelude::*, sink::TcpSink, test_utils::*, Record};
+
+    fn encode(logger_name: &str, payload: &str) -> String {
+        let record = Record::new(Level::Warn, payload, None, Some(logger_name));
+        serde_json::to_string(&record).unwrap() + "\n"
+    }
+
+    fn wait_for_records(sink: &TestSink, count: usize) {
+        let begin = Instant::now();
+        while sink.log_count() < count {
+            assert!(begin.elapsed() < Duration::from_secs(10), "timed out");
+            thread::sleep(Duration::from_millis(10));
+        }
+    }
+
+    fn receiver() -> (Receiver, Arc<TestSink>) {
+        receiver_with(|b| b)
+    }
+
+    fn receiver_with(
+        f: impl FnOnce(ReceiverBuilder<Arc<Logger>>) -> ReceiverBuilder<Arc<Logger>>,
+    ) -> (Receiver, Arc<TestSink>) {
+        let sink = Arc::new(TestSink::new());
+        let logger = Arc::new(build_test_logger(|b| b.sink(sink.clone())));
+        (f(Receiver::builder().logger(logger)).build().unwrap(), sink)
+    }
+
+    #[test]
+    fn dispatch() {
+        let (receiver, sink) = receiver();
+
+        receiver
+            .dispatch(encode("remote", "hello").as_bytes())
+            .unwrap();
+        assert!(matches!(
+            receiver.dispatch(b"{\"not\":\"a record\"}"),
+            Err(Error::DecodeRecord(_))
+        ));
+
+        let records = sink.records();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].logger_name(), Some("remote"));
+        assert_eq!(records[0].level(), Level::Warn);
+        assert_eq!(records[0].payload(), "hello");
+    }
+
+    #[test]
+    fn serve_tcp() {
+        let (receiver, sink) = receiver();
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        thread::spawn(move || receiver.serve_tcp(listener));
+
+        let mut stream = TcpStream::connect(addr).unwrap();
+        stream
+            .write_all((encode("a", "1") + "\n" + &encode("b", "2")).as_bytes())
+            .unwrap();
+        wait_for_records(&sink, 2);
+        assert_eq!(sink.payloads(), ["1", "2"]);
+    }
+
+    #[test]
+    fn tcp_sink_to_receiver() {
+        let (receiver, sink) = receiver();
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        thread::spawn(move || receiver.serve_tcp(listener));
+
+        let tcp_sink = Arc::new(
+            TcpSink::builder()
+                .address(addr.to_string())
+                .formatter(Box::new(JsonFormatter::new()))
+                .build()
+                .unwrap(),
+        );
+        let sender = build_test_logger(|b| b.sink(tcp_sink).name("remote"));
+        let mut record = Record::new(Level::Error, "multi\nline", None, Some("remote"));
+        record.set_bytes(Some(b"\x00\xff"));
+        sender.log(&record);
+        warn!(logger: sender, "hello");
+        sender.flush();
+
+        wait_for_records(&sink, 2);
+        let records = sink.records();
+        assert_eq!(records[0].logger_name(), Some("remote"));
+        assert_eq!(records[0].level(), Level::Error);
+        assert_eq!(records[0].payload(), "multi\nline");
+        assert_eq!(records[0].bytes(), Some(&b"\x00\xff"[..]));
+        assert_eq!(records[0].tid(), record.tid());
+        assert_eq!(records[1].level(), Level::Warn);
+        assert_eq!(records[1].payload(), "hello");
+    }
+
+    #[test]
+    fn limits() {
+        static ERRORS: AtomicUsize = AtomicUsize::new(0);
+
+        let (receiver, sink) = receiver_with(|b| {
+            b.max_record_len(256)
+                .max_connections(1)
+                .error_handler(|_| _ = ERRORS.fetch_add(1, Ordering::SeqCst))
+        });
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        thread::spawn(move || receiver.serve_tcp(listener));
+
+        let mut first = TcpStream::connect(addr).unwrap();
+        first.write_all(encode("a", "1").as_bytes()).unwrap();
+        wait_for_records(&sink, 1);
+
+        // The second connection is closed since the first one is still open.
+        let mut second = TcpStream::connect(addr).unwrap();
+        let mut buf = [0; 1];
+        assert_eq!(second.read(&mut buf).unwrap_or(0), 0);
+        assert_eq!(ERRORS.load(Ordering::SeqCst), 1);
+
+        // A record exceeding the maximum length closes the connection.
+        first.write_all(&[b'x'; 512]).unwrap();
+        assert_eq!(first.read(&mut buf).unwrap_or(0), 0);
+        assert_eq!(ERRORS.load(Ordering::SeqCst), 2);
+
+        // The slot of the closed connection can be used again.
+        let begin = Instant::now();
+        loop {
+            let mut third = TcpStream::connect(addr).unwrap();
+            _ = third.write_all(encode("c", "3").as_bytes());
+            thread::sleep(Duration::from_millis(50));
+            if sink.log_count() == 2 {
+                break;
+            }
+            assert!(begin.elapsed() < Duration::from_secs(10), "timed out");
+        }
+        assert_eq!(sink.payloads(), ["1", "3"]);
+    }
+
+    #[test]
+    fn serve_udp() {
+        let (receiver, sink) = receiver();
+        let socket = UdpSocket::bind("127.0.0.1:0").unwrap();
+        let addr = socket.local_addr().unwrap();
+        thread::spawn(move || receiver.serve_udp(socket));
+
+        let client = UdpSocket::bind("127.0.0.1:0").unwrap();
+        client.send_to(encode("a", "1").as_bytes(), addr).unwrap();
+        wait_for_records(&sink, 1);
+        assert_eq!(sink.payloads(), ["1"]);
+    }
+}
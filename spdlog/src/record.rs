@@ -1,10 +1,11 @@
 use std::{
     borrow::{Borrow, Cow},
     cell::RefCell,
+    ops::Deref,
     time::SystemTime,
 };
 
-use crate::{Level, SourceLocation};
+use crate::{sync::*, Level, SourceLocation};
 
 /// Represents a log record.
 ///
@@ -24,15 +25,46 @@ use crate::{Level, SourceLocation};
 pub struct Record<'a> {
     logger_name: Option<Cow<'a, str>>,
     payload: Cow<'a, str>,
+    code: Option<Cow<'a, str>>,
+    bytes: Option<Cow<'a, [u8]>>,
+    destination: Option<Cow<'a, str>>,
+    key_values: KeyValues<'a>,
     inner: Cow<'a, RecordInner>,
 }
 
+// Like `Cow<'a, [(Cow<'a, str>, Cow<'a, str>)]>`, but covariant over `'a` as
+// `Record` is.
+#[derive(Clone, Debug)]
+enum KeyValues<'a> {
+    Borrowed(&'a [(Cow<'a, str>, Cow<'a, str>)]),
+    Owned(Vec<(Cow<'a, str>, Cow<'a, str>)>),
+}
+
+impl<'a> Deref for KeyValues<'a> {
+    type Target = [(Cow<'a, str>, Cow<'a, str>)];
+
+    fn deref(&self) -> &Self::Target {
+        match self {
+            Self::Borrowed(key_values) => key_values,
+            Self::Owned(key_values) => key_values,
+        }
+    }
+}
+
 #[derive(Clone, Debug)]
 struct RecordInner {
     level: Level,
     source_location: Option<SourceLocation>,
+    // Taken by logging macros regardless of whether source locations are.
+    module_path: Option<&'static str>,
     time: SystemTime,
+    // Whether the time is supplied explicitly, rather than taken when the record
+    // was created. An explicit time is not replaced by the clock of loggers.
+    explicit_time: bool,
     tid: u64,
+    thread_name: Option<Arc<str>>,
+    seq: u64,
+    global_seq: u64,
 }
 
 impl<'a> Record<'a> {
@@ -46,11 +78,20 @@ impl<'a> Record<'a> {
         Record {
             logger_name: logger_name.map(Cow::Borrowed),
             payload: payload.into(),
+            code: None,
+            bytes: None,
+            destination: None,
+            key_values: KeyValues::Borrowed(&[]),
             inner: Cow::Owned(RecordInner {
                 level,
                 source_location: srcloc,
+                module_path: None,
                 time: SystemTime::now(),
+                explicit_time: false,
                 tid: get_current_tid(),
+                thread_name: get_current_thread_name(),
+                seq: 0,
+                global_seq: 0,
             }),
         }
     }
@@ -61,6 +102,14 @@ impl<'a> Record<'a> {
         RecordOwned {
             logger_name: self.logger_name.clone().map(|n| n.into_owned()),
             payload: self.payload.to_string(),
+            code: self.code.clone().map(|c| c.into_owned()),
+            bytes: self.bytes.clone().map(|b| b.into_owned()),
+            destination: self.destination.clone().map(|d| d.into_owned()),
+            key_values: self
+                .key_values
+                .iter()
+                .map(|(k, v)| (Cow::Owned(k.to_string()), Cow::Owned(v.to_string())))
+                .collect(),
             inner: self.inner.clone().into_owned(),
         }
     }
@@ -83,13 +132,119 @@ impl<'a> Record<'a> {
         self.payload.borrow()
     }
 
+    /// Whether the payload spans multiple lines, i.e. contains line feeds `\n`.
+    ///
+    /// See [`MultiLine`] for how formatters write such payloads.
+    ///
+    /// [`MultiLine`]: crate::formatter::MultiLine
+    #[must_use]
+    pub fn is_multi_line(&self) -> bool {
+        self.payload().contains('\n')
+    }
+
+    /// Gets the error code.
+    ///
+    /// An error code is a stable identifier of the error or category of the
+    /// record (e.g. `E1042`), so alerts can key off it rather than the payload.
+    /// It can be set by log macros, see [`log!`] for the syntax.
+    ///
+    /// [`log!`]: crate::log
+    #[must_use]
+    pub fn code(&self) -> Option<&str> {
+        self.code.as_deref()
+    }
+
+    /// Gets the raw bytes attached to the record.
+    ///
+    /// Raw bytes carry data which is not valid UTF-8 (e.g. wire frames)
+    /// alongside the payload. They can be attached by log macros, see [`log!`]
+    /// for the syntax.
+    ///
+    /// Text formatters render them as configured by a [`BytesEncoding`],
+    /// while sinks writing binary data can access them untouched via this
+    /// method.
+    ///
+    /// [`log!`]: crate::log
+    /// [`BytesEncoding`]: crate::formatter::BytesEncoding
+    #[must_use]
+    pub fn bytes(&self) -> Option<&[u8]> {
+        self.bytes.as_deref()
+    }
+
+    /// Gets the name of the destination the record is intended for.
+    ///
+    /// A destination hint tags a record for a specific named destination (e.g.
+    /// `audit`), so that sinks such as [`RouterSink`] can route it apart from
+    /// ordinary records, e.g. to an audit trail or a security log. It can be
+    /// set by log macros with the `sink` parameter, see [`log!`] for the
+    /// syntax.
+    ///
+    /// Sinks that do not route records ignore the hint.
+    ///
+    /// [`RouterSink`]: crate::sink::RouterSink
+    /// [`log!`]: crate::log
+    #[must_use]
+    pub fn destination(&self) -> Option<&str> {
+        self.destination.as_deref()
+    }
+
+    /// Gets an iterator over the structured key-value fields of the record, in
+    /// the order they are specified.
+    ///
+    /// Key-value fields carry data as separate fields besides the payload, so
+    /// that they can be indexed and queried after the records are collected.
+    /// They can be attached by log macros, see [`log!`] for the syntax. Values
+    /// are rendered with [`Display`] when the record is created.
+    ///
+    /// [`FullFormatter`], pattern `{fields}` of [`PatternFormatter`] and
+    /// [`JsonFormatter`] render them along with the static fields of the
+    /// formatter.
+    ///
+    /// [`log!`]: crate::log
+    /// [`Display`]: std::fmt::Display
+    /// [`FullFormatter`]: crate::formatter::FullFormatter
+    /// [`PatternFormatter`]: crate::formatter::PatternFormatter
+    /// [`JsonFormatter`]: crate::formatter::JsonFormatter
+    pub fn key_values(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.key_values
+            .iter()
+            .map(|(k, v)| (k.as_ref(), v.as_ref()))
+    }
+
+    /// Gets the value of the key-value field with the given key.
+    ///
+    /// If the key is specified multiple times, the first value is returned.
+    #[must_use]
+    pub fn key_value(&self, key: &str) -> Option<&str> {
+        self.key_values().find(|(k, _)| *k == key).map(|(_, v)| v)
+    }
+
     /// Gets the source location.
     #[must_use]
     pub fn source_location(&self) -> Option<&SourceLocation> {
         self.inner.source_location.as_ref()
     }
 
+    /// Gets the module path of the code that created the record.
+    ///
+    /// Logging macros always capture it, even if crate feature
+    /// `source-location` is not enabled. Otherwise, it is the module path of
+    /// the source location if any.
+    #[must_use]
+    pub fn module_path(&self) -> Option<&str> {
+        self.inner
+            .module_path
+            .or_else(|| self.source_location().map(|srcloc| srcloc.module_path()))
+    }
+
     /// Gets the time when the record was created.
+    ///
+    /// It is read from the [clock] of the logger, unless the time is specified
+    /// explicitly with the `time` parameter of [logging macros], e.g. to
+    /// re-emit an event received from another system with its original time.
+    ///
+    /// [clock]: crate::LoggerBuilder::clock
+    /// [logging macros]: crate::log
     #[must_use]
     pub fn time(&self) -> SystemTime {
         self.inner.time
@@ -101,6 +256,43 @@ impl<'a> Record<'a> {
         self.inner.tid
     }
 
+    /// Gets the name of the thread when the record was created.
+    ///
+    /// It is the name set via [`set_thread_name`], or the name of the thread
+    /// in the standard library (see [`std::thread::Builder::name`]) if none is
+    /// set.
+    #[must_use]
+    pub fn thread_name(&self) -> Option<&str> {
+        self.inner.thread_name.as_deref()
+    }
+
+    /// Gets the sequence number of the record within its logger.
+    ///
+    /// Each logger numbers the records it logs consecutively, starting from 0.
+    /// Consumers can detect dropped or reordered records by gaps or
+    /// inversions in the numbers, e.g. after an asynchronous sink or a network
+    /// transport.
+    ///
+    /// Note that records filtered out by the level filter of a sink are
+    /// numbered as well, so they also leave gaps in the output of that sink.
+    #[must_use]
+    pub fn seq(&self) -> u64 {
+        self.inner.seq
+    }
+
+    /// Gets the sequence number of the record across all loggers in the
+    /// process.
+    ///
+    /// It is like [`Record::seq`], except that the records of all loggers are
+    /// numbered with a single counter. It is always 0 unless it is enabled by
+    /// [`LoggerBuilder::global_seq`] for the logger.
+    ///
+    /// [`LoggerBuilder::global_seq`]: crate::LoggerBuilder::global_seq
+    #[must_use]
+    pub fn global_seq(&self) -> u64 {
+        self.inner.global_seq
+    }
+
     // When adding more getters, also add to `RecordOwned`
 
     #[must_use]
@@ -108,10 +300,34 @@ impl<'a> Record<'a> {
         Self {
             logger_name: self.logger_name.clone(),
             payload: new.into(),
+            code: self.code.clone(),
+            bytes: self.bytes.clone(),
+            destination: self.destination.clone(),
+            key_values: KeyValues::Borrowed(&self.key_values),
             inner: Cow::Borrowed(&self.inner),
         }
     }
 
+    pub(crate) fn set_module_path(&mut self, module_path: Option<&'static str>) {
+        self.inner.to_mut().module_path = module_path;
+    }
+
+    pub(crate) fn set_code(&mut self, code: Option<&'a str>) {
+        self.code = code.map(Cow::Borrowed);
+    }
+
+    pub(crate) fn set_bytes(&mut self, bytes: Option<&'a [u8]>) {
+        self.bytes = bytes.map(Cow::Borrowed);
+    }
+
+    pub(crate) fn set_destination(&mut self, destination: Option<&'a str>) {
+        self.destination = destination.map(Cow::Borrowed);
+    }
+
+    pub(crate) fn set_key_values(&mut self, key_values: Vec<(Cow<'a, str>, Cow<'a, str>)>) {
+        self.key_values = KeyValues::Owned(key_values);
+    }
+
     #[cfg(feature = "log")]
     #[must_use]
     pub(crate) fn from_log_crate_record(
@@ -136,40 +352,132 @@ impl<'a> Record<'a> {
                 Some(literal_str) => literal_str.into(),
                 None => args.to_string().into(),
             },
+            code: None,
+            bytes: None,
+            destination: None,
+            key_values: KeyValues::Borrowed(&[]),
             inner: Cow::Owned(RecordInner {
                 level: record.level().into(),
                 source_location: SourceLocation::from_log_crate_record(record),
+                module_path: record.module_path_static(),
                 time,
+                explicit_time: false,
                 // For records from `log` crate, they never seem to come from different threads, so
                 // getting the current TID here should be correct
                 tid: get_current_tid(),
+                thread_name: get_current_thread_name(),
+                seq: 0,
+                global_seq: 0,
+            }),
+        }
+    }
+
+    #[must_use]
+    pub(crate) fn stamped(
+        &self,
+        time: Option<SystemTime>,
+        seq: u64,
+        global_seq: u64,
+    ) -> Record<'_> {
+        Record {
+            logger_name: self.logger_name.as_deref().map(Cow::Borrowed),
+            payload: Cow::Borrowed(&self.payload),
+            code: self.code.as_deref().map(Cow::Borrowed),
+            bytes: self.bytes.as_deref().map(Cow::Borrowed),
+            destination: self.destination.as_deref().map(Cow::Borrowed),
+            key_values: KeyValues::Borrowed(&self.key_values),
+            inner: Cow::Owned(RecordInner {
+                time: match time {
+                    Some(time) if !self.inner.explicit_time => time,
+                    _ => self.inner.time,
+                },
+                seq,
+                global_seq,
+                ..self.inner.as_ref().clone()
             }),
         }
     }
 
-    #[cfg(test)]
     pub(crate) fn set_time(&mut self, new: SystemTime) {
-        self.inner.to_mut().time = new;
+        let inner = self.inner.to_mut();
+        inner.time = new;
+        inner.explicit_time = true;
     }
 }
 
 /// [`Record`] without lifetimes version.
+///
+/// ## Schema
+///
+/// This struct is implemented [`serde::Serialize`] and [`serde::Deserialize`]
+/// if crate feature `serde` is enabled, so records can be persisted, sent
+/// across processes, and replayed into sinks via [`RecordOwned::as_ref`].
+/// [`Record`] is also implemented [`serde::Serialize`] with the same schema.
+///
+/// | Field             | Type                       |
+/// |-------------------|----------------------------|
+/// | `logger_name`     | Option\<String\>           |
+/// | `level`           | String                     |
+/// | `payload`         | String                     |
+/// | `code`            | Option\<String\>           |
+/// | `bytes`           | Option\<Vec\<u8\>\>        |
+/// | `destination`     | Option\<String\>           |
+/// | `key_values`      | Vec\<(String, String)\>    |
+/// | `source_location` | Option\<[SourceLocation]\> |
+/// | `time`            | [`SystemTime`]             |
+/// | `tid`             | u64                        |
+/// | `thread_name`     | Option\<String\>           |
+/// | `seq`             | u64                        |
+/// | `global_seq`      | u64                        |
+///
+/// `level` is the name returned by [`Level::as_str`]. `time` uses the
+/// representation of [`SystemTime`] in serde. `code`, `bytes` and
+/// `destination` are omitted if the record has no error code, raw bytes or
+/// destination hint. `key_values` is a sequence of key-value pairs, omitted if
+/// the record has no key-value fields and empty if it is missing. `thread_name`
+/// is omitted if the thread has no name. `seq` and `global_seq`
+/// default to 0 if they are missing.
+///
+/// # Examples
+///
+/// ```
+/// # #[cfg(feature = "serde_json")]
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use spdlog::{sink::Sink, RecordOwned};
+///
+/// # let sink = spdlog::default_logger().sinks()[0].clone();
+/// # let json = r#"{"logger_name":null,"level":"info","payload":"hello","source_location":null,"time":{"secs_since_epoch":0,"nanos_since_epoch":0},"tid":1}"#;
+/// // Replays a record received from another process.
+/// let record: RecordOwned = serde_json::from_str(json)?;
+/// sink.log(&record.as_ref())?;
+/// # Ok(()) }
+/// # #[cfg(not(feature = "serde_json"))]
+/// # fn main() {}
+/// ```
 // We do not `impl From<&Record> for RecordOwned` because it does not follow the
 // Rust naming convention. Use `record.to_owned()` instead.
 #[derive(Clone, Debug)]
 pub struct RecordOwned {
     logger_name: Option<String>,
     payload: String,
+    code: Option<String>,
+    bytes: Option<Vec<u8>>,
+    destination: Option<String>,
+    key_values: Vec<(Cow<'static, str>, Cow<'static, str>)>,
     inner: RecordInner,
 }
 
 impl RecordOwned {
     /// References as [`Record`] cheaply.
     #[must_use]
-    pub fn as_ref(&self) -> Record {
+    pub fn as_ref(&self) -> Record<'_> {
         Record {
             logger_name: self.logger_name.as_deref().map(Cow::Borrowed),
             payload: Cow::Borrowed(&self.payload),
+            code: self.code.as_deref().map(Cow::Borrowed),
+            bytes: self.bytes.as_deref().map(Cow::Borrowed),
+            destination: self.destination.as_deref().map(Cow::Borrowed),
+            key_values: KeyValues::Borrowed(&self.key_values),
             inner: Cow::Borrowed(&self.inner),
         }
     }
@@ -192,12 +500,65 @@ impl RecordOwned {
         self.payload.borrow()
     }
 
+    /// Whether the payload spans multiple lines, i.e. contains line feeds `\n`.
+    #[must_use]
+    pub fn is_multi_line(&self) -> bool {
+        self.payload().contains('\n')
+    }
+
+    /// Gets the error code.
+    #[must_use]
+    pub fn code(&self) -> Option<&str> {
+        self.code.as_deref()
+    }
+
+    /// Gets the raw bytes attached to the record.
+    #[must_use]
+    pub fn bytes(&self) -> Option<&[u8]> {
+        self.bytes.as_deref()
+    }
+
+    /// Gets the name of the destination the record is intended for.
+    ///
+    /// See [`Record::destination`] for more details.
+    #[must_use]
+    pub fn destination(&self) -> Option<&str> {
+        self.destination.as_deref()
+    }
+
+    /// Gets an iterator over the structured key-value fields of the record.
+    ///
+    /// See [`Record::key_values`] for more details.
+    pub fn key_values(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.key_values
+            .iter()
+            .map(|(k, v)| (k.as_ref(), v.as_ref()))
+    }
+
+    /// Gets the value of the key-value field with the given key.
+    ///
+    /// See [`Record::key_value`] for more details.
+    #[must_use]
+    pub fn key_value(&self, key: &str) -> Option<&str> {
+        self.key_values().find(|(k, _)| *k == key).map(|(_, v)| v)
+    }
+
     /// Gets the source location.
     #[must_use]
     pub fn source_location(&self) -> Option<&SourceLocation> {
         self.inner.source_location.as_ref()
     }
 
+    /// Gets the module path of the code that created the record.
+    ///
+    /// See [`Record::module_path`] for more details.
+    #[must_use]
+    pub fn module_path(&self) -> Option<&str> {
+        self.inner
+            .module_path
+            .or_else(|| self.source_location().map(|srcloc| srcloc.module_path()))
+    }
+
     /// Gets the time when the record was created.
     #[must_use]
     pub fn time(&self) -> SystemTime {
@@ -210,9 +571,218 @@ impl RecordOwned {
         self.inner.tid
     }
 
+    /// Gets the name of the thread when the record was created.
+    ///
+    /// See [`Record::thread_name`] for more details.
+    #[must_use]
+    pub fn thread_name(&self) -> Option<&str> {
+        self.inner.thread_name.as_deref()
+    }
+
+    /// Gets the sequence number of the record within its logger.
+    ///
+    /// See [`Record::seq`] for more details.
+    #[must_use]
+    pub fn seq(&self) -> u64 {
+        self.inner.seq
+    }
+
+    /// Gets the sequence number of the record across all loggers in the
+    /// process.
+    ///
+    /// See [`Record::global_seq`] for more details.
+    #[must_use]
+    pub fn global_seq(&self) -> u64 {
+        self.inner.global_seq
+    }
+
     // When adding more getters, also add to `Record`
 }
 
+/// The properties of a record constructed from an external source, e.g. parsed
+/// from a log file.
+///
+/// The properties missing from the source are zero or empty.
+#[derive(Clone, Debug)]
+pub(crate) struct RecordFields {
+    pub(crate) logger_name: Option<String>,
+    pub(crate) level: Level,
+    pub(crate) payload: String,
+    pub(crate) code: Option<String>,
+    pub(crate) bytes: Option<Vec<u8>>,
+    pub(crate) destination: Option<String>,
+    pub(crate) key_values: Vec<(String, String)>,
+    pub(crate) source_location: Option<SourceLocation>,
+    pub(crate) time: SystemTime,
+    pub(crate) tid: u64,
+    pub(crate) thread_name: Option<String>,
+    pub(crate) seq: u64,
+    pub(crate) global_seq: u64,
+}
+
+impl RecordFields {
+    #[must_use]
+    pub(crate) fn new(level: Level, payload: String) -> Self {
+        Self {
+            logger_name: None,
+            level,
+            payload,
+            code: None,
+            bytes: None,
+            destination: None,
+            key_values: vec![],
+            source_location: None,
+            time: SystemTime::UNIX_EPOCH,
+            tid: 0,
+            thread_name: None,
+            seq: 0,
+            global_seq: 0,
+        }
+    }
+}
+
+impl From<RecordFields> for RecordOwned {
+    fn from(fields: RecordFields) -> Self {
+        RecordOwned {
+            logger_name: fields.logger_name,
+            payload: fields.payload,
+            code: fields.code,
+            bytes: fields.bytes,
+            destination: fields.destination,
+            key_values: fields
+                .key_values
+                .into_iter()
+                .map(|(k, v)| (Cow::Owned(k), Cow::Owned(v)))
+                .collect(),
+            inner: RecordInner {
+                level: fields.level,
+                source_location: fields.source_location,
+                module_path: None,
+                time: fields.time,
+                // Records read or received from elsewhere keep their times.
+                explicit_time: true,
+                tid: fields.tid,
+                thread_name: fields.thread_name.map(Arc::from),
+                seq: fields.seq,
+                global_seq: fields.global_seq,
+            },
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+mod serde_impl {
+    use std::time::SystemTime;
+
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use super::*;
+
+    #[derive(Serialize)]
+    struct RecordRef<'a> {
+        logger_name: Option<&'a str>,
+        level: Level,
+        payload: &'a str,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        code: Option<&'a str>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        bytes: Option<&'a [u8]>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        destination: Option<&'a str>,
+        #[serde(skip_serializing_if = "<[_]>::is_empty")]
+        key_values: &'a [(Cow<'a, str>, Cow<'a, str>)],
+        source_location: Option<&'a SourceLocation>,
+        time: SystemTime,
+        tid: u64,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        thread_name: Option<&'a str>,
+        seq: u64,
+        global_seq: u64,
+    }
+
+    #[derive(Deserialize)]
+    struct RecordDe {
+        logger_name: Option<String>,
+        level: Level,
+        payload: String,
+        #[serde(default)]
+        code: Option<String>,
+        #[serde(default)]
+        bytes: Option<Vec<u8>>,
+        #[serde(default)]
+        destination: Option<String>,
+        #[serde(default)]
+        key_values: Vec<(String, String)>,
+        source_location: Option<SourceLocation>,
+        time: SystemTime,
+        tid: u64,
+        #[serde(default)]
+        thread_name: Option<String>,
+        #[serde(default)]
+        seq: u64,
+        #[serde(default)]
+        global_seq: u64,
+    }
+
+    impl Serialize for Record<'_> {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            RecordRef {
+                logger_name: self.logger_name(),
+                level: self.level(),
+                payload: self.payload(),
+                code: self.code(),
+                bytes: self.bytes(),
+                destination: self.destination(),
+                key_values: &self.key_values,
+                source_location: self.source_location(),
+                time: self.time(),
+                tid: self.tid(),
+                thread_name: self.thread_name(),
+                seq: self.seq(),
+                global_seq: self.global_seq(),
+            }
+            .serialize(serializer)
+        }
+    }
+
+    impl Serialize for RecordOwned {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            self.as_ref().serialize(serializer)
+        }
+    }
+
+    impl<'de> Deserialize<'de> for RecordOwned {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            let record = RecordDe::deserialize(deserializer)?;
+            Ok(RecordFields {
+                logger_name: record.logger_name,
+                level: record.level,
+                payload: record.payload,
+                code: record.code,
+                bytes: record.bytes,
+                destination: record.destination,
+                key_values: record.key_values,
+                source_location: record.source_location,
+                time: record.time,
+                tid: record.tid,
+                thread_name: record.thread_name,
+                seq: record.seq,
+                global_seq: record.global_seq,
+            }
+            .into())
+        }
+    }
+}
+
 fn get_current_tid() -> u64 {
     #[cfg(target_os = "linux")]
     #[must_use]
@@ -246,3 +816,112 @@ fn get_current_tid() -> u64 {
 
     TID.with(|tid| *tid.borrow_mut().get_or_insert_with(get_current_tid_inner))
 }
+
+/// Sets the name of the current thread for logging.
+///
+/// The name is captured by records created on the current thread afterwards,
+/// see [`Record::thread_name`], and can be rendered by the `{thread_name}`
+/// pattern. Unlike [`std::thread::Builder::name`], it can be set for threads
+/// spawned by other libraries, e.g. the workers of an asynchronous runtime.
+///
+/// # Examples
+///
+/// ```
+/// use spdlog::prelude::*;
+///
+/// std::thread::spawn(|| {
+///     spdlog::set_thread_name("worker-3");
+///     info!("records on this thread are tagged with its name");
+/// })
+/// .join()
+/// .unwrap();
+/// ```
+pub fn set_thread_name(name: impl Into<String>) {
+    let name: Arc<str> = Arc::from(name.into());
+    THREAD_NAME.with(|thread_name| *thread_name.borrow_mut() = Some(Some(name)));
+}
+
+thread_local! {
+    // `None` if not yet initialized, `Some(None)` if the thread has no name.
+    static THREAD_NAME: RefCell<Option<Option<Arc<str>>>> = const { RefCell::new(None) };
+}
+
+fn get_current_thread_name() -> Option<Arc<str>> {
+    THREAD_NAME.with(|thread_name| {
+        thread_name
+            .borrow_mut()
+            .get_or_insert_with(|| std::thread::current().name().map(Arc::from))
+            .clone()
+    })
+}
+
+#[cfg(all(test, feature = "serde_json"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serde_round_trip() {
+        crate::set_thread_name("worker");
+        let mut record = Record::new(
+            Level::Warn,
+            "payload",
+            Some(SourceLocation::__new("module", "file.rs", 1, 2)),
+            Some("logger"),
+        );
+        record.set_time(SystemTime::UNIX_EPOCH + std::time::Duration::from_millis(1500));
+        let record = record.to_owned();
+
+        let json = serde_json::to_string(&record).unwrap();
+        assert_eq!(
+            json,
+            format!(
+                r#"{{"logger_name":"logger","level":"warn","payload":"payload","source_location":{{"module_path":"module","file":"file.rs","line":1,"column":2}},"time":{{"secs_since_epoch":1,"nanos_since_epoch":500000000}},"tid":{},"thread_name":"worker","seq":0,"global_seq":0}}"#,
+                record.tid()
+            )
+        );
+        assert_eq!(serde_json::to_string(&record.as_ref()).unwrap(), json);
+
+        let de: RecordOwned = serde_json::from_str(&json).unwrap();
+        assert_eq!(de.logger_name(), Some("logger"));
+        assert_eq!(de.level(), Level::Warn);
+        assert_eq!(de.payload(), "payload");
+        assert_eq!(de.code(), None);
+        let srcloc = de.source_location().unwrap();
+        assert_eq!(
+            (
+                srcloc.module_path(),
+                srcloc.file(),
+                srcloc.line(),
+                srcloc.column()
+            ),
+            ("module", "file.rs", 1, 2)
+        );
+        assert_eq!(de.time(), record.time());
+        assert_eq!(de.tid(), record.tid());
+        assert_eq!(de.thread_name(), Some("worker"));
+        assert_eq!(de.seq(), 0);
+
+        assert!(serde_json::from_str::<RecordOwned>(&json.replace("warn", "loud")).is_err());
+    }
+
+    #[test]
+    fn serde_code_and_bytes() {
+        let mut record = Record::new(Level::Error, "payload", None, None);
+        record.set_code(Some("E1042"));
+        record.set_bytes(Some(&[0, 255]));
+        record.set_destination(Some("audit"));
+        record.set_key_values(vec![("user_id".into(), "42".into())]);
+        let record = record.to_owned();
+
+        let json = serde_json::to_string(&record).unwrap();
+        assert!(json.contains(
+            r#""payload":"payload","code":"E1042","bytes":[0,255],"destination":"audit","key_values":[["user_id","42"]],"#
+        ));
+
+        let de: RecordOwned = serde_json::from_str(&json).unwrap();
+        assert_eq!(de.code(), Some("E1042"));
+        assert_eq!(de.bytes(), Some(&[0, 255][..]));
+        assert_eq!(de.destination(), Some("audit"));
+        assert_eq!(de.key_value("user_id"), Some("42"));
+    }
+}
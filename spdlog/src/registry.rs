@@ -0,0 +1,205 @@
+use std::collections::HashMap;
+
+use crate::{error::InvalidArgumentError, sync::*, Error, Logger, Result};
+
+type LoggerFactory = dyn Fn(&str) -> Result<Logger> + Send + Sync;
+
+static LOGGERS: Lazy<RwLock<HashMap<String, Arc<Logger>>>> = Lazy::new(Default::default);
+
+static LOGGER_FACTORY: Lazy<RwLock<Option<Arc<LoggerFactory>>>> = Lazy::new(Default::default);
+
+/// Registers a logger under its name in the global registry, and returns the
+/// logger previously registered under the same name, if any.
+///
+/// Registered loggers can be looked up by name with [`get_logger`] from
+/// anywhere in the program, without passing them around. Using the registry is
+/// optional, loggers work the same whether they are registered or not.
+///
+/// # Error
+///
+/// If the logger does not have a name, [`Error::InvalidArgument`] will be
+/// returned.
+///
+/// # Examples
+///
+/// ```
+/// use std::sync::Arc;
+///
+/// use spdlog::prelude::*;
+///
+/// # fn main() -> Result<(), spdlog::Error> {
+/// let logger = Arc::new(Logger::builder().name("network").build()?);
+/// spdlog::register_logger(logger)?;
+///
+/// let logger = spdlog::get_logger("network").unwrap();
+/// info!(logger: logger, "connected");
+/// # Ok(()) }
+/// ```
+pub fn register_logger(logger: Arc<Logger>) -> Result<Option<Arc<Logger>>> {
+    let name = logger
+        .name()
+        .ok_or(Error::InvalidArgument(InvalidArgumentError::UnnamedLogger))?
+        .to_string();
+    Ok(LOGGERS.write_expect().insert(name, logger))
+}
+
+/// Unregisters the logger of the given name from the global registry, and
+/// returns it.
+pub fn unregister_logger(name: &str) -> Option<Arc<Logger>> {
+    LOGGERS.write_expect().remove(name)
+}
+
+/// Gets the logger of the given name from the global registry.
+///
+/// Returns `None` if no logger is registered under the name. See also
+/// [`get_or_create`].
+#[must_use]
+pub fn get_logger(name: &str) -> Option<Arc<Logger>> {
+    LOGGERS.read_expect().get(name).cloned()
+}
+
+/// Sets the factory constructing loggers on demand for [`get_or_create`].
+///
+/// The factory receives the name of the requested logger, and should return a
+/// logger with that name. It is a template of loggers, e.g. all the loggers it
+/// constructs may share the same sinks and level filter, so that each module
+/// can simply get a logger named after itself.
+///
+/// Setting a factory again replaces the previous one. Loggers constructed by
+/// the previous factory stay registered.
+///
+/// # Examples
+///
+/// ```
+/// use std::sync::Arc;
+///
+/// use spdlog::{
+///     prelude::*,
+///     sink::{StdStream, StdStreamSink},
+/// };
+///
+/// # fn main() -> Result<(), spdlog::Error> {
+/// let sink = Arc::new(
+///     StdStreamSink::builder()
+///         .std_stream(StdStream::Stderr)
+///         .build()?,
+/// );
+///
+/// spdlog::set_logger_factory(move |name| {
+///     Logger::builder()
+///         .name(name)
+///         .sink(sink.clone())
+///         .level_filter(LevelFilter::All)
+///         .build()
+/// });
+///
+/// let db_logger = spdlog::get_or_create("app::db")?;
+/// debug!(logger: db_logger, "connection pool created");
+/// # Ok(()) }
+/// ```
+pub fn set_logger_factory<F>(factory: F)
+where
+    F: Fn(&str) -> Result<Logger> + Send + Sync + 'static,
+{
+    *LOGGER_FACTORY.write_expect() = Some(Arc::new(factory));
+}
+
+/// Gets the logger of the given name from the global registry, or constructs
+/// and registers one if there is none.
+///
+/// Loggers are constructed by the factory set with [`set_logger_factory`]. If
+/// no factory is set, loggers are forked from the [default logger] with the
+/// given name (see [`Logger::fork_with_name`]).
+///
+/// # Error
+///
+/// Errors returned by the factory are returned as is. If no factory is set and
+/// the name is invalid, [`Error::InvalidArgument`] will be returned.
+///
+/// [default logger]: crate::default_logger
+pub fn get_or_create(name: &str) -> Result<Arc<Logger>> {
+    if let Some(logger) = get_logger(name) {
+        return Ok(logger);
+    }
+
+    // Clone the factory to avoid calling it while holding the lock, it may get
+    // or create other loggers.
+    let factory = LOGGER_FACTORY.read_expect().clone();
+    let logger = match factory {
+        Some(factory) => Arc::new(factory(name)?),
+        None => crate::default_logger().fork_with_name(Some(name))?,
+    };
+
+    // Another thread may have registered a logger with the same name meanwhile,
+    // the first registered one wins.
+    Ok(LOGGERS
+        .write_expect()
+        .entry(name.to_string())
+        .or_insert(logger)
+        .clone())
+}
+
+/// Gets all the registered loggers, or nothing if the registry is locked.
+///
+/// It never blocks, so it can be used where the lock may be held by the
+/// current thread, e.g. in a crash handler.
+#[must_use]
+pub(crate) fn try_registered_loggers() -> Vec<Arc<Logger>> {
+    LOGGERS
+        .try_read()
+        .map(|loggers| loggers.values().cloned().collect())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{prelude::*, test_utils::*};
+
+    #[test]
+    fn registry() {
+        let logger = Arc::new(build_test_logger(|b| b.name("registry-test")));
+        assert!(register_logger(logger.clone()).unwrap().is_none());
+        assert!(Arc::ptr_eq(&get_logger("registry-test").unwrap(), &logger));
+        assert!(Arc::ptr_eq(
+            &get_or_create("registry-test").unwrap(),
+            &logger
+        ));
+
+        assert!(matches!(
+            register_logger(Arc::new(build_test_logger(|b| b))),
+            Err(Error::InvalidArgument(InvalidArgumentError::UnnamedLogger))
+        ));
+
+        assert!(Arc::ptr_eq(
+            &unregister_logger("registry-test").unwrap(),
+            &logger
+        ));
+        assert!(get_logger("registry-test").is_none());
+    }
+
+    #[test]
+    fn factory() {
+        let test_sink = Arc::new(TestSink::new());
+        let sink = test_sink.clone();
+        set_logger_factory(move |name| {
+            Logger::builder()
+                .name(name)
+                .sink(sink.clone())
+                .level_filter(LevelFilter::All)
+                .build()
+        });
+
+        let db = get_or_create("factory-test::db").unwrap();
+        assert_eq!(db.name(), Some("factory-test::db"));
+        assert!(Arc::ptr_eq(
+            &get_or_create("factory-test::db").unwrap(),
+            &db
+        ));
+        trace!(logger: db, "from db");
+        assert_eq!(test_sink.payloads(), ["from db"]);
+
+        assert!(get_or_create("invalid,name").is_err());
+        assert!(get_logger("invalid,name").is_none());
+    }
+}
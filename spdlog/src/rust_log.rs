@@ -0,0 +1,305 @@
+use std::{env, env::VarError, ffi::OsStr};
+
+use crate::{error::EnvLevelError, Level, LevelFilter};
+
+/// A filter compatible with the `RUST_LOG` directive grammar of
+/// [env_logger crate].
+///
+/// The grammar is `directive[,directive]*[/filter]`, where a directive is one
+/// of:
+///
+/// - `level`: sets the level of all targets, e.g. `info`.
+/// - `target`: enables all levels of the target, e.g. `hyper`.
+/// - `target=level`: sets the level of the target, e.g. `hyper=warn`.
+///
+/// A target matches all targets starting with it, and the directive with the
+/// longest matching target wins. If the same target is specified multiple
+/// times, the last one wins. If no directive is specified, only `error` logs
+/// are enabled.
+///
+/// The optional `filter` after `/` filters logs by message. If crate feature
+/// `regex` is enabled, it is a regular expression that must match a part of
+/// the message, otherwise it is a substring that the message must contain.
+///
+/// Level names are case-insensitive. In addition to the levels of
+/// `env_logger`, `critical` and `all` are also accepted.
+///
+/// With crate feature `log` enabled, the filter can be applied to the log
+/// crate proxy via [`init_rust_log`] or [`LogCrateProxy::set_rust_log_filter`].
+///
+/// ## Examples
+///
+/// ```
+/// use spdlog::{Level, RustLogFilter};
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let filter = RustLogFilter::parse("info,hyper=warn,hyper::client=off/login")?;
+///
+/// assert!(filter.enabled("app", Level::Info));
+/// assert!(!filter.enabled("hyper::server", Level::Info));
+/// assert!(!filter.enabled("hyper::client", Level::Error));
+///
+/// assert!(filter.matches("app", Level::Info, "user login ok"));
+/// assert!(!filter.matches("app", Level::Info, "user logout"));
+/// # Ok(()) }
+/// ```
+///
+/// [env_logger crate]: https://crates.io/crates/env_logger
+/// [`init_rust_log`]: crate::init_rust_log
+/// [`LogCrateProxy::set_rust_log_filter`]: crate::LogCrateProxy::set_rust_log_filter
+#[derive(Clone, Debug)]
+pub struct RustLogFilter {
+    // Sorted by the length of target names in ascending order, the directive
+    // without target name comes first.
+    directives: Vec<Directive>,
+    message_filter: Option<MessageFilter>,
+}
+
+#[derive(Clone, Debug)]
+struct Directive {
+    target: Option<String>,
+    level_filter: LevelFilter,
+}
+
+#[derive(Clone, Debug)]
+enum MessageFilter {
+    #[cfg(feature = "regex")]
+    Regex(regex::Regex),
+    #[cfg(not(feature = "regex"))]
+    Substring(String),
+}
+
+impl RustLogFilter {
+    /// Parses a filter from a `RUST_LOG`-style string.
+    ///
+    /// Returns [`EnvLevelError::ParseEnvVar`] if the string contains an
+    /// invalid directive or an invalid regex.
+    pub fn parse(spec: &str) -> Result<Self, EnvLevelError> {
+        let (directives_str, message_filter_str) = match spec.split_once('/') {
+            Some((directives, filter)) => (directives, Some(filter.trim())),
+            None => (spec, None),
+        };
+
+        let mut directives = Vec::<Directive>::new();
+        for directive_str in directives_str.split(',').map(str::trim) {
+            if directive_str.is_empty() {
+                continue;
+            }
+
+            let mut parts = directive_str.split('=');
+            let (left, right) = (parts.next().map(str::trim), parts.next().map(str::trim));
+            let directive = match (left, right, parts.next()) {
+                (Some(text), None, None) => match LevelFilter::from_str_for_env(text) {
+                    Some(level_filter) => Directive {
+                        target: None,
+                        level_filter,
+                    },
+                    None => Directive {
+                        target: Some(text.into()),
+                        level_filter: LevelFilter::All,
+                    },
+                },
+                (Some(target), Some(level), None) if !target.is_empty() => {
+                    let level_filter = if level.is_empty() {
+                        LevelFilter::All
+                    } else {
+                        LevelFilter::from_str_for_env(level).ok_or_else(|| {
+                            EnvLevelError::ParseEnvVar(format!(
+                                "cannot parse level for target '{}': '{}'",
+                                target, directive_str
+                            ))
+                        })?
+                    };
+                    Directive {
+                        target: Some(target.into()),
+                        level_filter,
+                    }
+                }
+                _ => {
+                    return Err(EnvLevelError::ParseEnvVar(format!(
+                        "invalid directive: '{}'",
+                        directive_str
+                    )))
+                }
+            };
+
+            match directives.iter_mut().find(|d| d.target == directive.target) {
+                Some(existing) => *existing = directive,
+                None => directives.push(directive),
+            }
+        }
+
+        if directives.is_empty() {
+            directives.push(Directive {
+                target: None,
+                level_filter: LevelFilter::MoreSevereEqual(Level::Error),
+            });
+        }
+        // Stable sort, the directive without target name has the length 0.
+        directives.sort_by_key(|d| d.target.as_ref().map_or(0, String::len));
+
+        let message_filter = match message_filter_str {
+            None | Some("") => None,
+            Some(filter) => Some(MessageFilter::new(filter)?),
+        };
+
+        Ok(Self {
+            directives,
+            message_filter,
+        })
+    }
+
+    /// Parses a filter from the environment variable `RUST_LOG`.
+    ///
+    /// Returns `Ok(None)` if the environment variable is not present.
+    pub fn from_env() -> Result<Option<Self>, EnvLevelError> {
+        Self::from_env_var("RUST_LOG")
+    }
+
+    /// Parses a filter from a specified environment variable.
+    ///
+    /// Returns `Ok(None)` if the environment variable is not present.
+    pub fn from_env_var<K: AsRef<OsStr>>(env_key: K) -> Result<Option<Self>, EnvLevelError> {
+        match env::var(env_key.as_ref()) {
+            Err(VarError::NotPresent) => Ok(None),
+            Err(err) => Err(EnvLevelError::FetchEnvVar(err)),
+            Ok(var) => Self::parse(&var).map(Some),
+        }
+    }
+
+    /// Determines if a log with the given target and level would be enabled,
+    /// without considering the message filter.
+    #[must_use]
+    pub fn enabled(&self, target: &str, level: Level) -> bool {
+        self.directives
+            .iter()
+            .rev()
+            .find(|d| match &d.target {
+                Some(name) => target.starts_with(name.as_str()),
+                None => true,
+            })
+            .map_or(false, |d| d.level_filter.test(level))
+    }
+
+    /// Determines if a log with the given target, level and message would be
+    /// enabled.
+    #[must_use]
+    pub fn matches(&self, target: &str, level: Level, message: &str) -> bool {
+        self.enabled(target, level)
+            && self
+                .message_filter
+                .as_ref()
+                .map_or(true, |filter| filter.is_match(message))
+    }
+
+    /// Returns whether the filter has a message filter.
+    #[cfg(feature = "log")]
+    #[must_use]
+    pub(crate) fn has_message_filter(&self) -> bool {
+        self.message_filter.is_some()
+    }
+
+    /// Returns the most verbose level that any directive enables.
+    #[cfg(feature = "log")]
+    #[must_use]
+    pub(crate) fn log_crate_max_level(&self) -> log::LevelFilter {
+        [
+            (Level::Trace, log::LevelFilter::Trace),
+            (Level::Debug, log::LevelFilter::Debug),
+            (Level::Info, log::LevelFilter::Info),
+            (Level::Warn, log::LevelFilter::Warn),
+            (Level::Error, log::LevelFilter::Error),
+        ]
+        .into_iter()
+        .find(|(level, _)| self.directives.iter().any(|d| d.level_filter.test(*level)))
+        .map_or(log::LevelFilter::Off, |(_, max_level)| max_level)
+    }
+}
+
+impl MessageFilter {
+    fn new(filter: &str) -> Result<Self, EnvLevelError> {
+        cfg_if::cfg_if! {
+            if #[cfg(feature = "regex")] {
+                regex::Regex::new(filter)
+                    .map(Self::Regex)
+                    .map_err(|err| EnvLevelError::ParseEnvVar(format!("invalid regex filter: {}", err)))
+            } else {
+                Ok(Self::Substring(filter.into()))
+            }
+        }
+    }
+
+    #[must_use]
+    fn is_match(&self, message: &str) -> bool {
+        match self {
+            #[cfg(feature = "regex")]
+            Self::Regex(regex) => regex.is_match(message),
+            #[cfg(not(feature = "regex"))]
+            Self::Substring(substring) => message.contains(substring.as_str()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_directives() {
+        let filter = RustLogFilter::parse("").unwrap();
+        assert!(filter.enabled("app", Level::Error));
+        assert!(!filter.enabled("app", Level::Warn));
+
+        let filter = RustLogFilter::parse("warn,hyper,hyper::client=error,tokio=").unwrap();
+        assert!(filter.enabled("app", Level::Warn));
+        assert!(!filter.enabled("app", Level::Info));
+        assert!(filter.enabled("hyper", Level::Trace));
+        assert!(filter.enabled("hyperlocal", Level::Trace));
+        assert!(filter.enabled("hyper::server", Level::Trace));
+        assert!(!filter.enabled("hyper::client::pool", Level::Warn));
+        assert!(filter.enabled("hyper::client::pool", Level::Error));
+        assert!(filter.enabled("tokio::net", Level::Trace));
+
+        // Without a default level, unmatched targets are disabled.
+        let filter = RustLogFilter::parse("app=DEBUG, app=Info").unwrap();
+        assert!(filter.enabled("app::db", Level::Info));
+        assert!(!filter.enabled("app::db", Level::Debug));
+        assert!(!filter.enabled("other", Level::Critical));
+
+        let filter = RustLogFilter::parse("off,app=all").unwrap();
+        assert!(!filter.enabled("other", Level::Critical));
+        assert!(filter.enabled("app", Level::Trace));
+
+        assert!(RustLogFilter::parse("app=verbose").is_err());
+        assert!(RustLogFilter::parse("=info").is_err());
+        assert!(RustLogFilter::parse("a=b=c").is_err());
+    }
+
+    #[test]
+    fn message_filter() {
+        let filter = RustLogFilter::parse("info/log.n").unwrap();
+        assert!(!filter.matches("app", Level::Debug, "login"));
+        #[cfg(feature = "regex")]
+        {
+            assert!(filter.matches("app", Level::Info, "user login"));
+            assert!(!filter.matches("app", Level::Info, "logout"));
+            assert!(RustLogFilter::parse("info/(").is_err());
+        }
+        #[cfg(not(feature = "regex"))]
+        {
+            assert!(!filter.matches("app", Level::Info, "user login"));
+            assert!(filter.matches("app", Level::Info, "log.n"));
+        }
+    }
+
+    #[cfg(feature = "log")]
+    #[test]
+    fn max_level() {
+        let max_level = |spec| RustLogFilter::parse(spec).unwrap().log_crate_max_level();
+        assert_eq!(max_level(""), log::LevelFilter::Error);
+        assert_eq!(max_level("warn,app=debug"), log::LevelFilter::Debug);
+        assert_eq!(max_level("app"), log::LevelFilter::Trace);
+        assert_eq!(max_level("off"), log::LevelFilter::Off);
+        assert_eq!(max_level("critical"), log::LevelFilter::Off);
+    }
+}
@@ -0,0 +1,134 @@
+use crate::{utils, Level, LevelFilter};
+
+/// Keeps a random fraction of records, with a rate for each level.
+///
+/// A sampler can be set on a logger via [`LoggerBuilder::sampler`]. Records
+/// that passed the level filter of the logger are then sampled once, before
+/// any sink sees them, so that all sinks receive the same sampled stream.
+///
+/// Rates are in the range from `0.0` (drop all records) to `1.0` (keep all
+/// records), out of range values are clamped. Levels without a specified rate
+/// keep all records.
+///
+/// # Examples
+///
+/// ```
+/// use spdlog::{prelude::*, Sampler};
+///
+/// # fn main() -> Result<(), spdlog::Error> {
+/// // Keeps 1% of debug records, 10% of info records and all others.
+/// let sampler = Sampler::new()
+///     .rate_filter(LevelFilter::MoreVerboseEqual(Level::Debug), 0.01)
+///     .rate(Level::Info, 0.1);
+///
+/// let logger = Logger::builder()
+///     .level_filter(LevelFilter::All)
+///     .sampler(sampler)
+///     .build()?;
+/// # Ok(()) }
+/// ```
+///
+/// [`LoggerBuilder::sampler`]: crate::LoggerBuilder::sampler
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Sampler {
+    // A record is kept if a random number is less than the threshold, or if the
+    // threshold is `u64::MAX`.
+    thresholds: [u64; Level::count()],
+}
+
+impl Sampler {
+    /// Constructs a `Sampler` keeping all records.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            thresholds: [u64::MAX; Level::count()],
+        }
+    }
+
+    /// Specifies the rate of records to keep for the level.
+    #[must_use]
+    pub fn rate(mut self, level: Level, rate: f64) -> Self {
+        self.thresholds[level as usize] = threshold(rate);
+        self
+    }
+
+    /// Specifies the rate of records to keep for all levels matched by the
+    /// level filter.
+    #[must_use]
+    pub fn rate_filter(mut self, level_filter: LevelFilter, rate: f64) -> Self {
+        Level::iter()
+            .filter(|level| level_filter.test(*level))
+            .for_each(|level| self.thresholds[level as usize] = threshold(rate));
+        self
+    }
+
+    /// Decides randomly whether to keep a record of the level.
+    #[must_use]
+    pub fn sample(&self, level: Level) -> bool {
+        match self.thresholds[level as usize] {
+            u64::MAX => true,
+            0 => false,
+            threshold => utils::random_u64() < threshold,
+        }
+    }
+}
+
+impl Default for Sampler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[must_use]
+fn threshold(rate: f64) -> u64 {
+    if rate >= 1.0 {
+        u64::MAX
+    } else if rate > 0.0 {
+        // Saturating, `u64::MAX` is reached only if the rate rounds to 1.0.
+        (rate * u64::MAX as f64) as u64
+    } else {
+        // Also for NaN.
+        0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{prelude::*, sync::*, test_utils::*};
+
+    #[test]
+    fn sample() {
+        let sampler = Sampler::new()
+            .rate_filter(LevelFilter::MoreVerboseEqual(Level::Debug), 0.0)
+            .rate(Level::Info, 0.5)
+            .rate(Level::Trace, 2.0);
+        assert!(sampler.sample(Level::Error));
+        assert!(sampler.sample(Level::Trace));
+        assert!(!sampler.sample(Level::Debug));
+
+        let kept = (0..10000).filter(|_| sampler.sample(Level::Info)).count();
+        assert!((4000..6000).contains(&kept), "{}", kept);
+    }
+
+    #[test]
+    fn logger_samples() {
+        let first_sink = Arc::new(TestSink::new());
+        let second_sink = Arc::new(TestSink::new());
+        let logger = build_test_logger(|b| {
+            b.sink(first_sink.clone())
+                .sink(second_sink.clone())
+                .sampler(Sampler::new().rate(Level::Info, 0.5).rate(Level::Warn, 0.0))
+        });
+
+        for i in 0..100 {
+            info!(logger: logger, "{}", i);
+            warn!(logger: logger, "{}", i);
+            error!(logger: logger, "{}", i);
+        }
+
+        let kept = first_sink.log_count();
+        assert!((120..180).contains(&kept), "{}", kept);
+        assert_eq!(first_sink.payloads(), second_sink.payloads());
+    }
+}
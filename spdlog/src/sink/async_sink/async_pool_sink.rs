@@ -1,5 +1,10 @@
+use std::{collections::VecDeque, mem};
+
+use crossbeam::channel::{self as mpmc, Sender};
+
 use crate::{
     default_error_handler, default_thread_pool,
+    error::{SendToChannelError, SendToChannelErrorDropped},
     formatter::Formatter,
     sink::{helper, OverflowPolicy, Sink, Sinks},
     sync::*,
@@ -93,6 +98,46 @@ impl AsyncPoolSink {
         self.thread_pool.assign_task(task, self.overflow_policy)
     }
 
+    // Queues the record in the pending queue of the sink for
+    // `OverflowPolicy::DropOldest`, so that only records of this sink are dropped
+    // when it is full, and schedules a task to log pending records if there is
+    // none yet.
+    fn log_drop_oldest(&self, record: &Record) -> Result<()> {
+        let (dropped, schedule) = {
+            let mut pending = self.backend.pending.lock_expect();
+            let dropped = if pending.records.len() >= self.thread_pool.capacity() {
+                pending.records.pop_front()
+            } else {
+                None
+            };
+            pending.records.push_back(record.to_owned());
+            let schedule = !pending.scheduled;
+            pending.scheduled = true;
+            (dropped, schedule)
+        };
+
+        // The channel is shared with other sinks, do not wait for it. If it is full,
+        // the records are kept pending until the next operation of this sink.
+        if schedule
+            && !self.thread_pool.try_assign_task(
+                Task::LogPending {
+                    backend: self.clone_backend(),
+                },
+                false,
+            )
+        {
+            self.backend.pending.lock_expect().scheduled = false;
+        }
+
+        if let Some(record) = dropped {
+            self.backend.handle_error(Error::SendToChannel(
+                SendToChannelError::Full,
+                SendToChannelErrorDropped::Record(Box::new(record)),
+            ));
+        }
+        Ok(())
+    }
+
     #[must_use]
     fn clone_backend(&self) -> Arc<Backend> {
         Arc::clone(&self.backend)
@@ -101,6 +146,9 @@ impl AsyncPoolSink {
 
 impl Sink for AsyncPoolSink {
     fn log(&self, record: &Record) -> Result<()> {
+        if self.overflow_policy == OverflowPolicy::DropOldest {
+            return self.log_drop_oldest(record);
+        }
         self.assign_task(Task::Log {
             backend: self.clone_backend(),
             record: record.to_owned(),
@@ -125,6 +173,78 @@ impl Sink for AsyncPoolSink {
         }
     }
 
+    /// For [`AsyncPoolSink`], the function blocks until the operations assigned
+    /// before have been performed and all internal sinks have been synced.
+    ///
+    /// Unlike other operations, errors that occur in syncing internal sinks are
+    /// returned rather than passed to the error handler. The operations are
+    /// performed in order only if the thread pool has a single thread, which
+    /// is the default.
+    fn sync(&self) -> Result<()> {
+        if crate::IS_TEARING_DOWN.load(Ordering::SeqCst) {
+            // See the comment in `flush`.
+            self.thread_pool.destroy();
+            return self.backend.sync();
+        }
+
+        let (sender, receiver) = mpmc::bounded(1);
+        self.thread_pool.assign_task(
+            Task::Sync {
+                backend: self.clone_backend(),
+                done: sender,
+            },
+            OverflowPolicy::Block,
+        )?;
+        receiver.recv().unwrap_or(Err(Error::SendToChannel(
+            SendToChannelError::Disconnected,
+            SendToChannelErrorDropped::Flush,
+        )))
+    }
+
+    /// For [`AsyncPoolSink`], the function returns [`Poll::Pending`] while the
+    /// channel of the thread pool is full.
+    ///
+    /// [`Poll::Pending`]: std::task::Poll::Pending
+    #[cfg(feature = "log-async")]
+    fn poll_capacity(&self, cx: &mut std::task::Context<'_>) -> std::task::Poll<()> {
+        if self.thread_pool.is_full() {
+            // The channel cannot notify tasks when it has space, yield to the
+            // executor and check again.
+            cx.waker().wake_by_ref();
+            std::task::Poll::Pending
+        } else {
+            std::task::Poll::Ready(())
+        }
+    }
+
+    /// For [`AsyncPoolSink`], the function opens all internal sinks
+    /// synchronously.
+    fn open(&self) -> Result<()> {
+        self.backend.open()
+    }
+
+    /// For [`AsyncPoolSink`], the function closes all internal sinks
+    /// asynchronously, after the operations assigned before.
+    fn close(&self) -> Result<()> {
+        if crate::IS_TEARING_DOWN.load(Ordering::SeqCst) {
+            // See the comment in `flush`.
+            self.thread_pool.destroy();
+            self.backend.close()
+        } else {
+            self.assign_task(Task::Close {
+                backend: self.clone_backend(),
+            })
+        }
+    }
+
+    /// For [`AsyncPoolSink`], the function checks all internal sinks.
+    fn health(&self) -> Result<()> {
+        #[allow(clippy::manual_try_fold)] // https://github.com/rust-lang/rust-clippy/issues/11554
+        self.backend.sinks.iter().fold(Ok(()), |result, sink| {
+            Error::push_result(result, sink.health())
+        })
+    }
+
     /// For [`AsyncPoolSink`], the function performs the same call to all
     /// internal sinks.
     fn set_formatter(&self, formatter: Box<dyn Formatter>) {
@@ -142,6 +262,30 @@ impl Sink for AsyncPoolSink {
     }
 }
 
+impl Drop for AsyncPoolSink {
+    fn drop(&mut self) {
+        if self.backend.pending.lock_expect().records.is_empty() {
+            return;
+        }
+        // Records may be left pending if the channel was full when they were queued.
+        if crate::IS_TEARING_DOWN.load(Ordering::SeqCst) {
+            // See the comment in `flush`.
+            self.thread_pool.destroy();
+            self.backend.log_pending();
+        } else {
+            let backend = self.clone_backend();
+            if !self.thread_pool.try_assign_task(
+                Task::LogPending {
+                    backend: backend.clone(),
+                },
+                true,
+            ) {
+                backend.log_pending();
+            }
+        }
+    }
+}
+
 #[allow(missing_docs)]
 pub struct AsyncPoolSinkBuilder {
     level_filter: LevelFilter,
@@ -195,6 +339,10 @@ impl AsyncPoolSinkBuilder {
         let backend = Arc::new(Backend {
             sinks: self.sinks.clone(),
             error_handler: Atomic::new(self.error_handler),
+            pending: Mutex::new(PendingRecords {
+                records: VecDeque::new(),
+                scheduled: false,
+            }),
         });
 
         let thread_pool = self.thread_pool.unwrap_or_else(default_thread_pool);
@@ -217,6 +365,14 @@ impl AsyncPoolSinkBuilder {
 pub(crate) struct Backend {
     sinks: Sinks,
     error_handler: helper::SinkErrorHandler,
+    pending: Mutex<PendingRecords>,
+}
+
+// Records queued by the sink itself for `OverflowPolicy::DropOldest`.
+struct PendingRecords {
+    records: VecDeque<RecordOwned>,
+    // Whether a `LogPending` task is in the channel.
+    scheduled: bool,
 }
 
 impl Backend {
@@ -228,7 +384,21 @@ impl Backend {
         result
     }
 
+    fn log_pending(&self) {
+        let records = {
+            let mut pending = self.pending.lock_expect();
+            pending.scheduled = false;
+            mem::take(&mut pending.records)
+        };
+        for record in records {
+            if let Err(err) = self.log(&record.as_ref()) {
+                self.handle_error(err)
+            }
+        }
+    }
+
     fn flush(&self) -> Result<()> {
+        self.log_pending();
         let mut result = Ok(());
         for sink in &self.sinks {
             result = Error::push_result(result, sink.flush());
@@ -236,21 +406,59 @@ impl Backend {
         result
     }
 
+    fn sync(&self) -> Result<()> {
+        self.log_pending();
+        let mut result = Ok(());
+        for sink in &self.sinks {
+            result = Error::push_result(result, sink.sync());
+        }
+        result
+    }
+
+    fn open(&self) -> Result<()> {
+        let mut result = Ok(());
+        for sink in &self.sinks {
+            result = Error::push_result(result, sink.open());
+        }
+        result
+    }
+
+    fn close(&self) -> Result<()> {
+        self.log_pending();
+        let mut result = Ok(());
+        for sink in &self.sinks {
+            result = Error::push_result(result, sink.close());
+        }
+        result
+    }
+
     fn handle_error(&self, err: Error) {
+        crate::diagnostics::report_error("AsyncPoolSink", &err);
         self.error_handler
             .load(Ordering::Relaxed)
             .unwrap_or(|err| default_error_handler("AsyncPoolSink", err))(err);
     }
 }
 
+#[allow(clippy::large_enum_variant)]
 pub(crate) enum Task {
     Log {
         backend: Arc<Backend>,
         record: RecordOwned,
     },
+    LogPending {
+        backend: Arc<Backend>,
+    },
     Flush {
         backend: Arc<Backend>,
     },
+    Sync {
+        backend: Arc<Backend>,
+        done: Sender<Result<()>>,
+    },
+    Close {
+        backend: Arc<Backend>,
+    },
 }
 
 impl Task {
@@ -262,11 +470,21 @@ impl Task {
                     backend.handle_error(err)
                 }
             }
+            Task::LogPending { backend } => backend.log_pending(),
             Task::Flush { backend } => {
                 if let Err(err) = backend.flush() {
                     backend.handle_error(err)
                 }
             }
+            Task::Sync { backend, done } => {
+                // The receiver may have gone if the waiting thread panicked.
+                _ = done.send(backend.sync());
+            }
+            Task::Close { backend } => {
+                if let Err(err) = backend.close() {
+                    backend.handle_error(err)
+                }
+            }
         }
     }
 }
@@ -361,6 +579,31 @@ mod tests {
         assert_eq!(counter_sink.flush_count(), 1);
     }
 
+    #[test]
+    fn sync() {
+        let counter_sink = Arc::new(TestSink::with_delay(Some(Duration::from_millis(50))));
+        let thread_pool = Arc::new(ThreadPool::builder().build().unwrap());
+        let logger = build_test_logger(|b| {
+            b.sink(Arc::new(
+                AsyncPoolSink::builder()
+                    .sink(counter_sink.clone())
+                    .thread_pool(thread_pool)
+                    .build()
+                    .unwrap(),
+            ))
+            .audit(true)
+        });
+
+        info!(logger: logger, "meow");
+        assert_eq!(counter_sink.log_count(), 1);
+        assert_eq!(counter_sink.flush_count(), 1);
+
+        crate::try_log!(logger: logger, Level::Info, "nya").unwrap();
+        logger.try_flush().unwrap();
+        assert_eq!(counter_sink.log_count(), 2);
+        assert_eq!(counter_sink.flush_count(), 3);
+    }
+
     #[test]
     fn async_opeartions() {
         let counter_sink = Arc::new(TestSink::with_delay(Some(Duration::from_secs(1))));
@@ -401,4 +644,80 @@ mod tests {
         assert_eq!(counter_sink.log_count(), 2);
         assert_eq!(counter_sink.flush_count(), 1);
     }
+
+    #[test]
+    fn drop_oldest() {
+        static DROPPED: AtomicUsize = AtomicUsize::new(0);
+
+        let counter_sink = Arc::new(TestSink::with_delay(Some(Duration::from_millis(50))));
+        let thread_pool = Arc::new(ThreadPool::builder().capacity(2).build().unwrap());
+        let logger = build_test_logger(|b| {
+            b.sink(Arc::new(
+                AsyncPoolSink::builder()
+                    .sink(counter_sink.clone())
+                    .thread_pool(thread_pool)
+                    .overflow_policy(OverflowPolicy::DropOldest)
+                    .error_handler(|_| _ = DROPPED.fetch_add(1, Ordering::Relaxed))
+                    .build()
+                    .unwrap(),
+            ))
+        });
+
+        for i in 0..10 {
+            info!(logger: logger, "{}", i);
+        }
+        // Dropping the logger drains the channel.
+        drop(logger);
+
+        let payloads = counter_sink.payloads();
+        assert!(payloads.ends_with(&["8".to_string(), "9".to_string()]));
+        assert_eq!(payloads.len() + DROPPED.load(Ordering::Relaxed), 10);
+    }
+
+    #[test]
+    fn drop_oldest_shared_pool() {
+        static BLOCK_ERRORS: AtomicUsize = AtomicUsize::new(0);
+        static DROPPED: AtomicUsize = AtomicUsize::new(0);
+
+        let thread_pool = Arc::new(ThreadPool::builder().capacity(2).build().unwrap());
+        let block_sink = Arc::new(TestSink::with_delay(Some(Duration::from_millis(5))));
+        let drop_sink = Arc::new(TestSink::with_delay(Some(Duration::from_millis(5))));
+        let logger = build_test_logger(|b| {
+            b.sink(Arc::new(
+                AsyncPoolSink::builder()
+                    .sink(block_sink.clone())
+                    .thread_pool(thread_pool.clone())
+                    .overflow_policy(OverflowPolicy::Block)
+                    .error_handler(|_| _ = BLOCK_ERRORS.fetch_add(1, Ordering::Relaxed))
+                    .build()
+                    .unwrap(),
+            ))
+            .sink(Arc::new(
+                AsyncPoolSink::builder()
+                    .sink(drop_sink.clone())
+                    .thread_pool(thread_pool)
+                    .overflow_policy(OverflowPolicy::DropOldest)
+                    .error_handler(|_| _ = DROPPED.fetch_add(1, Ordering::Relaxed))
+                    .build()
+                    .unwrap(),
+            ))
+            .flush_level_filter(LevelFilter::MoreSevereEqual(Level::Error))
+        });
+
+        for i in 0..20 {
+            info!(logger: logger, "{}", i);
+        }
+        error!(logger: logger, "20");
+        drop(logger);
+
+        let expected = (0..=20).map(|i| i.to_string()).collect::<Vec<_>>();
+        assert_eq!(block_sink.payloads(), expected);
+        assert_eq!(block_sink.flush_count(), 1);
+        assert_eq!(BLOCK_ERRORS.load(Ordering::Relaxed), 0);
+
+        let payloads = drop_sink.payloads();
+        assert_eq!(payloads.last().unwrap(), "20");
+        assert_eq!(payloads.len() + DROPPED.load(Ordering::Relaxed), 21);
+        assert_eq!(drop_sink.flush_count(), 1);
+    }
 }
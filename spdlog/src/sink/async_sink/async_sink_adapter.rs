@@ -0,0 +1,976 @@
+use std::{
+    collections::VecDeque,
+    convert::Infallible,
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll, Wake, Waker},
+    thread::{self, JoinHandle},
+    time::Instant,
+};
+
+use crate::{
+    diagnostics,
+    error::{InvalidArgumentError, SendToChannelError, SendToChannelErrorDropped},
+    sink::{helper, BatchConfig, OverflowPolicy, Sink},
+    sync::*,
+    thread_options, Error, Record, RecordOwned, Result,
+};
+
+/// An owned dynamically typed future, which can be sent across threads.
+pub type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
+
+/// Represents a sink writing records to a natively asynchronous destination,
+/// such as HTTP, gRPC or asynchronous sockets.
+///
+/// An `AsyncSink` is used via an [`AsyncSinkAdapter`], which is a normal
+/// [`Sink`] queuing records for the asynchronous sink.
+///
+/// Methods return boxed futures, so the trait can be implemented with `async`
+/// blocks:
+///
+/// ```
+/// use spdlog::{
+///     sink::{AsyncSink, BoxFuture},
+///     RecordOwned, Result,
+/// };
+///
+/// struct MyHttpSink {/* an asynchronous HTTP client */}
+///
+/// impl AsyncSink for MyHttpSink {
+///     fn log<'a>(&'a self, record: &'a RecordOwned, formatted: &'a str) -> BoxFuture<'a, Result<()>> {
+///         Box::pin(async move {
+///             // self.client.post(...).body(formatted).send().await
+///             Ok(())
+///         })
+///     }
+///
+///     fn flush(&self) -> BoxFuture<'_, Result<()>> {
+///         Box::pin(async { Ok(()) })
+///     }
+/// }
+/// ```
+pub trait AsyncSink: Send + Sync {
+    /// Logs a record.
+    ///
+    /// `formatted` is the record formatted by the formatter of the adapter.
+    fn log<'a>(&'a self, record: &'a RecordOwned, formatted: &'a str) -> BoxFuture<'a, Result<()>>;
+
+    /// Logs a batch of records.
+    ///
+    /// Each element is a record and its formatted text. Batches are made
+    /// according to the [`BatchConfig`] of the adapter.
+    ///
+    /// The default implementation logs the records one by one with
+    /// [`AsyncSink::log`]. Sinks whose destinations accept multiple records in
+    /// a single request should override it.
+    fn log_batch<'a>(&'a self, batch: &'a [(RecordOwned, String)]) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let mut result = Ok(());
+            for (record, formatted) in batch {
+                result = Error::push_result(result, self.log(record, formatted).await);
+            }
+            result
+        })
+    }
+
+    /// Flushes any buffered records.
+    fn flush(&self) -> BoxFuture<'_, Result<()>>;
+}
+
+type Spawner = Box<dyn FnOnce(BoxFuture<'static, ()>) + Send>;
+
+/// A [`Sink`] forwarding records to an [`AsyncSink`].
+///
+/// Records are formatted by the formatter of the adapter, and queued for the
+/// asynchronous sink. The queue is processed in order by a driver future,
+/// which is spawned on an asynchronous runtime via
+/// [`AsyncSinkAdapterBuilder::spawner`], or runs on a dedicated thread if no
+/// spawner is specified.
+///
+/// Queued records are delivered in batches via [`AsyncSink::log_batch`],
+/// according to the [`BatchConfig`] specified by
+/// [`AsyncSinkAdapterBuilder::batch`]. A flush waits for the batches in flight
+/// and delivers the pending records before flushing the asynchronous sink.
+///
+/// When the adapter is dropped, the driver finishes after the remaining
+/// records are processed. The dedicated threads, if any, are joined.
+///
+/// # Examples
+///
+/// With `tokio`:
+///
+/// ```ignore
+/// let sink = AsyncSinkAdapter::builder()
+///     .sink(Arc::new(MyHttpSink::new()))
+///     .spawner(|driver| {
+///         tokio::spawn(driver);
+///     })
+///     .build()?;
+/// ```
+pub struct AsyncSinkAdapter {
+    inner: Arc<AdapterInner>,
+    thread: Option<JoinHandle<()>>,
+    timer_thread: Option<JoinHandle<()>>,
+}
+
+struct AdapterInner {
+    common_impl: helper::CommonImpl,
+    sink: Arc<dyn AsyncSink>,
+    capacity: usize,
+    overflow_policy: OverflowPolicy,
+    batch: BatchConfig,
+    queue: Mutex<AdapterQueue>,
+    not_full: Condvar,
+    idle: Condvar,
+    timer: Condvar,
+}
+
+struct AdapterQueue {
+    tasks: VecDeque<AdapterTask>,
+    waker: Option<Waker>,
+    // Wakers of tasks waiting for space in the queue.
+    producers: Vec<Waker>,
+    in_flight: usize,
+    batch_deadline: Option<Instant>,
+    is_closed: bool,
+}
+
+// Almost all tasks are `Log`, boxing it would only add an allocation.
+#[allow(clippy::large_enum_variant)]
+enum AdapterTask {
+    Log {
+        record: RecordOwned,
+        formatted: String,
+    },
+    Flush,
+}
+
+impl AsyncSinkAdapter {
+    /// Gets a builder of `AsyncSinkAdapter` with default parameters:
+    ///
+    /// | Parameter         | Default Value                     |
+    /// |-------------------|-----------------------------------|
+    /// | [level_filter]    | `All`                             |
+    /// | [formatter]       | `FullFormatter`                   |
+    /// | [error_handler]   | [default error handler]           |
+    /// |                   |                                   |
+    /// | [sink]            | *must be specified*               |
+    /// | [capacity]        | `8192` (may change in the future) |
+    /// | [overflow_policy] | `Block`                           |
+    /// | [batch]           | `BatchConfig::new()`              |
+    /// | [spawner]         | `None` (a dedicated thread)       |
+    ///
+    /// [level_filter]: AsyncSinkAdapterBuilder::level_filter
+    /// [formatter]: AsyncSinkAdapterBuilder::formatter
+    /// [error_handler]: AsyncSinkAdapterBuilder::error_handler
+    /// [default error handler]: error/index.html#default-error-handler
+    /// [sink]: AsyncSinkAdapterBuilder::sink
+    /// [capacity]: AsyncSinkAdapterBuilder::capacity
+    /// [overflow_policy]: AsyncSinkAdapterBuilder::overflow_policy
+    /// [batch]: AsyncSinkAdapterBuilder::batch
+    /// [spawner]: AsyncSinkAdapterBuilder::spawner
+    #[must_use]
+    pub fn builder() -> AsyncSinkAdapterBuilder<()> {
+        AsyncSinkAdapterBuilder {
+            common_builder_impl: helper::CommonBuilderImpl::new(),
+            sink: (),
+            capacity: 8192,
+            overflow_policy: OverflowPolicy::Block,
+            batch: BatchConfig::new(),
+            spawner: None,
+        }
+    }
+
+    /// Gets a reference to the asynchronous sink.
+    #[must_use]
+    pub fn sink(&self) -> &Arc<dyn AsyncSink> {
+        &self.inner.sink
+    }
+}
+
+impl AdapterInner {
+    fn push(&self, task: AdapterTask) -> Result<()> {
+        let mut queue = self.queue.lock_expect();
+        let mut dropped = None;
+        while queue.tasks.len() >= self.capacity {
+            match self.overflow_policy {
+                OverflowPolicy::Block => queue = self.not_full.wait(queue).unwrap(),
+                OverflowPolicy::DropIncoming => {
+                    let dropped = match task {
+                        AdapterTask::Log { record, .. } => {
+                            SendToChannelErrorDropped::Record(Box::new(record))
+                        }
+                        AdapterTask::Flush => SendToChannelErrorDropped::Flush,
+                    };
+                    return Err(Error::SendToChannel(SendToChannelError::Full, dropped));
+                }
+                OverflowPolicy::DropOldest => {
+                    let oldest = queue
+                        .tasks
+                        .iter()
+                        .position(|task| matches!(task, AdapterTask::Log { .. }));
+                    match oldest.and_then(|index| queue.tasks.remove(index)) {
+                        Some(AdapterTask::Log { record, .. }) => {
+                            dropped = Some(record);
+                            break;
+                        }
+                        // Only flushes are pending, which are never dropped.
+                        _ => queue = self.not_full.wait(queue).unwrap(),
+                    }
+                }
+            }
+        }
+        queue.tasks.push_back(task);
+        if let Some(waker) = queue.waker.take() {
+            waker.wake();
+        }
+        drop(queue);
+
+        if let Some(record) = dropped {
+            self.handle_error(Error::SendToChannel(
+                SendToChannelError::Full,
+                SendToChannelErrorDropped::Record(Box::new(record)),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Takes the next batch or flush from the queue, as a future to be run by
+    /// the driver.
+    fn take_work(&self, cx: &Context<'_>, in_flight: usize) -> Work {
+        let mut queue = self.queue.lock_expect();
+
+        let ready = match queue.tasks.front() {
+            None if queue.is_closed && in_flight == 0 => {
+                self.idle.notify_all();
+                return Work::Done;
+            }
+            None => None,
+            // A flush waits for all batches in flight.
+            Some(AdapterTask::Flush) if in_flight == 0 => Some(1),
+            Some(AdapterTask::Flush) => None,
+            Some(AdapterTask::Log { .. }) if in_flight >= self.batch.concurrency() => None,
+            Some(AdapterTask::Log { .. }) => self.ready_batch_len(&mut queue),
+        };
+
+        match ready {
+            Some(len) => {
+                queue.in_flight += 1;
+                queue.batch_deadline = None;
+                self.not_full.notify_all();
+                queue.producers.drain(..).for_each(Waker::wake);
+
+                let mut tasks = queue.tasks.drain(..len);
+                let work = match tasks.next() {
+                    Some(AdapterTask::Flush) => {
+                        let sink = self.sink.clone();
+                        Box::pin(async move { sink.flush().await }) as BoxFuture<'static, _>
+                    }
+                    Some(AdapterTask::Log { record, formatted }) => {
+                        let batch = std::iter::once((record, formatted))
+                            .chain(tasks.map(|task| match task {
+                                AdapterTask::Log { record, formatted } => (record, formatted),
+                                AdapterTask::Flush => unreachable!(),
+                            }))
+                            .collect::<Vec<_>>();
+                        let sink = self.sink.clone();
+                        Box::pin(async move { sink.log_batch(&batch).await })
+                    }
+                    None => unreachable!(),
+                };
+                Work::Run(work)
+            }
+            None => {
+                queue.waker = Some(cx.waker().clone());
+                Work::Pending
+            }
+        }
+    }
+
+    /// Returns the number of records of the batch at the front of the queue if
+    /// it is ready to be delivered.
+    fn ready_batch_len(&self, queue: &mut AdapterQueue) -> Option<usize> {
+        let (mut records, mut bytes) = (0, 0);
+        for task in &queue.tasks {
+            match task {
+                AdapterTask::Log { formatted, .. }
+                    if self.batch.fits(records, bytes, formatted.len()) =>
+                {
+                    records += 1;
+                    bytes += formatted.len();
+                }
+                _ => break,
+            }
+        }
+
+        let now = Instant::now();
+        let is_ready = records < queue.tasks.len()
+            || self.batch.is_full(records, bytes)
+            || queue.is_closed
+            || self.batch.max_latency().is_zero()
+            || queue
+                .batch_deadline
+                .map_or(false, |deadline| now >= deadline);
+        if is_ready {
+            Some(records)
+        } else {
+            if queue.batch_deadline.is_none() {
+                queue.batch_deadline = Some(now + self.batch.max_latency());
+                self.timer.notify_all();
+            }
+            None
+        }
+    }
+
+    fn finish_work(&self, count: usize) {
+        let mut queue = self.queue.lock_expect();
+        queue.in_flight -= count;
+        if queue.in_flight == 0 && queue.tasks.is_empty() {
+            self.idle.notify_all();
+        }
+    }
+
+    fn wait_idle(&self) {
+        let queue = self.queue.lock_expect();
+        let _queue = self
+            .idle
+            .wait_while(queue, |queue| {
+                queue.in_flight != 0 || !queue.tasks.is_empty()
+            })
+            .unwrap();
+    }
+
+    fn close(&self) {
+        let mut queue = self.queue.lock_expect();
+        queue.is_closed = true;
+        if let Some(waker) = queue.waker.take() {
+            waker.wake();
+        }
+        queue.producers.drain(..).for_each(Waker::wake);
+        self.timer.notify_all();
+    }
+
+    fn handle_error(&self, err: Error) {
+        diagnostics::report_error("AsyncSinkAdapter", &err);
+        self.common_impl
+            .non_returnable_error("AsyncSinkAdapter", err);
+    }
+
+    /// Wakes the driver when the deadline of the pending batch is reached.
+    fn run_timer(&self) {
+        let mut queue = self.queue.lock_expect();
+        while !queue.is_closed {
+            queue = match queue.batch_deadline {
+                Some(deadline) => match deadline.checked_duration_since(Instant::now()) {
+                    Some(timeout) if !timeout.is_zero() => {
+                        self.timer.wait_timeout(queue, timeout).unwrap().0
+                    }
+                    _ => {
+                        if let Some(waker) = queue.waker.take() {
+                            waker.wake();
+                        }
+                        // Waits for the driver to take the batch.
+                        self.timer.wait(queue).unwrap()
+                    }
+                },
+                None => self.timer.wait(queue).unwrap(),
+            };
+        }
+    }
+}
+
+enum Work {
+    Run(BoxFuture<'static, Result<()>>),
+    Pending,
+    Done,
+}
+
+/// Runs batches and flushes taken from the queue, until the adapter is closed
+/// and the queue is drained.
+struct Driver {
+    inner: Arc<AdapterInner>,
+    in_flight: Vec<BoxFuture<'static, Result<()>>>,
+}
+
+impl Future for Driver {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let this = self.get_mut();
+        loop {
+            let (mut index, mut finished) = (0, 0);
+            while index < this.in_flight.len() {
+                match this.in_flight[index].as_mut().poll(cx) {
+                    Poll::Ready(res) => {
+                        if let Err(err) = res {
+                            this.inner.handle_error(err);
+                        }
+                        drop(this.in_flight.swap_remove(index));
+                        finished += 1;
+                    }
+                    Poll::Pending => index += 1,
+                }
+            }
+            if finished != 0 {
+                this.inner.finish_work(finished);
+            }
+
+            match this.inner.take_work(cx, this.in_flight.len()) {
+                Work::Run(work) => this.in_flight.push(work),
+                Work::Pending => return Poll::Pending,
+                Work::Done => return Poll::Ready(()),
+            }
+        }
+    }
+}
+
+fn block_on<F: Future>(future: F) -> F::Output {
+    struct ThreadWaker(thread::Thread);
+
+    impl Wake for ThreadWaker {
+        fn wake(self: Arc<Self>) {
+            self.0.unpark();
+        }
+    }
+
+    let mut future = Box::pin(future);
+    let waker = Waker::from(Arc::new(ThreadWaker(thread::current())));
+    let mut cx = Context::from_waker(&waker);
+    loop {
+        match future.as_mut().poll(&mut cx) {
+            Poll::Ready(output) => return output,
+            Poll::Pending => thread::park(),
+        }
+    }
+}
+
+impl Sink for AsyncSinkAdapter {
+    fn log(&self, record: &Record) -> Result<()> {
+        let formatted = self
+            .inner
+            .common_impl
+            .format_with(record, |string_buf, _| Ok(string_buf.to_string()))?;
+
+        self.inner.push(AdapterTask::Log {
+            record: record.to_owned(),
+            formatted,
+        })
+    }
+
+    fn flush(&self) -> Result<()> {
+        self.inner.push(AdapterTask::Flush)?;
+        if crate::IS_TEARING_DOWN.load(Ordering::SeqCst) && self.thread.is_some() {
+            // This is the final flush, wait for the dedicated thread to process
+            // the remaining records before the program exits.
+            self.inner.wait_idle();
+        }
+        Ok(())
+    }
+
+    /// For [`AsyncSinkAdapter`], the function blocks until the queued records
+    /// have been written and the [`AsyncSink`] has been flushed.
+    ///
+    /// Errors returned by the [`AsyncSink`] are still passed to the error
+    /// handler. If the adapter is driven by a [spawner], the function must not
+    /// be called on the threads of the runtime driving it.
+    ///
+    /// [spawner]: AsyncSinkAdapterBuilder::spawner
+    fn sync(&self) -> Result<()> {
+        self.inner.push(AdapterTask::Flush)?;
+        if self.thread.is_some() || !crate::IS_TEARING_DOWN.load(Ordering::SeqCst) {
+            self.inner.wait_idle();
+        }
+        Ok(())
+    }
+
+    /// For [`AsyncSinkAdapter`], the function returns [`Poll::Pending`] while
+    /// the queue is full, and wakes the task once the driver takes records
+    /// from the queue.
+    #[cfg(feature = "log-async")]
+    fn poll_capacity(&self, cx: &mut Context<'_>) -> Poll<()> {
+        let mut queue = self.inner.queue.lock_expect();
+        if queue.tasks.len() < self.inner.capacity || queue.is_closed {
+            return Poll::Ready(());
+        }
+        if !queue.producers.iter().any(|w| w.will_wake(cx.waker())) {
+            queue.producers.push(cx.waker().clone());
+        }
+        Poll::Pending
+    }
+
+    helper::common_impl!(@Sink: inner.common_impl);
+}
+
+impl Drop for AsyncSinkAdapter {
+    fn drop(&mut self) {
+        self.inner.close();
+        if let Some(thread) = self.thread.take() {
+            thread
+                .join()
+                .expect("a thread running an AsyncSinkAdapter driver panicked");
+        }
+        if let Some(thread) = self.timer_thread.take() {
+            thread
+                .join()
+                .expect("a timer thread of an AsyncSinkAdapter panicked");
+        }
+    }
+}
+
+/// #
+#[doc = include_str!("../../include/doc/generic-builder-note.md")]
+pub struct AsyncSinkAdapterBuilder<ArgS> {
+    common_builder_impl: helper::CommonBuilderImpl,
+    sink: ArgS,
+    capacity: usize,
+    overflow_policy: OverflowPolicy,
+    batch: BatchConfig,
+    spawner: Option<Spawner>,
+}
+
+impl<ArgS> AsyncSinkAdapterBuilder<ArgS> {
+    /// Specifies the asynchronous sink.
+    ///
+    /// This parameter is **required**.
+    #[must_use]
+    pub fn sink(self, sink: Arc<dyn AsyncSink>) -> AsyncSinkAdapterBuilder<Arc<dyn AsyncSink>> {
+        AsyncSinkAdapterBuilder {
+            common_builder_impl: self.common_builder_impl,
+            sink,
+            capacity: self.capacity,
+            overflow_policy: self.overflow_policy,
+            batch: self.batch,
+            spawner: self.spawner,
+        }
+    }
+
+    /// Specifies the maximum number of queued operations.
+    ///
+    /// When the queue is full, incoming operations are handled according to
+    /// the overflow policy.
+    ///
+    /// This parameter is **optional**.
+    #[must_use]
+    pub fn capacity(mut self, capacity: usize) -> Self {
+        self.capacity = capacity;
+        self
+    }
+
+    /// Specifies the overflow policy.
+    ///
+    /// This parameter is **optional**.
+    #[must_use]
+    pub fn overflow_policy(mut self, overflow_policy: OverflowPolicy) -> Self {
+        self.overflow_policy = overflow_policy;
+        self
+    }
+
+    /// Specifies how records are batched before being delivered to the
+    /// asynchronous sink.
+    ///
+    /// If the maximum latency is not zero, a dedicated thread is spawned to
+    /// deliver pending batches in time.
+    ///
+    /// This parameter is **optional**.
+    #[must_use]
+    pub fn batch(mut self, batch: BatchConfig) -> Self {
+        self.batch = batch;
+        self
+    }
+
+    /// Specifies a function spawning the driver future of the adapter on an
+    /// asynchronous runtime, e.g. `|driver| { tokio::spawn(driver); }`.
+    ///
+    /// If it is not specified, the driver runs on a dedicated thread.
+    ///
+    /// This parameter is **optional**.
+    #[must_use]
+    pub fn spawner<F>(mut self, spawner: F) -> Self
+    where
+        F: FnOnce(BoxFuture<'static, ()>) + Send + 'static,
+    {
+        self.spawner = Some(Box::new(spawner));
+        self
+    }
+
+    helper::common_impl!(@SinkBuilder: common_builder_impl);
+}
+
+impl AsyncSinkAdapterBuilder<()> {
+    #[doc(hidden)]
+    #[deprecated(note = "\n\n\
+        builder compile-time error:\n\
+        - missing required parameter `sink`\n\n\
+    ")]
+    pub fn build(self, _: Infallible) {}
+}
+
+impl AsyncSinkAdapterBuilder<Arc<dyn AsyncSink>> {
+    /// Builds an [`AsyncSinkAdapter`].
+    ///
+    /// # Error
+    ///
+    /// If the capacity is `0`, or the batch configuration is invalid,
+    /// [`Error::InvalidArgument`] will be returned.
+    pub fn build(self) -> Result<AsyncSinkAdapter> {
+        if self.capacity == 0 {
+            return Err(Error::InvalidArgument(InvalidArgumentError::Capacity(
+                "cannot be 0".to_string(),
+            )));
+        }
+        self.batch.validate()?;
+
+        let inner = Arc::new(AdapterInner {
+            common_impl: helper::CommonImpl::from_builder(self.common_builder_impl),
+            sink: self.sink,
+            capacity: self.capacity,
+            overflow_policy: self.overflow_policy,
+            batch: self.batch,
+            queue: Mutex::new(AdapterQueue {
+                tasks: VecDeque::new(),
+                waker: None,
+                producers: vec![],
+                in_flight: 0,
+                batch_deadline: None,
+                is_closed: false,
+            }),
+            not_full: Condvar::new(),
+            idle: Condvar::new(),
+            timer: Condvar::new(),
+        });
+
+        let timer_thread = (!self.batch.max_latency().is_zero()).then(|| {
+            let inner = inner.clone();
+            thread_options::spawn_worker("spdlog-batch", move || inner.run_timer())
+        });
+        let driver = Box::pin(Driver {
+            inner: inner.clone(),
+            in_flight: vec![],
+        });
+        let thread = match self.spawner {
+            Some(spawner) => {
+                spawner(driver);
+                None
+            }
+            None => Some(thread_options::spawn_worker("spdlog-async", move || {
+                block_on(driver)
+            })),
+        };
+
+        Ok(AsyncSinkAdapter {
+            inner,
+            thread,
+            timer_thread,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use super::*;
+    use crate::{prelude::*, test_utils::*};
+
+    // Returns `Pending` once before completing, to exercise waking.
+    struct YieldOnce(bool);
+
+    impl Future for YieldOnce {
+        type Output = ();
+
+        fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+            if self.0 {
+                Poll::Ready(())
+            } else {
+                self.0 = true;
+                cx.waker().wake_by_ref();
+                Poll::Pending
+            }
+        }
+    }
+
+    #[derive(Default)]
+    struct MockAsyncSink {
+        logged: Mutex<Vec<String>>,
+        flush_count: AtomicUsize,
+    }
+
+    impl AsyncSink for MockAsyncSink {
+        fn log<'a>(
+            &'a self,
+            record: &'a RecordOwned,
+            formatted: &'a str,
+        ) -> BoxFuture<'a, Result<()>> {
+            Box::pin(async move {
+                YieldOnce(false).await;
+                assert_eq!(record.payload(), formatted);
+                self.logged.lock_expect().push(formatted.to_string());
+                Ok(())
+            })
+        }
+
+        fn flush(&self) -> BoxFuture<'_, Result<()>> {
+            Box::pin(async move {
+                self.flush_count.fetch_add(1, Ordering::SeqCst);
+                Ok(())
+            })
+        }
+    }
+
+    #[test]
+    fn dedicated_thread() {
+        let async_sink = Arc::new(MockAsyncSink::default());
+        let adapter = Arc::new(
+            AsyncSinkAdapter::builder()
+                .sink(async_sink.clone())
+                .formatter(Box::new(NoModFormatter::new()))
+                .capacity(2)
+                .build()
+                .unwrap(),
+        );
+        let logger = build_test_logger(|b| b.sink(adapter.clone()));
+
+        for i in 0..10 {
+            info!(logger: logger, "{}", i);
+        }
+        logger.flush();
+        drop(logger);
+        drop(adapter);
+
+        assert_eq!(
+            *async_sink.logged.lock_expect(),
+            (0..10).map(|i| i.to_string()).collect::<Vec<_>>()
+        );
+        assert_eq!(async_sink.flush_count.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn spawner() {
+        let async_sink = Arc::new(MockAsyncSink::default());
+        let adapter = Arc::new(
+            AsyncSinkAdapter::builder()
+                .sink(async_sink.clone())
+                .formatter(Box::new(NoModFormatter::new()))
+                .spawner(|driver| {
+                    thread::spawn(move || block_on(driver));
+                })
+                .build()
+                .unwrap(),
+        );
+        let logger = build_test_logger(|b| b.sink(adapter));
+
+        info!(logger: logger, "hello");
+        info!(logger: logger, "world");
+
+        let deadline = Instant::now() + Duration::from_secs(10);
+        while async_sink.logged.lock_expect().len() < 2 {
+            assert!(Instant::now() < deadline);
+            thread::sleep(Duration::from_millis(1));
+        }
+        assert_eq!(*async_sink.logged.lock_expect(), ["hello", "world"]);
+    }
+
+    #[test]
+    fn batch() {
+        #[derive(Default)]
+        struct BatchSink {
+            batches: Mutex<Vec<Vec<String>>>,
+        }
+
+        impl AsyncSink for BatchSink {
+            fn log<'a>(&'a self, _: &'a RecordOwned, _: &'a str) -> BoxFuture<'a, Result<()>> {
+                unreachable!()
+            }
+
+            fn log_batch<'a>(
+                &'a self,
+                batch: &'a [(RecordOwned, String)],
+            ) -> BoxFuture<'a, Result<()>> {
+                Box::pin(async move {
+                    let batch = batch.iter().map(|(_, formatted)| formatted.clone());
+                    self.batches.lock_expect().push(batch.collect());
+                    Ok(())
+                })
+            }
+
+            fn flush(&self) -> BoxFuture<'_, Result<()>> {
+                Box::pin(async { Ok(()) })
+            }
+        }
+
+        let async_sink = Arc::new(BatchSink::default());
+        let adapter = Arc::new(
+            AsyncSinkAdapter::builder()
+                .sink(async_sink.clone())
+                .formatter(Box::new(NoModFormatter::new()))
+                .batch(
+                    BatchConfig::new()
+                        .with_max_records(3)
+                        .with_max_latency(Duration::from_secs(60)),
+                )
+                .build()
+                .unwrap(),
+        );
+        let logger = build_test_logger(|b| b.sink(adapter.clone()));
+
+        for i in 0..5 {
+            info!(logger: logger, "{}", i);
+        }
+        let deadline = Instant::now() + Duration::from_secs(10);
+        while async_sink.batches.lock_expect().is_empty() {
+            assert!(Instant::now() < deadline);
+            thread::sleep(Duration::from_millis(1));
+        }
+        // The rest waits for more records until a flush.
+        assert_eq!(*async_sink.batches.lock_expect(), [["0", "1", "2"]]);
+
+        info!(logger: logger, "5");
+        logger.flush();
+        info!(logger: logger, "6");
+        drop(logger);
+        drop(adapter);
+
+        assert_eq!(
+            *async_sink.batches.lock_expect(),
+            [vec!["0", "1", "2"], vec!["3", "4", "5"], vec!["6"]]
+        );
+
+        assert!(matches!(
+            AsyncSinkAdapter::builder()
+                .sink(async_sink)
+                .batch(BatchConfig::new().with_max_records(0))
+                .build(),
+            Err(Error::InvalidArgument(InvalidArgumentError::BatchConfig(_)))
+        ));
+    }
+
+    #[cfg(feature = "log-async")]
+    #[test]
+    fn log_async() {
+        let async_sink = Arc::new(MockAsyncSink::default());
+        let adapter = Arc::new(
+            AsyncSinkAdapter::builder()
+                .sink(async_sink.clone())
+                .formatter(Box::new(NoModFormatter::new()))
+                .capacity(1)
+                .overflow_policy(OverflowPolicy::DropIncoming)
+                .build()
+                .unwrap(),
+        );
+        let logger = build_test_logger(|b| b.sink(adapter.clone()));
+
+        // Records are not dropped, as producers wait for the queue to have room.
+        block_on(async {
+            for i in 0..10 {
+                crate::log_async!(logger: logger, Level::Info, "{}", i).await;
+            }
+        });
+        // Dropping the adapter drains the queue.
+        drop(logger);
+        drop(adapter);
+
+        assert_eq!(
+            *async_sink.logged.lock_expect(),
+            (0..10).map(|i| i.to_string()).collect::<Vec<_>>()
+        );
+    }
+
+    #[cfg(feature = "log-async")]
+    #[test]
+    fn log_async_backtrace() {
+        let test_sink = Arc::new(TestSink::new());
+        let logger = build_test_logger(|b| {
+            b.sink(test_sink.clone())
+                .level_filter(LevelFilter::MoreSevereEqual(Level::Info))
+        });
+        logger.enable_backtrace(2);
+
+        block_on(async {
+            crate::log_async!(logger: logger, Level::Debug, "debug").await;
+            crate::log_async!(logger: logger, Level::Info, "info").await;
+        });
+        assert_eq!(test_sink.payloads(), ["info"]);
+
+        logger.dump_backtrace();
+        assert_eq!(test_sink.payloads()[2..4], ["debug", "info"]);
+    }
+
+    #[test]
+    fn drop_incoming() {
+        struct PendingSink;
+
+        impl AsyncSink for PendingSink {
+            fn log<'a>(&'a self, _: &'a RecordOwned, _: &'a str) -> BoxFuture<'a, Result<()>> {
+                Box::pin(std::future::pending())
+            }
+
+            fn flush(&self) -> BoxFuture<'_, Result<()>> {
+                Box::pin(std::future::pending())
+            }
+        }
+
+        // The driver is never run, so the queue is never consumed.
+        let adapter = AsyncSinkAdapter::builder()
+            .sink(Arc::new(PendingSink))
+            .capacity(1)
+            .overflow_policy(OverflowPolicy::DropIncoming)
+            .spawner(|_| {})
+            .build()
+            .unwrap();
+
+        assert!(adapter.flush().is_ok());
+        assert!(matches!(
+            adapter.flush(),
+            Err(Error::SendToChannel(
+                SendToChannelError::Full,
+                SendToChannelErrorDropped::Flush
+            ))
+        ));
+    }
+
+    #[test]
+    fn drop_oldest() {
+        static DROPPED: AtomicUsize = AtomicUsize::new(0);
+
+        // The driver is never run, so the queue is never consumed.
+        let adapter = Arc::new(
+            AsyncSinkAdapter::builder()
+                .sink(Arc::new(MockAsyncSink::default()))
+                .formatter(Box::new(NoModFormatter::new()))
+                .capacity(3)
+                .overflow_policy(OverflowPolicy::DropOldest)
+                .error_handler(|err| {
+                    assert!(matches!(
+                        err,
+                        Error::SendToChannel(
+                            SendToChannelError::Full,
+                            SendToChannelErrorDropped::Record(_)
+                        )
+                    ));
+                    DROPPED.fetch_add(1, Ordering::Relaxed);
+                })
+                .spawner(|_| {})
+                .build()
+                .unwrap(),
+        );
+        let logger = build_test_logger(|b| b.sink(adapter.clone()));
+
+        info!(logger: logger, "0");
+        adapter.flush().unwrap();
+        for i in 1..5 {
+            info!(logger: logger, "{}", i);
+        }
+
+        let queue = adapter.inner.queue.lock_expect();
+        let tasks = queue
+            .tasks
+            .iter()
+            .map(|task| match task {
+                AdapterTask::Log { formatted, .. } => formatted.as_str(),
+                AdapterTask::Flush => "flush",
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(tasks, ["flush", "3", "4"]);
+        assert_eq!(DROPPED.load(Ordering::Relaxed), 3);
+    }
+}
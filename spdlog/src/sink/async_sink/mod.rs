@@ -1,13 +1,16 @@
 mod async_pool_sink;
+mod async_sink_adapter;
 
 pub use async_pool_sink::*;
+pub use async_sink_adapter::*;
 
 /// Overflow policy for [asynchronous sinks].
 ///
-/// When the channel is full, an incoming operation is handled according to the
-/// specified policy.
+/// When the channel (or the queue of [`AsyncSinkAdapter`]) is full, an incoming
+/// operation is handled according to the specified policy.
 ///
 /// [asynchronous sinks]: crate::sink::AsyncPoolSink
+/// [`AsyncSinkAdapter`]: crate::sink::AsyncSinkAdapter
 #[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
 #[non_exhaustive]
 pub enum OverflowPolicy {
@@ -15,5 +18,20 @@ pub enum OverflowPolicy {
     Block,
     /// Drops the incoming operation.
     DropIncoming,
-    // DropOldest, // waiting for https://github.com/crossbeam-rs/crossbeam/issues/400
+    /// Drops the oldest pending `log` operation to make room for the incoming
+    /// operation.
+    ///
+    /// The dropped record is reported to the error handler of the sink it was
+    /// sent to, as an [`Error::SendToChannel`]. Other pending operations, e.g.
+    /// `flush`, are never dropped.
+    ///
+    /// Since the channel of a thread pool is shared by all [`AsyncPoolSink`]s
+    /// on it, an [`AsyncPoolSink`] with this policy queues its records in its
+    /// own queue as large as the capacity of the thread pool, and only drops
+    /// its own records. Its other operations wait for the channel like
+    /// [`OverflowPolicy::Block`].
+    ///
+    /// [`Error::SendToChannel`]: crate::Error::SendToChannel
+    /// [`AsyncPoolSink`]: crate::sink::AsyncPoolSink
+    DropOldest,
 }
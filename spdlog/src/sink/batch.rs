@@ -0,0 +1,189 @@
+use std::time::Duration;
+
+use crate::{error::InvalidArgumentError, Error, Result};
+
+/// Represents how a sink batches records before delivering them to its
+/// destination.
+///
+/// A batch is delivered as soon as any of the limits is reached:
+///
+/// - it contains [`max_records`] records;
+/// - its formatted records add up to [`max_bytes`] bytes;
+/// - its oldest record has waited for [`max_latency`].
+///
+/// Up to [`concurrency`] batches may be in flight at the same time. With a
+/// concurrency greater than 1, batches may be delivered out of order.
+///
+/// Larger and less frequent batches increase throughput, while smaller and
+/// more frequent batches reduce the delivery delay. The default configuration
+/// delivers each record on its own, as soon as possible.
+///
+/// | Limit           | Default Value |
+/// |-----------------|---------------|
+/// | [`max_records`] | `1`           |
+/// | [`max_bytes`]   | unlimited     |
+/// | [`max_latency`] | `0`           |
+/// | [`concurrency`] | `1`           |
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+///
+/// use spdlog::sink::BatchConfig;
+///
+/// let config = BatchConfig::new()
+///     .with_max_records(500)
+///     .with_max_bytes(1024 * 1024)
+///     .with_max_latency(Duration::from_millis(200))
+///     .with_concurrency(4);
+/// ```
+///
+/// [`max_records`]: BatchConfig::with_max_records
+/// [`max_bytes`]: BatchConfig::with_max_bytes
+/// [`max_latency`]: BatchConfig::with_max_latency
+/// [`concurrency`]: BatchConfig::with_concurrency
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct BatchConfig {
+    max_records: usize,
+    max_bytes: Option<usize>,
+    max_latency: Duration,
+    concurrency: usize,
+}
+
+impl BatchConfig {
+    /// Constructs a `BatchConfig` with default limits.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            max_records: 1,
+            max_bytes: None,
+            max_latency: Duration::ZERO,
+            concurrency: 1,
+        }
+    }
+
+    /// Gets the maximum number of records in a batch.
+    #[must_use]
+    pub fn max_records(&self) -> usize {
+        self.max_records
+    }
+
+    /// Sets the maximum number of records in a batch.
+    ///
+    /// It must be greater than 0.
+    #[must_use]
+    pub fn with_max_records(mut self, max_records: usize) -> Self {
+        self.max_records = max_records;
+        self
+    }
+
+    /// Gets the maximum total length of formatted records in a batch, in
+    /// bytes.
+    ///
+    /// `None` means unlimited.
+    #[must_use]
+    pub fn max_bytes(&self) -> Option<usize> {
+        self.max_bytes
+    }
+
+    /// Sets the maximum total length of formatted records in a batch, in
+    /// bytes.
+    ///
+    /// A record longer than the limit is delivered in a batch on its own.
+    #[must_use]
+    pub fn with_max_bytes(mut self, max_bytes: usize) -> Self {
+        self.max_bytes = Some(max_bytes);
+        self
+    }
+
+    /// Gets the maximum time a record waits for its batch to be filled.
+    #[must_use]
+    pub fn max_latency(&self) -> Duration {
+        self.max_latency
+    }
+
+    /// Sets the maximum time a record waits for its batch to be filled.
+    ///
+    /// If it is `0`, a batch is delivered with the records available at the
+    /// time, without waiting for more.
+    #[must_use]
+    pub fn with_max_latency(mut self, max_latency: Duration) -> Self {
+        self.max_latency = max_latency;
+        self
+    }
+
+    /// Gets the maximum number of batches in flight at the same time.
+    #[must_use]
+    pub fn concurrency(&self) -> usize {
+        self.concurrency
+    }
+
+    /// Sets the maximum number of batches in flight at the same time.
+    ///
+    /// It must be greater than 0.
+    #[must_use]
+    pub fn with_concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency;
+        self
+    }
+
+    /// Determines if a batch with the given number of records and total length
+    /// is full.
+    #[must_use]
+    pub(crate) fn is_full(&self, records: usize, bytes: usize) -> bool {
+        records >= self.max_records || self.max_bytes.map_or(false, |max| bytes >= max)
+    }
+
+    /// Determines if a record with the given length still fits in a batch with
+    /// the given number of records and total length.
+    #[must_use]
+    pub(crate) fn fits(&self, records: usize, bytes: usize, len: usize) -> bool {
+        records == 0
+            || (records < self.max_records && self.max_bytes.map_or(true, |max| bytes + len <= max))
+    }
+
+    pub(crate) fn validate(&self) -> Result<()> {
+        let err = if self.max_records == 0 {
+            "'max_records' cannot be 0"
+        } else if self.concurrency == 0 {
+            "'concurrency' cannot be 0"
+        } else {
+            return Ok(());
+        };
+        Err(Error::InvalidArgument(InvalidArgumentError::BatchConfig(
+            err.to_string(),
+        )))
+    }
+}
+
+impl Default for BatchConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn limits() {
+        let config = BatchConfig::new().with_max_records(3).with_max_bytes(10);
+
+        assert!(config.fits(0, 0, 100));
+        assert!(config.fits(2, 4, 6));
+        assert!(!config.fits(2, 4, 7));
+        assert!(!config.fits(3, 3, 1));
+
+        assert!(!config.is_full(2, 9));
+        assert!(config.is_full(3, 3));
+        assert!(config.is_full(1, 10));
+
+        assert!(BatchConfig::new().validate().is_ok());
+        assert!(matches!(
+            BatchConfig::new().with_concurrency(0).validate(),
+            Err(Error::InvalidArgument(InvalidArgumentError::BatchConfig(_)))
+        ));
+    }
+}
@@ -180,6 +180,44 @@ impl Sink for DedupSink {
         self.flush_sinks()
     }
 
+    /// For [`DedupSink`], the function syncs all internal sinks.
+    fn sync(&self) -> Result<()> {
+        #[allow(clippy::manual_try_fold)] // https://github.com/rust-lang/rust-clippy/issues/11554
+        self.sinks.iter().fold(Ok(()), |result, sink| {
+            Error::push_result(result, sink.sync())
+        })
+    }
+
+    /// For [`DedupSink`], the function polls all internal sinks.
+    #[cfg(feature = "log-async")]
+    fn poll_capacity(&self, cx: &mut std::task::Context<'_>) -> std::task::Poll<()> {
+        crate::sink::poll_capacity(&self.sinks, cx)
+    }
+
+    /// For [`DedupSink`], the function opens all internal sinks.
+    fn open(&self) -> Result<()> {
+        #[allow(clippy::manual_try_fold)] // https://github.com/rust-lang/rust-clippy/issues/11554
+        self.sinks.iter().fold(Ok(()), |result, sink| {
+            Error::push_result(result, sink.open())
+        })
+    }
+
+    /// For [`DedupSink`], the function closes all internal sinks.
+    fn close(&self) -> Result<()> {
+        #[allow(clippy::manual_try_fold)] // https://github.com/rust-lang/rust-clippy/issues/11554
+        self.sinks.iter().fold(Ok(()), |result, sink| {
+            Error::push_result(result, sink.close())
+        })
+    }
+
+    /// For [`DedupSink`], the function checks all internal sinks.
+    fn health(&self) -> Result<()> {
+        #[allow(clippy::manual_try_fold)] // https://github.com/rust-lang/rust-clippy/issues/11554
+        self.sinks.iter().fold(Ok(()), |result, sink| {
+            Error::push_result(result, sink.health())
+        })
+    }
+
     helper::common_impl!(@Sink: common_impl);
 }
 
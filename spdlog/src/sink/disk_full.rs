@@ -0,0 +1,316 @@
+use std::{
+    collections::VecDeque,
+    io,
+    time::{Duration, SystemTime},
+};
+
+use crate::{
+    diagnostics, sink::Sink, sync::*, Error, Level, Record, RecordOwned, Result, StringBuf,
+};
+
+/// How long a file sink keeps degrading after the disk becomes full, before
+/// trying to write to the file again.
+const RETRY_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Represents how a file sink behaves when the disk is full.
+///
+/// Except for [`DiskFullStrategy::Error`], the sink only returns the error of
+/// the write that found the disk full. Afterwards, records are handled by the
+/// strategy, and the sink tries writing to the file again at most once per
+/// second (according to the time of records), until the disk accepts writes.
+#[derive(Clone)]
+#[non_exhaustive]
+pub enum DiskFullStrategy {
+    /// Returns an error for each record that cannot be written.
+    ///
+    /// This is the default strategy.
+    Error,
+    /// Drops records while the disk is full.
+    ///
+    /// The number of dropped records is reported to the [diagnostics logger]
+    /// once the disk accepts writes again.
+    ///
+    /// [diagnostics logger]: crate::diagnostics
+    Drop,
+    /// Keeps the most recent formatted records in memory while the disk is
+    /// full, and writes them before other records once the disk accepts writes
+    /// again.
+    ///
+    /// Records exceeding the capacity are dropped from the oldest, and the
+    /// number of them is reported like [`DiskFullStrategy::Drop`].
+    Buffer {
+        /// The maximum number of records kept in memory.
+        capacity: usize,
+    },
+    /// Forwards records to another sink while the disk is full.
+    Failover(Arc<dyn Sink>),
+}
+
+impl Default for DiskFullStrategy {
+    fn default() -> Self {
+        Self::Error
+    }
+}
+
+pub(crate) struct DiskFullHandler {
+    strategy: DiskFullStrategy,
+    state: SpinMutex<DiskFullState>,
+}
+
+#[derive(Default)]
+struct DiskFullState {
+    degraded_since: Option<SystemTime>,
+    buffer: VecDeque<(RecordOwned, StringBuf)>,
+    dropped: u64,
+}
+
+impl DiskFullHandler {
+    #[must_use]
+    pub(crate) fn new(strategy: DiskFullStrategy) -> Self {
+        Self {
+            strategy,
+            state: SpinMutex::new(DiskFullState::default()),
+        }
+    }
+
+    /// Writes a formatted record with `write`, handling the record by the
+    /// strategy if the disk is full.
+    pub(crate) fn log(
+        &self,
+        from: &str,
+        record: &Record,
+        string_buf: &StringBuf,
+        mut write: impl FnMut(&Record, &StringBuf) -> Result<()>,
+    ) -> Result<()> {
+        if let DiskFullStrategy::Error = self.strategy {
+            return write(record, string_buf);
+        }
+
+        let mut state = self.state.lock();
+
+        if let Some(since) = state.degraded_since {
+            let retry = record
+                .time()
+                .duration_since(since)
+                .map_or(true, |elapsed| elapsed >= RETRY_INTERVAL);
+            if !retry {
+                return self.degrade(&mut state, record, string_buf);
+            }
+        }
+
+        while let Some((buffered, buffered_buf)) = state.buffer.front() {
+            match write(&buffered.as_ref(), buffered_buf) {
+                Ok(()) => {
+                    state.buffer.pop_front();
+                }
+                Err(err) if is_disk_full(&err) => {
+                    state.degraded_since = Some(record.time());
+                    return self.degrade(&mut state, record, string_buf);
+                }
+                Err(err) => return Err(err),
+            }
+        }
+
+        match write(record, string_buf) {
+            Ok(()) => {
+                if state.degraded_since.take().is_some() {
+                    report_recovered(from, &mut state);
+                }
+                Ok(())
+            }
+            Err(err) if is_disk_full(&err) => {
+                let is_first = state.degraded_since.replace(record.time()).is_none();
+                self.degrade(&mut state, record, string_buf)?;
+                if is_first {
+                    Err(err)
+                } else {
+                    Ok(())
+                }
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Flushes with `flush`, ignoring the error if the disk is full and the
+    /// strategy handles it.
+    pub(crate) fn flush(&self, flush: impl FnOnce() -> Result<()>) -> Result<()> {
+        let res = match (&self.strategy, flush()) {
+            (DiskFullStrategy::Error, res) => res,
+            (_, Err(err)) if is_disk_full(&err) => {
+                let mut state = self.state.lock();
+                if state.degraded_since.is_some() {
+                    Ok(())
+                } else {
+                    state.degraded_since = Some(SystemTime::now());
+                    Err(err)
+                }
+            }
+            (_, res) => res,
+        };
+
+        if let DiskFullStrategy::Failover(sink) = &self.strategy {
+            Error::push_result(res, sink.flush())
+        } else {
+            res
+        }
+    }
+
+    fn degrade(
+        &self,
+        state: &mut DiskFullState,
+        record: &Record,
+        string_buf: &StringBuf,
+    ) -> Result<()> {
+        match &self.strategy {
+            DiskFullStrategy::Error => unreachable!(),
+            DiskFullStrategy::Drop => {
+                state.dropped += 1;
+                Ok(())
+            }
+            DiskFullStrategy::Buffer { capacity } => {
+                if *capacity == 0 {
+                    state.dropped += 1;
+                    return Ok(());
+                }
+                if state.buffer.len() >= *capacity {
+                    state.buffer.pop_front();
+                    state.dropped += 1;
+                }
+                state
+                    .buffer
+                    .push_back((record.to_owned(), string_buf.clone()));
+                Ok(())
+            }
+            DiskFullStrategy::Failover(sink) => {
+                if sink.should_log(record.level()) {
+                    sink.log(record)
+                } else {
+                    Ok(())
+                }
+            }
+        }
+    }
+}
+
+fn report_recovered(from: &str, state: &mut DiskFullState) {
+    let dropped = std::mem::take(&mut state.dropped);
+    diagnostics::report(
+        Level::Warn,
+        from,
+        format_args!(
+            "disk accepts writes again, {} records dropped while it was full",
+            dropped
+        ),
+    );
+}
+
+#[must_use]
+fn is_disk_full(err: &Error) -> bool {
+    match err {
+        Error::WriteRecord(err) | Error::FlushBuffer(err) | Error::OpenFile(err) => {
+            is_disk_full_io(err)
+        }
+        Error::Multiple(errs) => errs.iter().any(is_disk_full),
+        _ => false,
+    }
+}
+
+#[must_use]
+fn is_disk_full_io(err: &io::Error) -> bool {
+    cfg_if::cfg_if! {
+        if #[cfg(unix)] {
+            err.raw_os_error() == Some(libc::ENOSPC)
+        } else if #[cfg(windows)] {
+            // `ERROR_HANDLE_DISK_FULL` and `ERROR_DISK_FULL`
+            matches!(err.raw_os_error(), Some(39) | Some(112))
+        } else {
+            let _ = err;
+            false
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use super::*;
+    use crate::test_utils::*;
+
+    fn disk_full() -> Error {
+        cfg_if::cfg_if! {
+            if #[cfg(unix)] {
+                Error::WriteRecord(io::Error::from_raw_os_error(libc::ENOSPC))
+            } else {
+                Error::WriteRecord(io::Error::from_raw_os_error(112))
+            }
+        }
+    }
+
+    fn record_at(payload: &'static str, secs: u64) -> Record<'static> {
+        let mut record = Record::new(Level::Info, payload, None, None);
+        record.set_time(SystemTime::UNIX_EPOCH + Duration::from_secs(secs));
+        record
+    }
+
+    #[test]
+    fn buffer() {
+        let handler = DiskFullHandler::new(DiskFullStrategy::Buffer { capacity: 2 });
+        let is_full = Cell::new(true);
+        let mut written = vec![];
+
+        let log = |payload: &'static str, secs: u64, written: &mut Vec<String>| {
+            let record = record_at(payload, secs);
+            handler.log("test", &record, &payload.into(), |record, _| {
+                if is_full.get() {
+                    Err(disk_full())
+                } else {
+                    written.push(record.payload().to_string());
+                    Ok(())
+                }
+            })
+        };
+
+        assert!(log("1", 0, &mut written).is_err());
+        assert!(log("2", 0, &mut written).is_ok());
+        assert!(log("3", 0, &mut written).is_ok());
+        is_full.set(false);
+        // Still within the retry interval.
+        assert!(log("4", 0, &mut written).is_ok());
+        assert!(written.is_empty());
+
+        assert!(log("5", 1, &mut written).is_ok());
+        assert_eq!(written, ["3", "4", "5"]);
+    }
+
+    #[test]
+    fn failover() {
+        let failover = Arc::new(TestSink::new());
+        let handler = DiskFullHandler::new(DiskFullStrategy::Failover(failover.clone()));
+
+        let record = record_at("a", 0);
+        let res = handler.log("test", &record, &"a".into(), |_, _| Err(disk_full()));
+        assert!(res.is_err());
+        let record = record_at("b", 0);
+        let res = handler.log("test", &record, &"b".into(), |_, _| Err(disk_full()));
+        assert!(res.is_ok());
+        assert_eq!(failover.payloads(), ["a", "b"]);
+
+        let record = record_at("c", 0);
+        let res = handler.log("test", &record, &"c".into(), |_, _| {
+            Err(Error::WriteRecord(io::Error::new(io::ErrorKind::Other, "")))
+        });
+        assert!(res.is_ok());
+        assert_eq!(failover.payloads(), ["a", "b", "c"]);
+    }
+
+    #[test]
+    fn other_errors() {
+        let handler = DiskFullHandler::new(DiskFullStrategy::Drop);
+        let record = record_at("a", 0);
+        let res = handler.log("test", &record, &"a".into(), |_, _| {
+            Err(Error::WriteRecord(io::Error::new(io::ErrorKind::Other, "")))
+        });
+        assert!(matches!(res, Err(Error::WriteRecord(_))));
+    }
+}
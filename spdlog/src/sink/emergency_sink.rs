@@ -0,0 +1,326 @@
+use std::{
+    fmt::{self, Write as _},
+    fs::File,
+    io,
+    path::PathBuf,
+};
+
+use crate::{
+    formatter::{Formatter, TimeZone},
+    sink::{helper::SINK_DEFAULT_LEVEL_FILTER, Sink},
+    sync::*,
+    Error, ErrorHandler, Level, LevelFilter, Record, Result,
+};
+
+// Longer lines are truncated.
+const MAX_LINE_LEN: usize = 1024;
+
+/// A sink that is safe to use when the program is crashing.
+///
+/// Records are written to a file opened in advance, or to `stderr`, with a raw
+/// system call (`write(2)` on Unix, `WriteFile` on Windows) for each record.
+/// Formatting and writing do not allocate memory or acquire locks, records are
+/// formatted into a fixed-size buffer on the stack. So the sink can be used
+/// from a signal handler, or in a panic hook while the program may be in an
+/// inconsistent state.
+///
+/// Records are always formatted in a fixed layout similar to
+/// [`FullFormatter`], with the time in UTC, since looking up the local time
+/// zone is not async-signal-safe. Records longer than 1024 bytes are
+/// truncated. Formatters and error handlers set on the sink are ignored,
+/// errors are returned to the caller.
+///
+/// Set the sink with [`set_emergency_sink`] to make the [crash handler] and the
+/// panic hook installed by [`init`] write their final records to it.
+///
+/// [`FullFormatter`]: crate::formatter::FullFormatter
+/// [`set_emergency_sink`]: crate::set_emergency_sink
+/// [crash handler]: crate::install_crash_handler
+/// [`init`]: crate::init
+pub struct EmergencySink {
+    level_filter: Atomic<LevelFilter>,
+    file: Option<File>,
+}
+
+impl EmergencySink {
+    /// Gets a builder of `EmergencySink` with default parameters:
+    ///
+    /// | Parameter        | Default Value |
+    /// |------------------|---------------|
+    /// | [level_filter]   | `All`         |
+    /// |                  |               |
+    /// | [path]           | `None`        |
+    /// | [truncate]       | `false`       |
+    ///
+    /// [level_filter]: EmergencySinkBuilder::level_filter
+    /// [path]: EmergencySinkBuilder::path
+    /// [truncate]: EmergencySinkBuilder::truncate
+    #[must_use]
+    pub fn builder() -> EmergencySinkBuilder {
+        EmergencySinkBuilder {
+            level_filter: SINK_DEFAULT_LEVEL_FILTER,
+            path: None,
+            truncate: false,
+        }
+    }
+
+    /// Writes a message on the given level directly, as if a record were
+    /// logged without a logger name.
+    ///
+    /// The message is written regardless of the level filter. It is
+    /// async-signal-safe as long as the [`Display`] implementations of the
+    /// formatted arguments are.
+    ///
+    /// [`Display`]: std::fmt::Display
+    pub fn write_message(&self, level: Level, message: fmt::Arguments) -> io::Result<()> {
+        let mut line = LineBuf::new();
+        _ = write!(line, "[{}] {}", level.as_str(), message);
+        self.write_line(line)
+    }
+
+    fn write_line(&self, mut line: LineBuf) -> io::Result<()> {
+        line.finish();
+        let mut bytes = line.as_bytes();
+        while !bytes.is_empty() {
+            match self.write_raw(bytes) {
+                Ok(0) => return Err(io::ErrorKind::WriteZero.into()),
+                Ok(written) => bytes = &bytes[written..],
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => {}
+                Err(err) => return Err(err),
+            }
+        }
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    fn write_raw(&self, bytes: &[u8]) -> io::Result<usize> {
+        use std::os::unix::io::AsRawFd;
+
+        let fd = match &self.file {
+            Some(file) => file.as_raw_fd(),
+            None => libc::STDERR_FILENO,
+        };
+        let written = unsafe { libc::write(fd, bytes.as_ptr().cast(), bytes.len()) };
+        if written < 0 {
+            Err(io::Error::last_os_error())
+        } else {
+            Ok(written as usize)
+        }
+    }
+
+    #[cfg(windows)]
+    fn write_raw(&self, bytes: &[u8]) -> io::Result<usize> {
+        use std::{os::windows::io::AsRawHandle, ptr};
+
+        use winapi::{shared::minwindef::DWORD, um::fileapi::WriteFile};
+
+        let handle = match &self.file {
+            Some(file) => file.as_raw_handle(),
+            None => io::stderr().as_raw_handle(),
+        };
+        let len = bytes.len().min(DWORD::MAX as usize) as DWORD;
+        let mut written: DWORD = 0;
+        let succeeded = unsafe {
+            WriteFile(
+                handle.cast(),
+                bytes.as_ptr().cast(),
+                len,
+                &mut written,
+                ptr::null_mut(),
+            )
+        };
+        if succeeded == 0 {
+            Err(io::Error::last_os_error())
+        } else {
+            Ok(written as usize)
+        }
+    }
+}
+
+impl Sink for EmergencySink {
+    fn log(&self, record: &Record) -> Result<()> {
+        let mut line = LineBuf::new();
+        let time = TimeZone::UTC.date_time(record.time());
+        let millisecond = record
+            .time()
+            .duration_since(std::time::SystemTime::UNIX_EPOCH)
+            .map_or(0, |since| since.subsec_millis());
+        _ = write!(
+            line,
+            "[{}-{:02}-{:02} {:02}:{:02}:{:02}.{:03}Z] [",
+            time.year(),
+            time.month(),
+            time.day(),
+            time.hour(),
+            time.minute(),
+            time.second(),
+            millisecond
+        );
+        if let Some(logger_name) = record.logger_name() {
+            _ = write!(line, "{}] [", logger_name);
+        }
+        _ = write!(line, "{}] {}", record.level().as_str(), record.payload());
+
+        self.write_line(line).map_err(Error::WriteRecord)
+    }
+
+    fn flush(&self) -> Result<()> {
+        // Records are not buffered.
+        Ok(())
+    }
+
+    fn level_filter(&self) -> LevelFilter {
+        self.level_filter.load(Ordering::Relaxed)
+    }
+
+    fn set_level_filter(&self, level_filter: LevelFilter) {
+        self.level_filter.store(level_filter, Ordering::Relaxed);
+    }
+
+    fn set_formatter(&self, _formatter: Box<dyn Formatter>) {
+        // Records are formatted in a fixed layout.
+    }
+
+    fn set_error_handler(&self, _handler: Option<ErrorHandler>) {
+        // Errors are always returned.
+    }
+}
+
+#[allow(missing_docs)]
+pub struct EmergencySinkBuilder {
+    level_filter: LevelFilter,
+    path: Option<PathBuf>,
+    truncate: bool,
+}
+
+impl EmergencySinkBuilder {
+    /// Specifies a log level filter.
+    ///
+    /// This parameter is **optional**.
+    #[must_use]
+    pub fn level_filter(mut self, level_filter: LevelFilter) -> Self {
+        self.level_filter = level_filter;
+        self
+    }
+
+    /// The path of the file to write records to.
+    ///
+    /// The file is opened when the sink is built, and stays open until the sink
+    /// is dropped. If it is not specified, records are written to `stderr`.
+    ///
+    /// This parameter is **optional**.
+    #[must_use]
+    pub fn path<P>(mut self, path: P) -> Self
+    where
+        P: Into<PathBuf>,
+    {
+        self.path = Some(path.into());
+        self
+    }
+
+    /// Truncates the contents when opening an existing file.
+    ///
+    /// If it is `true`, the existing contents of the file will be discarded.
+    ///
+    /// This parameter is **optional**.
+    #[must_use]
+    pub fn truncate(mut self, truncate: bool) -> Self {
+        self.truncate = truncate;
+        self
+    }
+
+    /// Builds a [`EmergencySink`].
+    ///
+    /// # Error
+    ///
+    /// If an error occurs opening the file, [`Error::CreateDirectory`] or
+    /// [`Error::OpenFile`] will be returned.
+    pub fn build(self) -> Result<EmergencySink> {
+        let file = self
+            .path
+            .map(|path| crate::utils::open_file(path, self.truncate))
+            .transpose()?;
+
+        Ok(EmergencySink {
+            level_filter: Atomic::new(self.level_filter),
+            file,
+        })
+    }
+}
+
+// A line buffer on the stack, silently truncating the content that does not
+// fit.
+struct LineBuf {
+    buf: [u8; MAX_LINE_LEN],
+    len: usize,
+}
+
+impl LineBuf {
+    #[must_use]
+    fn new() -> Self {
+        Self {
+            buf: [0; MAX_LINE_LEN],
+            len: 0,
+        }
+    }
+
+    fn finish(&mut self) {
+        // Reserve the last byte for the line ending.
+        let len = self.len.min(MAX_LINE_LEN - 1);
+        self.buf[len] = b'\n';
+        self.len = len + 1;
+    }
+
+    #[must_use]
+    fn as_bytes(&self) -> &[u8] {
+        &self.buf[..self.len]
+    }
+}
+
+impl fmt::Write for LineBuf {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let remaining = MAX_LINE_LEN - 1 - self.len.min(MAX_LINE_LEN - 1);
+        let mut len = s.len().min(remaining);
+        // Do not split a character.
+        while !s.is_char_boundary(len) {
+            len -= 1;
+        }
+        self.buf[self.len..self.len + len].copy_from_slice(&s.as_bytes()[..len]);
+        self.len += len;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+    use crate::{prelude::*, test_utils::*};
+
+    #[test]
+    fn write_to_file() {
+        let path = TEST_LOGS_PATH.join("emergency_sink.log");
+        let sink = Arc::new(
+            EmergencySink::builder()
+                .path(&path)
+                .truncate(true)
+                .build()
+                .unwrap(),
+        );
+        let logger = build_test_logger(|b| b.name("crash").sink(sink.clone()));
+
+        error!(logger: logger, "out of memory");
+        sink.write_message(Level::Critical, format_args!("signal {}", 11))
+            .unwrap();
+        error!(logger: logger, "{}", "é".repeat(MAX_LINE_LEN));
+
+        let contents = fs::read_to_string(&path).unwrap();
+        let lines: Vec<_> = contents.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].starts_with('['));
+        assert!(lines[0].ends_with("Z] [crash] [error] out of memory"));
+        assert_eq!(lines[1], "[critical] signal 11");
+        assert!(lines[2].len() < MAX_LINE_LEN && lines[2].ends_with('é'));
+    }
+}
@@ -2,16 +2,19 @@
 
 use std::{
     convert::Infallible,
-    fs::File,
+    fs::{self, File, OpenOptions},
     io::{BufWriter, Write},
     path::{Path, PathBuf},
+    sync::atomic::AtomicUsize,
 };
 
 use crate::{
-    formatter::FormatterContext,
-    sink::{helper, Sink},
+    sink::{
+        disk_full::{DiskFullHandler, DiskFullStrategy},
+        helper, Sink,
+    },
     sync::*,
-    utils, Error, Record, Result, StringBuf,
+    utils, Error, Record, Result,
 };
 
 /// A sink with a file as the target.
@@ -19,7 +22,13 @@ use crate::{
 /// It writes logs to a single file. If you want to automatically rotate into
 /// multiple files, see  [`RotatingFileSink`].
 ///
-/// The file and directories will be created recursively if they do not exist.
+/// The file and directories will be created recursively if they do not exist,
+/// see [`FileSinkBuilder::create_dirs`].
+///
+/// The sink can recover from transient filesystem problems, e.g. the file or
+/// its directory being removed, or a network filesystem being remounted, by
+/// reopening the file after consecutive write errors. See
+/// [`FileSinkBuilder::reopen_after_errors`].
 ///
 /// # Examples
 ///
@@ -29,20 +38,46 @@ use crate::{
 /// [./examples]: https://github.com/SpriteOvO/spdlog-rs/tree/main/spdlog/examples
 pub struct FileSink {
     common_impl: helper::CommonImpl,
+    path: PathBuf,
     file: SpinMutex<BufWriter<File>>,
+    disk_full: DiskFullHandler,
+    reopen_after_errors: usize,
+    consecutive_errors: AtomicUsize,
+    buffer_capacity: usize,
+    open_options: FileOpenOptions,
+}
+
+type ConfigureOpenOptions = dyn Fn(&mut OpenOptions) + Send + Sync;
+
+// Options for opening the file, kept for reopening it.
+#[derive(Clone)]
+struct FileOpenOptions {
+    create_dirs: bool,
+    custom: Option<Arc<ConfigureOpenOptions>>,
+    #[cfg_attr(not(windows), allow(dead_code))]
+    share_mode: Option<u32>,
 }
 
+// The same as the default capacity of `BufWriter`.
+const DEFAULT_BUFFER_CAPACITY: usize = 8 * 1024;
+
 impl FileSink {
     /// Gets a builder of `FileSink` with default parameters:
     ///
-    /// | Parameter       | Default Value           |
-    /// |-----------------|-------------------------|
-    /// | [level_filter]  | `All`                   |
-    /// | [formatter]     | `FullFormatter`         |
-    /// | [error_handler] | [default error handler] |
-    /// |                 |                         |
-    /// | [path]          | *must be specified*     |
-    /// | [truncate]      | `false`                 |
+    /// | Parameter       | Default Value             |
+    /// |-----------------|---------------------------|
+    /// | [level_filter]  | `All`                     |
+    /// | [formatter]     | `FullFormatter`           |
+    /// | [error_handler] | [default error handler]   |
+    /// |                 |                           |
+    /// | [path]          | *must be specified*       |
+    /// | [truncate]      | `false`                   |
+    /// | [disk_full]     | `DiskFullStrategy::Error` |
+    /// | [reopen]        | `0` (never)               |
+    /// | [buffer]        | `8192` (8 KiB)            |
+    /// | [create_dirs]   | `true`                    |
+    /// | [open_options]  | `None`                    |
+    /// | [share_mode]    | *read, write and delete*  |
     ///
     /// [level_filter]: FileSinkBuilder::level_filter
     /// [formatter]: FileSinkBuilder::formatter
@@ -50,11 +85,25 @@ impl FileSink {
     /// [default error handler]: error/index.html#default-error-handler
     /// [path]: FileSinkBuilder::path
     /// [truncate]: FileSinkBuilder::truncate
+    /// [disk_full]: FileSinkBuilder::disk_full_strategy
+    /// [reopen]: FileSinkBuilder::reopen_after_errors
+    /// [buffer]: FileSinkBuilder::buffer_capacity
+    /// [create_dirs]: FileSinkBuilder::create_dirs
+    /// [open_options]: FileSinkBuilder::open_options
+    /// [share_mode]: FileSinkBuilder::share_mode
     #[must_use]
     pub fn builder() -> FileSinkBuilder<()> {
         FileSinkBuilder {
             path: (),
             truncate: false,
+            disk_full_strategy: DiskFullStrategy::Error,
+            reopen_after_errors: 0,
+            buffer_capacity: DEFAULT_BUFFER_CAPACITY,
+            open_options: FileOpenOptions {
+                create_dirs: true,
+                custom: None,
+                share_mode: None,
+            },
             common_builder_impl: helper::CommonBuilderImpl::new(),
         }
     }
@@ -82,27 +131,98 @@ impl FileSink {
             .truncate(truncate)
             .build()
     }
+
+    // Counts consecutive errors, and reopens the file if there are too many.
+    fn check_result(&self, file: &mut BufWriter<File>, res: Result<()>) -> Result<()> {
+        let err = match res {
+            Ok(()) => {
+                self.consecutive_errors.store(0, Ordering::Relaxed);
+                return Ok(());
+            }
+            Err(err) => err,
+        };
+        if self.reopen_after_errors == 0 {
+            return Err(err);
+        }
+
+        let errors = self.consecutive_errors.fetch_add(1, Ordering::Relaxed) + 1;
+        if errors < self.reopen_after_errors {
+            return Err(err);
+        }
+        self.consecutive_errors.store(0, Ordering::Relaxed);
+
+        match self.reopen(file) {
+            Ok(()) => {
+                self.common_impl.non_returnable_error(
+                    "FileSink",
+                    Error::Recovered(format!(
+                        "reopened file '{}' after {} consecutive errors",
+                        self.path.display(),
+                        errors
+                    )),
+                );
+                Err(err)
+            }
+            Err(reopen_err) => Err(Error::Multiple(vec![err, reopen_err])),
+        }
+    }
+
+    fn reopen(&self, file: &mut BufWriter<File>) -> Result<()> {
+        let new_file = BufWriter::with_capacity(
+            self.buffer_capacity,
+            self.open_options.open(&self.path, false)?,
+        );
+        // Moves the data not written yet to the new file, without flushing it to
+        // the old one.
+        let (_, buffered) = std::mem::replace(file, new_file).into_parts();
+        if let Ok(buffered) = buffered {
+            file.write_all(&buffered).map_err(Error::WriteRecord)?;
+        }
+        Ok(())
+    }
 }
 
 impl Sink for FileSink {
     fn log(&self, record: &Record) -> Result<()> {
-        let mut string_buf = StringBuf::new();
-        let mut ctx = FormatterContext::new();
-        self.common_impl
-            .formatter
-            .read()
-            .format(record, &mut string_buf, &mut ctx)?;
-
-        self.file
-            .lock()
-            .write_all(string_buf.as_bytes())
-            .map_err(Error::WriteRecord)?;
-
-        Ok(())
+        self.common_impl.format_with(record, |string_buf, _| {
+            self.disk_full
+                .log("FileSink", record, string_buf, |_, string_buf| {
+                    let mut file = self.file.lock();
+                    let res = file
+                        .write_all(string_buf.as_bytes())
+                        .map_err(Error::WriteRecord);
+                    self.check_result(&mut file, res)
+                })
+        })
     }
 
     fn flush(&self) -> Result<()> {
-        self.file.lock().flush().map_err(Error::FlushBuffer)
+        self.disk_full.flush(|| {
+            let mut file = self.file.lock();
+            let res = file.flush().map_err(Error::FlushBuffer);
+            self.check_result(&mut file, res)
+        })
+    }
+
+    /// For [`FileSink`], the function flushes the buffer and synchronizes the
+    /// file contents to the storage device.
+    fn sync(&self) -> Result<()> {
+        self.disk_full.flush(|| {
+            let mut file = self.file.lock();
+            let res = file
+                .flush()
+                .and_then(|_| file.get_ref().sync_data())
+                .map_err(Error::FlushBuffer);
+            self.check_result(&mut file, res)
+        })
+    }
+
+    /// Returns an error if the file is no longer accessible at its path, for
+    /// example, it has been removed.
+    fn health(&self) -> Result<()> {
+        fs::metadata(&self.path)
+            .map(|_| ())
+            .map_err(Error::QueryFileMetadata)
     }
 
     helper::common_impl!(@Sink: common_impl);
@@ -125,6 +245,10 @@ pub struct FileSinkBuilder<ArgPath> {
     common_builder_impl: helper::CommonBuilderImpl,
     path: ArgPath,
     truncate: bool,
+    disk_full_strategy: DiskFullStrategy,
+    reopen_after_errors: usize,
+    buffer_capacity: usize,
+    open_options: FileOpenOptions,
 }
 
 impl<ArgPath> FileSinkBuilder<ArgPath> {
@@ -140,6 +264,10 @@ impl<ArgPath> FileSinkBuilder<ArgPath> {
             common_builder_impl: self.common_builder_impl,
             path: path.into(),
             truncate: self.truncate,
+            disk_full_strategy: self.disk_full_strategy,
+            reopen_after_errors: self.reopen_after_errors,
+            buffer_capacity: self.buffer_capacity,
+            open_options: self.open_options,
         }
     }
 
@@ -154,6 +282,153 @@ impl<ArgPath> FileSinkBuilder<ArgPath> {
         self
     }
 
+    /// Specifies how the sink behaves when the disk is full.
+    ///
+    /// See [`DiskFullStrategy`] for the details.
+    ///
+    /// This parameter is **optional**.
+    #[must_use]
+    pub fn disk_full_strategy(mut self, strategy: DiskFullStrategy) -> Self {
+        self.disk_full_strategy = strategy;
+        self
+    }
+
+    /// Reopens the file after the given number of consecutive write or flush
+    /// errors.
+    ///
+    /// The file and directories are recreated if they no longer exist, and the
+    /// data not written yet is moved to the reopened file. A successful reopen
+    /// is reported to the error handler as [`Error::Recovered`], and a failed
+    /// one is returned along with the write error. Either way, the counting
+    /// starts over.
+    ///
+    /// Specify `0` to never reopen the file.
+    ///
+    /// This parameter is **optional**.
+    #[must_use]
+    pub fn reopen_after_errors(mut self, errors: usize) -> Self {
+        self.reopen_after_errors = errors;
+        self
+    }
+
+    /// Specifies the capacity of the write buffer in bytes.
+    ///
+    /// Formatted records are collected in the buffer, and written to the file
+    /// when the buffer is full or the sink is flushed. A larger buffer makes
+    /// fewer system calls under high throughput, at the cost of records
+    /// staying in memory longer, so it is usually combined with a flush policy
+    /// of the logger, e.g. [`LoggerBuilder::flush_level_filter`] or
+    /// [`Logger::set_flush_period`]. Records larger than the buffer are written
+    /// directly.
+    ///
+    /// Specify `0` to write each record to the file directly.
+    ///
+    /// This parameter is **optional**.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::{sync::Arc, time::Duration};
+    ///
+    /// use spdlog::{prelude::*, sink::FileSink};
+    ///
+    /// # fn main() -> Result<(), spdlog::Error> {
+    /// # let path = std::env::temp_dir().join("buffered.log");
+    /// let sink = FileSink::builder()
+    ///     .path(path)
+    ///     .buffer_capacity(1024 * 1024)
+    ///     .build()?;
+    /// let logger = Arc::new(
+    ///     Logger::builder()
+    ///         .sink(Arc::new(sink))
+    ///         .flush_level_filter(LevelFilter::MoreSevereEqual(Level::Warn))
+    ///         .build()?,
+    /// );
+    /// logger.set_flush_period(Some(Duration::from_secs(1)));
+    /// # Ok(()) }
+    /// ```
+    ///
+    /// [`LoggerBuilder::flush_level_filter`]: crate::LoggerBuilder::flush_level_filter
+    /// [`Logger::set_flush_period`]: crate::Logger::set_flush_period
+    #[must_use]
+    pub fn buffer_capacity(mut self, capacity: usize) -> Self {
+        self.buffer_capacity = capacity;
+        self
+    }
+
+    /// Specifies whether to create the missing parent directories of the file.
+    ///
+    /// If it is `false` and the directory of the file does not exist, building
+    /// the sink fails with [`Error::OpenFile`]. It also applies when the file
+    /// is reopened (see [`FileSinkBuilder::reopen_after_errors`]).
+    ///
+    /// This parameter is **optional**.
+    #[must_use]
+    pub fn create_dirs(mut self, create_dirs: bool) -> Self {
+        self.open_options.create_dirs = create_dirs;
+        self
+    }
+
+    /// Customizes the [`OpenOptions`] used to open the file.
+    ///
+    /// The function is called with the options set by the sink, i.e. `create`
+    /// and either `append` or `write` and `truncate` (see
+    /// [`FileSinkBuilder::truncate`]), and can override them or set
+    /// platform-specific ones, such as the permissions of a new file on Unix.
+    /// It is also called when the file is reopened (see
+    /// [`FileSinkBuilder::reopen_after_errors`]), with `truncate` unset.
+    ///
+    /// This parameter is **optional**.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(unix)]
+    /// # fn main() -> Result<(), spdlog::Error> {
+    /// use std::os::unix::fs::OpenOptionsExt;
+    ///
+    /// use spdlog::sink::FileSink;
+    ///
+    /// # let path = std::env::temp_dir().join("private.log");
+    /// let sink = FileSink::builder()
+    ///     .path(path)
+    ///     .open_options(|open_options| {
+    ///         open_options.mode(0o600);
+    ///     })
+    ///     .build()?;
+    /// # Ok(()) }
+    /// # #[cfg(not(unix))]
+    /// # fn main() {}
+    /// ```
+    #[must_use]
+    pub fn open_options<F>(mut self, configure: F) -> Self
+    where
+        F: Fn(&mut OpenOptions) + Send + Sync + 'static,
+    {
+        self.open_options.custom = Some(Arc::new(configure));
+        self
+    }
+
+    /// Specifies the sharing mode of the file on Windows, which determines
+    /// whether other processes can open the file while the sink holds it open.
+    ///
+    /// The value is a combination of `FILE_SHARE_READ` (`0x1`),
+    /// `FILE_SHARE_WRITE` (`0x2`) and `FILE_SHARE_DELETE` (`0x4`). By default,
+    /// all of them are allowed, so that external tools can read the file, and
+    /// rename or delete it for rotation. Specify a narrower mode to prevent
+    /// other processes from doing so.
+    ///
+    /// The mode also applies when the file is reopened (see
+    /// [`FileSinkBuilder::reopen_after_errors`]).
+    ///
+    /// This parameter is **optional**, and only available on Windows.
+    #[cfg(any(windows, all(doc, not(doctest))))]
+    #[must_use]
+    pub fn share_mode(mut self, share_mode: u32) -> Self {
+        self.open_options.share_mode = Some(share_mode);
+        self
+    }
+
     helper::common_impl!(@SinkBuilder: common_builder_impl);
 }
 
@@ -174,13 +449,199 @@ impl FileSinkBuilder<PathBuf> {
     /// If an error occurs opening the file, [`Error::CreateDirectory`] or
     /// [`Error::OpenFile`] will be returned.
     pub fn build(self) -> Result<FileSink> {
-        let file = utils::open_file(self.path, self.truncate)?;
+        let file = self.open_options.open(&self.path, self.truncate)?;
 
         let sink = FileSink {
             common_impl: helper::CommonImpl::from_builder(self.common_builder_impl),
-            file: SpinMutex::new(BufWriter::new(file)),
+            path: self.path,
+            file: SpinMutex::new(BufWriter::with_capacity(self.buffer_capacity, file)),
+            disk_full: DiskFullHandler::new(self.disk_full_strategy),
+            reopen_after_errors: self.reopen_after_errors,
+            consecutive_errors: AtomicUsize::new(0),
+            buffer_capacity: self.buffer_capacity,
+            open_options: self.open_options,
         };
 
         Ok(sink)
     }
 }
+
+impl FileOpenOptions {
+    fn open(&self, path: &Path, truncate: bool) -> Result<File> {
+        utils::open_file_with(path, truncate, self.create_dirs, |open_options| {
+            #[cfg(windows)]
+            if let Some(share_mode) = self.share_mode {
+                use std::os::windows::fs::OpenOptionsExt;
+
+                open_options.share_mode(share_mode);
+            }
+            if let Some(custom) = &self.custom {
+                custom(open_options);
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{prelude::*, test_utils::*};
+
+    #[test]
+    fn health() {
+        let path = TEST_LOGS_PATH.join("file_sink_health.log");
+        let sink = Arc::new(FileSink::builder().path(&path).build().unwrap());
+        let logger = build_test_logger(|b| b.sink(sink.clone()));
+
+        info!(logger: logger, "hello");
+        assert!(sink.health().is_ok());
+        assert!(logger.health().is_ok());
+
+        fs::remove_file(&path).unwrap();
+        assert!(matches!(sink.health(), Err(Error::QueryFileMetadata(_))));
+        assert!(matches!(logger.health(), Err(Error::QueryFileMetadata(_))));
+    }
+
+    #[test]
+    fn buffer_capacity() {
+        let build = |file_name, capacity| {
+            let path = TEST_LOGS_PATH.join(file_name);
+            let sink = FileSink::builder()
+                .path(&path)
+                .truncate(true)
+                .buffer_capacity(capacity)
+                .formatter(Box::new(NoModFormatter::new()))
+                .build()
+                .unwrap();
+            (sink, path)
+        };
+        let record = Record::new(Level::Info, "hello", None, None);
+
+        let (sink, path) = build("file_sink_buffered.log", 1024);
+        sink.log(&record).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "");
+        sink.flush().unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "hello");
+
+        let (sink, path) = build("file_sink_unbuffered.log", 0);
+        sink.log(&record).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "hello");
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn reopen_after_errors() {
+        static RECOVERED: AtomicUsize = AtomicUsize::new(0);
+
+        let sink = FileSink::builder()
+            .path("/dev/full")
+            .reopen_after_errors(2)
+            .error_handler(|err| {
+                assert!(matches!(err, Error::Recovered(_)));
+                RECOVERED.fetch_add(1, Ordering::Relaxed);
+            })
+            .build()
+            .unwrap();
+
+        let record = Record::new(Level::Info, "a".repeat(16 * 1024), None, None);
+        for _ in 0..5 {
+            assert!(sink.log(&record).is_err());
+        }
+        assert_eq!(RECOVERED.load(Ordering::Relaxed), 2);
+    }
+
+    // Removing an open file fails on Windows.
+    #[cfg(unix)]
+    #[test]
+    fn reopen_removed_directory() {
+        let dir = TEST_LOGS_PATH.join("file_sink_recreate");
+        let path = dir.join("recreate.log");
+        let sink = FileSink::builder()
+            .path(&path)
+            .truncate(true)
+            .formatter(Box::new(NoModFormatter::new()))
+            .build()
+            .unwrap();
+
+        sink.log(&Record::new(Level::Info, "lost", None, None))
+            .unwrap();
+        sink.flush().unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        sink.reopen(&mut sink.file.lock()).unwrap();
+        sink.log(&Record::new(Level::Info, "kept", None, None))
+            .unwrap();
+        sink.flush().unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "kept");
+    }
+
+    #[test]
+    fn open_options() {
+        let dir = TEST_LOGS_PATH.join("file_sink_open_options");
+        if dir.exists() {
+            fs::remove_dir_all(&dir).unwrap();
+        }
+        let path = dir.join("open_options.log");
+
+        let res = FileSink::builder().path(&path).create_dirs(false).build();
+        assert!(matches!(res, Err(Error::OpenFile(_))));
+        assert!(!dir.exists());
+
+        let res = FileSink::builder()
+            .path(&path)
+            .open_options(|open_options| {
+                open_options.create(false);
+            })
+            .build();
+        assert!(matches!(res, Err(Error::OpenFile(_))));
+        assert!(dir.exists() && !path.exists());
+
+        fs::write(&path, "existing").unwrap();
+        let sink = FileSink::builder()
+            .path(&path)
+            .open_options(|open_options| {
+                open_options.create(false);
+            })
+            .formatter(Box::new(NoModFormatter::new()))
+            .build()
+            .unwrap();
+        sink.log(&Record::new(Level::Info, " appended", None, None))
+            .unwrap();
+        sink.flush().unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "existing appended");
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn share_mode() {
+        let path = TEST_LOGS_PATH.join("file_sink_share_mode.log");
+        let sink = FileSink::builder()
+            .path(&path)
+            .share_mode(0x1) // FILE_SHARE_READ
+            .build()
+            .unwrap();
+
+        assert!(fs::File::open(&path).is_ok());
+        assert!(fs::OpenOptions::new().append(true).open(&path).is_err());
+        drop(sink);
+        assert!(fs::OpenOptions::new().append(true).open(&path).is_ok());
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn disk_full_failover() {
+        let failover = Arc::new(TestSink::new());
+        let sink = FileSink::builder()
+            .path("/dev/full")
+            .disk_full_strategy(DiskFullStrategy::Failover(failover.clone()))
+            .build()
+            .unwrap();
+
+        let record = Record::new(Level::Info, "a".repeat(16 * 1024), None, None);
+        assert!(sink.log(&record).is_err());
+        assert!(sink.log(&record).is_ok());
+        assert!(sink.flush().is_ok());
+        assert_eq!(failover.log_count(), 2);
+        assert_eq!(failover.flush_count(), 1);
+    }
+}
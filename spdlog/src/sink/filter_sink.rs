@@ -0,0 +1,223 @@
+use std::sync::Arc;
+
+use crate::{
+    filter::{self, Filter, Filters},
+    sink::{helper, Sink, Sinks},
+    Error, Record, Result,
+};
+
+/// A [combined sink], forwards only records passing a chain of [`Filter`]s to
+/// its internal sinks.
+///
+/// This attaches filters to specific sinks, while filters attached to a logger
+/// via [`LoggerBuilder::filter`] apply to all sinks of the logger.
+///
+/// # Examples
+///
+/// ```
+/// use std::sync::Arc;
+///
+/// use spdlog::{prelude::*, sink::FilterSink, FilterResult, ModulePathFilter};
+/// # use spdlog::sink::WriteSink;
+///
+/// # fn main() -> Result<(), spdlog::Error> {
+/// # let db_file_sink = Arc::new(WriteSink::builder().target(Vec::new()).build()?);
+/// let sink = Arc::new(
+///     FilterSink::builder()
+///         .sink(db_file_sink)
+///         .filter(ModulePathFilter::new("my_app::db").on_mismatch(FilterResult::Reject))
+///         .build()?,
+/// );
+/// # let doctest = Logger::builder().sink(sink).build()?;
+///
+/// // ... Add the `sink` to a logger
+/// # Ok(()) }
+/// ```
+///
+/// [combined sink]: index.html#combined-sink
+/// [`LoggerBuilder::filter`]: crate::LoggerBuilder::filter
+pub struct FilterSink {
+    common_impl: helper::CommonImpl,
+    sinks: Sinks,
+    filters: Filters,
+}
+
+impl FilterSink {
+    /// Gets a builder of `FilterSink` with default parameters:
+    ///
+    /// | Parameter       | Default Value           |
+    /// |-----------------|-------------------------|
+    /// | [level_filter]  | `All`                   |
+    /// | [formatter]     | `FullFormatter`         |
+    /// | [error_handler] | [default error handler] |
+    /// |                 |                         |
+    /// | [sinks]         | `[]`                    |
+    /// | [filters]       | `[]`                    |
+    ///
+    /// [level_filter]: FilterSinkBuilder::level_filter
+    /// [formatter]: FilterSinkBuilder::formatter
+    /// [error_handler]: FilterSinkBuilder::error_handler
+    /// [default error handler]: error/index.html#default-error-handler
+    /// [sinks]: FilterSinkBuilder::sink
+    /// [filters]: FilterSinkBuilder::filter
+    #[must_use]
+    pub fn builder() -> FilterSinkBuilder {
+        FilterSinkBuilder {
+            common_builder_impl: helper::CommonBuilderImpl::new(),
+            sinks: vec![],
+            filters: vec![],
+        }
+    }
+
+    /// Gets a reference to internal sinks in the combined sink.
+    #[must_use]
+    pub fn sinks(&self) -> &[Arc<dyn Sink>] {
+        &self.sinks
+    }
+
+    /// Gets a reference to the filter chain.
+    #[must_use]
+    pub fn filters(&self) -> &[Arc<dyn Filter>] {
+        &self.filters
+    }
+}
+
+impl Sink for FilterSink {
+    fn log(&self, record: &Record) -> Result<()> {
+        if !filter::filter_chain(&self.filters, record) {
+            return Ok(());
+        }
+
+        #[allow(clippy::manual_try_fold)] // https://github.com/rust-lang/rust-clippy/issues/11554
+        self.sinks.iter().fold(Ok(()), |result, sink| {
+            Error::push_result(result, sink.log(record))
+        })
+    }
+
+    fn flush(&self) -> Result<()> {
+        #[allow(clippy::manual_try_fold)] // https://github.com/rust-lang/rust-clippy/issues/11554
+        self.sinks.iter().fold(Ok(()), |result, sink| {
+            Error::push_result(result, sink.flush())
+        })
+    }
+
+    /// For [`FilterSink`], the function syncs all internal sinks.
+    fn sync(&self) -> Result<()> {
+        #[allow(clippy::manual_try_fold)] // https://github.com/rust-lang/rust-clippy/issues/11554
+        self.sinks.iter().fold(Ok(()), |result, sink| {
+            Error::push_result(result, sink.sync())
+        })
+    }
+
+    /// For [`FilterSink`], the function polls all internal sinks.
+    #[cfg(feature = "log-async")]
+    fn poll_capacity(&self, cx: &mut std::task::Context<'_>) -> std::task::Poll<()> {
+        crate::sink::poll_capacity(&self.sinks, cx)
+    }
+
+    /// For [`FilterSink`], the function opens all internal sinks.
+    fn open(&self) -> Result<()> {
+        #[allow(clippy::manual_try_fold)] // https://github.com/rust-lang/rust-clippy/issues/11554
+        self.sinks.iter().fold(Ok(()), |result, sink| {
+            Error::push_result(result, sink.open())
+        })
+    }
+
+    /// For [`FilterSink`], the function closes all internal sinks.
+    fn close(&self) -> Result<()> {
+        #[allow(clippy::manual_try_fold)] // https://github.com/rust-lang/rust-clippy/issues/11554
+        self.sinks.iter().fold(Ok(()), |result, sink| {
+            Error::push_result(result, sink.close())
+        })
+    }
+
+    /// For [`FilterSink`], the function checks all internal sinks.
+    fn health(&self) -> Result<()> {
+        #[allow(clippy::manual_try_fold)] // https://github.com/rust-lang/rust-clippy/issues/11554
+        self.sinks.iter().fold(Ok(()), |result, sink| {
+            Error::push_result(result, sink.health())
+        })
+    }
+
+    helper::common_impl!(@Sink: common_impl);
+}
+
+#[allow(missing_docs)]
+pub struct FilterSinkBuilder {
+    common_builder_impl: helper::CommonBuilderImpl,
+    sinks: Sinks,
+    filters: Filters,
+}
+
+impl FilterSinkBuilder {
+    /// Add a [`Sink`].
+    #[must_use]
+    pub fn sink(mut self, sink: Arc<dyn Sink>) -> Self {
+        self.sinks.push(sink);
+        self
+    }
+
+    /// Add multiple [`Sink`]s.
+    #[must_use]
+    pub fn sinks<I>(mut self, sinks: I) -> Self
+    where
+        I: IntoIterator<Item = Arc<dyn Sink>>,
+    {
+        self.sinks.append(&mut sinks.into_iter().collect());
+        self
+    }
+
+    /// Add a [`Filter`] to the filter chain.
+    ///
+    /// This parameter is **optional**, and can be specified multiple times.
+    #[must_use]
+    pub fn filter<F>(mut self, filter: F) -> Self
+    where
+        F: Filter + 'static,
+    {
+        self.filters.push(Arc::new(filter));
+        self
+    }
+
+    helper::common_impl!(@SinkBuilder: common_builder_impl);
+
+    /// Builds a [`FilterSink`].
+    pub fn build(self) -> Result<FilterSink> {
+        Ok(FilterSink {
+            common_impl: helper::CommonImpl::from_builder(self.common_builder_impl),
+            sinks: self.sinks,
+            filters: self.filters,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{prelude::*, test_utils::*, FilterResult};
+
+    #[test]
+    fn filter_chain() {
+        let test_sink = Arc::new(TestSink::new());
+        let filter_sink = Arc::new(
+            FilterSink::builder()
+                .sink(test_sink.clone())
+                .filter(|record: &Record| match record.payload() {
+                    "keep" => FilterResult::Accept,
+                    _ => FilterResult::Neutral,
+                })
+                .filter(|record: &Record| match record.level() {
+                    Level::Error => FilterResult::Neutral,
+                    _ => FilterResult::Reject,
+                })
+                .build()
+                .unwrap(),
+        );
+        let test = build_test_logger(|b| b.sink(filter_sink));
+
+        info!(logger: test, "keep");
+        info!(logger: test, "drop");
+        error!(logger: test, "error");
+        assert_eq!(test_sink.payloads(), ["keep", "error"]);
+    }
+}
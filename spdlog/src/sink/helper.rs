@@ -1,10 +1,12 @@
+use std::{cell::RefCell, io};
+
 use cfg_if::cfg_if;
 
 use crate::{
-    formatter::{Formatter, FullFormatter},
+    formatter::{Formatter, FormatterContext, FullFormatter},
     prelude::*,
     sync::*,
-    Error, ErrorHandler,
+    timing, Error, ErrorHandler, Record, Result, StringBuf,
 };
 
 pub(crate) type SinkErrorHandler = Atomic<Option<ErrorHandler>>;
@@ -17,6 +19,17 @@ cfg_if! {
 
 pub(crate) const SINK_DEFAULT_LEVEL_FILTER: LevelFilter = LevelFilter::All;
 
+// Buffers longer than this are not kept for reuse, so that a single huge record
+// does not hold memory for the lifetime of the thread.
+const MAX_REUSED_BUF_LEN: usize = 64 * 1024;
+
+thread_local! {
+    // Not `const`, `StringBuf` may not be constructible in const contexts
+    // depending on crate features.
+    #[allow(clippy::missing_const_for_thread_local)]
+    static STRING_BUF: RefCell<StringBuf> = RefCell::new(StringBuf::new());
+}
+
 pub(crate) struct CommonImpl {
     pub(crate) level_filter: Atomic<LevelFilter>,
     pub(crate) formatter: SpinRwLock<Box<dyn Formatter>>,
@@ -51,6 +64,32 @@ impl CommonImpl {
         }
     }
 
+    // Formats a record with the formatter, measured if the logger enables
+    // timing.
+    pub(crate) fn format(
+        &self,
+        record: &Record,
+        dest: &mut StringBuf,
+        ctx: &mut FormatterContext,
+    ) -> Result<()> {
+        timing::measure_format(|| self.formatter.read().format(record, dest, ctx))
+    }
+
+    // Formats a record into a buffer reused by the current thread, and passes
+    // the formatted text to `f`, so that logging does not allocate a buffer for
+    // each record in the steady state.
+    pub(crate) fn format_with<R>(
+        &self,
+        record: &Record,
+        f: impl FnOnce(&StringBuf, &FormatterContext) -> Result<R>,
+    ) -> Result<R> {
+        with_string_buf(|string_buf| {
+            let mut ctx = FormatterContext::new();
+            self.format(record, string_buf, &mut ctx)?;
+            f(string_buf, &ctx)
+        })
+    }
+
     pub(crate) fn non_returnable_error(&self, from: impl AsRef<str>, err: Error) {
         match self.error_handler.load(Ordering::Relaxed) {
             Some(handler) => handler(err),
@@ -59,6 +98,98 @@ impl CommonImpl {
     }
 }
 
+// Runs `f` with an empty buffer reused by the current thread. A new buffer is
+// used instead if the reused one is borrowed, e.g. a sink logs to another sink
+// while formatting, or the thread is being torn down.
+pub(crate) fn with_string_buf<R>(f: impl FnOnce(&mut StringBuf) -> R) -> R {
+    let mut f = Some(f);
+    let res = STRING_BUF.try_with(|string_buf| {
+        let mut string_buf = string_buf.try_borrow_mut().ok()?;
+        string_buf.clear();
+        let res = (f.take().unwrap())(&mut string_buf);
+        if string_buf.len() > MAX_REUSED_BUF_LEN {
+            *string_buf = StringBuf::new();
+        }
+        Some(res)
+    });
+    match res {
+        Ok(Some(res)) => res,
+        _ => (f.take().unwrap())(&mut StringBuf::new()),
+    }
+}
+
+// Keeps the last I/O error of a network sink for `Sink::health`, since
+// `io::Error` cannot be cloned.
+pub(crate) struct IoHealth {
+    last_error: SpinMutex<Option<LastIoError>>,
+}
+
+struct LastIoError {
+    to_error: fn(io::Error) -> Error,
+    kind: io::ErrorKind,
+    message: String,
+}
+
+impl IoHealth {
+    #[must_use]
+    pub(crate) fn new() -> Self {
+        Self {
+            last_error: SpinMutex::new(None),
+        }
+    }
+
+    // Constructs it in the state of not being connected yet.
+    #[must_use]
+    pub(crate) fn disconnected() -> Self {
+        let health = Self::new();
+        health.set_disconnected();
+        health
+    }
+
+    pub(crate) fn set_disconnected(&self) {
+        self.set_err(
+            Error::Connect,
+            &io::Error::new(io::ErrorKind::NotConnected, "not connected"),
+        );
+    }
+
+    pub(crate) fn set_err(&self, to_error: fn(io::Error) -> Error, err: &io::Error) {
+        *self.last_error.lock() = Some(LastIoError {
+            to_error,
+            kind: err.kind(),
+            message: err.to_string(),
+        });
+    }
+
+    // Records the result of an I/O operation, and converts its error.
+    pub(crate) fn track<T>(
+        &self,
+        result: io::Result<T>,
+        to_error: fn(io::Error) -> Error,
+    ) -> Result<T> {
+        match result {
+            Ok(value) => {
+                *self.last_error.lock() = None;
+                Ok(value)
+            }
+            Err(err) => {
+                self.set_err(to_error, &err);
+                Err(to_error(err))
+            }
+        }
+    }
+
+    pub(crate) fn check(&self) -> Result<()> {
+        match &*self.last_error.lock() {
+            None => Ok(()),
+            Some(last_error) => Err((last_error.to_error)(io::Error::new(
+                last_error.kind,
+                last_error.message.clone(),
+            ))),
+        }
+    }
+}
+
 pub(crate) struct CommonBuilderImpl {
     pub(crate) level_filter: LevelFilter,
     pub(crate) formatter: Option<Box<dyn Formatter>>,
@@ -1,51 +1,12 @@
 use std::{io, os::raw::c_int};
 
 use crate::{
-    formatter::{FormatterContext, JournaldFormatter},
-    sink::{helper, Sink},
-    Error, Level, Record, Result, StdResult, StringBuf,
+    error::InvalidArgumentError,
+    formatter::JournaldFormatter,
+    sink::{helper, Sink, SyslogFacility, SyslogLevels},
+    Error, Record, Result, StdResult,
 };
 
-#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
-enum SyslogLevel {
-    _Emerg = 0,
-    _Alert = 1,
-    Crit = 2,
-    Err = 3,
-    Warning = 4,
-    _Notice = 5,
-    Info = 6,
-    Debug = 7,
-}
-
-#[derive(Clone, Eq, PartialEq, Hash, Debug)]
-struct SyslogLevels([SyslogLevel; Level::count()]);
-
-impl SyslogLevels {
-    #[must_use]
-    const fn new() -> Self {
-        Self([
-            SyslogLevel::Crit,    // Critical
-            SyslogLevel::Err,     // Error
-            SyslogLevel::Warning, // Warn
-            SyslogLevel::Info,    // Info
-            SyslogLevel::Debug,   // Debug
-            SyslogLevel::Debug,   // Trace
-        ])
-    }
-
-    #[must_use]
-    fn level(&self, level: Level) -> SyslogLevel {
-        self.0[level as usize]
-    }
-}
-
-impl Default for SyslogLevels {
-    fn default() -> Self {
-        Self::new()
-    }
-}
-
 fn journal_send(args: impl Iterator<Item = impl AsRef<str>>) -> StdResult<(), io::Error> {
     #[cfg(not(doc))] // https://github.com/rust-lang/rust/issues/97976
     use libsystemd_sys::{const_iovec, journal as ffi};
@@ -72,6 +33,15 @@ fn journal_send(args: impl Iterator<Item = impl AsRef<str>>) -> StdResult<(), io
 /// | `Debug`    | `debug`   |
 /// | `Trace`    | `debug`   |
 ///
+/// The mapping can be customized with [`JournaldSinkBuilder::syslog_levels`].
+///
+/// # Syslog Fields
+///
+/// The syslog facility, identifier (app-name) and message ID can be specified
+/// on the builder, and are sent as journal fields `SYSLOG_FACILITY`,
+/// `SYSLOG_IDENTIFIER` and `MESSAGE_ID`. Additional fields with fixed values
+/// can be added with [`JournaldSinkBuilder::field`].
+///
 /// # Note
 ///
 /// It requires an additional system dependency `libsystemd`.
@@ -89,11 +59,12 @@ fn journal_send(args: impl Iterator<Item = impl AsRef<str>>) -> StdResult<(), io
 /// ```
 pub struct JournaldSink {
     common_impl: helper::CommonImpl,
+    syslog_levels: SyslogLevels,
+    // Formatted as `NAME=value`
+    static_fields: Vec<String>,
 }
 
 impl JournaldSink {
-    const SYSLOG_LEVELS: SyslogLevels = SyslogLevels::new();
-
     /// Gets a builder of `JournaldSink` with default parameters:
     ///
     /// | Parameter       | Default Value           |
@@ -101,45 +72,58 @@ impl JournaldSink {
     /// | [level_filter]  | `All`                   |
     /// | [formatter]     | `JournaldFormatter`     |
     /// | [error_handler] | [default error handler] |
+    /// | [syslog_levels] | `SyslogLevels::new()`    |
+    /// | [facility]      | `None`                  |
+    /// | [identifier]    | `None`                  |
+    /// | [message_id]    | `None`                  |
     ///
     /// [level_filter]: JournaldSinkBuilder::level_filter
     /// [formatter]: JournaldSinkBuilder::formatter
     /// [error_handler]: JournaldSinkBuilder::error_handler
     /// [default error handler]: error/index.html#default-error-handler
+    /// [syslog_levels]: JournaldSinkBuilder::syslog_levels
+    /// [facility]: JournaldSinkBuilder::facility
+    /// [identifier]: JournaldSinkBuilder::identifier
+    /// [message_id]: JournaldSinkBuilder::message_id
     #[must_use]
     pub fn builder() -> JournaldSinkBuilder {
         JournaldSinkBuilder {
             common_builder_impl: helper::CommonBuilderImpl::new(),
+            syslog_levels: SyslogLevels::new(),
+            facility: None,
+            identifier: None,
+            message_id: None,
+            fields: vec![],
         }
     }
 }
 
 impl Sink for JournaldSink {
     fn log(&self, record: &Record) -> Result<()> {
-        let mut string_buf = StringBuf::new();
-        let mut ctx = FormatterContext::new();
-        self.common_impl
-            .formatter
-            .read()
-            .format(record, &mut string_buf, &mut ctx)?;
-
-        let kvs = [
-            format!("MESSAGE={}", string_buf),
-            format!(
-                "PRIORITY={}",
-                JournaldSink::SYSLOG_LEVELS.level(record.level()) as u32
-            ),
-        ];
-
-        let srcloc_kvs = match record.source_location() {
-            Some(srcloc) => [
-                Some(format!("CODE_FILE={}", srcloc.file_name())),
-                Some(format!("CODE_LINE={}", srcloc.line())),
-            ],
-            None => [None, None],
-        };
+        self.common_impl.format_with(record, |string_buf, _| {
+            let kvs = [
+                format!("MESSAGE={}", string_buf),
+                format!(
+                    "PRIORITY={}",
+                    self.syslog_levels.level(record.level()).code()
+                ),
+            ];
 
-        journal_send(kvs.iter().chain(srcloc_kvs.iter().flatten())).map_err(Error::WriteRecord)
+            let srcloc_kvs = match record.source_location() {
+                Some(srcloc) => [
+                    Some(format!("CODE_FILE={}", srcloc.file_name())),
+                    Some(format!("CODE_LINE={}", srcloc.line())),
+                ],
+                None => [None, None],
+            };
+
+            journal_send(
+                kvs.iter()
+                    .chain(srcloc_kvs.iter().flatten())
+                    .chain(self.static_fields.iter()),
+            )
+            .map_err(Error::WriteRecord)
+        })
     }
 
     fn flush(&self) -> Result<()> {
@@ -152,19 +136,126 @@ impl Sink for JournaldSink {
 #[allow(missing_docs)]
 pub struct JournaldSinkBuilder {
     common_builder_impl: helper::CommonBuilderImpl,
+    syslog_levels: SyslogLevels,
+    facility: Option<SyslogFacility>,
+    identifier: Option<String>,
+    message_id: Option<String>,
+    fields: Vec<(String, String)>,
 }
 
 impl JournaldSinkBuilder {
+    /// Specifies the mapping from log levels to journald priorities.
+    ///
+    /// This parameter is **optional**.
+    #[must_use]
+    pub fn syslog_levels(mut self, syslog_levels: SyslogLevels) -> Self {
+        self.syslog_levels = syslog_levels;
+        self
+    }
+
+    /// Specifies the syslog facility, sent as field `SYSLOG_FACILITY`.
+    ///
+    /// This parameter is **optional**.
+    #[must_use]
+    pub fn facility(mut self, facility: SyslogFacility) -> Self {
+        self.facility = Some(facility);
+        self
+    }
+
+    /// Specifies the syslog identifier (app-name), sent as field
+    /// `SYSLOG_IDENTIFIER`.
+    ///
+    /// If it is not specified, journald uses the name of the process.
+    ///
+    /// This parameter is **optional**.
+    #[must_use]
+    pub fn identifier<S>(mut self, identifier: S) -> Self
+    where
+        S: Into<String>,
+    {
+        self.identifier = Some(identifier.into());
+        self
+    }
+
+    /// Specifies the message ID (msgid), sent as field `MESSAGE_ID`.
+    ///
+    /// This parameter is **optional**.
+    #[must_use]
+    pub fn message_id<S>(mut self, message_id: S) -> Self
+    where
+        S: Into<String>,
+    {
+        self.message_id = Some(message_id.into());
+        self
+    }
+
+    /// Adds a field with a fixed value, sent with every record.
+    ///
+    /// Field names must consist of uppercase letters, digits and underscores,
+    /// and must not start with an underscore or a digit. Otherwise,
+    /// [`Error::InvalidArgument`] will be returned when building the sink.
+    ///
+    /// This parameter is **optional**, and can be specified multiple times.
+    #[must_use]
+    pub fn field<N, V>(mut self, name: N, value: V) -> Self
+    where
+        N: Into<String>,
+        V: Into<String>,
+    {
+        self.fields.push((name.into(), value.into()));
+        self
+    }
+
     helper::common_impl!(@SinkBuilder: common_builder_impl);
 
     /// Builds a [`JournaldSink`].
+    ///
+    /// # Error
+    ///
+    /// If a field added by [`JournaldSinkBuilder::field`] has an invalid name,
+    /// [`Error::InvalidArgument`] will be returned.
     pub fn build(self) -> Result<JournaldSink> {
+        let static_fields = self
+            .facility
+            .map(|facility| ("SYSLOG_FACILITY".to_string(), facility.code().to_string()))
+            .into_iter()
+            .chain(
+                self.identifier
+                    .map(|id| ("SYSLOG_IDENTIFIER".to_string(), id)),
+            )
+            .chain(self.message_id.map(|id| ("MESSAGE_ID".to_string(), id)))
+            .chain(self.fields)
+            .map(|(name, value)| {
+                check_field_name(&name)?;
+                Ok(format!("{}={}", name, value))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
         let sink = JournaldSink {
             common_impl: helper::CommonImpl::from_builder_with_formatter(
                 self.common_builder_impl,
                 || Box::new(JournaldFormatter::new()),
             ),
+            syslog_levels: self.syslog_levels,
+            static_fields,
         };
         Ok(sink)
     }
 }
+
+fn check_field_name(name: &str) -> Result<()> {
+    let is_valid = name
+        .chars()
+        .all(|ch| ch.is_ascii_uppercase() || ch.is_ascii_digit() || ch == '_')
+        && name
+            .chars()
+            .next()
+            .map_or(false, |ch| ch != '_' && !ch.is_ascii_digit());
+    if is_valid {
+        Ok(())
+    } else {
+        Err(Error::InvalidArgument(InvalidArgumentError::JournaldField(
+            format!("invalid field name '{}'", name),
+        )))
+    }
+}
@@ -0,0 +1,153 @@
+use std::sync::Arc;
+
+use crate::{
+    formatter::Formatter,
+    sink::{helper, Sink},
+    sync::*,
+    ErrorHandler, LevelFilter, Record, Result,
+};
+
+/// A sink overriding the level filter of another sink.
+///
+/// Records are filtered by the level filter of the `LevelOverrideSink` and
+/// then logged to the internal sink, regardless of the level filter of the
+/// internal sink. This allows a single shared sink instance to have different
+/// effective levels in different loggers.
+///
+/// Other operations are forwarded to the internal sink, including setting the
+/// formatter and the error handler, which therefore affect all loggers sharing
+/// the internal sink.
+///
+/// See also [`LoggerBuilder::sink_with_level`].
+///
+/// # Examples
+///
+/// ```
+/// use std::sync::Arc;
+///
+/// use spdlog::{prelude::*, sink::LevelOverrideSink};
+/// # use spdlog::sink::WriteSink;
+///
+/// # fn main() -> Result<(), spdlog::Error> {
+/// # let shared_sink = Arc::new(WriteSink::builder().target(Vec::new()).build()?);
+/// let verbose = Logger::builder()
+///     .level_filter(LevelFilter::All)
+///     .sink(Arc::new(LevelOverrideSink::new(
+///         shared_sink.clone(),
+///         LevelFilter::All,
+///     )))
+///     .build()?;
+/// let quiet = Logger::builder()
+///     .sink(Arc::new(LevelOverrideSink::new(
+///         shared_sink,
+///         LevelFilter::MoreSevereEqual(Level::Warn),
+///     )))
+///     .build()?;
+/// # Ok(()) }
+/// ```
+///
+/// [`LoggerBuilder::sink_with_level`]: crate::LoggerBuilder::sink_with_level
+pub struct LevelOverrideSink {
+    sink: Arc<dyn Sink>,
+    level_filter: Atomic<LevelFilter>,
+}
+
+impl LevelOverrideSink {
+    /// Constructs a `LevelOverrideSink` logging records passing the level
+    /// filter to the sink.
+    #[must_use]
+    pub fn new(sink: Arc<dyn Sink>, level_filter: LevelFilter) -> Self {
+        Self {
+            sink,
+            level_filter: Atomic::new(level_filter),
+        }
+    }
+
+    /// Gets a reference to the internal sink.
+    #[must_use]
+    pub fn sink(&self) -> &Arc<dyn Sink> {
+        &self.sink
+    }
+}
+
+impl Sink for LevelOverrideSink {
+    fn log(&self, record: &Record) -> Result<()> {
+        self.sink.log(record)
+    }
+
+    fn flush(&self) -> Result<()> {
+        self.sink.flush()
+    }
+
+    /// For [`LevelOverrideSink`], the function syncs the internal sink.
+    fn sync(&self) -> Result<()> {
+        self.sink.sync()
+    }
+
+    /// For [`LevelOverrideSink`], the function polls the internal sink.
+    #[cfg(feature = "log-async")]
+    fn poll_capacity(&self, cx: &mut std::task::Context<'_>) -> std::task::Poll<()> {
+        self.sink.poll_capacity(cx)
+    }
+
+    /// For [`LevelOverrideSink`], the function checks the internal sink.
+    fn health(&self) -> Result<()> {
+        self.sink.health()
+    }
+
+    /// For [`LevelOverrideSink`], the function opens the internal sink.
+    fn open(&self) -> Result<()> {
+        self.sink.open()
+    }
+
+    /// For [`LevelOverrideSink`], the function closes the internal sink.
+    fn close(&self) -> Result<()> {
+        self.sink.close()
+    }
+
+    /// For [`LevelOverrideSink`], the function sets the formatter of the
+    /// internal sink.
+    fn set_formatter(&self, formatter: Box<dyn Formatter>) {
+        self.sink.set_formatter(formatter)
+    }
+
+    /// For [`LevelOverrideSink`], the function sets the error handler of the
+    /// internal sink.
+    fn set_error_handler(&self, handler: Option<ErrorHandler>) {
+        self.sink.set_error_handler(handler)
+    }
+
+    helper::common_impl!(@SinkCustom {
+        level_filter: level_filter,
+        formatter: None,
+        error_handler: None,
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{prelude::*, test_utils::*};
+
+    #[test]
+    fn override_level() {
+        let test_sink = Arc::new(TestSink::new());
+        test_sink.set_level_filter(LevelFilter::Off);
+
+        let verbose = build_test_logger(|b| {
+            b.level_filter(LevelFilter::All)
+                .sink_with_level(test_sink.clone(), LevelFilter::All)
+        });
+        let quiet = build_test_logger(|b| {
+            b.level_filter(LevelFilter::All)
+                .sink_with_level(test_sink.clone(), LevelFilter::MoreSevereEqual(Level::Warn))
+        });
+
+        debug!(logger: verbose, "verbose debug");
+        debug!(logger: quiet, "quiet debug");
+        warn!(logger: quiet, "quiet warn");
+
+        assert_eq!(test_sink.payloads(), ["verbose debug", "quiet warn"]);
+        assert_eq!(test_sink.level_filter(), LevelFilter::Off);
+    }
+}
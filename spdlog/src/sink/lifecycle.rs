@@ -0,0 +1,103 @@
+// Counts loggers that opened each sink, so that a sink shared by multiple
+// loggers is opened by the first of them and closed by the last one.
+
+use crate::{
+    sink::{Sink, Sinks},
+    sync::*,
+    Error, Result,
+};
+
+// Weak references keep the allocations of sinks, so their addresses are not
+// reused by other sinks while they are counted.
+type OpenCounts = Vec<(Weak<dyn Sink>, usize)>;
+
+static OPEN_COUNTS: Lazy<Mutex<OpenCounts>> = Lazy::new(|| Mutex::new(Vec::new()));
+
+#[must_use]
+fn position(counts: &OpenCounts, sink: &Arc<dyn Sink>) -> Option<usize> {
+    let sink = Arc::as_ptr(sink) as *const ();
+    counts
+        .iter()
+        .position(|(counted, _)| counted.as_ptr() as *const () == sink)
+}
+
+// Opens the sinks not opened by other loggers yet, and counts them. If one of
+// them fails to open, the sinks counted before are released and the error is
+// returned.
+pub(crate) fn acquire(sinks: &[Arc<dyn Sink>]) -> Result<()> {
+    for (index, sink) in sinks.iter().enumerate() {
+        let first = {
+            let mut counts = OPEN_COUNTS.lock_expect();
+            match position(&counts, sink) {
+                Some(pos) => {
+                    counts[pos].1 += 1;
+                    false
+                }
+                None => {
+                    counts.push((Arc::downgrade(sink), 1));
+                    true
+                }
+            }
+        };
+        // Do not hold the lock while opening, since it may take long.
+        if first {
+            if let Err(err) = sink.open() {
+                _ = release_one(sink, |_| Ok(()));
+                release(&sinks[..index], |_| {});
+                return Err(err);
+            }
+        }
+    }
+    Ok(())
+}
+
+// Counts the sinks already opened by other loggers, without opening them.
+// Returns the counted sinks.
+#[must_use]
+pub(crate) fn retain(sinks: &[Arc<dyn Sink>]) -> Sinks {
+    let mut counts = OPEN_COUNTS.lock_expect();
+    sinks
+        .iter()
+        .filter(|sink| match position(&counts, sink) {
+            Some(pos) => {
+                counts[pos].1 += 1;
+                true
+            }
+            None => false,
+        })
+        .cloned()
+        .collect()
+}
+
+// Releases the sinks counted by `acquire` or `retain`, and closes the sinks no
+// longer opened by any logger.
+pub(crate) fn release(sinks: &[Arc<dyn Sink>], mut on_error: impl FnMut(Error)) {
+    for sink in sinks {
+        if let Err(err) = release_one(sink, |sink| sink.close()) {
+            on_error(err);
+        }
+    }
+}
+
+fn release_one(sink: &Arc<dyn Sink>, close: impl FnOnce(&dyn Sink) -> Result<()>) -> Result<()> {
+    let last = {
+        let mut counts = OPEN_COUNTS.lock_expect();
+        match position(&counts, sink) {
+            Some(pos) => {
+                counts[pos].1 -= 1;
+                if counts[pos].1 == 0 {
+                    counts.swap_remove(pos);
+                    true
+                } else {
+                    false
+                }
+            }
+            None => false,
+        }
+    };
+    if last {
+        close(&**sink)
+    } else {
+        Ok(())
+    }
+}
@@ -9,6 +9,11 @@
 //! A sink has its own level filter that is not shared with the logger, and a
 //! [`Logger`] can combine multiple [`Sink`]s.
 //!
+//! Sinks are shared as `Arc<dyn Sink>` between loggers and the places that
+//! configure them, so the level filter, formatter and error handler of a sink
+//! are set through shared references and can be updated while the sink is in
+//! use by loggers.
+//!
 //! # Combined sink
 //!
 //! A combined sink is also a sink, but instead of having its own target and
@@ -21,31 +26,67 @@
 
 #[cfg(feature = "multi-thread")]
 pub(crate) mod async_sink;
+#[cfg(feature = "multi-thread")]
+mod batch;
 mod dedup_sink;
+mod disk_full;
+#[cfg(any(unix, windows))]
+mod emergency_sink;
 mod file_sink;
+mod filter_sink;
 mod helper;
 #[cfg(any(
     all(target_os = "linux", feature = "native", feature = "libsystemd"),
     all(doc, not(doctest))
 ))]
 mod journald_sink;
+mod level_override_sink;
+pub(crate) mod lifecycle;
+mod rate_limit_sink;
+#[cfg(feature = "regex")]
+mod regex_filter_sink;
+mod ring_buffer_sink;
 mod rotating_file_sink;
+mod router_sink;
 mod std_stream_sink;
+mod syslog_common;
+mod syslog_sink;
+mod tcp_sink;
+mod udp_sink;
 #[cfg(any(all(windows, feature = "native"), all(doc, not(doctest))))]
 mod win_debug_sink;
 mod write_sink;
 
+#[cfg(feature = "log-async")]
+use std::task::{Context, Poll};
+
 #[cfg(feature = "multi-thread")]
 pub use async_sink::*;
+#[cfg(feature = "multi-thread")]
+pub use batch::*;
 pub use dedup_sink::*;
+pub use disk_full::DiskFullStrategy;
+#[cfg(any(unix, windows))]
+pub use emergency_sink::*;
 pub use file_sink::*;
+pub use filter_sink::*;
 #[cfg(any(
     all(target_os = "linux", feature = "native", feature = "libsystemd"),
     all(doc, not(doctest))
 ))]
 pub use journald_sink::*;
+pub use level_override_sink::*;
+pub use rate_limit_sink::*;
+#[cfg(feature = "regex")]
+pub use regex_filter_sink::*;
+pub use ring_buffer_sink::*;
 pub use rotating_file_sink::*;
+pub use router_sink::*;
 pub use std_stream_sink::*;
+pub use syslog_common::*;
+pub use syslog_sink::*;
+pub use tcp_sink::*;
+pub use udp_sink::*;
 #[cfg(any(all(windows, feature = "native"), all(doc, not(doctest))))]
 pub use win_debug_sink::*;
 pub use write_sink::*;
@@ -66,14 +107,105 @@ pub trait Sink: Sync + Send {
     /// Flushes any buffered records.
     fn flush(&self) -> Result<()>;
 
+    /// Flushes any buffered records, and waits until they are stored durably.
+    ///
+    /// Unlike [`Sink::flush`], which may only hand records over to the
+    /// operating system or a background thread, this method returns only after
+    /// the records logged so far have reached their target, e.g. file contents
+    /// have been synchronized to the storage device, so that an `Ok` result
+    /// confirms their durability. Loggers in [audit mode] call this method
+    /// instead of [`Sink::flush`].
+    ///
+    /// The default implementation calls [`Sink::flush`], which is enough for
+    /// sinks that do not buffer records.
+    ///
+    /// [audit mode]: crate::LoggerBuilder::audit
+    fn sync(&self) -> Result<()> {
+        self.flush()
+    }
+
+    /// Polls whether the sink has capacity to accept a record without blocking
+    /// or dropping it.
+    ///
+    /// Asynchronous sinks return [`Poll::Pending`] while their queues are
+    /// full, and wake the task of `cx` once there is space again.
+    /// [`Logger::log_async`] awaits this before passing a record to the sink,
+    /// so that asynchronous log producers are slowed down instead of blocking
+    /// their threads or losing records.
+    ///
+    /// The default implementation returns [`Poll::Ready`], which is correct
+    /// for sinks that never block for long.
+    ///
+    /// This method is only available if crate feature `log-async` is enabled.
+    ///
+    /// [`Logger::log_async`]: crate::Logger::log_async
+    #[cfg(feature = "log-async")]
+    fn poll_capacity(&self, _cx: &mut Context<'_>) -> Poll<()> {
+        Poll::Ready(())
+    }
+
+    /// Checks whether the sink is able to write records.
+    ///
+    /// Applications can expose the result in readiness probes to report the
+    /// health of the logging pipeline. An error describing the cause is
+    /// returned if the sink is unhealthy. Implementations should be cheap and
+    /// must not block for long.
+    ///
+    /// The default implementation returns `Ok(())`.
+    fn health(&self) -> Result<()> {
+        Ok(())
+    }
+
+    /// Acquires resources needed for writing records, such as connections.
+    ///
+    /// [`LoggerBuilder::build`] calls this method for each sink of the logger,
+    /// so that connection-oriented sinks can establish resources
+    /// deterministically at startup rather than lazily on the first log.
+    ///
+    /// Loggers count the sinks they opened, a sink shared by multiple loggers
+    /// is only opened when the first of them is built, and only closed when
+    /// the last of them is closed or dropped. Sinks added with
+    /// [`Logger::sinks_mut`] are neither opened nor closed by the logger.
+    ///
+    /// The default implementation does nothing.
+    ///
+    /// [`LoggerBuilder::build`]: crate::LoggerBuilder::build
+    /// [`Logger::sinks_mut`]: crate::Logger::sinks_mut
+    fn open(&self) -> Result<()> {
+        Ok(())
+    }
+
+    /// Releases resources acquired by [`Sink::open`].
+    ///
+    /// [`Logger::close`] calls this method for each sink of the logger no
+    /// longer used by other loggers after flushing them, and dropping a logger
+    /// does so without flushing. The default logger is closed at the program
+    /// exit.
+    ///
+    /// A closed sink may still be called to log records, implementations
+    /// should either reopen resources or return an error.
+    ///
+    /// The default implementation does nothing.
+    ///
+    /// [`Logger::close`]: crate::Logger::close
+    fn close(&self) -> Result<()> {
+        Ok(())
+    }
+
     /// Gets the log level filter.
     #[must_use]
     fn level_filter(&self) -> LevelFilter;
 
     /// Sets the log level filter.
+    ///
+    /// It may be called concurrently with logging, implementations usually
+    /// store the level filter in an atomic.
     fn set_level_filter(&self, level_filter: LevelFilter);
 
     /// Sets the formatter.
+    ///
+    /// It may be called concurrently with logging, implementations usually
+    /// keep the formatter behind a lock.
     fn set_formatter(&self, formatter: Box<dyn Formatter>);
 
     /// Sets a error handler.
@@ -92,3 +224,19 @@ pub trait Sink: Sync + Send {
 
 /// Container type for [`Sink`]s.
 pub type Sinks = Vec<Arc<dyn Sink>>;
+
+// Polls the capacity of all the given sinks, so that each pending sink
+// registers the waker.
+#[cfg(feature = "log-async")]
+pub(crate) fn poll_capacity<'a, I>(sinks: I, cx: &mut Context<'_>) -> Poll<()>
+where
+    I: IntoIterator<Item = &'a Arc<dyn Sink>>,
+{
+    sinks.into_iter().fold(Poll::Ready(()), |poll, sink| {
+        if sink.poll_capacity(cx).is_pending() {
+            Poll::Pending
+        } else {
+            poll
+        }
+    })
+}
@@ -0,0 +1,381 @@
+use std::{sync::Arc, time::Instant};
+
+use crate::{
+    sink::{helper, Sink, Sinks},
+    sync::*,
+    Error, Level, Record, Result,
+};
+
+#[derive(Clone, Copy)]
+struct TokenBucket {
+    rate: f64,
+    burst: f64,
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    #[must_use]
+    fn new(rate: u32, burst: u32) -> Self {
+        let burst = burst.max(1) as f64;
+        Self {
+            rate: rate as f64,
+            burst,
+            tokens: burst,
+            last_refill: Instant::now(),
+        }
+    }
+
+    #[must_use]
+    fn try_take(&mut self, now: Instant) -> bool {
+        let elapsed = now.saturating_duration_since(self.last_refill);
+        self.last_refill = now;
+        self.tokens = (self.tokens + elapsed.as_secs_f64() * self.rate).min(self.burst);
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+struct RateLimitSinkState {
+    global: Option<TokenBucket>,
+    levels: [Option<TokenBucket>; Level::count()],
+    dropped: [usize; Level::count()],
+}
+
+/// A [combined sink], caps the number of records per second forwarded to
+/// internal sinks.
+///
+/// Records are limited by [token buckets]: a bucket holds up to `burst` tokens
+/// and is refilled with `rate` tokens per second, and each forwarded record
+/// takes a token. Records arriving when the bucket is empty are dropped.
+///
+/// A global bucket applies to all levels, see [`RateLimitSinkBuilder::rate`].
+/// Levels can also be limited by their own buckets instead, see
+/// [`RateLimitSinkBuilder::level_rate`]. Records of levels without any bucket
+/// are never dropped.
+///
+/// When records have been dropped, a summary record
+/// `"(dropped {count} records)"` is forwarded for each level before the next
+/// forwarded record, as well as when the sink is flushed or dropped.
+///
+/// This keeps a flood of records, e.g. caused by hostile input, from
+/// exhausting the disk or the I/O bandwidth.
+///
+/// # Examples
+///
+/// ```
+/// use std::sync::Arc;
+///
+/// use spdlog::{prelude::*, sink::RateLimitSink};
+/// # use spdlog::sink::WriteSink;
+///
+/// # fn main() -> Result<(), spdlog::Error> {
+/// # let underlying_sink = Arc::new(WriteSink::builder().target(Vec::new()).build()?);
+/// let sink = Arc::new(
+///     RateLimitSink::builder()
+///         .sink(underlying_sink)
+///         // At most 100 records per second, with bursts of up to 500 records.
+///         .rate(100)
+///         .burst(500)
+///         // Errors are limited separately, so they are not crowded out.
+///         .level_rate(Level::Error, 10, 50)
+///         .build()?,
+/// );
+/// # let doctest = Logger::builder().sink(sink).build()?;
+///
+/// // ... Add the `sink` to a logger
+/// # Ok(()) }
+/// ```
+///
+/// [combined sink]: index.html#combined-sink
+/// [token buckets]: https://en.wikipedia.org/wiki/Token_bucket
+pub struct RateLimitSink {
+    common_impl: helper::CommonImpl,
+    sinks: Sinks,
+    state: Mutex<RateLimitSinkState>,
+}
+
+impl RateLimitSink {
+    /// Gets a builder of `RateLimitSink` with default parameters:
+    ///
+    /// | Parameter       | Default Value           |
+    /// |-----------------|-------------------------|
+    /// | [level_filter]  | `All`                   |
+    /// | [formatter]     | `FullFormatter`         |
+    /// | [error_handler] | [default error handler] |
+    /// |                 |                         |
+    /// | [sinks]         | `[]`                    |
+    /// | [rate]          | `None` (unlimited)      |
+    /// | [burst]         | The same as [rate]      |
+    /// | [level_rate]    | `[]`                    |
+    ///
+    /// [level_filter]: RateLimitSinkBuilder::level_filter
+    /// [formatter]: RateLimitSinkBuilder::formatter
+    /// [error_handler]: RateLimitSinkBuilder::error_handler
+    /// [default error handler]: error/index.html#default-error-handler
+    /// [sinks]: RateLimitSinkBuilder::sink
+    /// [rate]: RateLimitSinkBuilder::rate
+    /// [burst]: RateLimitSinkBuilder::burst
+    /// [level_rate]: RateLimitSinkBuilder::level_rate
+    #[must_use]
+    pub fn builder() -> RateLimitSinkBuilder {
+        RateLimitSinkBuilder {
+            common_builder_impl: helper::CommonBuilderImpl::new(),
+            sinks: vec![],
+            rate: None,
+            burst: None,
+            level_rates: vec![],
+        }
+    }
+
+    /// Gets a reference to internal sinks in the combined sink.
+    #[must_use]
+    pub fn sinks(&self) -> &[Arc<dyn Sink>] {
+        &self.sinks
+    }
+
+    fn log_dropped_summary(&self, state: &mut RateLimitSinkState) -> Result<()> {
+        let mut result = Ok(());
+        for level in Level::iter() {
+            let dropped = std::mem::take(&mut state.dropped[level as usize]);
+            if dropped != 0 {
+                let record =
+                    Record::new(level, format!("(dropped {} records)", dropped), None, None);
+                result = Error::push_result(result, self.log_record(&record));
+            }
+        }
+        result
+    }
+
+    fn log_record(&self, record: &Record) -> Result<()> {
+        #[allow(clippy::manual_try_fold)] // https://github.com/rust-lang/rust-clippy/issues/11554
+        self.sinks.iter().fold(Ok(()), |result, sink| {
+            Error::push_result(result, sink.log(record))
+        })
+    }
+
+    fn flush_sinks(&self) -> Result<()> {
+        #[allow(clippy::manual_try_fold)] // https://github.com/rust-lang/rust-clippy/issues/11554
+        self.sinks.iter().fold(Ok(()), |result, sink| {
+            Error::push_result(result, sink.flush())
+        })
+    }
+}
+
+impl Sink for RateLimitSink {
+    fn log(&self, record: &Record) -> Result<()> {
+        let state = &mut *self.state.lock_expect();
+
+        let level = record.level();
+        let bucket = state.levels[level as usize]
+            .as_mut()
+            .or(state.global.as_mut());
+        if let Some(bucket) = bucket {
+            if !bucket.try_take(Instant::now()) {
+                state.dropped[level as usize] += 1;
+                return Ok(());
+            }
+        }
+
+        let result = self.log_dropped_summary(state);
+        Error::push_result(result, self.log_record(record))
+    }
+
+    fn flush(&self) -> Result<()> {
+        let result = self.log_dropped_summary(&mut self.state.lock_expect());
+        Error::push_result(result, self.flush_sinks())
+    }
+
+    /// For [`RateLimitSink`], the function syncs all internal sinks.
+    fn sync(&self) -> Result<()> {
+        #[allow(clippy::manual_try_fold)] // https://github.com/rust-lang/rust-clippy/issues/11554
+        self.sinks.iter().fold(Ok(()), |result, sink| {
+            Error::push_result(result, sink.sync())
+        })
+    }
+
+    /// For [`RateLimitSink`], the function polls all internal sinks.
+    #[cfg(feature = "log-async")]
+    fn poll_capacity(&self, cx: &mut std::task::Context<'_>) -> std::task::Poll<()> {
+        crate::sink::poll_capacity(&self.sinks, cx)
+    }
+
+    /// For [`RateLimitSink`], the function opens all internal sinks.
+    fn open(&self) -> Result<()> {
+        #[allow(clippy::manual_try_fold)] // https://github.com/rust-lang/rust-clippy/issues/11554
+        self.sinks.iter().fold(Ok(()), |result, sink| {
+            Error::push_result(result, sink.open())
+        })
+    }
+
+    /// For [`RateLimitSink`], the function closes all internal sinks.
+    fn close(&self) -> Result<()> {
+        #[allow(clippy::manual_try_fold)] // https://github.com/rust-lang/rust-clippy/issues/11554
+        self.sinks.iter().fold(Ok(()), |result, sink| {
+            Error::push_result(result, sink.close())
+        })
+    }
+
+    /// For [`RateLimitSink`], the function checks all internal sinks.
+    fn health(&self) -> Result<()> {
+        #[allow(clippy::manual_try_fold)] // https://github.com/rust-lang/rust-clippy/issues/11554
+        self.sinks.iter().fold(Ok(()), |result, sink| {
+            Error::push_result(result, sink.health())
+        })
+    }
+
+    helper::common_impl!(@Sink: common_impl);
+}
+
+impl Drop for RateLimitSink {
+    fn drop(&mut self) {
+        if let Err(err) = self.log_dropped_summary(&mut self.state.lock_expect()) {
+            self.common_impl.non_returnable_error("RateLimitSink", err);
+        }
+        if let Err(err) = self.flush_sinks() {
+            self.common_impl.non_returnable_error("RateLimitSink", err);
+        }
+    }
+}
+
+#[allow(missing_docs)]
+pub struct RateLimitSinkBuilder {
+    common_builder_impl: helper::CommonBuilderImpl,
+    sinks: Sinks,
+    rate: Option<u32>,
+    burst: Option<u32>,
+    level_rates: Vec<(Level, u32, u32)>,
+}
+
+impl RateLimitSinkBuilder {
+    /// Add a [`Sink`].
+    #[must_use]
+    pub fn sink(mut self, sink: Arc<dyn Sink>) -> Self {
+        self.sinks.push(sink);
+        self
+    }
+
+    /// Add multiple [`Sink`]s.
+    #[must_use]
+    pub fn sinks<I>(mut self, sinks: I) -> Self
+    where
+        I: IntoIterator<Item = Arc<dyn Sink>>,
+    {
+        self.sinks.append(&mut sinks.into_iter().collect());
+        self
+    }
+
+    /// Specifies the number of records per second forwarded in the long run,
+    /// for all levels without their own limits.
+    ///
+    /// This parameter is **optional**.
+    #[must_use]
+    pub fn rate(mut self, records_per_sec: u32) -> Self {
+        self.rate = Some(records_per_sec);
+        self
+    }
+
+    /// Specifies the number of records that can be forwarded at once, after
+    /// no records have been forwarded for a while. It applies to the limit
+    /// specified by [`RateLimitSinkBuilder::rate`], and is at least `1`.
+    ///
+    /// This parameter is **optional**.
+    #[must_use]
+    pub fn burst(mut self, records: u32) -> Self {
+        self.burst = Some(records);
+        self
+    }
+
+    /// Limits records of the level by their own rate and burst size, see
+    /// [`RateLimitSinkBuilder::rate`] and [`RateLimitSinkBuilder::burst`].
+    ///
+    /// Records of the level are then not limited by the global limit, nor do
+    /// they count towards it.
+    ///
+    /// This parameter is **optional**, and can be specified multiple times.
+    #[must_use]
+    pub fn level_rate(mut self, level: Level, records_per_sec: u32, burst: u32) -> Self {
+        self.level_rates.push((level, records_per_sec, burst));
+        self
+    }
+
+    helper::common_impl!(@SinkBuilder: common_builder_impl);
+
+    /// Builds a [`RateLimitSink`].
+    pub fn build(self) -> Result<RateLimitSink> {
+        let global = self
+            .rate
+            .map(|rate| TokenBucket::new(rate, self.burst.unwrap_or(rate)));
+        let mut levels = [None; Level::count()];
+        for (level, rate, burst) in self.level_rates {
+            levels[level as usize] = Some(TokenBucket::new(rate, burst));
+        }
+
+        Ok(RateLimitSink {
+            common_impl: helper::CommonImpl::from_builder(self.common_builder_impl),
+            sinks: self.sinks,
+            state: Mutex::new(RateLimitSinkState {
+                global,
+                levels,
+                dropped: [0; Level::count()],
+            }),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{prelude::*, test_utils::*};
+
+    #[test]
+    fn rate_limit() {
+        let test_sink = Arc::new(TestSink::new());
+        let rate_limit_sink = Arc::new(
+            RateLimitSink::builder()
+                .sink(test_sink.clone())
+                .rate(1)
+                .burst(3)
+                .level_rate(Level::Error, 1, 1)
+                .build()
+                .unwrap(),
+        );
+        let test = build_test_logger(|b| b.sink(rate_limit_sink));
+
+        for i in 0..10 {
+            info!(logger: test, "info {}", i);
+            error!(logger: test, "error {}", i);
+        }
+        critical!(logger: test, "critical");
+        test.flush();
+
+        assert_eq!(
+            test_sink.payloads(),
+            [
+                "info 0",
+                "error 0",
+                "info 1",
+                "(dropped 1 records)",
+                "info 2",
+                "(dropped 1 records)",
+                "(dropped 8 records)",
+                "(dropped 7 records)",
+            ]
+        );
+        let levels: Vec<_> = test_sink.records().iter().map(|r| r.level()).collect();
+        assert_eq!(
+            levels[3..],
+            [
+                Level::Error,
+                Level::Info,
+                Level::Critical,
+                Level::Error,
+                Level::Info
+            ]
+        );
+    }
+}
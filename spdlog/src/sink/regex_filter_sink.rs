@@ -0,0 +1,339 @@
+use std::{convert::Infallible, sync::Arc};
+
+use regex::Regex;
+
+use crate::{
+    error::InvalidArgumentError,
+    sink::{helper, Sink, Sinks},
+    Error, Filter, FilterResult, Record, Result,
+};
+
+/// Includes or excludes records by matching their payloads against regular
+/// expressions.
+///
+/// A record passes the filter if its payload matches any of the include
+/// regexes (or no include regex is specified), and matches none of the exclude
+/// regexes.
+///
+/// This is used by [`RegexFilterSink`]. It is also a [`Filter`], which
+/// rejects records not passing it and has no opinion on the others, so it can
+/// be attached to a logger via [`LoggerBuilder::filter`] to filter all records
+/// of the logger.
+///
+/// This type is only available if crate feature `regex` is enabled.
+///
+/// # Examples
+///
+/// ```
+/// use spdlog::sink::RegexFilter;
+///
+/// # fn main() -> Result<(), spdlog::Error> {
+/// let filter = RegexFilter::new()
+///     .exclude(r"^GET /health\b")?
+///     .exclude("connection reset by peer")?;
+///
+/// assert!(!filter.test("GET /health 200"));
+/// assert!(filter.test("GET /users 200"));
+/// # Ok(()) }
+/// ```
+///
+/// [`LoggerBuilder::filter`]: crate::LoggerBuilder::filter
+#[derive(Clone, Debug, Default)]
+pub struct RegexFilter {
+    includes: Vec<Regex>,
+    excludes: Vec<Regex>,
+}
+
+impl RegexFilter {
+    /// Constructs a `RegexFilter` passing all records.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds an include regex.
+    ///
+    /// # Error
+    ///
+    /// If the regex is invalid, [`Error::InvalidArgument`] will be returned.
+    pub fn include(mut self, regex: &str) -> Result<Self> {
+        self.includes.push(compile(regex)?);
+        Ok(self)
+    }
+
+    /// Adds an exclude regex.
+    ///
+    /// # Error
+    ///
+    /// If the regex is invalid, [`Error::InvalidArgument`] will be returned.
+    pub fn exclude(mut self, regex: &str) -> Result<Self> {
+        self.excludes.push(compile(regex)?);
+        Ok(self)
+    }
+
+    /// Determines if a record with the given payload passes the filter.
+    #[must_use]
+    pub fn test(&self, payload: &str) -> bool {
+        (self.includes.is_empty() || self.includes.iter().any(|re| re.is_match(payload)))
+            && !self.excludes.iter().any(|re| re.is_match(payload))
+    }
+}
+
+impl Filter for RegexFilter {
+    fn filter(&self, record: &Record) -> FilterResult {
+        if self.test(record.payload()) {
+            FilterResult::Neutral
+        } else {
+            FilterResult::Reject
+        }
+    }
+}
+
+fn compile(regex: &str) -> Result<Regex> {
+    Regex::new(regex)
+        .map_err(|err| Error::InvalidArgument(InvalidArgumentError::Regex(err.to_string())))
+}
+
+/// A [combined sink], forwards only records passing a [`RegexFilter`] to its
+/// internal sinks.
+///
+/// This is useful for quickly muting known-noisy messages.
+///
+/// This sink is only available if crate feature `regex` is enabled.
+///
+/// # Examples
+///
+/// ```
+/// use std::sync::Arc;
+///
+/// use spdlog::{
+///     prelude::*,
+///     sink::{RegexFilter, RegexFilterSink, StdStream, StdStreamSink},
+/// };
+///
+/// # fn main() -> Result<(), spdlog::Error> {
+/// let sink = Arc::new(
+///     RegexFilterSink::builder()
+///         .sink(Arc::new(StdStreamSink::builder().std_stream(StdStream::Stdout).build()?))
+///         .filter(RegexFilter::new().exclude("^heartbeat")?)
+///         .build()?,
+/// );
+/// let logger = Logger::builder().sink(sink).build()?;
+///
+/// info!(logger: logger, "heartbeat from peer 3"); // muted
+/// info!(logger: logger, "peer 3 joined");
+/// # Ok(()) }
+/// ```
+///
+/// [combined sink]: index.html#combined-sink
+pub struct RegexFilterSink {
+    common_impl: helper::CommonImpl,
+    sinks: Sinks,
+    filter: RegexFilter,
+}
+
+impl RegexFilterSink {
+    /// Gets a builder of `RegexFilterSink` with default parameters:
+    ///
+    /// | Parameter       | Default Value           |
+    /// |-----------------|-------------------------|
+    /// | [level_filter]  | `All`                   |
+    /// | [formatter]     | `FullFormatter`         |
+    /// | [error_handler] | [default error handler] |
+    /// |                 |                         |
+    /// | [sinks]         | `[]`                    |
+    /// | [filter]        | *must be specified*     |
+    ///
+    /// [level_filter]: RegexFilterSinkBuilder::level_filter
+    /// [formatter]: RegexFilterSinkBuilder::formatter
+    /// [error_handler]: RegexFilterSinkBuilder::error_handler
+    /// [default error handler]: error/index.html#default-error-handler
+    /// [sinks]: RegexFilterSinkBuilder::sink
+    /// [filter]: RegexFilterSinkBuilder::filter
+    #[must_use]
+    pub fn builder() -> RegexFilterSinkBuilder<()> {
+        RegexFilterSinkBuilder {
+            common_builder_impl: helper::CommonBuilderImpl::new(),
+            sinks: vec![],
+            filter: (),
+        }
+    }
+
+    /// Gets a reference to internal sinks in the combined sink.
+    #[must_use]
+    pub fn sinks(&self) -> &[Arc<dyn Sink>] {
+        &self.sinks
+    }
+
+    /// Gets a reference to the filter.
+    #[must_use]
+    pub fn filter(&self) -> &RegexFilter {
+        &self.filter
+    }
+}
+
+impl Sink for RegexFilterSink {
+    fn log(&self, record: &Record) -> Result<()> {
+        if !self.filter.test(record.payload()) {
+            return Ok(());
+        }
+
+        #[allow(clippy::manual_try_fold)] // https://github.com/rust-lang/rust-clippy/issues/11554
+        self.sinks.iter().fold(Ok(()), |result, sink| {
+            Error::push_result(result, sink.log(record))
+        })
+    }
+
+    fn flush(&self) -> Result<()> {
+        #[allow(clippy::manual_try_fold)] // https://github.com/rust-lang/rust-clippy/issues/11554
+        self.sinks.iter().fold(Ok(()), |result, sink| {
+            Error::push_result(result, sink.flush())
+        })
+    }
+
+    /// For [`RegexFilterSink`], the function syncs all internal sinks.
+    fn sync(&self) -> Result<()> {
+        #[allow(clippy::manual_try_fold)] // https://github.com/rust-lang/rust-clippy/issues/11554
+        self.sinks.iter().fold(Ok(()), |result, sink| {
+            Error::push_result(result, sink.sync())
+        })
+    }
+
+    /// For [`RegexFilterSink`], the function polls all internal sinks.
+    #[cfg(feature = "log-async")]
+    fn poll_capacity(&self, cx: &mut std::task::Context<'_>) -> std::task::Poll<()> {
+        crate::sink::poll_capacity(&self.sinks, cx)
+    }
+
+    /// For [`RegexFilterSink`], the function opens all internal sinks.
+    fn open(&self) -> Result<()> {
+        #[allow(clippy::manual_try_fold)] // https://github.com/rust-lang/rust-clippy/issues/11554
+        self.sinks.iter().fold(Ok(()), |result, sink| {
+            Error::push_result(result, sink.open())
+        })
+    }
+
+    /// For [`RegexFilterSink`], the function closes all internal sinks.
+    fn close(&self) -> Result<()> {
+        #[allow(clippy::manual_try_fold)] // https://github.com/rust-lang/rust-clippy/issues/11554
+        self.sinks.iter().fold(Ok(()), |result, sink| {
+            Error::push_result(result, sink.close())
+        })
+    }
+
+    /// For [`RegexFilterSink`], the function checks all internal sinks.
+    fn health(&self) -> Result<()> {
+        #[allow(clippy::manual_try_fold)] // https://github.com/rust-lang/rust-clippy/issues/11554
+        self.sinks.iter().fold(Ok(()), |result, sink| {
+            Error::push_result(result, sink.health())
+        })
+    }
+
+    helper::common_impl!(@Sink: common_impl);
+}
+
+/// #
+#[doc = include_str!("../include/doc/generic-builder-note.md")]
+pub struct RegexFilterSinkBuilder<ArgF> {
+    common_builder_impl: helper::CommonBuilderImpl,
+    sinks: Sinks,
+    filter: ArgF,
+}
+
+impl<ArgF> RegexFilterSinkBuilder<ArgF> {
+    /// Add a [`Sink`].
+    #[must_use]
+    pub fn sink(mut self, sink: Arc<dyn Sink>) -> Self {
+        self.sinks.push(sink);
+        self
+    }
+
+    /// Add multiple [`Sink`]s.
+    #[must_use]
+    pub fn sinks<I>(mut self, sinks: I) -> Self
+    where
+        I: IntoIterator<Item = Arc<dyn Sink>>,
+    {
+        self.sinks.append(&mut sinks.into_iter().collect());
+        self
+    }
+
+    /// Specifies the filter deciding which records are forwarded.
+    ///
+    /// This parameter is **required**.
+    #[must_use]
+    pub fn filter(self, filter: RegexFilter) -> RegexFilterSinkBuilder<RegexFilter> {
+        RegexFilterSinkBuilder {
+            common_builder_impl: self.common_builder_impl,
+            sinks: self.sinks,
+            filter,
+        }
+    }
+
+    helper::common_impl!(@SinkBuilder: common_builder_impl);
+}
+
+impl RegexFilterSinkBuilder<()> {
+    #[doc(hidden)]
+    #[deprecated(note = "\n\n\
+        builder compile-time error:\n\
+        - missing required parameter `filter`\n\n\
+    ")]
+    pub fn build(self, _: Infallible) {}
+}
+
+impl RegexFilterSinkBuilder<RegexFilter> {
+    /// Builds a [`RegexFilterSink`].
+    pub fn build(self) -> Result<RegexFilterSink> {
+        Ok(RegexFilterSink {
+            common_impl: helper::CommonImpl::from_builder(self.common_builder_impl),
+            sinks: self.sinks,
+            filter: self.filter,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{prelude::*, test_utils::*};
+
+    #[test]
+    fn filter() {
+        let test_sink = Arc::new(TestSink::new());
+        let filter_sink = Arc::new(
+            RegexFilterSink::builder()
+                .sink(test_sink.clone())
+                .filter(
+                    RegexFilter::new()
+                        .include("^request")
+                        .unwrap()
+                        .exclude(r"/health\b")
+                        .unwrap(),
+                )
+                .build()
+                .unwrap(),
+        );
+        let test = build_test_logger(|b| b.sink(filter_sink));
+
+        info!(logger: test, "request GET /users");
+        info!(logger: test, "request GET /health");
+        info!(logger: test, "request GET /healthz");
+        info!(logger: test, "response 200");
+
+        assert_eq!(
+            test_sink.payloads(),
+            ["request GET /users", "request GET /healthz"]
+        );
+
+        let filter = RegexFilter::new().exclude("^heartbeat").unwrap();
+        let record = |payload| Record::new(Level::Info, payload, None, None);
+        assert_eq!(filter.filter(&record("heartbeat")), FilterResult::Reject);
+        assert_eq!(filter.filter(&record("joined")), FilterResult::Neutral);
+
+        assert!(matches!(
+            RegexFilter::new().exclude("("),
+            Err(Error::InvalidArgument(InvalidArgumentError::Regex(_)))
+        ));
+    }
+}
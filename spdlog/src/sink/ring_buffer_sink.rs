@@ -0,0 +1,216 @@
+use std::{collections::VecDeque, convert::Infallible};
+
+use crate::{
+    formatter::FormatterContext,
+    sink::{helper, Sink},
+    sync::*,
+    Error, Record, RecordOwned, Result, StringBuf,
+};
+
+/// A sink keeping the latest records in memory.
+///
+/// Up to [`capacity`] records are kept, older ones are discarded as new ones
+/// arrive. Records are kept as they are and only formatted when dumped, so
+/// verbose logs can be kept cheaply, and flushed to other sinks only when they
+/// are needed, e.g. after an error occurred.
+///
+/// # Examples
+///
+/// ```
+/// use std::sync::Arc;
+///
+/// use spdlog::{prelude::*, sink::RingBufferSink};
+/// # use spdlog::sink::WriteSink;
+///
+/// # fn main() -> Result<(), spdlog::Error> {
+/// # let file_sink = Arc::new(WriteSink::builder().target(Vec::new()).build()?);
+/// let ring_buffer = Arc::new(RingBufferSink::builder().capacity(1000).build()?);
+/// let logger = Logger::builder()
+///     .level_filter(LevelFilter::All)
+///     .sink(ring_buffer.clone())
+///     .build()?;
+///
+/// trace!(logger: logger, "connecting to peer 3");
+/// trace!(logger: logger, "handshake done");
+///
+/// // Something went wrong, save the latest trace logs.
+/// ring_buffer.drain_to(&*file_sink)?;
+/// # Ok(()) }
+/// ```
+///
+/// [`capacity`]: RingBufferSinkBuilder::capacity
+pub struct RingBufferSink {
+    common_impl: helper::CommonImpl,
+    capacity: usize,
+    records: Mutex<VecDeque<RecordOwned>>,
+}
+
+impl RingBufferSink {
+    /// Gets a builder of `RingBufferSink` with default parameters:
+    ///
+    /// | Parameter       | Default Value           |
+    /// |-----------------|-------------------------|
+    /// | [level_filter]  | `All`                   |
+    /// | [formatter]     | `FullFormatter`         |
+    /// | [error_handler] | [default error handler] |
+    /// |                 |                         |
+    /// | [capacity]      | *must be specified*     |
+    ///
+    /// [level_filter]: RingBufferSinkBuilder::level_filter
+    /// [formatter]: RingBufferSinkBuilder::formatter
+    /// [error_handler]: RingBufferSinkBuilder::error_handler
+    /// [default error handler]: error/index.html#default-error-handler
+    /// [capacity]: RingBufferSinkBuilder::capacity
+    #[must_use]
+    pub fn builder() -> RingBufferSinkBuilder<()> {
+        RingBufferSinkBuilder {
+            common_builder_impl: helper::CommonBuilderImpl::new(),
+            capacity: (),
+        }
+    }
+
+    /// Gets the maximum number of records kept.
+    #[must_use]
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Gets the kept records, the oldest first.
+    #[must_use]
+    pub fn records(&self) -> Vec<RecordOwned> {
+        self.records.lock_expect().iter().cloned().collect()
+    }
+
+    /// Formats the kept records with the formatter of the sink, the oldest
+    /// first.
+    pub fn dump(&self) -> Result<Vec<String>> {
+        self.records
+            .lock_expect()
+            .iter()
+            .map(|record| {
+                let mut string_buf = StringBuf::new();
+                let mut ctx = FormatterContext::new();
+                self.common_impl
+                    .format(&record.as_ref(), &mut string_buf, &mut ctx)?;
+                Ok(string_buf.to_string())
+            })
+            .collect()
+    }
+
+    /// Logs the kept records to the sink, the oldest first, then flushes the
+    /// sink and discards the records.
+    ///
+    /// The records are formatted by the formatter of the given sink, and
+    /// filtered by its level filter.
+    pub fn drain_to(&self, sink: &dyn Sink) -> Result<()> {
+        let records = std::mem::take(&mut *self.records.lock_expect());
+        #[allow(clippy::manual_try_fold)] // https://github.com/rust-lang/rust-clippy/issues/11554
+        let result = records
+            .iter()
+            .map(|record| record.as_ref())
+            .filter(|record| sink.should_log(record.level()))
+            .fold(Ok(()), |result, record| {
+                Error::push_result(result, sink.log(&record))
+            });
+        Error::push_result(result, sink.flush())
+    }
+
+    /// Discards the kept records.
+    pub fn clear(&self) {
+        self.records.lock_expect().clear();
+    }
+}
+
+impl Sink for RingBufferSink {
+    fn log(&self, record: &Record) -> Result<()> {
+        if self.capacity == 0 {
+            return Ok(());
+        }
+        let mut records = self.records.lock_expect();
+        if records.len() == self.capacity {
+            records.pop_front();
+        }
+        records.push_back(record.to_owned());
+        Ok(())
+    }
+
+    fn flush(&self) -> Result<()> {
+        Ok(())
+    }
+
+    helper::common_impl!(@Sink: common_impl);
+}
+
+/// #
+#[doc = include_str!("../include/doc/generic-builder-note.md")]
+pub struct RingBufferSinkBuilder<ArgC> {
+    common_builder_impl: helper::CommonBuilderImpl,
+    capacity: ArgC,
+}
+
+impl<ArgC> RingBufferSinkBuilder<ArgC> {
+    /// Specifies the maximum number of records kept.
+    ///
+    /// This parameter is **required**.
+    #[must_use]
+    pub fn capacity(self, capacity: usize) -> RingBufferSinkBuilder<usize> {
+        RingBufferSinkBuilder {
+            common_builder_impl: self.common_builder_impl,
+            capacity,
+        }
+    }
+
+    helper::common_impl!(@SinkBuilder: common_builder_impl);
+}
+
+impl RingBufferSinkBuilder<()> {
+    #[doc(hidden)]
+    #[deprecated(note = "\n\n\
+        builder compile-time error:\n\
+        - missing required parameter `capacity`\n\n\
+    ")]
+    pub fn build(self, _: Infallible) {}
+}
+
+impl RingBufferSinkBuilder<usize> {
+    /// Builds a [`RingBufferSink`].
+    pub fn build(self) -> Result<RingBufferSink> {
+        Ok(RingBufferSink {
+            common_impl: helper::CommonImpl::from_builder(self.common_builder_impl),
+            capacity: self.capacity,
+            records: Mutex::new(VecDeque::new()),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{prelude::*, test_utils::*};
+
+    #[test]
+    fn keep_latest() {
+        let ring_buffer = Arc::new(
+            RingBufferSink::builder()
+                .capacity(3)
+                .formatter(Box::new(NoModFormatter::new()))
+                .build()
+                .unwrap(),
+        );
+        let test = build_test_logger(|b| b.sink(ring_buffer.clone()));
+
+        for i in 0..5 {
+            info!(logger: test, "{}", i);
+        }
+        assert_eq!(ring_buffer.dump().unwrap(), ["2", "3", "4"]);
+        assert_eq!(ring_buffer.records()[0].payload(), "2");
+
+        let test_sink = TestSink::new();
+        test_sink.set_level_filter(LevelFilter::MoreSevereEqual(Level::Warn));
+        warn!(logger: test, "5");
+        ring_buffer.drain_to(&test_sink).unwrap();
+        assert_eq!(test_sink.payloads(), ["5"]);
+        assert_eq!(test_sink.flush_count(), 1);
+        assert!(ring_buffer.records().is_empty());
+    }
+}
@@ -11,15 +11,23 @@ use std::{
     result::Result as StdResult,
     time::{Duration, SystemTime},
 };
+#[cfg(feature = "gzip")]
+use std::{io, sync::mpsc, thread::JoinHandle};
 
-use chrono::prelude::*;
-
+#[cfg(feature = "gzip")]
+use crate::thread_options;
 use crate::{
+    clock::Clock,
+    diagnostics,
     error::InvalidArgumentError,
-    formatter::FormatterContext,
-    sink::{helper, Sink},
+    formatter::TimeZone,
+    periodic_worker::PeriodicWorker,
+    sink::{
+        disk_full::{DiskFullHandler, DiskFullStrategy},
+        helper, Sink,
+    },
     sync::*,
-    utils, Error, Record, Result, StringBuf,
+    utils, Error, ErrorHandler, Level, Record, Result, StringBuf,
 };
 
 /// Rotation policies for [`RotatingFileSink`].
@@ -59,6 +67,9 @@ pub enum RotationPolicy {
         u64,
     ),
     /// Rotating to a new log file at a specified time point within a day.
+    ///
+    /// The time point is in the time zone specified by
+    /// [`RotatingFileSinkBuilder::time_zone`], the local time zone by default.
     Daily {
         /// Hour of the time point. Range: [0, 23].
         hour: u32,
@@ -86,6 +97,9 @@ trait Rotator {
     #[allow(clippy::ptr_arg)]
     fn log(&self, record: &Record, string_buf: &StringBuf) -> Result<()>;
     fn flush(&self) -> Result<()>;
+    fn sync(&self) -> Result<()>;
+    #[must_use]
+    fn current_file_path(&self) -> PathBuf;
     fn drop_flush(&mut self) -> Result<()> {
         self.flush()
     }
@@ -100,6 +114,8 @@ struct RotatorFileSize {
     base_path: PathBuf,
     max_size: u64,
     max_files: usize,
+    #[cfg(feature = "gzip")]
+    compressor: Option<Compressor>,
     inner: SpinMutex<RotatorFileSizeInner>,
 }
 
@@ -111,7 +127,10 @@ struct RotatorFileSizeInner {
 struct RotatorTimePoint {
     base_path: PathBuf,
     time_point: TimePoint,
+    time_zone: TimeZone,
     max_files: usize,
+    #[cfg(feature = "gzip")]
+    compressor: Option<Compressor>,
     inner: SpinMutex<RotatorTimePointInner>,
 }
 
@@ -124,6 +143,7 @@ enum TimePoint {
 
 struct RotatorTimePointInner {
     file: BufWriter<File>,
+    file_path: PathBuf,
     rotation_time_point: SystemTime,
     file_paths: Option<LinkedList<PathBuf>>,
 }
@@ -148,6 +168,8 @@ struct RotatorTimePointInner {
 pub struct RotatingFileSink {
     common_impl: helper::CommonImpl,
     rotator: RotatorKind,
+    disk_full: DiskFullHandler,
+    _janitor: Option<PeriodicWorker>,
 }
 
 /// #
@@ -158,21 +180,34 @@ pub struct RotatingFileSinkBuilder<ArgBP, ArgRP> {
     rotation_policy: ArgRP,
     max_files: usize,
     rotate_on_open: bool,
+    clock: Option<Arc<dyn Clock>>,
+    time_zone: TimeZone,
+    disk_full_strategy: DiskFullStrategy,
+    cleanup_interval: Option<Duration>,
+    max_age: Option<Duration>,
+    #[cfg(feature = "gzip")]
+    compress: bool,
 }
 
 impl RotatingFileSink {
     /// Gets a builder of `RotatingFileSink` with default parameters:
     ///
-    /// | Parameter         | Default Value           |
-    /// |-------------------|-------------------------|
-    /// | [level_filter]    | `All`                   |
-    /// | [formatter]       | `FullFormatter`         |
-    /// | [error_handler]   | [default error handler] |
-    /// |                   |                         |
-    /// | [base_path]       | *must be specified*     |
-    /// | [rotation_policy] | *must be specified*     |
-    /// | [max_files]       | `0`                     |
-    /// | [rotate_on_open]  | `false`                 |
+    /// | Parameter         | Default Value             |
+    /// |-------------------|---------------------------|
+    /// | [level_filter]    | `All`                     |
+    /// | [formatter]       | `FullFormatter`           |
+    /// | [error_handler]   | [default error handler]   |
+    /// |                   |                           |
+    /// | [base_path]       | *must be specified*       |
+    /// | [rotation_policy] | *must be specified*       |
+    /// | [max_files]       | `0`                       |
+    /// | [rotate_on_open]  | `false`                   |
+    /// | [clock]           | `None` (system time)      |
+    /// | [time_zone]       | `TimeZone::LOCAL`         |
+    /// | [disk_full]       | `DiskFullStrategy::Error` |
+    /// | [cleanup]         | `None`                    |
+    /// | [max_age]         | `None`                    |
+    /// | [compress]        | `false`                   |
     ///
     /// [level_filter]: RotatingFileSinkBuilder::level_filter
     /// [formatter]: RotatingFileSinkBuilder::formatter
@@ -182,6 +217,12 @@ impl RotatingFileSink {
     /// [rotation_policy]: RotatingFileSinkBuilder::rotation_policy
     /// [max_files]: RotatingFileSinkBuilder::max_files
     /// [rotate_on_open]: RotatingFileSinkBuilder::rotate_on_open
+    /// [clock]: RotatingFileSinkBuilder::clock
+    /// [time_zone]: RotatingFileSinkBuilder::time_zone
+    /// [disk_full]: RotatingFileSinkBuilder::disk_full_strategy
+    /// [cleanup]: RotatingFileSinkBuilder::cleanup_interval
+    /// [max_age]: RotatingFileSinkBuilder::max_age
+    /// [compress]: RotatingFileSinkBuilder::compress
     #[must_use]
     pub fn builder() -> RotatingFileSinkBuilder<(), ()> {
         RotatingFileSinkBuilder {
@@ -190,6 +231,13 @@ impl RotatingFileSink {
             rotation_policy: (),
             max_files: 0,
             rotate_on_open: false,
+            clock: None,
+            time_zone: TimeZone::LOCAL,
+            disk_full_strategy: DiskFullStrategy::Error,
+            cleanup_interval: None,
+            max_age: None,
+            #[cfg(feature = "gzip")]
+            compress: false,
         }
     }
 
@@ -249,18 +297,32 @@ impl RotatingFileSink {
 
 impl Sink for RotatingFileSink {
     fn log(&self, record: &Record) -> Result<()> {
-        let mut string_buf = StringBuf::new();
-        let mut ctx = FormatterContext::new();
-        self.common_impl
-            .formatter
-            .read()
-            .format(record, &mut string_buf, &mut ctx)?;
-
-        self.rotator.log(record, &string_buf)
+        self.common_impl.format_with(record, |string_buf, _| {
+            self.disk_full.log(
+                "RotatingFileSink",
+                record,
+                string_buf,
+                |record, string_buf| self.rotator.log(record, string_buf),
+            )
+        })
     }
 
     fn flush(&self) -> Result<()> {
-        self.rotator.flush()
+        self.disk_full.flush(|| self.rotator.flush())
+    }
+
+    /// For [`RotatingFileSink`], the function flushes the buffer and
+    /// synchronizes the contents of the current file to the storage device.
+    fn sync(&self) -> Result<()> {
+        self.disk_full.flush(|| self.rotator.sync())
+    }
+
+    /// Returns an error if the current file is no longer accessible at its
+    /// path, for example, it has been removed.
+    fn health(&self) -> Result<()> {
+        fs::metadata(self.rotator.current_file_path())
+            .map(|_| ())
+            .map_err(Error::QueryFileMetadata)
     }
 
     helper::common_impl!(@Sink: common_impl);
@@ -323,6 +385,20 @@ impl Rotator for RotatorKind {
         }
     }
 
+    fn sync(&self) -> Result<()> {
+        match self {
+            Self::FileSize(rotator) => rotator.sync(),
+            Self::TimePoint(rotator) => rotator.sync(),
+        }
+    }
+
+    fn current_file_path(&self) -> PathBuf {
+        match self {
+            Self::FileSize(rotator) => rotator.current_file_path(),
+            Self::TimePoint(rotator) => rotator.current_file_path(),
+        }
+    }
+
     fn drop_flush(&mut self) -> Result<()> {
         match self {
             Self::FileSize(rotator) => rotator.drop_flush(),
@@ -337,6 +413,7 @@ impl RotatorFileSize {
         max_size: u64,
         max_files: usize,
         rotate_on_open: bool,
+        #[cfg(feature = "gzip")] compressor: Option<Compressor>,
     ) -> Result<Self> {
         let file = utils::open_file(&base_path, false)?;
         let current_size = file.metadata().map_err(Error::QueryFileMetadata)?.len();
@@ -345,6 +422,8 @@ impl RotatorFileSize {
             base_path,
             max_size,
             max_files,
+            #[cfg(feature = "gzip")]
+            compressor,
             inner: SpinMutex::new(RotatorFileSizeInner::new(file, current_size)),
         };
 
@@ -362,19 +441,18 @@ impl RotatorFileSize {
     }
 
     fn rotate(&self, opened_file: &mut SpinMutexGuard<RotatorFileSizeInner>) -> Result<()> {
+        #[cfg(feature = "gzip")]
+        if let Some(compressor) = &self.compressor {
+            if self.max_files > 1 {
+                return self.rotate_compressed(compressor, opened_file);
+            }
+        }
+
         let inner = || {
             for i in (1..self.max_files).rev() {
                 let src = Self::calc_file_path(&self.base_path, i - 1);
-                if !src.exists() {
-                    continue;
-                }
-
                 let dst = Self::calc_file_path(&self.base_path, i);
-                if dst.exists() {
-                    fs::remove_file(&dst).map_err(Error::RemoveFile)?;
-                }
-
-                fs::rename(src, dst).map_err(Error::RenameFile)?;
+                shift_file(src, dst)?;
             }
             Ok(())
         };
@@ -387,6 +465,38 @@ impl RotatorFileSize {
         }
 
         opened_file.file = Some(BufWriter::new(self.reopen()?));
+        if res.is_ok() {
+            report_rotated(&self.base_path);
+        }
+
+        res
+    }
+
+    // Moves the file aside to be shifted into place and compressed by the
+    // compressor, so that rotating never waits for previous compressions.
+    #[cfg(feature = "gzip")]
+    fn rotate_compressed(
+        &self,
+        compressor: &Compressor,
+        opened_file: &mut SpinMutexGuard<RotatorFileSizeInner>,
+    ) -> Result<()> {
+        opened_file.file = None;
+
+        let staged = compressor.staging_path(&self.base_path);
+        let res = fs::rename(&self.base_path, &staged).map_err(Error::RenameFile);
+        if res.is_err() {
+            opened_file.current_size = 0;
+        }
+
+        opened_file.file = Some(BufWriter::new(self.reopen()?));
+        if res.is_ok() {
+            report_rotated(&self.base_path);
+            compressor.push(CompressJob::Rotated {
+                staged,
+                base_path: self.base_path.clone(),
+                max_files: self.max_files,
+            });
+        }
 
         res
     }
@@ -419,7 +529,7 @@ impl RotatorFileSize {
     }
 
     // if `self.inner.file` is `None`, try to reopen the file.
-    fn lock_inner(&self) -> Result<SpinMutexGuard<RotatorFileSizeInner>> {
+    fn lock_inner(&self) -> Result<SpinMutexGuard<'_, RotatorFileSizeInner>> {
         let mut inner = self.inner.lock();
         if inner.file.is_none() {
             inner.file = Some(BufWriter::new(self.reopen()?));
@@ -455,6 +565,18 @@ impl Rotator for RotatorFileSize {
             .map_err(Error::FlushBuffer)
     }
 
+    fn sync(&self) -> Result<()> {
+        let mut inner = self.lock_inner()?;
+        let file = inner.file.as_mut().unwrap();
+        file.flush()
+            .and_then(|_| file.get_ref().sync_data())
+            .map_err(Error::FlushBuffer)
+    }
+
+    fn current_file_path(&self) -> PathBuf {
+        self.base_path.clone()
+    }
+
     fn drop_flush(&mut self) -> Result<()> {
         let mut inner = self.inner.lock();
         if let Some(file) = inner.file.as_mut() {
@@ -480,23 +602,29 @@ impl RotatorTimePoint {
         override_now: Option<SystemTime>,
         base_path: PathBuf,
         time_point: TimePoint,
+        time_zone: TimeZone,
         max_files: usize,
         truncate: bool,
+        #[cfg(feature = "gzip")] compressor: Option<Compressor>,
     ) -> Result<Self> {
         let now = override_now.unwrap_or_else(SystemTime::now);
-        let file_path = Self::calc_file_path(base_path.as_path(), time_point, now);
-        let file = utils::open_file(file_path, truncate)?;
+        let file_path = Self::calc_file_path(base_path.as_path(), time_point, time_zone, now);
+        let file = utils::open_file(&file_path, truncate)?;
 
         let inner = RotatorTimePointInner {
             file: BufWriter::new(file),
-            rotation_time_point: Self::next_rotation_time_point(time_point, now),
+            file_path,
+            rotation_time_point: Self::next_rotation_time_point(time_point, time_zone, now),
             file_paths: None,
         };
 
         let mut res = Self {
             base_path,
             time_point,
+            time_zone,
             max_files,
+            #[cfg(feature = "gzip")]
+            compressor,
             inner: SpinMutex::new(inner),
         };
 
@@ -510,9 +638,10 @@ impl RotatorTimePoint {
             let mut file_paths = LinkedList::new();
 
             for _ in 0..max_files {
-                let file_path = Self::calc_file_path(&self.base_path, self.time_point, now);
+                let file_path =
+                    Self::calc_file_path(&self.base_path, self.time_point, self.time_zone, now);
 
-                if !file_path.exists() {
+                if !self.exists(&file_path) {
                     break;
                 }
 
@@ -527,40 +656,37 @@ impl RotatorTimePoint {
     // a little expensive, should only be called when rotation is needed or in
     // constructor.
     #[must_use]
-    fn next_rotation_time_point(time_point: TimePoint, now: SystemTime) -> SystemTime {
-        let now: DateTime<Local> = now.into();
-        let mut rotation_time = now;
+    fn next_rotation_time_point(
+        time_point: TimePoint,
+        time_zone: TimeZone,
+        now: SystemTime,
+    ) -> SystemTime {
+        let local_now = time_zone.date_time(now);
 
         match time_point {
             TimePoint::Daily { hour, minute } => {
-                rotation_time = rotation_time
-                    .with_hour(hour)
-                    .unwrap()
-                    .with_minute(minute)
-                    .unwrap()
-                    .with_second(0)
-                    .unwrap()
-                    .with_nanosecond(0)
-                    .unwrap()
+                let local = local_now.local_timestamp_at(hour, minute, 0);
+                let rotation_time = time_zone.local_timestamp_to_time(local, local_now.offset());
+                if rotation_time > now {
+                    rotation_time
+                } else {
+                    // Count a day on the clocks of the time zone rather than 24
+                    // hours, days across daylight saving time transitions are
+                    // shorter or longer.
+                    time_zone
+                        .local_timestamp_to_time(local + SECONDS_PER_DAY as i64, local_now.offset())
+                }
             }
             TimePoint::Hourly => {
-                rotation_time = rotation_time
-                    .with_minute(0)
-                    .unwrap()
-                    .with_second(0)
-                    .unwrap()
-                    .with_nanosecond(0)
-                    .unwrap()
+                let rotation_time = time_zone.time_of_day(&local_now, local_now.hour(), 0, 0);
+                if rotation_time > now {
+                    rotation_time
+                } else {
+                    rotation_time + HOUR_1
+                }
             }
-            TimePoint::Period { .. } => {}
-        };
-
-        if rotation_time <= now {
-            rotation_time = rotation_time
-                .checked_add_signed(time_point.delta_chrono())
-                .unwrap();
+            TimePoint::Period(duration) => now + duration,
         }
-        rotation_time.into()
     }
 
     fn push_new_remove_old(
@@ -573,7 +699,16 @@ impl RotatorTimePoint {
         while file_paths.len() >= self.max_files {
             let old = file_paths.pop_front().unwrap();
             if old.exists() {
-                fs::remove_file(old).map_err(Error::RemoveFile)?;
+                fs::remove_file(&old).map_err(Error::RemoveFile)?;
+                report_removed(&old);
+            }
+            #[cfg(feature = "gzip")]
+            if self.compressor.is_some() {
+                let old = archive_path(&old);
+                if old.exists() {
+                    fs::remove_file(&old).map_err(Error::RemoveFile)?;
+                    report_removed(&old);
+                }
             }
         }
         file_paths.push_back(new);
@@ -581,14 +716,25 @@ impl RotatorTimePoint {
         Ok(())
     }
 
+    // Checks if the file exists, either as it is or compressed.
+    #[must_use]
+    fn exists(&self, file_path: &Path) -> bool {
+        #[cfg(feature = "gzip")]
+        if self.compressor.is_some() && archive_path(file_path).exists() {
+            return true;
+        }
+        file_path.exists()
+    }
+
     #[must_use]
     fn calc_file_path(
         base_path: impl AsRef<Path>,
         time_point: TimePoint,
+        time_zone: TimeZone,
         system_time: SystemTime,
     ) -> PathBuf {
         let base_path = base_path.as_ref();
-        let local_time: DateTime<Local> = system_time.into();
+        let local_time = time_zone.date_time(system_time);
 
         let mut file_name = base_path
             .file_stem()
@@ -652,11 +798,21 @@ impl Rotator for RotatorTimePoint {
             file_path = Some(Self::calc_file_path(
                 &self.base_path,
                 self.time_point,
+                self.time_zone,
                 record_time,
             ));
             inner.file = BufWriter::new(utils::open_file(file_path.as_ref().unwrap(), true)?);
+            // The previous file has been flushed and closed by dropping its writer.
+            #[cfg(feature = "gzip")]
+            if let Some(compressor) = &self.compressor {
+                if inner.file_path != *file_path.as_ref().unwrap() {
+                    compressor.push(CompressJob::File(inner.file_path.clone()));
+                }
+            }
+            inner.file_path = file_path.clone().unwrap();
+            report_rotated(file_path.as_ref().unwrap());
             inner.rotation_time_point =
-                Self::next_rotation_time_point(self.time_point, record_time);
+                Self::next_rotation_time_point(self.time_point, self.time_zone, record_time);
         }
 
         inner
@@ -674,6 +830,17 @@ impl Rotator for RotatorTimePoint {
     fn flush(&self) -> Result<()> {
         self.inner.lock().file.flush().map_err(Error::FlushBuffer)
     }
+
+    fn sync(&self) -> Result<()> {
+        let file = &mut self.inner.lock().file;
+        file.flush()
+            .and_then(|_| file.get_ref().sync_data())
+            .map_err(Error::FlushBuffer)
+    }
+
+    fn current_file_path(&self) -> PathBuf {
+        self.inner.lock().file_path.clone()
+    }
 }
 
 impl TimePoint {
@@ -685,15 +852,6 @@ impl TimePoint {
             Self::Period(duration) => *duration,
         }
     }
-
-    #[must_use]
-    fn delta_chrono(&self) -> chrono::Duration {
-        match self {
-            Self::Daily { .. } => chrono::Duration::days(1),
-            Self::Hourly { .. } => chrono::Duration::hours(1),
-            Self::Period(duration) => chrono::Duration::from_std(*duration).unwrap(),
-        }
-    }
 }
 
 impl<ArgBP, ArgRP> RotatingFileSinkBuilder<ArgBP, ArgRP> {
@@ -728,6 +886,13 @@ impl<ArgBP, ArgRP> RotatingFileSinkBuilder<ArgBP, ArgRP> {
             rotation_policy: self.rotation_policy,
             max_files: self.max_files,
             rotate_on_open: self.rotate_on_open,
+            clock: self.clock,
+            time_zone: self.time_zone,
+            disk_full_strategy: self.disk_full_strategy,
+            cleanup_interval: self.cleanup_interval,
+            max_age: self.max_age,
+            #[cfg(feature = "gzip")]
+            compress: self.compress,
         }
     }
 
@@ -745,6 +910,13 @@ impl<ArgBP, ArgRP> RotatingFileSinkBuilder<ArgBP, ArgRP> {
             rotation_policy,
             max_files: self.max_files,
             rotate_on_open: self.rotate_on_open,
+            clock: self.clock,
+            time_zone: self.time_zone,
+            disk_full_strategy: self.disk_full_strategy,
+            cleanup_interval: self.cleanup_interval,
+            max_age: self.max_age,
+            #[cfg(feature = "gzip")]
+            compress: self.compress,
         }
     }
 
@@ -777,6 +949,167 @@ impl<ArgBP, ArgRP> RotatingFileSinkBuilder<ArgBP, ArgRP> {
         self
     }
 
+    /// Specifies the clock providing the time when constructing
+    /// `RotatingFileSink`.
+    ///
+    /// The time determines the initial file and the first rotation time point
+    /// for the [`RotationPolicy::Daily`], [`RotationPolicy::Hourly`], and
+    /// [`RotationPolicy::Period`] rotation policies. Subsequent rotations are
+    /// determined by the time of records, which can be driven by the same
+    /// clock via [`LoggerBuilder::clock`].
+    ///
+    /// This parameter is **optional**.
+    ///
+    /// [`LoggerBuilder::clock`]: crate::LoggerBuilder::clock
+    #[must_use]
+    pub fn clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = Some(clock);
+        self
+    }
+
+    /// Specifies the time zone in which the rotation time points are
+    /// determined and the file names are rendered, for the
+    /// [`RotationPolicy::Daily`], [`RotationPolicy::Hourly`], and
+    /// [`RotationPolicy::Period`] rotation policies.
+    ///
+    /// For example, with [`RotationPolicy::Daily`] at `00:00` and the time zone
+    /// `America/New_York`, files are rotated at midnight in New York regardless
+    /// of the time zone of the host. Daylight saving time transitions of the
+    /// time zone are taken into account.
+    ///
+    /// This parameter is **optional**.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use spdlog::{
+    ///     formatter::TimeZone,
+    ///     sink::{RotatingFileSink, RotationPolicy},
+    /// };
+    ///
+    /// # fn main() -> Result<(), spdlog::Error> {
+    /// # #[cfg(feature = "chrono-tz")]
+    /// let sink = RotatingFileSink::builder()
+    ///     .base_path("logs/app.log")
+    ///     .rotation_policy(RotationPolicy::Daily { hour: 0, minute: 0 })
+    ///     .time_zone(TimeZone::named("America/New_York")?)
+    ///     .build()?;
+    /// # Ok(()) }
+    /// ```
+    #[must_use]
+    pub fn time_zone(mut self, time_zone: TimeZone) -> Self {
+        self.time_zone = time_zone;
+        self
+    }
+
+    /// Specifies how the sink behaves when the disk is full.
+    ///
+    /// See [`DiskFullStrategy`] for the details.
+    ///
+    /// This parameter is **optional**.
+    #[must_use]
+    pub fn disk_full_strategy(mut self, strategy: DiskFullStrategy) -> Self {
+        self.disk_full_strategy = strategy;
+        self
+    }
+
+    /// Enables a janitor cleaning up the directory of the log files when
+    /// building the sink and then at the given interval.
+    ///
+    /// Rotation only deletes old files that the current process created, files
+    /// left by previous runs of the program are never deleted if the program
+    /// restarts before rotating enough times, or the file names changed in
+    /// between. The janitor applies [`max_files`] and [`max_age`] to all files
+    /// in the directory matching the file names of the sink, keeping the latest
+    /// ones, and removes empty directories named after dates (e.g.
+    /// `2024-03-01`).
+    ///
+    /// Errors occurred in cleaning up are reported to the error handler
+    /// specified in this builder.
+    ///
+    /// This parameter is **optional**.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the interval is zero.
+    ///
+    /// [`max_files`]: RotatingFileSinkBuilder::max_files
+    /// [`max_age`]: RotatingFileSinkBuilder::max_age
+    #[must_use]
+    pub fn cleanup_interval(mut self, interval: Duration) -> Self {
+        assert!(!interval.is_zero(), "cleanup interval must not be zero");
+        self.cleanup_interval = Some(interval);
+        self
+    }
+
+    /// Specifies the maximum age of rotated files, older files are deleted by
+    /// the janitor.
+    ///
+    /// For the [`RotationPolicy::Daily`], [`RotationPolicy::Hourly`], and
+    /// [`RotationPolicy::Period`] rotation policies, the age of a file is
+    /// determined by the time point in its name, and the current file is never
+    /// deleted. For the [`RotationPolicy::FileSize`] rotation policy, it is
+    /// determined by the modification time of the file.
+    ///
+    /// This enables the janitor (see [`cleanup_interval`]), which runs hourly
+    /// if no interval is specified. It works together with [`max_files`], a
+    /// file is deleted if either limit is exceeded.
+    ///
+    /// This parameter is **optional**.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// use spdlog::sink::{RotatingFileSink, RotationPolicy};
+    ///
+    /// # fn main() -> Result<(), spdlog::Error> {
+    /// # let dir = std::env::temp_dir();
+    /// // Keeps log files of the last 7 days.
+    /// let sink = RotatingFileSink::builder()
+    ///     .base_path(dir.join("app.log"))
+    ///     .rotation_policy(RotationPolicy::Daily { hour: 0, minute: 0 })
+    ///     .max_age(Duration::from_secs(7 * 24 * 60 * 60))
+    ///     .build()?;
+    /// # Ok(()) }
+    /// ```
+    ///
+    /// [`cleanup_interval`]: RotatingFileSinkBuilder::cleanup_interval
+    /// [`max_files`]: RotatingFileSinkBuilder::max_files
+    #[must_use]
+    pub fn max_age(mut self, max_age: Duration) -> Self {
+        self.max_age = Some(max_age);
+        self
+    }
+
+    /// Specifies whether to compress rotated files into gzip archives.
+    ///
+    /// When a file is rotated, it is compressed on a background thread into an
+    /// archive with the `.gz` extension appended (e.g. `base_file_1.log.gz`),
+    /// and then removed. The archives count towards [`max_files`], so it also
+    /// specifies how many archives are kept, and they are cleaned up by the
+    /// janitor as well.
+    ///
+    /// Compressing never blocks logging. For the [`RotationPolicy::FileSize`]
+    /// rotation policy, the rotated file is moved aside under a temporary name
+    /// (e.g. `base_file.log.1234-0.rotating`), and the previous archives are
+    /// shifted on the background thread as well before it is compressed.
+    /// Dropping the sink waits for the queued compressions to complete.
+    ///
+    /// Errors occurred in compressing are reported to the error handler
+    /// specified in this builder.
+    ///
+    /// This parameter is **optional**, and requires crate feature `gzip`.
+    ///
+    /// [`max_files`]: RotatingFileSinkBuilder::max_files
+    #[cfg(feature = "gzip")]
+    #[must_use]
+    pub fn compress(mut self, compress: bool) -> Self {
+        self.compress = compress;
+        self
+    }
+
     helper::common_impl!(@SinkBuilder: common_builder_impl);
 }
 
@@ -807,7 +1140,8 @@ impl RotatingFileSinkBuilder<PathBuf, RotationPolicy> {
     /// the file, [`Error::CreateDirectory`] or [`Error::OpenFile`] will be
     /// returned.
     pub fn build(self) -> Result<RotatingFileSink> {
-        self.build_with_initial_time(None)
+        let now = self.clock.as_ref().map(|clock| clock.now());
+        self.build_with_initial_time(now)
     }
 
     fn build_with_initial_time(self, override_now: Option<SystemTime>) -> Result<RotatingFileSink> {
@@ -815,49 +1149,474 @@ impl RotatingFileSinkBuilder<PathBuf, RotationPolicy> {
             .validate()
             .map_err(|err| Error::InvalidArgument(InvalidArgumentError::RotationPolicy(err)))?;
 
+        let janitor = Janitor {
+            base_path: self.base_path.clone(),
+            rotation_policy: self.rotation_policy,
+            max_files: self.max_files,
+            max_age: self.max_age,
+            time_zone: self.time_zone,
+            clock: self.clock.clone(),
+            error_handler: self.common_builder_impl.error_handler,
+            #[cfg(feature = "gzip")]
+            compress: self.compress,
+        };
+
+        #[cfg(feature = "gzip")]
+        let compressor = self
+            .compress
+            .then(|| Compressor::new(self.common_builder_impl.error_handler));
+
         let rotator = match self.rotation_policy {
             RotationPolicy::FileSize(max_size) => RotatorKind::FileSize(RotatorFileSize::new(
                 self.base_path,
                 max_size,
                 self.max_files,
                 self.rotate_on_open,
+                #[cfg(feature = "gzip")]
+                compressor,
             )?),
             RotationPolicy::Daily { hour, minute } => {
                 RotatorKind::TimePoint(RotatorTimePoint::new(
                     override_now,
                     self.base_path,
                     TimePoint::Daily { hour, minute },
+                    self.time_zone,
                     self.max_files,
                     self.rotate_on_open,
+                    #[cfg(feature = "gzip")]
+                    compressor,
                 )?)
             }
             RotationPolicy::Hourly => RotatorKind::TimePoint(RotatorTimePoint::new(
                 override_now,
                 self.base_path,
                 TimePoint::Hourly,
+                self.time_zone,
                 self.max_files,
                 self.rotate_on_open,
+                #[cfg(feature = "gzip")]
+                compressor,
             )?),
             RotationPolicy::Period(duration) => RotatorKind::TimePoint(RotatorTimePoint::new(
                 override_now,
                 self.base_path,
                 TimePoint::Period(duration),
+                self.time_zone,
                 self.max_files,
                 self.rotate_on_open,
+                #[cfg(feature = "gzip")]
+                compressor,
             )?),
         };
 
+        let cleanup_interval = match (self.cleanup_interval, self.max_age) {
+            (None, Some(_)) => Some(HOUR_1),
+            (interval, _) => interval,
+        };
+        let janitor = cleanup_interval.map(|interval| {
+            janitor.clean_up();
+            PeriodicWorker::new(
+                "spdlog-janitor",
+                move || {
+                    janitor.clean_up();
+                    true
+                },
+                move |_| interval,
+                self.clock,
+            )
+        });
+
         let res = RotatingFileSink {
             common_impl: helper::CommonImpl::from_builder(self.common_builder_impl),
             rotator,
+            disk_full: DiskFullHandler::new(self.disk_full_strategy),
+            _janitor: janitor,
         };
 
         Ok(res)
     }
 }
 
+// Cleans up files matching the file names of a sink that exceed the maximum
+// number of files or the maximum age, including ones left by previous
+// processes, and empty directories named after dates.
+struct Janitor {
+    base_path: PathBuf,
+    rotation_policy: RotationPolicy,
+    max_files: usize,
+    max_age: Option<Duration>,
+    time_zone: TimeZone,
+    clock: Option<Arc<dyn Clock>>,
+    error_handler: Option<ErrorHandler>,
+    #[cfg(feature = "gzip")]
+    compress: bool,
+}
+
+impl Janitor {
+    fn clean_up(&self) {
+        if let Err(err) = self.clean_up_impl() {
+            match self.error_handler {
+                Some(handler) => handler(err),
+                None => crate::default_error_handler("RotatingFileSink", err),
+            }
+        }
+    }
+
+    fn clean_up_impl(&self) -> Result<()> {
+        let dir = match self.base_path.parent() {
+            Some(dir) if !dir.as_os_str().is_empty() => dir,
+            _ => Path::new("."),
+        };
+        let prefix = format!(
+            "{}_",
+            self.base_path
+                .file_stem()
+                .unwrap_or_default()
+                .to_string_lossy()
+        );
+        let suffix = self
+            .base_path
+            .extension()
+            .map(|ext| format!(".{}", ext.to_string_lossy()))
+            .unwrap_or_default();
+
+        let mut result = Ok(());
+        // Pairs of the rendered index or time point and the path.
+        let mut files = vec![];
+
+        for entry in fs::read_dir(dir).map_err(Error::QueryFileMetadata)? {
+            let (entry, file_type) = match entry.and_then(|entry| {
+                let file_type = entry.file_type()?;
+                Ok((entry, file_type))
+            }) {
+                Ok(entry) => entry,
+                Err(err) => {
+                    result = Error::push_err(result, Error::QueryFileMetadata(err));
+                    continue;
+                }
+            };
+            let file_name = entry.file_name();
+            let file_name = match file_name.to_str() {
+                Some(file_name) => file_name,
+                None => continue,
+            };
+
+            if file_type.is_dir() {
+                if matches_shape(file_name, "dddd-dd-dd") {
+                    if let Err(err) = remove_empty_dir(&entry.path()) {
+                        result = Error::push_err(result, err);
+                    }
+                }
+            } else if let Some(stamp) = file_name
+                .strip_prefix(&prefix)
+                .map(|rest| self.strip_archive_extension(rest))
+                .and_then(|rest| rest.strip_suffix(&suffix))
+            {
+                if self.matches_file_stamp(stamp) {
+                    files.push((stamp.to_owned(), entry.path()));
+                }
+            }
+        }
+
+        let now = self
+            .clock
+            .as_ref()
+            .map_or_else(SystemTime::now, |clock| clock.now());
+        let expired_before = self.max_age.and_then(|max_age| now.checked_sub(max_age));
+        let max_files = match self.max_files {
+            0 => usize::MAX,
+            max_files => max_files,
+        };
+
+        let stale: Vec<_> = match self.rotation_policy {
+            // Index 0 is the base path, files with indexes in `[1, max_files)` are kept.
+            RotationPolicy::FileSize(_) => files
+                .into_iter()
+                .filter(|(index, path)| {
+                    index.parse::<usize>().map_or(true, |i| i >= max_files)
+                        || expired_before.map_or(false, |expired_before| {
+                            fs::metadata(path)
+                                .and_then(|metadata| metadata.modified())
+                                .map_or(false, |modified| modified < expired_before)
+                        })
+                })
+                .collect(),
+            // Time points are rendered in a sortable form, the latest ones are kept.
+            _ => {
+                // Files whose time point is earlier than the one of the expiry time only
+                // contain records before it.
+                let expired_stamp = expired_before.map(|expired_before| self.stamp(expired_before));
+                files.sort_by(|(a, _), (b, _)| b.cmp(a));
+                files
+                    .into_iter()
+                    .enumerate()
+                    .filter(|(i, (stamp, _))| {
+                        *i >= max_files
+                            || (*i > 0
+                                && expired_stamp
+                                    .as_ref()
+                                    .map_or(false, |expired_stamp| stamp < expired_stamp))
+                    })
+                    .map(|(_, file)| file)
+                    .collect()
+            }
+        };
+
+        for (_, path) in stale {
+            match fs::remove_file(&path) {
+                Ok(()) => report_removed(&path),
+                Err(err) => result = Error::push_err(result, Error::RemoveFile(err)),
+            }
+        }
+        result
+    }
+
+    #[must_use]
+    fn strip_archive_extension<'a>(&self, file_name: &'a str) -> &'a str {
+        #[cfg(feature = "gzip")]
+        if self.compress {
+            return file_name
+                .strip_suffix(ARCHIVE_EXTENSION)
+                .unwrap_or(file_name);
+        }
+        file_name
+    }
+
+    // Renders the time point of the file containing records at the given time.
+    #[must_use]
+    fn stamp(&self, time: SystemTime) -> String {
+        let time_point = match self.rotation_policy {
+            RotationPolicy::FileSize(_) => unreachable!(),
+            RotationPolicy::Daily { hour, minute } => TimePoint::Daily { hour, minute },
+            RotationPolicy::Hourly => TimePoint::Hourly,
+            RotationPolicy::Period(duration) => TimePoint::Period(duration),
+        };
+        let path =
+            RotatorTimePoint::calc_file_path(&self.base_path, time_point, self.time_zone, time);
+        let file_stem = path.file_stem().unwrap_or_default().to_string_lossy();
+        let base_stem = self
+            .base_path
+            .file_stem()
+            .unwrap_or_default()
+            .to_string_lossy();
+        file_stem[base_stem.len() + 1..].to_owned()
+    }
+
+    #[must_use]
+    fn matches_file_stamp(&self, stamp: &str) -> bool {
+        match self.rotation_policy {
+            RotationPolicy::FileSize(_) => {
+                !stamp.is_empty()
+                    && !stamp.starts_with('0')
+                    && stamp.bytes().all(|b| b.is_ascii_digit())
+            }
+            RotationPolicy::Daily { .. } => matches_shape(stamp, "dddd-dd-dd"),
+            RotationPolicy::Hourly => matches_shape(stamp, "dddd-dd-dd_dd"),
+            RotationPolicy::Period(_) => matches_shape(stamp, "dddd-dd-dd_dd-dd"),
+        }
+    }
+}
+
+// Checks if the string matches the shape, in which `d` matches an ASCII digit
+// and other characters match themselves.
+#[must_use]
+fn matches_shape(s: &str, shape: &str) -> bool {
+    s.len() == shape.len()
+        && s.bytes()
+            .zip(shape.bytes())
+            .all(|(b, expected)| match expected {
+                b'd' => b.is_ascii_digit(),
+                _ => b == expected,
+            })
+}
+
+fn remove_empty_dir(path: &Path) -> Result<()> {
+    let is_empty = fs::read_dir(path)
+        .map_err(Error::QueryFileMetadata)?
+        .next()
+        .is_none();
+    if is_empty {
+        fs::remove_dir(path).map_err(Error::RemoveFile)?;
+        report_removed(path);
+    }
+    Ok(())
+}
+
+#[cfg(feature = "gzip")]
+const ARCHIVE_EXTENSION: &str = ".gz";
+
+#[cfg(feature = "gzip")]
+enum CompressJob {
+    // Compresses the file into an archive next to it.
+    File(PathBuf),
+    // Shifts the files rotated by size, and compresses the staged file into the
+    // first one.
+    Rotated {
+        staged: PathBuf,
+        base_path: PathBuf,
+        max_files: usize,
+    },
+}
+
+// Compresses rotated files into gzip archives on a persistent background
+// thread, one job at a time in the order they are queued.
+#[cfg(feature = "gzip")]
+struct Compressor {
+    // `mpsc::Sender` is not `Sync` on the minimum supported Rust version.
+    sender: Mutex<Option<mpsc::Sender<CompressJob>>>,
+    worker: Option<JoinHandle<()>>,
+    staged_count: AtomicUsize,
+}
+
+#[cfg(feature = "gzip")]
+impl Compressor {
+    #[must_use]
+    fn new(error_handler: Option<ErrorHandler>) -> Self {
+        let (sender, receiver) = mpsc::channel();
+        let worker = thread_options::spawn_worker("spdlog-compressor", move || {
+            for job in receiver {
+                let res = match job {
+                    CompressJob::File(path) => compress_file(&path, &archive_path(&path)),
+                    CompressJob::Rotated {
+                        staged,
+                        base_path,
+                        max_files,
+                    } => compress_rotated(&staged, &base_path, max_files),
+                };
+                if let Err(err) = res {
+                    match error_handler {
+                        Some(handler) => handler(err),
+                        None => crate::default_error_handler("RotatingFileSink", err),
+                    }
+                }
+            }
+        });
+
+        Self {
+            sender: Mutex::new(Some(sender)),
+            worker: Some(worker),
+            staged_count: AtomicUsize::new(0),
+        }
+    }
+
+    // Queues the job without waiting for it.
+    fn push(&self, job: CompressJob) {
+        if let Some(sender) = &*self.sender.lock_expect() {
+            _ = sender.send(job);
+        }
+    }
+
+    // Gets a unique path to move a rotated file to until it is compressed. It
+    // does not match the file names recognized by the janitor.
+    #[must_use]
+    fn staging_path(&self, base_path: &Path) -> PathBuf {
+        let mut path = base_path.as_os_str().to_owned();
+        path.push(format!(
+            ".{}-{}.rotating",
+            std::process::id(),
+            self.staged_count.fetch_add(1, Ordering::Relaxed)
+        ));
+        PathBuf::from(path)
+    }
+}
+
+#[cfg(feature = "gzip")]
+impl Drop for Compressor {
+    fn drop(&mut self) {
+        // Disconnecting the channel stops the worker after the queued jobs.
+        self.sender.lock_expect().take();
+        if let Some(worker) = self.worker.take() {
+            _ = worker.join();
+        }
+    }
+}
+
+#[cfg(feature = "gzip")]
+#[must_use]
+fn archive_path(path: &Path) -> PathBuf {
+    let mut archive_path = path.as_os_str().to_owned();
+    archive_path.push(ARCHIVE_EXTENSION);
+    PathBuf::from(archive_path)
+}
+
+// Shifts the files rotated by size by one index, and compresses the staged file
+// into the first one.
+#[cfg(feature = "gzip")]
+fn compress_rotated(staged: &Path, base_path: &Path, max_files: usize) -> Result<()> {
+    for i in (2..max_files).rev() {
+        let src = RotatorFileSize::calc_file_path(base_path, i - 1);
+        let dst = RotatorFileSize::calc_file_path(base_path, i);
+        shift_file(archive_path(&src), archive_path(&dst))?;
+        shift_file(src, dst)?;
+    }
+
+    let first = RotatorFileSize::calc_file_path(base_path, 1);
+    compress_file(staged, &archive_path(&first)).map_err(|err| {
+        // Keep the file uncompressed rather than under the temporary name.
+        _ = fs::rename(staged, &first);
+        err
+    })
+}
+
+// Compresses the file into the archive, and removes the file.
+#[cfg(feature = "gzip")]
+fn compress_file(path: &Path, archive_path: &Path) -> Result<()> {
+    use flate2::{write::GzEncoder, Compression};
+
+    let mut file = File::open(path).map_err(Error::OpenFile)?;
+    let archive = File::create(archive_path).map_err(Error::OpenFile)?;
+
+    let mut encoder = GzEncoder::new(BufWriter::new(archive), Compression::default());
+    let res = io::copy(&mut file, &mut encoder)
+        .and_then(|_| encoder.finish())
+        .and_then(|mut writer| writer.flush());
+    if let Err(err) = res {
+        _ = fs::remove_file(archive_path);
+        return Err(Error::CompressFile(err));
+    }
+    drop(file);
+
+    fs::remove_file(path).map_err(Error::RemoveFile)?;
+    diagnostics::report(
+        Level::Info,
+        "RotatingFileSink",
+        format_args!("compressed file '{}'", archive_path.display()),
+    );
+    Ok(())
+}
+
+// Moves the file to the destination if it exists, replacing the destination.
+fn shift_file(src: PathBuf, dst: PathBuf) -> Result<()> {
+    if !src.exists() {
+        return Ok(());
+    }
+    if dst.exists() {
+        fs::remove_file(&dst).map_err(Error::RemoveFile)?;
+        report_removed(&dst);
+    }
+    fs::rename(src, dst).map_err(Error::RenameFile)
+}
+
+fn report_rotated(path: &Path) {
+    diagnostics::report(
+        Level::Info,
+        "RotatingFileSink",
+        format_args!("rotated to file '{}'", path.display()),
+    );
+}
+
+fn report_removed(path: &Path) {
+    diagnostics::report(
+        Level::Info,
+        "RotatingFileSink",
+        format_args!("removed old file '{}'", path.display()),
+    );
+}
+
 #[cfg(test)]
 mod tests {
+    use chrono::{Datelike, Local, TimeZone as _, Timelike};
+
     use super::*;
     use crate::{prelude::*, test_utils::*, Level, Record};
 
@@ -1087,10 +1846,85 @@ mod tests {
                 )
             );
         }
+
+        #[test]
+        fn cleanup() {
+            let dir = BASE_LOGS_PATH.join("policy_file_size_cleanup");
+            _ = fs::remove_dir_all(&dir);
+            fs::create_dir_all(&dir).unwrap();
+            for index in 1..=5 {
+                fs::write(dir.join(format!("app_{index}.log")), "").unwrap();
+            }
+
+            let _sink = RotatingFileSink::builder()
+                .base_path(dir.join("app.log"))
+                .rotation_policy(RotationPolicy::FileSize(1024))
+                .max_files(3)
+                .cleanup_interval(HOUR_1)
+                .build()
+                .unwrap();
+
+            let mut file_names = fs::read_dir(&dir)
+                .unwrap()
+                .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+                .collect::<Vec<_>>();
+            file_names.sort();
+            assert_eq!(file_names, ["app.log", "app_1.log", "app_2.log"]);
+        }
+
+        #[cfg(feature = "gzip")]
+        #[test]
+        fn compress() {
+            use std::io::Read;
+
+            use flate2::read::GzDecoder;
+
+            let dir = LOGS_PATH.join("compress");
+            if dir.exists() {
+                fs::remove_dir_all(&dir).unwrap();
+            }
+            fs::create_dir_all(&dir).unwrap();
+            let base_path = dir.join("app.log");
+
+            {
+                let sink = RotatingFileSink::builder()
+                    .base_path(&base_path)
+                    .rotation_policy(RotationPolicy::FileSize(16))
+                    .max_files(3)
+                    .compress(true)
+                    .build()
+                    .unwrap();
+                sink.set_formatter(Box::new(NoModFormatter::new()));
+                let logger = build_test_logger(|b| b.sink(Arc::new(sink)));
+                for payload in ["first message", "second message", "third message", "fourth"] {
+                    info!(logger: logger, "{}", payload);
+                }
+                // Dropping the sink waits for the pending compression.
+            }
+
+            let mut file_names = fs::read_dir(&dir)
+                .unwrap()
+                .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+                .collect::<Vec<_>>();
+            file_names.sort();
+            assert_eq!(file_names, ["app.log", "app_1.log.gz", "app_2.log.gz"]);
+
+            let read_archive = |file_name| {
+                let mut contents = String::new();
+                GzDecoder::new(File::open(dir.join(file_name)).unwrap())
+                    .read_to_string(&mut contents)
+                    .unwrap();
+                contents
+            };
+            assert_eq!(read_archive("app_1.log.gz"), "third message");
+            assert_eq!(read_archive("app_2.log.gz"), "second message");
+            assert_eq!(fs::read_to_string(&base_path).unwrap(), "fourth");
+        }
     }
 
     mod policy_time_point {
         use super::*;
+        use crate::clock::ManualClock;
 
         static LOGS_PATH: Lazy<PathBuf> = Lazy::new(|| {
             let path = BASE_LOGS_PATH.join("policy_time_point");
@@ -1124,6 +1958,7 @@ mod tests {
                 RotatorTimePoint::calc_file_path(
                     base_path,
                     TimePoint::Daily { hour: 8, minute: 9 },
+                    TimeZone::LOCAL,
                     system_time,
                 )
                 .to_str()
@@ -1132,16 +1967,22 @@ mod tests {
             };
 
             let calc_hourly = |base_path| {
-                RotatorTimePoint::calc_file_path(base_path, TimePoint::Hourly, system_time)
-                    .to_str()
-                    .unwrap()
-                    .to_string()
+                RotatorTimePoint::calc_file_path(
+                    base_path,
+                    TimePoint::Hourly,
+                    TimeZone::LOCAL,
+                    system_time,
+                )
+                .to_str()
+                .unwrap()
+                .to_string()
             };
 
             let calc_period = |base_path| {
                 RotatorTimePoint::calc_file_path(
                     base_path,
                     TimePoint::Period(10 * MINUTE_1),
+                    TimeZone::LOCAL,
                     system_time,
                 )
                 .to_str()
@@ -1258,6 +2099,51 @@ mod tests {
             }
         }
 
+        #[test]
+        fn specified_time_zone() {
+            let time_zone = TimeZone::fixed_offset(-5 * 3600).unwrap();
+            // 2024-03-10 04:59:00 UTC, 2024-03-09 23:59:00 -05:00
+            let now = SystemTime::UNIX_EPOCH + Duration::from_secs(1_710_046_740);
+
+            let daily = TimePoint::Daily { hour: 0, minute: 0 };
+            assert_eq!(
+                RotatorTimePoint::next_rotation_time_point(daily, time_zone, now),
+                now + MINUTE_1
+            );
+            assert_eq!(
+                RotatorTimePoint::calc_file_path("/tmp/test.log", daily, time_zone, now),
+                PathBuf::from("/tmp/test_2024-03-09.log")
+            );
+        }
+
+        #[cfg(feature = "chrono-tz")]
+        #[test]
+        fn daylight_saving_time() {
+            let time_zone = TimeZone::named("America/New_York").unwrap();
+            let daily = TimePoint::Daily { hour: 0, minute: 0 };
+            let next = |now| RotatorTimePoint::next_rotation_time_point(daily, time_zone, now);
+
+            // 2024-03-10 00:00:00 EST, the day is 23 hours long.
+            let midnight = SystemTime::UNIX_EPOCH + Duration::from_secs(1_710_046_800);
+            // 2024-03-11 00:00:00 EDT
+            assert_eq!(
+                next(midnight),
+                SystemTime::UNIX_EPOCH + Duration::from_secs(1_710_129_600)
+            );
+
+            // 2024-11-03 00:00:00 EDT, the day is 25 hours long.
+            let midnight = SystemTime::UNIX_EPOCH + Duration::from_secs(1_730_606_400);
+            // 2024-11-04 00:00:00 EST
+            assert_eq!(
+                next(midnight),
+                SystemTime::UNIX_EPOCH + Duration::from_secs(1_730_696_400)
+            );
+            assert_eq!(
+                next(midnight + 24 * HOUR_1),
+                SystemTime::UNIX_EPOCH + Duration::from_secs(1_730_696_400)
+            );
+        }
+
         // This test may only detect issues if the system time zone is not UTC.
         #[test]
         fn respect_local_tz() {
@@ -1348,6 +2234,126 @@ mod tests {
                 assert_files_count(prefix, 3);
             }
         }
+
+        #[test]
+        fn driven_by_clock() {
+            let prefix = "driven_by_clock";
+
+            let clock = Arc::new(ManualClock::new(
+                Local
+                    .with_ymd_and_hms(2024, 8, 29, 11, 45, 14)
+                    .unwrap()
+                    .to_utc()
+                    .into(),
+            ));
+
+            let logger = {
+                let hourly_sink = RotatingFileSink::builder()
+                    .base_path(LOGS_PATH.join(format!("{prefix}.log")))
+                    .rotation_policy(RotationPolicy::Hourly)
+                    .rotate_on_open(true)
+                    .clock(clock.clone())
+                    .build()
+                    .unwrap();
+
+                build_test_logger(|b| {
+                    b.sink(Arc::new(hourly_sink))
+                        .level_filter(LevelFilter::All)
+                        .clock(clock.clone())
+                })
+            };
+
+            assert_files_count(prefix, 1);
+
+            info!(logger: logger, "test log message");
+            assert_files_count(prefix, 1);
+
+            clock.advance(MINUTE_1 * 10);
+            info!(logger: logger, "test log message");
+            assert_files_count(prefix, 1);
+
+            clock.advance(HOUR_1);
+            info!(logger: logger, "test log message");
+            assert_files_count(prefix, 2);
+
+            clock.advance(HOUR_1);
+            info!(logger: logger, "test log message");
+            assert_files_count(prefix, 3);
+        }
+
+        #[test]
+        fn cleanup() {
+            let dir = LOGS_PATH.join("cleanup");
+            fs::create_dir_all(dir.join("2020-01-01")).unwrap();
+            fs::create_dir_all(dir.join("2020-01-02")).unwrap();
+            fs::write(dir.join("2020-01-02").join("kept.log"), "").unwrap();
+            for day in 1..=5 {
+                fs::write(dir.join(format!("app_2020-01-0{day}.log")), "").unwrap();
+            }
+            fs::write(dir.join("other_2020-01-01.log"), "").unwrap();
+
+            let _sink = RotatingFileSink::builder()
+                .base_path(dir.join("app.log"))
+                .rotation_policy(RotationPolicy::Daily { hour: 0, minute: 0 })
+                .max_files(2)
+                .cleanup_interval(HOUR_1)
+                .build()
+                .unwrap();
+
+            let mut file_names = fs::read_dir(&dir)
+                .unwrap()
+                .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+                .collect::<Vec<_>>();
+            file_names.sort();
+            let today = Local::now();
+            assert_eq!(
+                file_names,
+                [
+                    "2020-01-02".to_string(),
+                    "app_2020-01-05.log".to_string(),
+                    format!(
+                        "app_{}-{:02}-{:02}.log",
+                        today.year(),
+                        today.month(),
+                        today.day()
+                    ),
+                    "other_2020-01-01.log".to_string(),
+                ]
+            );
+        }
+
+        #[test]
+        fn max_age() {
+            let dir = LOGS_PATH.join("max_age");
+            _ = fs::remove_dir_all(&dir);
+            fs::create_dir_all(&dir).unwrap();
+            for day in 1..=4 {
+                fs::write(dir.join(format!("app_2020-01-0{day}.log")), "").unwrap();
+            }
+
+            let now = Local.with_ymd_and_hms(2020, 1, 5, 12, 0, 0).unwrap();
+            let _sink = RotatingFileSink::builder()
+                .base_path(dir.join("app.log"))
+                .rotation_policy(RotationPolicy::Daily { hour: 0, minute: 0 })
+                .max_age(2 * 24 * HOUR_1)
+                .clock(Arc::new(ManualClock::new(now.into())))
+                .build()
+                .unwrap();
+
+            let mut file_names = fs::read_dir(&dir)
+                .unwrap()
+                .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+                .collect::<Vec<_>>();
+            file_names.sort();
+            assert_eq!(
+                file_names,
+                [
+                    "app_2020-01-03.log",
+                    "app_2020-01-04.log",
+                    "app_2020-01-05.log"
+                ]
+            );
+        }
     }
 
     #[test]
@@ -0,0 +1,252 @@
+use std::sync::Arc;
+
+use crate::{
+    sink::{helper, Sink, Sinks},
+    Error, Record, Result,
+};
+
+/// A [combined sink], routes records to internal sinks by their destination
+/// hints.
+///
+/// A record tagged with a destination hint (see [`Record::destination`]) is
+/// forwarded only to the sinks of the route with the same name, bypassing the
+/// default sinks. Records without a hint, or with a hint that no route is
+/// named after, are forwarded to the default sinks.
+///
+/// This is useful for sending specific events, e.g. audit trails or security
+/// events, to dedicated destinations while keeping them out of ordinary logs.
+///
+/// # Examples
+///
+/// ```
+/// use std::sync::Arc;
+///
+/// use spdlog::{
+///     prelude::*,
+///     sink::{FileSink, RouterSink, StdStream, StdStreamSink},
+/// };
+///
+/// # fn main() -> Result<(), spdlog::Error> {
+/// # let dir = std::env::temp_dir();
+/// let sink = Arc::new(
+///     RouterSink::builder()
+///         .sink(Arc::new(StdStreamSink::builder().std_stream(StdStream::Stdout).build()?))
+///         .route("audit", Arc::new(FileSink::builder().path(dir.join("audit.log")).build()?))
+///         .build()?,
+/// );
+/// let logger = Logger::builder().sink(sink).build()?;
+///
+/// info!(logger: logger, "peer 3 joined"); // to stdout
+/// info!(logger: logger, sink = "audit", "user alice signed in"); // to `audit.log` only
+/// # Ok(()) }
+/// ```
+///
+/// [combined sink]: index.html#combined-sink
+pub struct RouterSink {
+    common_impl: helper::CommonImpl,
+    sinks: Sinks,
+    routes: Vec<(String, Arc<dyn Sink>)>,
+}
+
+impl RouterSink {
+    /// Gets a builder of `RouterSink` with default parameters:
+    ///
+    /// | Parameter       | Default Value           |
+    /// |-----------------|-------------------------|
+    /// | [level_filter]  | `All`                   |
+    /// | [formatter]     | `FullFormatter`         |
+    /// | [error_handler] | [default error handler] |
+    /// |                 |                         |
+    /// | [sinks]         | `[]`                    |
+    /// | [routes]        | `[]`                    |
+    ///
+    /// [level_filter]: RouterSinkBuilder::level_filter
+    /// [formatter]: RouterSinkBuilder::formatter
+    /// [error_handler]: RouterSinkBuilder::error_handler
+    /// [default error handler]: error/index.html#default-error-handler
+    /// [sinks]: RouterSinkBuilder::sink
+    /// [routes]: RouterSinkBuilder::route
+    #[must_use]
+    pub fn builder() -> RouterSinkBuilder {
+        RouterSinkBuilder {
+            common_builder_impl: helper::CommonBuilderImpl::new(),
+            sinks: vec![],
+            routes: vec![],
+        }
+    }
+
+    /// Gets a reference to the default sinks in the combined sink.
+    #[must_use]
+    pub fn sinks(&self) -> &[Arc<dyn Sink>] {
+        &self.sinks
+    }
+
+    /// Gets an iterator over the sinks of the route with the given name.
+    pub fn route_sinks<'a>(
+        &'a self,
+        name: &'a str,
+    ) -> impl Iterator<Item = &'a Arc<dyn Sink>> + 'a {
+        self.routes
+            .iter()
+            .filter(move |(route, _)| route == name)
+            .map(|(_, sink)| sink)
+    }
+
+    #[must_use]
+    fn has_route(&self, name: &str) -> bool {
+        self.routes.iter().any(|(route, _)| route == name)
+    }
+
+    fn all_sinks(&self) -> impl Iterator<Item = &Arc<dyn Sink>> {
+        self.sinks
+            .iter()
+            .chain(self.routes.iter().map(|(_, sink)| sink))
+    }
+}
+
+impl Sink for RouterSink {
+    fn log(&self, record: &Record) -> Result<()> {
+        #[allow(clippy::manual_try_fold)] // https://github.com/rust-lang/rust-clippy/issues/11554
+        match record.destination() {
+            Some(name) if self.has_route(name) => {
+                self.route_sinks(name).fold(Ok(()), |result, sink| {
+                    Error::push_result(result, sink.log(record))
+                })
+            }
+            _ => self.sinks.iter().fold(Ok(()), |result, sink| {
+                Error::push_result(result, sink.log(record))
+            }),
+        }
+    }
+
+    fn flush(&self) -> Result<()> {
+        #[allow(clippy::manual_try_fold)] // https://github.com/rust-lang/rust-clippy/issues/11554
+        self.all_sinks().fold(Ok(()), |result, sink| {
+            Error::push_result(result, sink.flush())
+        })
+    }
+
+    /// For [`RouterSink`], the function syncs all internal sinks.
+    fn sync(&self) -> Result<()> {
+        #[allow(clippy::manual_try_fold)] // https://github.com/rust-lang/rust-clippy/issues/11554
+        self.all_sinks().fold(Ok(()), |result, sink| {
+            Error::push_result(result, sink.sync())
+        })
+    }
+
+    /// For [`RouterSink`], the function polls all internal sinks.
+    #[cfg(feature = "log-async")]
+    fn poll_capacity(&self, cx: &mut std::task::Context<'_>) -> std::task::Poll<()> {
+        crate::sink::poll_capacity(self.all_sinks(), cx)
+    }
+
+    /// For [`RouterSink`], the function opens all internal sinks.
+    fn open(&self) -> Result<()> {
+        #[allow(clippy::manual_try_fold)] // https://github.com/rust-lang/rust-clippy/issues/11554
+        self.all_sinks().fold(Ok(()), |result, sink| {
+            Error::push_result(result, sink.open())
+        })
+    }
+
+    /// For [`RouterSink`], the function closes all internal sinks.
+    fn close(&self) -> Result<()> {
+        #[allow(clippy::manual_try_fold)] // https://github.com/rust-lang/rust-clippy/issues/11554
+        self.all_sinks().fold(Ok(()), |result, sink| {
+            Error::push_result(result, sink.close())
+        })
+    }
+
+    /// For [`RouterSink`], the function checks all internal sinks.
+    fn health(&self) -> Result<()> {
+        #[allow(clippy::manual_try_fold)] // https://github.com/rust-lang/rust-clippy/issues/11554
+        self.all_sinks().fold(Ok(()), |result, sink| {
+            Error::push_result(result, sink.health())
+        })
+    }
+
+    helper::common_impl!(@Sink: common_impl);
+}
+
+#[allow(missing_docs)]
+pub struct RouterSinkBuilder {
+    common_builder_impl: helper::CommonBuilderImpl,
+    sinks: Sinks,
+    routes: Vec<(String, Arc<dyn Sink>)>,
+}
+
+impl RouterSinkBuilder {
+    /// Add a default [`Sink`], receiving records that are not routed
+    /// elsewhere.
+    #[must_use]
+    pub fn sink(mut self, sink: Arc<dyn Sink>) -> Self {
+        self.sinks.push(sink);
+        self
+    }
+
+    /// Add multiple default [`Sink`]s.
+    #[must_use]
+    pub fn sinks<I>(mut self, sinks: I) -> Self
+    where
+        I: IntoIterator<Item = Arc<dyn Sink>>,
+    {
+        self.sinks.append(&mut sinks.into_iter().collect());
+        self
+    }
+
+    /// Add a [`Sink`] to the route with the given name, receiving records
+    /// whose destination hint is the name.
+    ///
+    /// This can be called multiple times with the same name to add multiple
+    /// sinks to a route.
+    #[must_use]
+    pub fn route<S>(mut self, name: S, sink: Arc<dyn Sink>) -> Self
+    where
+        S: Into<String>,
+    {
+        self.routes.push((name.into(), sink));
+        self
+    }
+
+    helper::common_impl!(@SinkBuilder: common_builder_impl);
+
+    /// Builds a [`RouterSink`].
+    pub fn build(self) -> Result<RouterSink> {
+        Ok(RouterSink {
+            common_impl: helper::CommonImpl::from_builder(self.common_builder_impl),
+            sinks: self.sinks,
+            routes: self.routes,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{prelude::*, test_utils::*};
+
+    #[test]
+    fn route() {
+        let default_sink = Arc::new(TestSink::new());
+        let audit_sink = Arc::new(TestSink::new());
+        let router_sink = Arc::new(
+            RouterSink::builder()
+                .sink(default_sink.clone())
+                .route("audit", audit_sink.clone())
+                .build()
+                .unwrap(),
+        );
+        let test = build_test_logger(|b| b.sink(router_sink));
+
+        info!(logger: test, "peer 3 joined");
+        info!(logger: test, sink = "audit", "user alice signed in");
+        warn!(logger: test, sink = "unknown", "disk almost full");
+        test.flush();
+
+        assert_eq!(
+            default_sink.payloads(),
+            ["peer 3 joined", "disk almost full"]
+        );
+        assert_eq!(audit_sink.payloads(), ["user alice signed in"]);
+        assert_eq!(audit_sink.flush_count(), 1);
+    }
+}
@@ -3,17 +3,57 @@
 use std::{
     convert::Infallible,
     io::{self, Write},
+    ops::Range,
 };
 
 use if_chain::if_chain;
 
 use crate::{
-    formatter::FormatterContext,
     sink::{helper, Sink},
-    terminal_style::{LevelStyles, Style, StyleMode},
-    Error, Level, Record, Result, StringBuf,
+    sync::*,
+    terminal_style::{LevelStyles, Style, StyleMode, Theme},
+    Error, Level, Record, Result,
 };
 
+static IS_TERMINAL_OVERRIDES: [Atomic<Option<bool>>; 2] = [Atomic::new(None), Atomic::new(None)];
+static ANSI_SUPPORT_OVERRIDE: Atomic<Option<bool>> = Atomic::new(None);
+
+/// Forces the result of detecting whether a standard stream is a terminal.
+///
+/// [`StdStreamSink`]s with [`StyleMode::Auto`] detect whether the target
+/// stream is a terminal to determine whether to output style escape codes.
+/// Tests, pagers and wrapper processes can force the detection result with
+/// this function to get deterministic styled or unstyled output. Pass `None`
+/// to restore the actual detection.
+///
+/// The override takes effect for sinks built or [`set_style_mode`] called
+/// afterwards.
+///
+/// See also [`override_ansi_support`].
+///
+/// [`set_style_mode`]: StdStreamSink::set_style_mode
+pub fn override_terminal_detection(stream: StdStream, is_terminal: Option<bool>) {
+    IS_TERMINAL_OVERRIDES[stream as usize].store(is_terminal, Ordering::Relaxed);
+}
+
+/// Forces the result of detecting whether the terminal supports ANSI escape
+/// sequences.
+///
+/// On Windows, [`StdStreamSink`]s with [`StyleMode::Auto`] try to enable ANSI
+/// escape sequences processing of the console, and only output style escape
+/// codes if it succeeds. On other platforms, ANSI escape sequences are always
+/// considered supported. Pass `None` to restore the actual detection.
+///
+/// The override takes effect for sinks built or [`set_style_mode`] called
+/// afterwards.
+///
+/// See also [`override_terminal_detection`].
+///
+/// [`set_style_mode`]: StdStreamSink::set_style_mode
+pub fn override_ansi_support(supported: Option<bool>) {
+    ANSI_SUPPORT_OVERRIDE.store(supported, Ordering::Relaxed);
+}
+
 /// An enum representing the available standard streams.
 #[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
 pub enum StdStream {
@@ -23,6 +63,26 @@ pub enum StdStream {
     Stderr,
 }
 
+/// Represents how [`StdStreamSink`] writes lines longer than the width of the
+/// terminal.
+///
+/// The width is detected for each record, so that resizing the terminal takes
+/// effect immediately. If the target stream is not a terminal, lines are
+/// always written as is. The width is counted in characters, wide characters
+/// such as CJK ideographs are counted as one column.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[non_exhaustive]
+pub enum LongLineMode {
+    /// Writes long lines as is, leaving them to the terminal.
+    Keep,
+    /// Wraps long lines at the width of the terminal, and indents the
+    /// continuation lines to the beginning of the payload.
+    Wrap,
+    /// Truncates long lines at the width of the terminal, and marks them with
+    /// an ellipsis `…`.
+    Truncate,
+}
+
 // `io::stdout()` and `io::stderr()` return different types, and
 // `Std***::lock()` is not in any trait, so we need this struct to abstract
 // them.
@@ -85,11 +145,16 @@ impl_write_for_dest!(StdStreamDest<io::StdoutLock<'_>, io::StderrLock<'_>>);
 /// the current terminal environment.
 ///
 /// Note that this sink always flushes the buffer once with each logging.
+///
+/// On Windows, if the target stream is a console whose output code page is not
+/// UTF-8, this sink writes text as UTF-16 via `WriteConsoleW`, so non-ASCII
+/// characters are rendered correctly.
 pub struct StdStreamSink {
     common_impl: helper::CommonImpl,
     dest: StdStreamDest<io::Stdout, io::Stderr>,
     should_render_style: bool,
     level_styles: LevelStyles,
+    long_line_mode: LongLineMode,
 }
 
 impl StdStreamSink {
@@ -103,6 +168,8 @@ impl StdStreamSink {
     /// |                   |                         |
     /// | [std_stream]      | *must be specified*     |
     /// | [style_mode]      | `Auto`                  |
+    /// | [theme]           | `Classic`               |
+    /// | [long_line_mode]  | `Keep`                  |
     ///
     /// [level_filter]: StdStreamSinkBuilder::level_filter
     /// [formatter]: StdStreamSinkBuilder::formatter
@@ -110,12 +177,16 @@ impl StdStreamSink {
     /// [default error handler]: error/index.html#default-error-handler
     /// [std_stream]: StdStreamSinkBuilder::std_stream
     /// [style_mode]: StdStreamSinkBuilder::style_mode
+    /// [theme]: StdStreamSinkBuilder::theme
+    /// [long_line_mode]: StdStreamSinkBuilder::long_line_mode
     #[must_use]
     pub fn builder() -> StdStreamSinkBuilder<()> {
         StdStreamSinkBuilder {
             common_builder_impl: helper::CommonBuilderImpl::new(),
             std_stream: (),
             style_mode: StyleMode::Auto,
+            theme: Theme::Classic,
+            long_line_mode: LongLineMode::Keep,
         }
     }
 
@@ -138,65 +209,139 @@ impl StdStreamSink {
         self.level_styles.set_style(level, style);
     }
 
+    /// Sets the styles of all log levels to the ones of the specified theme.
+    pub fn set_theme(&mut self, theme: Theme) {
+        self.level_styles = theme.into();
+    }
+
     /// Sets the style mode.
     pub fn set_style_mode(&mut self, style_mode: StyleMode) {
         self.should_render_style = Self::should_render_style(style_mode, self.dest.stream_type());
     }
 
+    /// Sets how lines longer than the width of the terminal are written.
+    pub fn set_long_line_mode(&mut self, long_line_mode: LongLineMode) {
+        self.long_line_mode = long_line_mode;
+    }
+
     #[must_use]
     fn should_render_style(style_mode: StyleMode, stream: StdStream) -> bool {
         use is_terminal::IsTerminal;
-        let is_terminal = match stream {
-            StdStream::Stdout => io::stdout().is_terminal(),
-            StdStream::Stderr => io::stderr().is_terminal(),
+        let is_terminal = || {
+            IS_TERMINAL_OVERRIDES[stream as usize]
+                .load(Ordering::Relaxed)
+                .unwrap_or_else(|| match stream {
+                    StdStream::Stdout => io::stdout().is_terminal(),
+                    StdStream::Stderr => io::stderr().is_terminal(),
+                })
+        };
+        let ansi_support = || {
+            ANSI_SUPPORT_OVERRIDE
+                .load(Ordering::Relaxed)
+                .unwrap_or_else(enable_ansi_escape_sequences)
         };
 
         match style_mode {
             StyleMode::Always => true,
-            StyleMode::Auto => is_terminal && enable_ansi_escape_sequences(),
+            StyleMode::Auto => is_terminal() && ansi_support(),
             StyleMode::Never => false,
         }
     }
+
+    // Returns `None` if the text does not need to be changed.
+    #[must_use]
+    fn fit_to_terminal(
+        &self,
+        record: &Record,
+        text: &str,
+        style_range: Option<Range<usize>>,
+    ) -> Option<(String, Option<Range<usize>>)> {
+        if self.long_line_mode == LongLineMode::Keep {
+            return None;
+        }
+        let width = terminal_width(self.dest.stream_type())?;
+
+        // Hang the continuation lines under the payload, if the payload is at the
+        // end of the first line and leaves enough room for it.
+        let content = text.trim_end_matches(crate::__EOL);
+        let indent = content
+            .strip_suffix(record.payload())
+            .filter(|prefix| !prefix.contains('\n'))
+            .map(|prefix| prefix.chars().count())
+            .filter(|indent| *indent <= width / 2)
+            .unwrap_or_else(|| 4.min(width / 2));
+
+        Some(fit_to_width(
+            text,
+            style_range,
+            width,
+            indent,
+            self.long_line_mode,
+        ))
+    }
+
+    fn write_styled(
+        &self,
+        record: &Record,
+        text: &str,
+        style_range: Option<Range<usize>>,
+        dest: &mut impl Write,
+    ) -> io::Result<()> {
+        if_chain! {
+            if self.should_render_style;
+            if let Some(style_range) = style_range;
+            then {
+                let style = self.level_styles.style(record.level());
+
+                dest.write_all(&text.as_bytes()[..style_range.start])?;
+                style.write_start(dest)?;
+                dest.write_all(&text.as_bytes()[style_range.start..style_range.end])?;
+                style.write_end(dest)?;
+                dest.write_all(&text.as_bytes()[style_range.end..])?;
+            } else {
+                dest.write_all(text.as_bytes())?;
+            }
+        }
+        Ok(())
+    }
 }
 
 impl Sink for StdStreamSink {
     fn log(&self, record: &Record) -> Result<()> {
-        let mut string_buf = StringBuf::new();
-        let mut ctx = FormatterContext::new();
-        self.common_impl
-            .formatter
-            .read()
-            .format(record, &mut string_buf, &mut ctx)?;
-
-        let mut dest = self.dest.lock();
-
-        (|| {
-            if_chain! {
-                if self.should_render_style;
-                if let Some(style_range) = ctx.style_range();
-                then {
-                    let style = self.level_styles.style(record.level());
-
-                    dest.write_all(string_buf[..style_range.start].as_bytes())?;
-                    style.write_start(&mut dest)?;
-                    dest.write_all(string_buf[style_range.start..style_range.end].as_bytes())?;
-                    style.write_end(&mut dest)?;
-                    dest.write_all(string_buf[style_range.end..].as_bytes())?;
-                } else {
-                    dest.write_all(string_buf.as_bytes())?;
-                }
+        self.common_impl.format_with(record, |string_buf, ctx| {
+            let fitted = self.fit_to_terminal(record, string_buf, ctx.style_range());
+            let (text, style_range) = match &fitted {
+                Some((text, style_range)) => (text.as_str(), style_range.clone()),
+                None => (&**string_buf, ctx.style_range()),
+            };
+
+            let mut dest = self.dest.lock();
+
+            // Writing UTF-8 bytes to a console whose code page is not UTF-8 renders
+            // non-ASCII characters as mojibake, so we write UTF-16 via the console
+            // API instead.
+            #[cfg(windows)]
+            if let Some(console) = wide_console::handle(self.dest.stream_type()) {
+                let mut buf = Vec::with_capacity(string_buf.len() + 16);
+                self.write_styled(record, text, style_range, &mut buf)
+                    .map_err(Error::WriteRecord)?;
+                // Keep the order with text written via `std::io`.
+                dest.flush().map_err(Error::FlushBuffer)?;
+                return wide_console::write(console, &String::from_utf8_lossy(&buf))
+                    .map_err(Error::WriteRecord);
             }
-            Ok(())
-        })()
-        .map_err(Error::WriteRecord)?;
 
-        // stderr is not buffered, so we don't need to flush it.
-        // https://doc.rust-lang.org/std/io/fn.stderr.html
-        if let StdStreamDest::Stdout(_) = dest {
-            dest.flush().map_err(Error::FlushBuffer)?;
-        }
+            self.write_styled(record, text, style_range, &mut dest)
+                .map_err(Error::WriteRecord)?;
 
-        Ok(())
+            // stderr is not buffered, so we don't need to flush it.
+            // https://doc.rust-lang.org/std/io/fn.stderr.html
+            if let StdStreamDest::Stdout(_) = dest {
+                dest.flush().map_err(Error::FlushBuffer)?;
+            }
+
+            Ok(())
+        })
     }
 
     fn flush(&self) -> Result<()> {
@@ -214,6 +359,8 @@ pub struct StdStreamSinkBuilder<ArgSS> {
     common_builder_impl: helper::CommonBuilderImpl,
     std_stream: ArgSS,
     style_mode: StyleMode,
+    theme: Theme,
+    long_line_mode: LongLineMode,
 }
 
 impl<ArgSS> StdStreamSinkBuilder<ArgSS> {
@@ -226,6 +373,8 @@ impl<ArgSS> StdStreamSinkBuilder<ArgSS> {
             common_builder_impl: self.common_builder_impl,
             std_stream,
             style_mode: self.style_mode,
+            theme: self.theme,
+            long_line_mode: self.long_line_mode,
         }
     }
 
@@ -238,6 +387,24 @@ impl<ArgSS> StdStreamSinkBuilder<ArgSS> {
         self
     }
 
+    /// Specifies the theme providing the styles of log levels.
+    ///
+    /// This parameter is **optional**.
+    #[must_use]
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+
+    /// Specifies how lines longer than the width of the terminal are written.
+    ///
+    /// This parameter is **optional**.
+    #[must_use]
+    pub fn long_line_mode(mut self, long_line_mode: LongLineMode) -> Self {
+        self.long_line_mode = long_line_mode;
+        self
+    }
+
     helper::common_impl!(@SinkBuilder: common_builder_impl);
 }
 
@@ -260,11 +427,120 @@ impl StdStreamSinkBuilder<StdStream> {
                 self.style_mode,
                 self.std_stream,
             ),
-            level_styles: LevelStyles::default(),
+            level_styles: self.theme.into(),
+            long_line_mode: self.long_line_mode,
         })
     }
 }
 
+// --------------------------------------------------
+
+// Fits each line of the text into the width, and returns the changed text with
+// the style range moved accordingly. The styled text is never split.
+#[must_use]
+fn fit_to_width(
+    text: &str,
+    style_range: Option<Range<usize>>,
+    width: usize,
+    indent: usize,
+    mode: LongLineMode,
+) -> (String, Option<Range<usize>>) {
+    let mut fitted = String::with_capacity(text.len() + text.len() / width.max(1) * (indent + 2));
+    let mut fitted_range = style_range.clone();
+    let mut line_begin = 0;
+
+    for line in text.split_inclusive('\n') {
+        let content = line.trim_end_matches(&['\r', '\n'][..]);
+        let is_long = content.chars().count() > width;
+        let mut column = 0;
+
+        for (index, ch) in line.char_indices() {
+            let pos = line_begin + index;
+            if let (Some(range), Some(fitted_range)) = (&style_range, &mut fitted_range) {
+                if pos == range.start {
+                    fitted_range.start = fitted.len();
+                }
+                if pos == range.end {
+                    fitted_range.end = fitted.len();
+                }
+            }
+
+            if is_long && index < content.len() {
+                match mode {
+                    LongLineMode::Keep => {}
+                    LongLineMode::Wrap => {
+                        let is_styled = style_range
+                            .as_ref()
+                            .map_or(false, |range| range.start < pos && pos < range.end);
+                        if column >= width && !is_styled {
+                            fitted.push_str(crate::__EOL);
+                            fitted.extend(std::iter::repeat(' ').take(indent));
+                            column = indent;
+                        }
+                    }
+                    LongLineMode::Truncate => {
+                        if column + 1 == width {
+                            fitted.push('…');
+                        }
+                        if column + 1 >= width {
+                            column += 1;
+                            continue;
+                        }
+                    }
+                }
+            }
+            fitted.push(ch);
+            column += 1;
+        }
+        line_begin += line.len();
+    }
+
+    if let (Some(range), Some(fitted_range)) = (&style_range, &mut fitted_range) {
+        if range.end == text.len() {
+            fitted_range.end = fitted.len();
+        }
+    }
+    (fitted, fitted_range)
+}
+
+#[must_use]
+fn terminal_width(stream: StdStream) -> Option<usize> {
+    cfg_if::cfg_if! {
+        if #[cfg(unix)] {
+            let fd = match stream {
+                StdStream::Stdout => libc::STDOUT_FILENO,
+                StdStream::Stderr => libc::STDERR_FILENO,
+            };
+            let mut size: libc::winsize = unsafe { std::mem::zeroed() };
+            if unsafe { libc::ioctl(fd, libc::TIOCGWINSZ, &mut size) } != 0 {
+                return None;
+            }
+            Some(size.ws_col as usize).filter(|width| *width > 0)
+        } else if #[cfg(windows)] {
+            use winapi::um::{
+                processenv::GetStdHandle,
+                winbase::{STD_ERROR_HANDLE, STD_OUTPUT_HANDLE},
+                wincon::{GetConsoleScreenBufferInfo, CONSOLE_SCREEN_BUFFER_INFO},
+            };
+
+            let handle = unsafe {
+                GetStdHandle(match stream {
+                    StdStream::Stdout => STD_OUTPUT_HANDLE,
+                    StdStream::Stderr => STD_ERROR_HANDLE,
+                })
+            };
+            let mut info: CONSOLE_SCREEN_BUFFER_INFO = unsafe { std::mem::zeroed() };
+            if unsafe { GetConsoleScreenBufferInfo(handle, &mut info) } == 0 {
+                return None;
+            }
+            Some((info.srWindow.Right - info.srWindow.Left + 1) as usize).filter(|width| *width > 0)
+        } else {
+            _ = stream;
+            None
+        }
+    }
+}
+
 // --------------------------------------------------
 #[cfg(windows)]
 #[must_use]
@@ -305,3 +581,152 @@ fn enable_ansi_escape_sequences() -> bool {
 fn enable_ansi_escape_sequences() -> bool {
     true
 }
+
+#[cfg(windows)]
+mod wide_console {
+    use std::{io, ptr};
+
+    use winapi::um::{
+        consoleapi::{GetConsoleMode, GetConsoleOutputCP, WriteConsoleW},
+        handleapi::INVALID_HANDLE_VALUE,
+        processenv::GetStdHandle,
+        winbase::{STD_ERROR_HANDLE, STD_OUTPUT_HANDLE},
+        winnt::HANDLE,
+    };
+
+    use super::StdStream;
+
+    const CP_UTF8: u32 = 65001;
+
+    // Returns the console handle of the stream, if the stream is a console and
+    // the output code page of the console is not UTF-8.
+    #[must_use]
+    pub(super) fn handle(stream: StdStream) -> Option<HANDLE> {
+        if unsafe { GetConsoleOutputCP() } == CP_UTF8 {
+            return None;
+        }
+
+        let std_handle = match stream {
+            StdStream::Stdout => STD_OUTPUT_HANDLE,
+            StdStream::Stderr => STD_ERROR_HANDLE,
+        };
+        let handle = unsafe { GetStdHandle(std_handle) };
+        if handle.is_null() || handle == INVALID_HANDLE_VALUE {
+            return None;
+        }
+
+        // Fails if the handle is redirected to a file or a pipe.
+        let mut mode = 0;
+        if unsafe { GetConsoleMode(handle, &mut mode) } == 0 {
+            return None;
+        }
+
+        Some(handle)
+    }
+
+    pub(super) fn write(handle: HANDLE, text: &str) -> io::Result<()> {
+        let wide = text.encode_utf16().collect::<Vec<u16>>();
+        let mut remaining = wide.as_slice();
+
+        while !remaining.is_empty() {
+            let len = remaining.len().min(u32::MAX as usize) as u32;
+            let mut written = 0;
+            let succeeded = unsafe {
+                WriteConsoleW(
+                    handle,
+                    remaining.as_ptr().cast(),
+                    len,
+                    &mut written,
+                    ptr::null_mut(),
+                )
+            };
+            if succeeded == 0 {
+                return Err(io::Error::last_os_error());
+            }
+            if written == 0 {
+                return Err(io::ErrorKind::WriteZero.into());
+            }
+            remaining = &remaining[written as usize..];
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detection_override() {
+        let should_render_style = |stream| {
+            StdStreamSink::builder()
+                .std_stream(stream)
+                .build()
+                .unwrap()
+                .should_render_style
+        };
+
+        override_terminal_detection(StdStream::Stderr, Some(true));
+        override_ansi_support(Some(true));
+        assert!(should_render_style(StdStream::Stderr));
+
+        override_ansi_support(Some(false));
+        assert!(!should_render_style(StdStream::Stderr));
+
+        override_ansi_support(None);
+        override_terminal_detection(StdStream::Stderr, Some(false));
+        assert!(!should_render_style(StdStream::Stderr));
+
+        override_terminal_detection(StdStream::Stderr, None);
+    }
+
+    #[test]
+    fn fit_to_width() {
+        let fit = |text, mode| super::fit_to_width(text, Some(1..5), 10, 7, mode);
+        let eol = crate::__EOL;
+
+        let short = format!("[info] ok{}", eol);
+        assert_eq!(fit(&short, LongLineMode::Wrap), (short.clone(), Some(1..5)));
+
+        let long = format!("[info] 0123456789é{}", eol);
+        assert_eq!(
+            fit(&long, LongLineMode::Wrap),
+            (
+                format!("[info] 012{eol}       345{eol}       678{eol}       9é{eol}"),
+                Some(1..5)
+            )
+        );
+        assert_eq!(
+            fit(&long, LongLineMode::Truncate),
+            (format!("[info] 01…{}", eol), Some(1..5))
+        );
+
+        // The styled text is not split.
+        assert_eq!(
+            super::fit_to_width("[critical] x", Some(1..9), 4, 2, LongLineMode::Wrap),
+            ("[critical\n  ] \n  x".replace('\n', eol), Some(1..9))
+        );
+        assert_eq!(
+            super::fit_to_width("[critical] x", Some(1..9), 4, 2, LongLineMode::Truncate),
+            ("[cr…".to_string(), Some(1..6))
+        );
+    }
+
+    #[test]
+    fn theme() {
+        let mut sink = StdStreamSink::builder()
+            .std_stream(StdStream::Stdout)
+            .theme(Theme::MonochromeDim)
+            .build()
+            .unwrap();
+        assert_eq!(sink.level_styles, LevelStyles::from(Theme::MonochromeDim));
+        assert_eq!(
+            sink.level_styles.style(Level::Debug),
+            &Style::builder().faint().build()
+        );
+
+        sink.set_theme(Theme::Classic);
+        assert_eq!(sink.level_styles, LevelStyles::default());
+    }
+}
@@ -0,0 +1,165 @@
+use crate::Level;
+
+/// Represents syslog severity levels (priorities), as defined in RFC 5424 and
+/// used by journald.
+///
+/// The numeric value of a variant is the standard numeric code of the severity
+/// level, which can be obtained via [`SyslogLevel::code`].
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum SyslogLevel {
+    /// `LOG_EMERG`, system is unusable.
+    Emerg = 0,
+    /// `LOG_ALERT`, action must be taken immediately.
+    Alert = 1,
+    /// `LOG_CRIT`, critical conditions.
+    Crit = 2,
+    /// `LOG_ERR`, error conditions.
+    Err = 3,
+    /// `LOG_WARNING`, warning conditions.
+    Warning = 4,
+    /// `LOG_NOTICE`, normal but significant condition.
+    Notice = 5,
+    /// `LOG_INFO`, informational messages.
+    Info = 6,
+    /// `LOG_DEBUG`, debug-level messages.
+    Debug = 7,
+}
+
+impl SyslogLevel {
+    /// Gets the standard numeric code of the severity level.
+    #[must_use]
+    pub const fn code(self) -> u8 {
+        self as u8
+    }
+}
+
+/// Represents a mapping from log levels to syslog severity levels.
+///
+/// The default mapping is:
+///
+/// | spdlog-rs  | syslog    |
+/// |------------|-----------|
+/// | `Critical` | `crit`    |
+/// | `Error`    | `err`     |
+/// | `Warn`     | `warning` |
+/// | `Info`     | `info`    |
+/// | `Debug`    | `debug`   |
+/// | `Trace`    | `debug`   |
+///
+/// # Examples
+///
+/// ```
+/// use spdlog::{
+///     sink::{SyslogLevel, SyslogLevels},
+///     Level,
+/// };
+///
+/// // Page on critical logs.
+/// let syslog_levels = SyslogLevels::new().with_level(Level::Critical, SyslogLevel::Alert);
+///
+/// assert_eq!(syslog_levels.level(Level::Critical), SyslogLevel::Alert);
+/// assert_eq!(syslog_levels.level(Level::Error), SyslogLevel::Err);
+/// ```
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub struct SyslogLevels([SyslogLevel; Level::count()]);
+
+impl SyslogLevels {
+    /// Constructs a `SyslogLevels` with the default mapping.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self([
+            SyslogLevel::Crit,    // Critical
+            SyslogLevel::Err,     // Error
+            SyslogLevel::Warning, // Warn
+            SyslogLevel::Info,    // Info
+            SyslogLevel::Debug,   // Debug
+            SyslogLevel::Debug,   // Trace
+        ])
+    }
+
+    /// Gets the syslog severity level a log level is mapped to.
+    #[must_use]
+    pub fn level(&self, level: Level) -> SyslogLevel {
+        self.0[level as usize]
+    }
+
+    /// Maps a log level to a syslog severity level.
+    pub fn set_level(&mut self, level: Level, syslog_level: SyslogLevel) {
+        self.0[level as usize] = syslog_level;
+    }
+
+    /// Maps a log level to a syslog severity level, and returns the mapping.
+    #[must_use]
+    pub fn with_level(mut self, level: Level, syslog_level: SyslogLevel) -> Self {
+        self.set_level(level, syslog_level);
+        self
+    }
+}
+
+impl Default for SyslogLevels {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Represents syslog facilities, as defined in RFC 5424.
+///
+/// The numeric value of a variant is the standard numeric code of the
+/// facility, which can be obtained via [`SyslogFacility::code`].
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum SyslogFacility {
+    /// `LOG_KERN`, kernel messages.
+    Kern = 0,
+    /// `LOG_USER`, user-level messages.
+    User = 1,
+    /// `LOG_MAIL`, mail system.
+    Mail = 2,
+    /// `LOG_DAEMON`, system daemons.
+    Daemon = 3,
+    /// `LOG_AUTH`, security/authorization messages.
+    Auth = 4,
+    /// `LOG_SYSLOG`, messages generated internally by syslogd.
+    Syslog = 5,
+    /// `LOG_LPR`, line printer subsystem.
+    Lpr = 6,
+    /// `LOG_NEWS`, network news subsystem.
+    News = 7,
+    /// `LOG_UUCP`, UUCP subsystem.
+    Uucp = 8,
+    /// `LOG_CRON`, clock daemon.
+    Cron = 9,
+    /// `LOG_AUTHPRIV`, security/authorization messages (private).
+    Authpriv = 10,
+    /// `LOG_FTP`, FTP daemon.
+    Ftp = 11,
+    /// `LOG_LOCAL0`, reserved for local use.
+    Local0 = 16,
+    /// `LOG_LOCAL1`, reserved for local use.
+    Local1 = 17,
+    /// `LOG_LOCAL2`, reserved for local use.
+    Local2 = 18,
+    /// `LOG_LOCAL3`, reserved for local use.
+    Local3 = 19,
+    /// `LOG_LOCAL4`, reserved for local use.
+    Local4 = 20,
+    /// `LOG_LOCAL5`, reserved for local use.
+    Local5 = 21,
+    /// `LOG_LOCAL6`, reserved for local use.
+    Local6 = 22,
+    /// `LOG_LOCAL7`, reserved for local use.
+    Local7 = 23,
+}
+
+impl SyslogFacility {
+    /// Gets the standard numeric code of the facility.
+    #[must_use]
+    pub const fn code(self) -> u8 {
+        self as u8
+    }
+}
+
+impl Default for SyslogFacility {
+    fn default() -> Self {
+        Self::User
+    }
+}
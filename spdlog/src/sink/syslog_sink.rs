@@ -0,0 +1,801 @@
+#[cfg(unix)]
+use std::os::unix::net::UnixDatagram;
+use std::{
+    fmt::{self, Write as _},
+    io::{self, Write as _},
+    net::{Shutdown, SocketAddr, TcpStream, ToSocketAddrs, UdpSocket},
+    path::PathBuf,
+    time::{Duration, UNIX_EPOCH},
+};
+
+use crate::{
+    formatter::{Formatter, FormatterContext, TimeZone},
+    sink::{helper, Sink, SyslogFacility, SyslogLevels},
+    sync::*,
+    Error, Record, Result, StringBuf,
+};
+
+/// Represents the message format of a [`SyslogSink`].
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum SyslogFormat {
+    /// The syslog protocol defined in [RFC 5424].
+    ///
+    /// [RFC 5424]: https://www.rfc-editor.org/rfc/rfc5424
+    Rfc5424,
+    /// The legacy BSD syslog format described in [RFC 3164], for collectors
+    /// that do not support RFC 5424.
+    ///
+    /// [RFC 3164]: https://www.rfc-editor.org/rfc/rfc3164
+    Rfc3164,
+}
+
+impl Default for SyslogFormat {
+    fn default() -> Self {
+        Self::Rfc5424
+    }
+}
+
+/// A sink with a syslog daemon or collector as the target.
+///
+/// Records are sent to a local syslog daemon via a Unix datagram socket
+/// (`/dev/log` by default), or to a remote collector via UDP or TCP, see
+/// [`SyslogSinkBuilder::unix_socket`], [`SyslogSinkBuilder::udp`] and
+/// [`SyslogSinkBuilder::tcp`].
+///
+/// # Log Level Mapping
+///
+/// | spdlog-rs  | syslog    |
+/// |------------|-----------|
+/// | `Critical` | `crit`    |
+/// | `Error`    | `err`     |
+/// | `Warn`     | `warning` |
+/// | `Info`     | `info`    |
+/// | `Debug`    | `debug`   |
+/// | `Trace`    | `debug`   |
+///
+/// The mapping can be customized with [`SyslogSinkBuilder::syslog_levels`].
+///
+/// # Message Format
+///
+/// By default, messages are in the [RFC 5424] format:
+///
+/// ```text
+/// <PRI>1 TIMESTAMP HOSTNAME APP-NAME PROCID MSGID - MSG
+/// ```
+///
+/// `MSGID` is the [error code] of the record if any, and `MSG` is the record
+/// formatted by the formatter of the sink, without the trailing line ending.
+/// The legacy [RFC 3164] format can be selected with
+/// [`SyslogSinkBuilder::format`].
+///
+/// Over TCP, messages are framed by octet counting for RFC 5424 and by line
+/// feeds for RFC 3164, as described in [RFC 6587]. If the connection is lost,
+/// the sink reconnects on the next record. Connecting and writing happen on the
+/// logging thread, they are bounded by [`SyslogSinkBuilder::connect_timeout`]
+/// and [`SyslogSinkBuilder::write_timeout`]. The connection can be established
+/// eagerly by [`Sink::open`], which loggers call when they are built.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::sync::Arc;
+///
+/// use spdlog::{
+///     prelude::*,
+///     sink::{SyslogFacility, SyslogSink},
+/// };
+///
+/// # fn main() -> Result<(), spdlog::Error> {
+/// let sink = Arc::new(
+///     SyslogSink::builder()
+///         .udp("logs.example.com:514")
+///         .facility(SyslogFacility::Local0)
+///         .app_name("my-app")
+///         .build()?,
+/// );
+/// let logger = Logger::builder().sink(sink).build()?;
+///
+/// info!(logger: logger, code = "E1042", "disk almost full");
+/// # Ok(()) }
+/// ```
+///
+/// [RFC 5424]: https://www.rfc-editor.org/rfc/rfc5424
+/// [RFC 3164]: https://www.rfc-editor.org/rfc/rfc3164
+/// [RFC 6587]: https://www.rfc-editor.org/rfc/rfc6587
+/// [error code]: crate::Record::code
+pub struct SyslogSink {
+    common_impl: helper::CommonImpl,
+    transport: Transport,
+    format: SyslogFormat,
+    facility: SyslogFacility,
+    syslog_levels: SyslogLevels,
+    time_zone: TimeZone,
+    hostname: String,
+    app_name: String,
+    proc_id: u32,
+    health: helper::IoHealth,
+}
+
+impl SyslogSink {
+    /// Gets a builder of `SyslogSink` with default parameters:
+    ///
+    /// | Parameter         | Default Value                                                   |
+    /// |-------------------|-----------------------------------------------------------------|
+    /// | [level_filter]    | `All`                                                           |
+    /// | [formatter]       | A formatter writing the logger name and payload                 |
+    /// | [error_handler]   | [default error handler]                                         |
+    /// |                   |                                                                 |
+    /// | [destination]     | `/dev/log` on Unix, `localhost:514` over UDP on other platforms |
+    /// | [format]          | `SyslogFormat::Rfc5424`                                         |
+    /// | [facility]        | `SyslogFacility::User`                                          |
+    /// | [syslog_levels]   | `SyslogLevels::new()`                                           |
+    /// | [time_zone]       | `TimeZone::LOCAL`                                               |
+    /// | [hostname]        | The host name of the system                                     |
+    /// | [app_name]        | The file name of the current executable                         |
+    /// | [connect_timeout] | 5 seconds                                                       |
+    /// | [write_timeout]   | 5 seconds                                                       |
+    ///
+    /// [level_filter]: SyslogSinkBuilder::level_filter
+    /// [formatter]: SyslogSinkBuilder::formatter
+    /// [error_handler]: SyslogSinkBuilder::error_handler
+    /// [default error handler]: error/index.html#default-error-handler
+    /// [destination]: SyslogSinkBuilder::unix_socket
+    /// [format]: SyslogSinkBuilder::format
+    /// [facility]: SyslogSinkBuilder::facility
+    /// [syslog_levels]: SyslogSinkBuilder::syslog_levels
+    /// [time_zone]: SyslogSinkBuilder::time_zone
+    /// [hostname]: SyslogSinkBuilder::hostname
+    /// [app_name]: SyslogSinkBuilder::app_name
+    /// [connect_timeout]: SyslogSinkBuilder::connect_timeout
+    /// [write_timeout]: SyslogSinkBuilder::write_timeout
+    #[must_use]
+    pub fn builder() -> SyslogSinkBuilder {
+        SyslogSinkBuilder {
+            common_builder_impl: helper::CommonBuilderImpl::new(),
+            destination: Destination::default(),
+            format: SyslogFormat::default(),
+            facility: SyslogFacility::default(),
+            syslog_levels: SyslogLevels::new(),
+            time_zone: TimeZone::LOCAL,
+            hostname: None,
+            app_name: None,
+            connect_timeout: Duration::from_secs(5),
+            write_timeout: Duration::from_secs(5),
+        }
+    }
+
+    fn format_message(
+        &self,
+        record: &Record,
+        msg: &str,
+    ) -> std::result::Result<String, fmt::Error> {
+        let pri = self.facility.code() as u32 * 8
+            + self.syslog_levels.level(record.level()).code() as u32;
+        let date_time = self.time_zone.date_time(record.time());
+        let mut message = String::with_capacity(msg.len() + 128);
+
+        match self.format {
+            SyslogFormat::Rfc5424 => {
+                let micros = record
+                    .time()
+                    .duration_since(UNIX_EPOCH)
+                    .map_or(0, |duration| duration.subsec_micros());
+                write!(
+                    message,
+                    "<{}>1 {:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{:06}",
+                    pri,
+                    date_time.year(),
+                    date_time.month(),
+                    date_time.day(),
+                    date_time.hour(),
+                    date_time.minute(),
+                    date_time.second(),
+                    micros,
+                )?;
+                match date_time.offset() {
+                    0 => message.push('Z'),
+                    offset => {
+                        let sign = if offset < 0 { '-' } else { '+' };
+                        let offset = offset.unsigned_abs();
+                        write!(
+                            message,
+                            "{}{:02}:{:02}",
+                            sign,
+                            offset / 3600,
+                            offset % 3600 / 60
+                        )?;
+                    }
+                }
+                let msg_id = record.code().map(|code| header_field(code, 32));
+                write!(
+                    message,
+                    " {} {} {} {} - {}",
+                    self.hostname,
+                    self.app_name,
+                    self.proc_id,
+                    msg_id.as_deref().unwrap_or("-"),
+                    msg,
+                )?;
+            }
+            SyslogFormat::Rfc3164 => {
+                const MONTHS: [&str; 12] = [
+                    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov",
+                    "Dec",
+                ];
+                write!(
+                    message,
+                    "<{}>{} {:>2} {:02}:{:02}:{:02} {} {}[{}]: {}",
+                    pri,
+                    MONTHS[date_time.month() as usize - 1],
+                    date_time.day(),
+                    date_time.hour(),
+                    date_time.minute(),
+                    date_time.second(),
+                    self.hostname,
+                    self.app_name,
+                    self.proc_id,
+                    msg,
+                )?;
+            }
+        }
+        Ok(message)
+    }
+}
+
+impl Sink for SyslogSink {
+    fn log(&self, record: &Record) -> Result<()> {
+        self.common_impl.format_with(record, |string_buf, _| {
+            let msg = string_buf.trim_end_matches(&['\r', '\n'][..]);
+            let message = self
+                .format_message(record, msg)
+                .map_err(Error::FormatRecord)?;
+            self.transport.send(&message, self.format, &self.health)
+        })
+    }
+
+    fn flush(&self) -> Result<()> {
+        self.transport.flush(&self.health)
+    }
+
+    /// For TCP, connects to the collector if not connected yet.
+    fn open(&self) -> Result<()> {
+        self.transport.open(&self.health)
+    }
+
+    /// For TCP, flushes and shuts down the connection. The sink connects again
+    /// if it is used after being closed.
+    fn close(&self) -> Result<()> {
+        self.transport.close(&self.health)
+    }
+
+    /// Returns the last error in sending messages. For TCP, an error is also
+    /// returned while not connected.
+    fn health(&self) -> Result<()> {
+        self.health.check()
+    }
+
+    helper::common_impl!(@Sink: common_impl);
+}
+
+#[allow(missing_docs)]
+pub struct SyslogSinkBuilder {
+    common_builder_impl: helper::CommonBuilderImpl,
+    destination: Destination,
+    format: SyslogFormat,
+    facility: SyslogFacility,
+    syslog_levels: SyslogLevels,
+    time_zone: TimeZone,
+    hostname: Option<String>,
+    app_name: Option<String>,
+    connect_timeout: Duration,
+    write_timeout: Duration,
+}
+
+impl SyslogSinkBuilder {
+    /// Sends records to a local syslog daemon via the Unix datagram socket at
+    /// the path, e.g. `/dev/log` (the default on Unix) or `/var/run/syslog`
+    /// on macOS.
+    ///
+    /// This function is only available on Unix.
+    ///
+    /// This parameter is **optional**, and overrides [`SyslogSinkBuilder::udp`]
+    /// and [`SyslogSinkBuilder::tcp`].
+    #[cfg(unix)]
+    #[must_use]
+    pub fn unix_socket<P>(mut self, path: P) -> Self
+    where
+        P: Into<PathBuf>,
+    {
+        self.destination = Destination::Unix(path.into());
+        self
+    }
+
+    /// Sends records to a collector via UDP, at an address in the form of
+    /// `host:port`.
+    ///
+    /// Each message is sent as a single datagram, messages exceeding the
+    /// datagram size limit of the network may be dropped.
+    ///
+    /// This parameter is **optional**, and overrides
+    /// [`SyslogSinkBuilder::unix_socket`] and [`SyslogSinkBuilder::tcp`].
+    #[must_use]
+    pub fn udp<S>(mut self, address: S) -> Self
+    where
+        S: Into<String>,
+    {
+        self.destination = Destination::Udp(address.into());
+        self
+    }
+
+    /// Sends records to a collector via TCP, at an address in the form of
+    /// `host:port`.
+    ///
+    /// The connection is established on the first record, and reestablished
+    /// on the next record after it is lost.
+    ///
+    /// This parameter is **optional**, and overrides
+    /// [`SyslogSinkBuilder::unix_socket`] and [`SyslogSinkBuilder::udp`].
+    #[must_use]
+    pub fn tcp<S>(mut self, address: S) -> Self
+    where
+        S: Into<String>,
+    {
+        self.destination = Destination::Tcp(address.into());
+        self
+    }
+
+    /// Specifies the message format.
+    ///
+    /// This parameter is **optional**.
+    #[must_use]
+    pub fn format(mut self, format: SyslogFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Specifies the syslog facility.
+    ///
+    /// This parameter is **optional**.
+    #[must_use]
+    pub fn facility(mut self, facility: SyslogFacility) -> Self {
+        self.facility = facility;
+        self
+    }
+
+    /// Specifies the mapping from log levels to syslog severity levels.
+    ///
+    /// This parameter is **optional**.
+    #[must_use]
+    pub fn syslog_levels(mut self, syslog_levels: SyslogLevels) -> Self {
+        self.syslog_levels = syslog_levels;
+        self
+    }
+
+    /// Specifies the time zone of timestamps in messages.
+    ///
+    /// This parameter is **optional**.
+    #[must_use]
+    pub fn time_zone(mut self, time_zone: TimeZone) -> Self {
+        self.time_zone = time_zone;
+        self
+    }
+
+    /// Specifies the host name (`HOSTNAME`) in messages.
+    ///
+    /// Characters other than printable ASCII are replaced with `_`.
+    ///
+    /// This parameter is **optional**.
+    #[must_use]
+    pub fn hostname<S>(mut self, hostname: S) -> Self
+    where
+        S: Into<String>,
+    {
+        self.hostname = Some(hostname.into());
+        self
+    }
+
+    /// Specifies the application name (`APP-NAME`, or `TAG` in RFC 3164) in
+    /// messages.
+    ///
+    /// Characters other than printable ASCII are replaced with `_`.
+    ///
+    /// This parameter is **optional**.
+    #[must_use]
+    pub fn app_name<S>(mut self, app_name: S) -> Self
+    where
+        S: Into<String>,
+    {
+        self.app_name = Some(app_name.into());
+        self
+    }
+
+    /// Specifies the timeout of connecting to each resolved address of the
+    /// collector over TCP.
+    ///
+    /// Since connecting happens on the logging thread, this is also how long
+    /// logging may be blocked for each resolved address of a collector that
+    /// is down.
+    ///
+    /// This parameter is **optional**, and only used with
+    /// [`SyslogSinkBuilder::tcp`].
+    #[must_use]
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = timeout;
+        self
+    }
+
+    /// Specifies the timeout of writing to the connection to the collector over
+    /// TCP. The connection is considered lost if a write times out.
+    ///
+    /// Since writing happens on the logging thread, this is also how long
+    /// logging may be blocked by a collector that stops reading. It must not be
+    /// zero, otherwise connecting fails.
+    ///
+    /// This parameter is **optional**, and only used with
+    /// [`SyslogSinkBuilder::tcp`].
+    #[must_use]
+    pub fn write_timeout(mut self, timeout: Duration) -> Self {
+        self.write_timeout = timeout;
+        self
+    }
+
+    helper::common_impl!(@SinkBuilder: common_builder_impl);
+
+    /// Builds a [`SyslogSink`].
+    ///
+    /// # Error
+    ///
+    /// If an error occurs in creating the socket or resolving the address of
+    /// the collector, [`Error::Connect`] will be returned.
+    pub fn build(self) -> Result<SyslogSink> {
+        let hostname = self.hostname.or_else(system_hostname);
+        let app_name = self.app_name.or_else(|| {
+            std::env::current_exe()
+                .ok()?
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+        });
+
+        let health = match self.destination {
+            Destination::Tcp(_) => helper::IoHealth::disconnected(),
+            _ => helper::IoHealth::new(),
+        };
+        let sink = SyslogSink {
+            common_impl: helper::CommonImpl::from_builder_with_formatter(
+                self.common_builder_impl,
+                || Box::new(SyslogFormatter),
+            ),
+            transport: Transport::new(self.destination, self.connect_timeout, self.write_timeout)?,
+            format: self.format,
+            facility: self.facility,
+            syslog_levels: self.syslog_levels,
+            time_zone: self.time_zone,
+            hostname: hostname.map_or_else(|| "-".to_string(), |name| header_field(&name, 255)),
+            app_name: app_name.map_or_else(|| "-".to_string(), |name| header_field(&name, 48)),
+            proc_id: std::process::id(),
+            health,
+        };
+        Ok(sink)
+    }
+}
+
+enum Destination {
+    #[cfg(unix)]
+    Unix(PathBuf),
+    Udp(String),
+    Tcp(String),
+}
+
+impl Default for Destination {
+    fn default() -> Self {
+        #[cfg(unix)]
+        return Self::Unix(PathBuf::from("/dev/log"));
+        #[cfg(not(unix))]
+        return Self::Udp("localhost:514".to_string());
+    }
+}
+
+enum Transport {
+    #[cfg(unix)]
+    Unix {
+        socket: UnixDatagram,
+        path: PathBuf,
+    },
+    Udp(UdpSocket),
+    Tcp {
+        addrs: Vec<SocketAddr>,
+        connect_timeout: Duration,
+        write_timeout: Duration,
+        stream: Mutex<Option<TcpStream>>,
+    },
+}
+
+impl Transport {
+    fn new(
+        destination: Destination,
+        connect_timeout: Duration,
+        write_timeout: Duration,
+    ) -> Result<Self> {
+        let transport = match destination {
+            #[cfg(unix)]
+            Destination::Unix(path) => Self::Unix {
+                socket: UnixDatagram::unbound().map_err(Error::Connect)?,
+                path,
+            },
+            Destination::Udp(address) => {
+                let addrs = resolve(&address)?;
+                let local: SocketAddr = match addrs[0] {
+                    SocketAddr::V4(_) => ([0, 0, 0, 0], 0).into(),
+                    SocketAddr::V6(_) => ([0u16; 8], 0).into(),
+                };
+                let socket = UdpSocket::bind(local).map_err(Error::Connect)?;
+                socket.connect(&addrs[..]).map_err(Error::Connect)?;
+                Self::Udp(socket)
+            }
+            Destination::Tcp(address) => Self::Tcp {
+                addrs: resolve(&address)?,
+                connect_timeout,
+                write_timeout,
+                stream: Mutex::new(None),
+            },
+        };
+        Ok(transport)
+    }
+
+    fn send(&self, message: &str, format: SyslogFormat, health: &helper::IoHealth) -> Result<()> {
+        match self {
+            #[cfg(unix)]
+            Self::Unix { socket, path } => health
+                .track(socket.send_to(message.as_bytes(), path), Error::WriteRecord)
+                .map(|_| ()),
+            Self::Udp(socket) => health
+                .track(socket.send(message.as_bytes()), Error::WriteRecord)
+                .map(|_| ()),
+            Self::Tcp {
+                addrs,
+                connect_timeout,
+                write_timeout,
+                stream,
+            } => {
+                let mut stream = stream.lock_expect();
+                if stream.is_none() {
+                    *stream = Some(health.track(
+                        connect_tcp(addrs, *connect_timeout, *write_timeout),
+                        Error::Connect,
+                    )?);
+                }
+                let conn = stream.as_mut().unwrap();
+                let result = match format {
+                    SyslogFormat::Rfc5424 => write!(conn, "{} {}", message.len(), message),
+                    SyslogFormat::Rfc3164 => writeln!(conn, "{}", message),
+                };
+                if result.is_err() {
+                    // Reconnect on the next record.
+                    *stream = None;
+                }
+                health.track(result, Error::WriteRecord)
+            }
+        }
+    }
+
+    fn flush(&self, health: &helper::IoHealth) -> Result<()> {
+        match self {
+            Self::Tcp { stream, .. } => match stream.lock_expect().as_mut() {
+                Some(stream) => health.track(stream.flush(), Error::FlushBuffer),
+                None => Ok(()),
+            },
+            _ => Ok(()),
+        }
+    }
+
+    fn open(&self, health: &helper::IoHealth) -> Result<()> {
+        match self {
+            Self::Tcp {
+                addrs,
+                connect_timeout,
+                write_timeout,
+                stream,
+            } => {
+                let mut stream = stream.lock_expect();
+                if stream.is_none() {
+                    *stream = Some(health.track(
+                        connect_tcp(addrs, *connect_timeout, *write_timeout),
+                        Error::Connect,
+                    )?);
+                }
+                Ok(())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn close(&self, health: &helper::IoHealth) -> Result<()> {
+        match self {
+            Self::Tcp { stream, .. } => {
+                let stream = stream.lock_expect().take();
+                health.set_disconnected();
+                match stream {
+                    Some(mut stream) => {
+                        let result = stream.flush().map_err(Error::FlushBuffer);
+                        _ = stream.shutdown(Shutdown::Both);
+                        result
+                    }
+                    None => Ok(()),
+                }
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+fn connect_tcp(
+    addrs: &[SocketAddr],
+    connect_timeout: Duration,
+    write_timeout: Duration,
+) -> io::Result<TcpStream> {
+    let mut last_err = None;
+    for addr in addrs {
+        match TcpStream::connect_timeout(addr, connect_timeout) {
+            Ok(stream) => {
+                stream.set_write_timeout(Some(write_timeout))?;
+                return Ok(stream);
+            }
+            Err(err) => last_err = Some(err),
+        }
+    }
+    // `addrs` is never empty, see `resolve`.
+    Err(last_err.unwrap())
+}
+
+fn resolve(address: &str) -> Result<Vec<SocketAddr>> {
+    let addrs: Vec<_> = address.to_socket_addrs().map_err(Error::Connect)?.collect();
+    if addrs.is_empty() {
+        return Err(Error::Connect(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("no address found for '{}'", address),
+        )));
+    }
+    Ok(addrs)
+}
+
+// Header fields consist of printable ASCII characters except spaces, and are
+// limited in length by RFC 5424.
+#[must_use]
+fn header_field(value: &str, max_len: usize) -> String {
+    let field: String = value
+        .chars()
+        .map(|ch| if ch.is_ascii_graphic() { ch } else { '_' })
+        .take(max_len)
+        .collect();
+    if field.is_empty() {
+        "-".to_string()
+    } else {
+        field
+    }
+}
+
+#[cfg(unix)]
+#[must_use]
+fn system_hostname() -> Option<String> {
+    let mut buf = [0u8; 256];
+    if unsafe { libc::gethostname(buf.as_mut_ptr().cast(), buf.len()) } != 0 {
+        return None;
+    }
+    let len = buf.iter().position(|&b| b == 0).unwrap_or(buf.len());
+    Some(String::from_utf8_lossy(&buf[..len]).into_owned())
+}
+
+#[cfg(not(unix))]
+#[must_use]
+fn system_hostname() -> Option<String> {
+    std::env::var("COMPUTERNAME").ok()
+}
+
+// The severity and time are in the header, so only the logger name and the
+// payload are formatted by default.
+#[derive(Clone)]
+struct SyslogFormatter;
+
+impl Formatter for SyslogFormatter {
+    fn format(
+        &self,
+        record: &Record,
+        dest: &mut StringBuf,
+        _ctx: &mut FormatterContext,
+    ) -> Result<()> {
+        (|| {
+            if let Some(logger_name) = record.logger_name() {
+                write!(dest, "[{}] ", logger_name)?;
+            }
+            dest.write_str(record.payload())
+        })()
+        .map_err(Error::FormatRecord)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{io::Read, net::TcpListener, time::Duration};
+
+    use super::*;
+    use crate::{prelude::*, test_utils::*};
+
+    #[test]
+    fn udp() {
+        let collector = UdpSocket::bind("127.0.0.1:0").unwrap();
+        collector
+            .set_read_timeout(Some(Duration::from_secs(5)))
+            .unwrap();
+        let sink = Arc::new(
+            SyslogSink::builder()
+                .udp(collector.local_addr().unwrap().to_string())
+                .facility(SyslogFacility::Local0)
+                .time_zone(TimeZone::UTC)
+                .hostname("host 1")
+                .app_name("app")
+                .build()
+                .unwrap(),
+        );
+        let logger = build_test_logger(|b| b.sink(sink.clone()).name("net"));
+
+        let mut buf = [0u8; 1024];
+        let mut receive = || {
+            let len = collector.recv(&mut buf).unwrap();
+            String::from_utf8(buf[..len].to_vec()).unwrap()
+        };
+
+        warn!(logger: logger, code = "E1042", "disk almost full");
+        let message = receive();
+        assert!(message.starts_with("<132>1 "), "{}", message);
+        assert!(message.contains("Z host_1 app "), "{}", message);
+        assert!(
+            message.ends_with(" E1042 - [net] disk almost full"),
+            "{}",
+            message
+        );
+
+        let sink = SyslogSink::builder()
+            .udp(collector.local_addr().unwrap().to_string())
+            .format(SyslogFormat::Rfc3164)
+            .hostname("host")
+            .app_name("app")
+            .formatter(Box::new(NoModFormatter::new()))
+            .build()
+            .unwrap();
+        sink.log(&Record::new(Level::Info, "hello", None, None))
+            .unwrap();
+        let message = receive();
+        assert!(message.starts_with("<14>"), "{}", message);
+        assert!(
+            message.ends_with(&format!(" host app[{}]: hello", std::process::id())),
+            "{}",
+            message
+        );
+    }
+
+    #[test]
+    fn tcp() {
+        let collector = TcpListener::bind("127.0.0.1:0").unwrap();
+        let sink = SyslogSink::builder()
+            .tcp(collector.local_addr().unwrap().to_string())
+            .formatter(Box::new(NoModFormatter::new()))
+            .build()
+            .unwrap();
+        assert!(matches!(sink.health(), Err(Error::Connect(_))));
+
+        sink.open().unwrap();
+        let (mut conn, _) = collector.accept().unwrap();
+        sink.health().unwrap();
+        sink.log(&Record::new(Level::Info, "hello", None, None))
+            .unwrap();
+
+        sink.close().unwrap();
+        assert!(matches!(sink.health(), Err(Error::Connect(_))));
+        let mut received = String::new();
+        conn.read_to_string(&mut received).unwrap();
+        let (len, message) = received.split_once(' ').unwrap();
+        assert_eq!(len.parse::<usize>().unwrap(), message.len());
+        assert!(message.starts_with("<14>1 "), "{}", message);
+        assert!(message.ends_with(" - - hello"), "{}", message);
+    }
+}
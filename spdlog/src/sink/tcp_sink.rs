@@ -0,0 +1,466 @@
+use std::{
+    collections::VecDeque,
+    convert::Infallible,
+    io::{self, Write},
+    net::{Shutdown, TcpStream, ToSocketAddrs},
+    time::{Duration, Instant},
+};
+
+use crate::{
+    sink::{helper, Sink},
+    sync::*,
+    Error, Record, Result,
+};
+
+struct TcpSinkState {
+    stream: Option<TcpStream>,
+    // Formatted records not sent yet, the oldest first.
+    pending: VecDeque<Vec<u8>>,
+    last_connect: Option<Instant>,
+}
+
+/// A sink with a remote TCP endpoint as the target, e.g. a Logstash TCP input.
+///
+/// Formatted records are written to the connection as they are, so the
+/// formatter decides the framing, e.g. [`FullFormatter`] and [`JsonFormatter`]
+/// terminate each record with a line ending.
+///
+/// # Reconnection
+///
+/// The connection is established by [`Sink::open`], which loggers call when
+/// they are built, or on the first record otherwise. It is reestablished when
+/// it is lost. Connecting and writing happen on the logging thread: an attempt
+/// to connect blocks it for up to the [connect timeout] for each resolved
+/// address, and a write blocks it for up to the [write timeout]. Attempts to
+/// connect are made at most once per [reconnect interval], so a collector that
+/// is down only slows down logging once per interval. To keep logging threads
+/// from being blocked at all, combine this sink with [`AsyncPoolSink`].
+///
+/// While disconnected, records are kept in memory and sent once connected
+/// again. At most [`buffer_capacity`] records are kept, older ones are dropped
+/// when it is exceeded.
+///
+/// Errors in connecting and writing are returned from [`Sink::log`] by the
+/// record that made the attempt, and the record is buffered anyway. Records
+/// logged before the next attempt is due are buffered and return `Ok(())`, so
+/// that a collector that is down does not flood the error handler. Meanwhile,
+/// [`Sink::health`] returns the last error, and [`TcpSink::pending_count`]
+/// tells how many records are buffered.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::{sync::Arc, time::Duration};
+///
+/// use spdlog::{prelude::*, sink::TcpSink};
+///
+/// # fn main() -> Result<(), spdlog::Error> {
+/// let sink = Arc::new(
+///     TcpSink::builder()
+///         .address("logstash.example.com:5000")
+///         .write_timeout(Duration::from_secs(1))
+///         .build()?,
+/// );
+/// let logger = Logger::builder().sink(sink).build()?;
+///
+/// info!(logger: logger, "service started");
+/// # Ok(()) }
+/// ```
+///
+/// [`FullFormatter`]: crate::formatter::FullFormatter
+/// [`JsonFormatter`]: crate::formatter::JsonFormatter
+/// [connect timeout]: TcpSinkBuilder::connect_timeout
+/// [write timeout]: TcpSinkBuilder::write_timeout
+/// [reconnect interval]: TcpSinkBuilder::reconnect_interval
+/// [`AsyncPoolSink`]: crate::sink::AsyncPoolSink
+/// [`buffer_capacity`]: TcpSinkBuilder::buffer_capacity
+pub struct TcpSink {
+    common_impl: helper::CommonImpl,
+    address: String,
+    connect_timeout: Duration,
+    write_timeout: Duration,
+    reconnect_interval: Duration,
+    buffer_capacity: usize,
+    state: Mutex<TcpSinkState>,
+    health: helper::IoHealth,
+}
+
+impl TcpSink {
+    /// Gets a builder of `TcpSink` with default parameters:
+    ///
+    /// | Parameter            | Default Value           |
+    /// |----------------------|-------------------------|
+    /// | [level_filter]       | `All`                   |
+    /// | [formatter]          | `FullFormatter`         |
+    /// | [error_handler]      | [default error handler] |
+    /// |                      |                         |
+    /// | [address]            | *must be specified*     |
+    /// | [connect_timeout]    | 5 seconds               |
+    /// | [write_timeout]      | 5 seconds               |
+    /// | [reconnect_interval] | 1 second                |
+    /// | [buffer_capacity]    | `1024`                  |
+    ///
+    /// [level_filter]: TcpSinkBuilder::level_filter
+    /// [formatter]: TcpSinkBuilder::formatter
+    /// [error_handler]: TcpSinkBuilder::error_handler
+    /// [default error handler]: error/index.html#default-error-handler
+    /// [address]: TcpSinkBuilder::address
+    /// [connect_timeout]: TcpSinkBuilder::connect_timeout
+    /// [write_timeout]: TcpSinkBuilder::write_timeout
+    /// [reconnect_interval]: TcpSinkBuilder::reconnect_interval
+    /// [buffer_capacity]: TcpSinkBuilder::buffer_capacity
+    #[must_use]
+    pub fn builder() -> TcpSinkBuilder<()> {
+        TcpSinkBuilder {
+            common_builder_impl: helper::CommonBuilderImpl::new(),
+            address: (),
+            connect_timeout: Duration::from_secs(5),
+            write_timeout: Duration::from_secs(5),
+            reconnect_interval: Duration::from_secs(1),
+            buffer_capacity: 1024,
+        }
+    }
+
+    /// Gets the number of records waiting to be sent.
+    #[must_use]
+    pub fn pending_count(&self) -> usize {
+        self.state.lock_expect().pending.len()
+    }
+
+    fn connect(&self) -> io::Result<TcpStream> {
+        let mut last_err = None;
+        for addr in self.address.to_socket_addrs()? {
+            match TcpStream::connect_timeout(&addr, self.connect_timeout) {
+                Ok(stream) => {
+                    stream.set_write_timeout(Some(self.write_timeout))?;
+                    return Ok(stream);
+                }
+                Err(err) => last_err = Some(err),
+            }
+        }
+        Err(last_err.unwrap_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("no address found for '{}'", self.address),
+            )
+        }))
+    }
+
+    // Sends pending records, connecting first if disconnected.
+    fn send_pending(&self, state: &mut TcpSinkState) -> Result<()> {
+        let result = self.send_pending_impl(state);
+        while state.pending.len() > self.buffer_capacity {
+            state.pending.pop_front();
+        }
+        result
+    }
+
+    fn send_pending_impl(&self, state: &mut TcpSinkState) -> Result<()> {
+        if state.stream.is_none() {
+            if let Some(last_connect) = state.last_connect {
+                if last_connect.elapsed() < self.reconnect_interval {
+                    return Ok(());
+                }
+            }
+            state.last_connect = Some(Instant::now());
+            state.stream = Some(self.health.track(self.connect(), Error::Connect)?);
+        }
+
+        let stream = state.stream.as_mut().unwrap();
+        while let Some(data) = state.pending.front() {
+            if let Err(err) = stream.write_all(data) {
+                state.stream = None;
+                return self.health.track(Err(err), Error::WriteRecord);
+            }
+            state.pending.pop_front();
+        }
+        Ok(())
+    }
+}
+
+impl Sink for TcpSink {
+    fn log(&self, record: &Record) -> Result<()> {
+        self.common_impl.format_with(record, |string_buf, _| {
+            let mut state = self.state.lock_expect();
+            state.pending.push_back(string_buf.as_bytes().to_vec());
+            self.send_pending(&mut state)
+        })
+    }
+
+    fn flush(&self) -> Result<()> {
+        let mut state = self.state.lock_expect();
+        if !state.pending.is_empty() {
+            self.send_pending(&mut state)?;
+        }
+        match state.stream.as_mut() {
+            Some(stream) => self.health.track(stream.flush(), Error::FlushBuffer),
+            None => Ok(()),
+        }
+    }
+
+    /// Connects to the remote endpoint if not connected yet, regardless of the
+    /// reconnect interval, and sends the buffered records.
+    fn open(&self) -> Result<()> {
+        let mut state = self.state.lock_expect();
+        if state.stream.is_none() {
+            state.last_connect = None;
+        }
+        self.send_pending(&mut state)
+    }
+
+    /// Sends the buffered records, connecting first if disconnected, and then
+    /// shuts down the connection. The sink connects again if it is used after
+    /// being closed.
+    fn close(&self) -> Result<()> {
+        let mut state = self.state.lock_expect();
+        let mut result = Ok(());
+        if !state.pending.is_empty() {
+            if state.stream.is_none() {
+                state.last_connect = None;
+            }
+            result = self.send_pending(&mut state);
+        }
+        if let Some(mut stream) = state.stream.take() {
+            result = Error::push_result(result, stream.flush().map_err(Error::FlushBuffer));
+            _ = stream.shutdown(Shutdown::Both);
+        }
+        // Reconnect on the next record if used after being closed.
+        state.last_connect = None;
+        self.health.set_disconnected();
+        result
+    }
+
+    /// Returns an error while disconnected, i.e. before connecting, after the
+    /// connection is lost, and while records are buffered for reconnecting.
+    /// The error is the last one in connecting or writing if any.
+    fn health(&self) -> Result<()> {
+        self.health.check()
+    }
+
+    helper::common_impl!(@Sink: common_impl);
+}
+
+impl Drop for TcpSink {
+    fn drop(&mut self) {
+        if let Err(err) = self.flush() {
+            self.common_impl.non_returnable_error("TcpSink", err)
+        }
+    }
+}
+
+/// #
+#[doc = include_str!("../include/doc/generic-builder-note.md")]
+pub struct TcpSinkBuilder<ArgA> {
+    common_builder_impl: helper::CommonBuilderImpl,
+    address: ArgA,
+    connect_timeout: Duration,
+    write_timeout: Duration,
+    reconnect_interval: Duration,
+    buffer_capacity: usize,
+}
+
+impl<ArgA> TcpSinkBuilder<ArgA> {
+    /// Specifies the address of the remote endpoint, in the form of
+    /// `host:port`.
+    ///
+    /// The address is resolved on each attempt to connect.
+    ///
+    /// This parameter is **required**.
+    #[must_use]
+    pub fn address<S>(self, address: S) -> TcpSinkBuilder<String>
+    where
+        S: Into<String>,
+    {
+        TcpSinkBuilder {
+            common_builder_impl: self.common_builder_impl,
+            address: address.into(),
+            connect_timeout: self.connect_timeout,
+            write_timeout: self.write_timeout,
+            reconnect_interval: self.reconnect_interval,
+            buffer_capacity: self.buffer_capacity,
+        }
+    }
+
+    /// Specifies the timeout of connecting to each resolved address.
+    ///
+    /// Since connecting happens on the logging thread, this is also how long
+    /// logging may be blocked for each resolved address of a collector that
+    /// is down.
+    ///
+    /// This parameter is **optional**.
+    #[must_use]
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = timeout;
+        self
+    }
+
+    /// Specifies the timeout of writing to the connection. The connection is
+    /// considered lost if a write times out.
+    ///
+    /// Since writing happens on the logging thread, this is also how long
+    /// logging may be blocked by a collector that stops reading. It must not be
+    /// zero, otherwise connecting fails.
+    ///
+    /// This parameter is **optional**.
+    #[must_use]
+    pub fn write_timeout(mut self, timeout: Duration) -> Self {
+        self.write_timeout = timeout;
+        self
+    }
+
+    /// Specifies the minimum interval between attempts to connect.
+    ///
+    /// This parameter is **optional**.
+    #[must_use]
+    pub fn reconnect_interval(mut self, interval: Duration) -> Self {
+        self.reconnect_interval = interval;
+        self
+    }
+
+    /// Specifies the maximum number of records kept in memory while
+    /// disconnected. `0` means records logged while disconnected are dropped.
+    ///
+    /// This parameter is **optional**.
+    #[must_use]
+    pub fn buffer_capacity(mut self, capacity: usize) -> Self {
+        self.buffer_capacity = capacity;
+        self
+    }
+
+    helper::common_impl!(@SinkBuilder: common_builder_impl);
+}
+
+impl TcpSinkBuilder<()> {
+    #[doc(hidden)]
+    #[deprecated(note = "\n\n\
+        builder compile-time error:\n\
+        - missing required parameter `address`\n\n\
+    ")]
+    pub fn build(self, _: Infallible) {}
+}
+
+impl TcpSinkBuilder<String> {
+    /// Builds a [`TcpSink`].
+    ///
+    /// The connection is not established until the first record is logged.
+    pub fn build(self) -> Result<TcpSink> {
+        Ok(TcpSink {
+            common_impl: helper::CommonImpl::from_builder(self.common_builder_impl),
+            address: self.address,
+            connect_timeout: self.connect_timeout,
+            write_timeout: self.write_timeout,
+            reconnect_interval: self.reconnect_interval,
+            buffer_capacity: self.buffer_capacity,
+            state: Mutex::new(TcpSinkState {
+                stream: None,
+                pending: VecDeque::new(),
+                last_connect: None,
+            }),
+            health: helper::IoHealth::disconnected(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{io::Read, net::TcpListener};
+
+    use super::*;
+    use crate::{prelude::*, test_utils::*};
+
+    #[test]
+    fn reconnect() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        drop(listener);
+
+        let sink = TcpSink::builder()
+            .address(address.to_string())
+            .formatter(Box::new(NoModFormatter::new()))
+            .reconnect_interval(Duration::ZERO)
+            .buffer_capacity(2)
+            .build()
+            .unwrap();
+        let log = |payload| sink.log(&Record::new(Level::Info, payload, None, None));
+
+        for payload in ["a", "b", "c"] {
+            assert!(matches!(log(payload), Err(Error::Connect(_))));
+        }
+        assert_eq!(sink.pending_count(), 2);
+
+        let listener = TcpListener::bind(address).unwrap();
+        log("d").unwrap();
+        log("e").unwrap();
+        assert_eq!(sink.pending_count(), 0);
+        drop(sink);
+
+        let mut received = String::new();
+        listener
+            .accept()
+            .unwrap()
+            .0
+            .read_to_string(&mut received)
+            .unwrap();
+        assert_eq!(received, "bcde");
+    }
+
+    #[test]
+    fn buffer_between_attempts() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        drop(listener);
+
+        let sink = TcpSink::builder()
+            .address(address.to_string())
+            .reconnect_interval(Duration::from_secs(3600))
+            .build()
+            .unwrap();
+        let log = |payload| sink.log(&Record::new(Level::Info, payload, None, None));
+
+        assert!(matches!(log("a"), Err(Error::Connect(_))));
+        // The next attempt is not due yet.
+        log("b").unwrap();
+        assert_eq!(sink.pending_count(), 2);
+        assert!(matches!(sink.health(), Err(Error::Connect(_))));
+    }
+
+    #[test]
+    fn lifecycle() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+
+        let sink = TcpSink::builder()
+            .address(address.to_string())
+            .formatter(Box::new(NoModFormatter::new()))
+            .reconnect_interval(Duration::from_secs(3600))
+            .build()
+            .unwrap();
+        assert!(matches!(sink.health(), Err(Error::Connect(_))));
+
+        sink.open().unwrap();
+        sink.health().unwrap();
+        let (mut conn, _) = listener.accept().unwrap();
+        sink.log(&Record::new(Level::Info, "a", None, None))
+            .unwrap();
+
+        sink.close().unwrap();
+        assert!(matches!(sink.health(), Err(Error::Connect(_))));
+        let mut received = String::new();
+        conn.read_to_string(&mut received).unwrap();
+        assert_eq!(received, "a");
+
+        // Reconnects if used after being closed.
+        sink.log(&Record::new(Level::Info, "b", None, None))
+            .unwrap();
+        assert_eq!(sink.pending_count(), 0);
+        sink.close().unwrap();
+        let mut received = String::new();
+        listener
+            .accept()
+            .unwrap()
+            .0
+            .read_to_string(&mut received)
+            .unwrap();
+        assert_eq!(received, "b");
+    }
+}
@@ -0,0 +1,314 @@
+use std::{
+    convert::Infallible,
+    io,
+    net::{SocketAddr, ToSocketAddrs, UdpSocket},
+};
+
+use crate::{
+    sink::{helper, Sink},
+    Error, Record, Result,
+};
+
+/// Policy for records exceeding the maximum datagram size of a [`UdpSink`].
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[non_exhaustive]
+pub enum OversizePolicy {
+    /// Truncates the record to the maximum size, at a character boundary.
+    Truncate,
+    /// Splits the record into multiple consecutive datagrams, at character
+    /// boundaries.
+    Chunk,
+}
+
+/// A sink with a remote UDP endpoint as the target, e.g. a GELF or statsd
+/// collector.
+///
+/// Each formatted record is sent as a single datagram. Records larger than
+/// the [maximum datagram size] are truncated or chunked according to the
+/// [oversize policy].
+///
+/// UDP does not guarantee delivery, records may be lost or reordered without
+/// errors. Some errors are still reported by the system, e.g. on some
+/// platforms, sending fails if a previous datagram was refused by the remote
+/// host. [`Sink::health`] returns the error of the last send if it failed.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::sync::Arc;
+///
+/// use spdlog::{prelude::*, sink::UdpSink};
+///
+/// # fn main() -> Result<(), spdlog::Error> {
+/// let sink = Arc::new(
+///     UdpSink::builder()
+///         .address("graylog.example.com:12201")
+///         .build()?,
+/// );
+/// let logger = Logger::builder().sink(sink).build()?;
+///
+/// info!(logger: logger, "service started");
+/// # Ok(()) }
+/// ```
+///
+/// [maximum datagram size]: UdpSinkBuilder::max_datagram_size
+/// [oversize policy]: UdpSinkBuilder::oversize_policy
+pub struct UdpSink {
+    common_impl: helper::CommonImpl,
+    socket: UdpSocket,
+    max_datagram_size: usize,
+    oversize_policy: OversizePolicy,
+    health: helper::IoHealth,
+}
+
+impl UdpSink {
+    /// Gets a builder of `UdpSink` with default parameters:
+    ///
+    /// | Parameter           | Default Value              |
+    /// |---------------------|----------------------------|
+    /// | [level_filter]      | `All`                      |
+    /// | [formatter]         | `FullFormatter`            |
+    /// | [error_handler]     | [default error handler]    |
+    /// |                     |                            |
+    /// | [address]           | *must be specified*        |
+    /// | [max_datagram_size] | `1400`                     |
+    /// | [oversize_policy]   | `OversizePolicy::Truncate` |
+    ///
+    /// [level_filter]: UdpSinkBuilder::level_filter
+    /// [formatter]: UdpSinkBuilder::formatter
+    /// [error_handler]: UdpSinkBuilder::error_handler
+    /// [default error handler]: error/index.html#default-error-handler
+    /// [address]: UdpSinkBuilder::address
+    /// [max_datagram_size]: UdpSinkBuilder::max_datagram_size
+    /// [oversize_policy]: UdpSinkBuilder::oversize_policy
+    #[must_use]
+    pub fn builder() -> UdpSinkBuilder<()> {
+        UdpSinkBuilder {
+            common_builder_impl: helper::CommonBuilderImpl::new(),
+            address: (),
+            max_datagram_size: 1400,
+            oversize_policy: OversizePolicy::Truncate,
+        }
+    }
+
+    fn send(&self, data: &str) -> Result<()> {
+        self.health
+            .track(self.socket.send(data.as_bytes()), Error::WriteRecord)
+            .map(|_| ())
+    }
+}
+
+impl Sink for UdpSink {
+    fn log(&self, record: &Record) -> Result<()> {
+        self.common_impl.format_with(record, |string_buf, _| {
+            let mut data: &str = string_buf;
+            loop {
+                let (datagram, rest) =
+                    data.split_at(floor_char_boundary(data, self.max_datagram_size));
+                self.send(datagram)?;
+                match self.oversize_policy {
+                    OversizePolicy::Chunk if !rest.is_empty() => data = rest,
+                    _ => return Ok(()),
+                }
+            }
+        })
+    }
+
+    fn flush(&self) -> Result<()> {
+        Ok(())
+    }
+
+    /// Returns the error of the last send if it failed.
+    fn health(&self) -> Result<()> {
+        self.health.check()
+    }
+
+    helper::common_impl!(@Sink: common_impl);
+}
+
+// Gets the largest index not exceeding `index` that is a character boundary,
+// and is at least 1 unless the string is empty.
+#[must_use]
+fn floor_char_boundary(s: &str, index: usize) -> usize {
+    if index >= s.len() {
+        return s.len();
+    }
+    match (1..=index).rev().find(|&i| s.is_char_boundary(i)) {
+        Some(i) => i,
+        // A character longer than `index`, includes the whole character.
+        None => (1..=s.len()).find(|&i| s.is_char_boundary(i)).unwrap_or(0),
+    }
+}
+
+/// #
+#[doc = include_str!("../include/doc/generic-builder-note.md")]
+pub struct UdpSinkBuilder<ArgA> {
+    common_builder_impl: helper::CommonBuilderImpl,
+    address: ArgA,
+    max_datagram_size: usize,
+    oversize_policy: OversizePolicy,
+}
+
+impl<ArgA> UdpSinkBuilder<ArgA> {
+    /// Specifies the address of the remote endpoint, in the form of
+    /// `host:port`.
+    ///
+    /// The address is resolved when building the sink.
+    ///
+    /// This parameter is **required**.
+    #[must_use]
+    pub fn address<S>(self, address: S) -> UdpSinkBuilder<String>
+    where
+        S: Into<String>,
+    {
+        UdpSinkBuilder {
+            common_builder_impl: self.common_builder_impl,
+            address: address.into(),
+            max_datagram_size: self.max_datagram_size,
+            oversize_policy: self.oversize_policy,
+        }
+    }
+
+    /// Specifies the maximum size of a datagram in bytes.
+    ///
+    /// The default value fits in the common Ethernet MTU of 1500 bytes with
+    /// the IP and UDP headers, so datagrams are not fragmented.
+    ///
+    /// This parameter is **optional**.
+    #[must_use]
+    pub fn max_datagram_size(mut self, size: usize) -> Self {
+        self.max_datagram_size = size;
+        self
+    }
+
+    /// Specifies how records larger than the maximum datagram size are sent.
+    ///
+    /// This parameter is **optional**.
+    #[must_use]
+    pub fn oversize_policy(mut self, policy: OversizePolicy) -> Self {
+        self.oversize_policy = policy;
+        self
+    }
+
+    helper::common_impl!(@SinkBuilder: common_builder_impl);
+}
+
+impl UdpSinkBuilder<()> {
+    #[doc(hidden)]
+    #[deprecated(note = "\n\n\
+        builder compile-time error:\n\
+        - missing required parameter `address`\n\n\
+    ")]
+    pub fn build(self, _: Infallible) {}
+}
+
+impl UdpSinkBuilder<String> {
+    /// Builds a [`UdpSink`].
+    ///
+    /// # Error
+    ///
+    /// If an error occurs in resolving the address or creating the socket,
+    /// [`Error::Connect`] will be returned.
+    pub fn build(self) -> Result<UdpSink> {
+        let addrs: Vec<_> = self
+            .address
+            .to_socket_addrs()
+            .map_err(Error::Connect)?
+            .collect();
+        let local: SocketAddr = match addrs.first() {
+            Some(SocketAddr::V4(_)) => ([0, 0, 0, 0], 0).into(),
+            Some(SocketAddr::V6(_)) => ([0u16; 8], 0).into(),
+            None => {
+                return Err(Error::Connect(io::Error::new(
+                    io::ErrorKind::NotFound,
+                    format!("no address found for '{}'", self.address),
+                )))
+            }
+        };
+        let socket = UdpSocket::bind(local).map_err(Error::Connect)?;
+        socket.connect(&addrs[..]).map_err(Error::Connect)?;
+
+        Ok(UdpSink {
+            common_impl: helper::CommonImpl::from_builder(self.common_builder_impl),
+            socket,
+            max_datagram_size: self.max_datagram_size,
+            oversize_policy: self.oversize_policy,
+            health: helper::IoHealth::new(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::{prelude::*, test_utils::*};
+
+    #[test]
+    fn oversize() {
+        let collector = UdpSocket::bind("127.0.0.1:0").unwrap();
+        collector
+            .set_read_timeout(Some(Duration::from_secs(5)))
+            .unwrap();
+        let build_sink = |policy| {
+            UdpSink::builder()
+                .address(collector.local_addr().unwrap().to_string())
+                .formatter(Box::new(NoModFormatter::new()))
+                .max_datagram_size(4)
+                .oversize_policy(policy)
+                .build()
+                .unwrap()
+        };
+        let receive = || {
+            let mut buf = [0u8; 64];
+            let len = collector.recv(&mut buf).unwrap();
+            String::from_utf8(buf[..len].to_vec()).unwrap()
+        };
+
+        let sink = build_sink(OversizePolicy::Truncate);
+        sink.log(&Record::new(Level::Info, "hello", None, None))
+            .unwrap();
+        assert_eq!(receive(), "hell");
+
+        let sink = build_sink(OversizePolicy::Chunk);
+        sink.log(&Record::new(Level::Info, "abc猫defg", None, None))
+            .unwrap();
+        assert_eq!(receive(), "abc");
+        assert_eq!(receive(), "猫d");
+        assert_eq!(receive(), "efg");
+    }
+
+    // Linux reports refused datagrams on the next send of a connected socket.
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn health() {
+        let collector = UdpSocket::bind("127.0.0.1:0").unwrap();
+        let address = collector.local_addr().unwrap();
+        drop(collector);
+
+        let sink = UdpSink::builder()
+            .address(address.to_string())
+            .build()
+            .unwrap();
+        sink.health().unwrap();
+
+        let refused = (0..10).any(|_| {
+            let res = sink.log(&Record::new(Level::Info, "hello", None, None));
+            std::thread::sleep(Duration::from_millis(10));
+            res.is_err()
+        });
+        assert!(refused);
+        assert!(
+            matches!(sink.health(), Err(Error::WriteRecord(err)) if err.kind() == io::ErrorKind::ConnectionRefused)
+        );
+    }
+
+    #[test]
+    fn char_boundary() {
+        assert_eq!(floor_char_boundary("abc", 5), 3);
+        assert_eq!(floor_char_boundary("a猫", 2), 1);
+        assert_eq!(floor_char_boundary("猫a", 2), 3);
+        assert_eq!(floor_char_boundary("", 0), 0);
+    }
+}
@@ -1,9 +1,8 @@
 use std::{ffi::OsStr, iter::once};
 
 use crate::{
-    formatter::FormatterContext,
     sink::{helper, Sink},
-    Record, Result, StringBuf,
+    Record, Result,
 };
 
 /// A sink with a win32 API `OutputDebugStringW` as the target.
@@ -48,22 +47,17 @@ impl Sink for WinDebugSink {
         #[cfg(windows)] // https://github.com/rust-lang/rust/issues/97976
         use std::os::windows::ffi::OsStrExt;
 
-        let mut string_buf = StringBuf::new();
-        let mut ctx = FormatterContext::new();
-        self.common_impl
-            .formatter
-            .read()
-            .format(record, &mut string_buf, &mut ctx)?;
+        self.common_impl.format_with(record, |string_buf, _| {
+            let wide: Vec<u16> = OsStr::new(string_buf)
+                .encode_wide()
+                .chain(once(0))
+                .collect();
+            let wide = wide.as_ptr();
 
-        let wide: Vec<u16> = OsStr::new(&string_buf)
-            .encode_wide()
-            .chain(once(0))
-            .collect();
-        let wide = wide.as_ptr();
+            unsafe { winapi::um::debugapi::OutputDebugStringW(wide) }
 
-        unsafe { winapi::um::debugapi::OutputDebugStringW(wide) }
-
-        Ok(())
+            Ok(())
+        })
     }
 
     fn flush(&self) -> Result<()> {
@@ -1,10 +1,9 @@
 use std::{convert::Infallible, io::Write, marker::PhantomData};
 
 use crate::{
-    formatter::FormatterContext,
     sink::{helper, Sink},
     sync::*,
-    Error, Record, Result, StringBuf,
+    Error, Record, Result,
 };
 
 /// A sink that writes log messages into an arbitrary `impl Write` object.
@@ -79,7 +78,7 @@ where
         callback(&mut *self.lock_target())
     }
 
-    fn lock_target(&self) -> MutexGuard<W> {
+    fn lock_target(&self) -> MutexGuard<'_, W> {
         self.target.lock_expect()
     }
 }
@@ -100,18 +99,13 @@ where
     W: Write + Send,
 {
     fn log(&self, record: &Record) -> Result<()> {
-        let mut string_buf = StringBuf::new();
-        let mut ctx = FormatterContext::new();
-        self.common_impl
-            .formatter
-            .read()
-            .format(record, &mut string_buf, &mut ctx)?;
-
-        self.lock_target()
-            .write_all(string_buf.as_bytes())
-            .map_err(Error::WriteRecord)?;
-
-        Ok(())
+        self.common_impl.format_with(record, |string_buf, _| {
+            self.lock_target()
+                .write_all(string_buf.as_bytes())
+                .map_err(Error::WriteRecord)?;
+
+            Ok(())
+        })
     }
 
     fn flush(&self) -> Result<()> {
@@ -0,0 +1,144 @@
+use std::{
+    borrow::Cow,
+    fmt,
+    panic::{RefUnwindSafe, UnwindSafe},
+};
+
+use slog::{Drain, Key, Never, OwnedKVList, Serializer, KV};
+
+use crate::{sync::*, Level, Logger, Record, SourceLocation};
+
+/// A [`slog::Drain`] forwarding records from [slog crate] to a `spdlog-rs`
+/// logger.
+///
+/// Key-values of slog records and then of slog loggers are attached to records
+/// as structured key-value fields, see [`Record::key_values`].
+///
+/// ## Examples
+///
+/// ```
+/// use slog::o;
+/// use spdlog::SlogDrain;
+///
+/// let drain = SlogDrain::new(spdlog::default_logger());
+/// let root = slog::Logger::root(drain, o!("version" => "1.0"));
+///
+/// slog::info!(root, "login ok"; "user_id" => 42);
+/// ```
+///
+/// [slog crate]: https://crates.io/crates/slog
+pub struct SlogDrain {
+    logger: Arc<Logger>,
+}
+
+impl SlogDrain {
+    /// Constructs a `SlogDrain` forwarding records to the given logger.
+    #[must_use]
+    pub fn new(logger: Arc<Logger>) -> Self {
+        Self { logger }
+    }
+}
+
+// `slog::Logger::root` requires drains to be unwind safe. A panic during
+// logging cannot leave `Logger` in an inconsistent state, as all its mutable
+// states are atomics or locks.
+impl UnwindSafe for SlogDrain {}
+impl RefUnwindSafe for SlogDrain {}
+
+impl Drain for SlogDrain {
+    type Ok = ();
+    type Err = Never;
+
+    fn log(&self, record: &slog::Record, values: &OwnedKVList) -> Result<(), Never> {
+        let level = level_from_slog(record.level());
+        if !self.logger.should_log(level) {
+            return Ok(());
+        }
+
+        let mut serializer = KeyValuesSerializer(vec![]);
+        // `KeyValuesSerializer` never fails.
+        _ = record.kv().serialize(record, &mut serializer);
+        _ = values.serialize(record, &mut serializer);
+
+        let srcloc = SourceLocation::__new(
+            record.module(),
+            record.file(),
+            record.line(),
+            record.column(),
+        );
+        let payload = match record.msg().as_str() {
+            Some(msg) => Cow::Borrowed(msg),
+            None => Cow::Owned(record.msg().to_string()),
+        };
+        let mut record = Record::new(level, payload, Some(srcloc), self.logger.name());
+        record.set_key_values(serializer.0);
+        self.logger.log(&record);
+        Ok(())
+    }
+
+    fn is_enabled(&self, level: slog::Level) -> bool {
+        self.logger.should_log(level_from_slog(level))
+    }
+}
+
+#[must_use]
+fn level_from_slog(level: slog::Level) -> Level {
+    match level {
+        slog::Level::Critical => Level::Critical,
+        slog::Level::Error => Level::Error,
+        slog::Level::Warning => Level::Warn,
+        slog::Level::Info => Level::Info,
+        slog::Level::Debug => Level::Debug,
+        slog::Level::Trace => Level::Trace,
+    }
+}
+
+struct KeyValuesSerializer(Vec<(Cow<'static, str>, Cow<'static, str>)>);
+
+impl Serializer for KeyValuesSerializer {
+    fn emit_arguments(&mut self, key: Key, val: &fmt::Arguments) -> slog::Result {
+        self.0
+            .push((Cow::Borrowed(key), Cow::Owned(val.to_string())));
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use slog::o;
+
+    use super::*;
+    use crate::{test_utils::*, LevelFilter};
+
+    #[test]
+    fn forward_records() {
+        let sink = Arc::new(TestSink::new());
+        let logger = Arc::new(build_test_logger(|b| {
+            b.sink(sink.clone())
+                .level_filter(LevelFilter::MoreSevereEqual(Level::Info))
+        }));
+        let root = slog::Logger::root(SlogDrain::new(logger), o!("version" => "1.0"));
+
+        slog::info!(root, "hello");
+        slog::warn!(root, "login {}", "ok"; "user_id" => 42);
+        slog::debug!(root, "filtered");
+
+        assert_eq!(sink.payloads(), ["hello", "login ok"]);
+
+        let records = sink.records();
+        assert_eq!(
+            records[0].key_values().collect::<Vec<_>>(),
+            [("version", "1.0")]
+        );
+        assert_eq!(
+            records[1].key_values().collect::<Vec<_>>(),
+            [("user_id", "42"), ("version", "1.0")]
+        );
+        assert_eq!(records[0].level(), Level::Info);
+        assert_eq!(records[1].level(), Level::Warn);
+        assert_eq!(
+            records[0].source_location().map(|srcloc| srcloc.file()),
+            Some(file!())
+        );
+    }
+}
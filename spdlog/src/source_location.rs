@@ -7,8 +7,12 @@ use std::path;
 ///
 /// ## Schema
 ///
-/// This struct is implemented [`serde::Serialize`] if crate feature `serde` is
-/// enabled.
+/// This struct is implemented [`serde::Serialize`] and [`serde::Deserialize`]
+/// if crate feature `serde` is enabled.
+///
+/// Since the strings are `&'static str`, deserialized strings are leaked.
+/// Identical strings are only leaked once, so deserializing many source
+/// locations of the same program does not grow the memory usage unboundedly.
 ///
 /// | Field         | Type   |
 /// |---------------|--------|
@@ -98,6 +102,65 @@ impl SourceLocation {
             }),
         }
     }
+
+    #[cfg(feature = "tracing")]
+    #[must_use]
+    pub(crate) fn from_tracing_metadata(metadata: &tracing::Metadata<'static>) -> Option<Self> {
+        let (module_path, file, line) = (metadata.module_path(), metadata.file(), metadata.line());
+
+        match (module_path, file, line) {
+            (None, None, None) => None,
+            _ => Some(Self {
+                module_path: module_path.unwrap_or(""),
+                file: file.unwrap_or(""),
+                line: line.unwrap_or(0),
+                column: 0,
+            }),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for SourceLocation {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        #[derive(serde::Deserialize)]
+        struct Owned {
+            module_path: String,
+            file: String,
+            line: u32,
+            column: u32,
+        }
+
+        let owned = Owned::deserialize(deserializer)?;
+        Ok(Self {
+            module_path: intern(owned.module_path),
+            file: intern(owned.file),
+            line: owned.line,
+            column: owned.column,
+        })
+    }
+}
+
+#[must_use]
+pub(crate) fn intern(string: String) -> &'static str {
+    use std::collections::HashSet;
+
+    use crate::sync::*;
+
+    static INTERNED: Lazy<Mutex<HashSet<&'static str>>> = Lazy::new(Default::default);
+
+    let mut interned = INTERNED.lock_expect();
+    match interned.get(string.as_str()) {
+        Some(interned) => interned,
+        None => {
+            let leaked: &'static str = Box::leak(string.into_boxed_str());
+            interned.insert(leaked);
+            leaked
+        }
+    }
 }
 
 /// Constructs a [`SourceLocation`] with current source location.
@@ -141,3 +204,13 @@ macro_rules! __private_source_location_current_inner {
         None
     };
 }
+
+// Module paths are taken regardless of crate feature `source-location`, as
+// module level filters rely on them.
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __private_module_path {
+    () => {
+        ::core::option::Option::Some(::core::module_path!())
+    };
+}
@@ -231,6 +231,88 @@ pub enum StyleMode {
     Never,
 }
 
+/// Represents a built-in set of styles for all log levels.
+///
+/// Themes only use the basic 8 colors, which are rendered according to the
+/// palette of the terminal. Each level can still be styled individually after
+/// applying a theme, see [`StdStreamSink::set_style`].
+///
+/// # Examples
+///
+/// ```
+/// use spdlog::{
+///     sink::{StdStream, StdStreamSink},
+///     terminal_style::Theme,
+/// };
+///
+/// # fn main() -> Result<(), spdlog::Error> {
+/// let sink = StdStreamSink::builder()
+///     .std_stream(StdStream::Stdout)
+///     .theme(Theme::HighContrast)
+///     .build()?;
+/// # Ok(()) }
+/// ```
+///
+/// [`StdStreamSink::set_style`]: crate::sink::StdStreamSink::set_style
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[non_exhaustive]
+pub enum Theme {
+    /// The colors of C++ `spdlog`, used by default.
+    Classic,
+    /// No colors, only bold text for errors and warnings and faint text for
+    /// debugging levels.
+    MonochromeDim,
+    /// Accent colors of the Solarized palette, without bold text.
+    Solarized,
+    /// Bold text and strong colors for all levels, for readability on low
+    /// quality displays or for users with low vision.
+    HighContrast,
+}
+
+impl Theme {
+    /// Gets the style of the specified log level in this theme.
+    #[must_use]
+    pub fn style(&self, level: Level) -> Style {
+        let mut builder = Style::builder();
+        match (self, level) {
+            (Theme::Classic, Level::Critical) => builder.bg_color(Color::Red).bold(),
+            (Theme::Classic, Level::Error) => builder.color(Color::Red).bold(),
+            (Theme::Classic, Level::Warn) => builder.color(Color::Yellow).bold(),
+            (Theme::Classic, Level::Info) => builder.color(Color::Green),
+            (Theme::Classic, Level::Debug) => builder.color(Color::Cyan),
+            (Theme::Classic, Level::Trace) => builder.color(Color::White),
+
+            (Theme::MonochromeDim, Level::Critical) => builder.bold().invert(),
+            (Theme::MonochromeDim, Level::Error | Level::Warn) => builder.bold(),
+            (Theme::MonochromeDim, Level::Info) => &mut builder,
+            (Theme::MonochromeDim, Level::Debug | Level::Trace) => builder.faint(),
+
+            (Theme::Solarized, Level::Critical) => builder.color(Color::White).bg_color(Color::Red),
+            (Theme::Solarized, Level::Error) => builder.color(Color::Red),
+            (Theme::Solarized, Level::Warn) => builder.color(Color::Yellow),
+            (Theme::Solarized, Level::Info) => builder.color(Color::Blue),
+            (Theme::Solarized, Level::Debug) => builder.color(Color::Cyan),
+            (Theme::Solarized, Level::Trace) => builder.faint(),
+
+            (Theme::HighContrast, Level::Critical) => {
+                builder.color(Color::White).bg_color(Color::Red).bold()
+            }
+            (Theme::HighContrast, Level::Error) => builder.color(Color::Red).bold().underline(),
+            (Theme::HighContrast, Level::Warn) => builder.color(Color::Yellow).bold(),
+            (Theme::HighContrast, Level::Info) => builder.color(Color::Green).bold(),
+            (Theme::HighContrast, Level::Debug) => builder.color(Color::Cyan).bold(),
+            (Theme::HighContrast, Level::Trace) => builder.color(Color::White).bold(),
+        }
+        .build()
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Theme::Classic
+    }
+}
+
 #[derive(Clone, Eq, PartialEq, Hash, Debug)]
 pub(crate) struct LevelStyles([Style; Level::count()]);
 
@@ -247,15 +329,21 @@ impl LevelStyles {
     }
 }
 
-impl Default for LevelStyles {
-    fn default() -> LevelStyles {
+impl From<Theme> for LevelStyles {
+    fn from(theme: Theme) -> LevelStyles {
         LevelStyles([
-            Style::builder().bg_color(Color::Red).bold().build(), // Critical
-            Style::builder().color(Color::Red).bold().build(),    // Error
-            Style::builder().color(Color::Yellow).bold().build(), // Warn
-            Style::builder().color(Color::Green).build(),         // Info
-            Style::builder().color(Color::Cyan).build(),          // Debug
-            Style::builder().color(Color::White).build(),         // Trace
+            theme.style(Level::Critical),
+            theme.style(Level::Error),
+            theme.style(Level::Warn),
+            theme.style(Level::Info),
+            theme.style(Level::Debug),
+            theme.style(Level::Trace),
         ])
     }
 }
+
+impl Default for LevelStyles {
+    fn default() -> LevelStyles {
+        Theme::default().into()
+    }
+}
@@ -0,0 +1,428 @@
+//! Provides a sink capturing records for testing logging behavior.
+//!
+//! [`CaptureSink`] records structured copies of all records it receives along
+//! with their formatted strings, and offers assertion helpers to check them.
+//! Payload matchers like [`contains`] can be passed to the helpers.
+//!
+//! # Examples
+//!
+//! ```
+//! use std::sync::Arc;
+//!
+//! use spdlog::{
+//!     prelude::*,
+//!     test_sink::{contains, starts_with, CaptureSink},
+//! };
+//!
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let sink = Arc::new(CaptureSink::new());
+//! let logger = Logger::builder().sink(sink.clone()).build()?;
+//!
+//! // Code under test
+//! warn!(logger: logger, "request timeout after {} ms", 500);
+//!
+//! sink.assert_logged(Level::Warn, contains("timeout"));
+//! sink.assert_not_logged(Level::Error, starts_with("request"));
+//! assert!(sink.contains(Level::Warn, "500 ms"));
+//! # Ok(()) }
+//! ```
+
+use std::fmt;
+
+use crate::{
+    formatter::{Formatter, FormatterContext, FullFormatter},
+    sink::Sink,
+    sync::*,
+    ErrorHandler, Level, LevelFilter, Record, RecordOwned, Result, StringBuf,
+};
+
+/// A sink that captures structured copies of records for testing.
+///
+/// Assertions are made against the level and the payload of records. Records
+/// are also formatted by the formatter of the sink, [`FullFormatter`] by
+/// default, the formatted strings can be checked with
+/// [`CaptureSink::formatted`]. See the [module level documentation](self) for
+/// examples.
+pub struct CaptureSink {
+    level_filter: Atomic<LevelFilter>,
+    formatter: SpinRwLock<Box<dyn Formatter>>,
+    records: Mutex<Vec<(RecordOwned, String)>>,
+    flush_count: AtomicUsize,
+}
+
+impl CaptureSink {
+    /// Constructs a `CaptureSink` capturing records of all levels.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            level_filter: Atomic::new(LevelFilter::All),
+            formatter: SpinRwLock::new(Box::new(FullFormatter::new())),
+            records: Mutex::new(vec![]),
+            flush_count: AtomicUsize::new(0),
+        }
+    }
+
+    /// Gets copies of all captured records.
+    #[must_use]
+    pub fn records(&self) -> Vec<RecordOwned> {
+        self.records
+            .lock_expect()
+            .iter()
+            .map(|(record, _)| record.clone())
+            .collect()
+    }
+
+    /// Gets payloads of all captured records.
+    #[must_use]
+    pub fn payloads(&self) -> Vec<String> {
+        self.records
+            .lock_expect()
+            .iter()
+            .map(|(record, _)| record.payload().to_string())
+            .collect()
+    }
+
+    /// Gets all captured records formatted by the formatter of the sink.
+    #[must_use]
+    pub fn formatted(&self) -> Vec<String> {
+        self.records
+            .lock_expect()
+            .iter()
+            .map(|(_, formatted)| formatted.clone())
+            .collect()
+    }
+
+    /// Gets the number of captured records.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.records.lock_expect().len()
+    }
+
+    /// Returns `true` if no record is captured.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.records.lock_expect().is_empty()
+    }
+
+    /// Gets the number of times this sink has been flushed.
+    #[must_use]
+    pub fn flush_count(&self) -> usize {
+        self.flush_count.load(Ordering::Relaxed)
+    }
+
+    /// Clears all captured records and the flush count.
+    pub fn clear(&self) {
+        self.records.lock_expect().clear();
+        self.flush_count.store(0, Ordering::Relaxed);
+    }
+
+    /// Returns `true` if a record with the given level and a payload containing
+    /// the text has been captured.
+    ///
+    /// It is a shorthand for `count(level, contains(text)) > 0`.
+    #[must_use]
+    pub fn contains(&self, level: Level, text: &str) -> bool {
+        self.count(level, contains(text)) > 0
+    }
+
+    /// Gets the number of captured records with the given level and a payload
+    /// matched by the matcher.
+    #[must_use]
+    pub fn count(&self, level: Level, matcher: impl PayloadMatcher) -> usize {
+        self.count_dyn(level, &matcher)
+    }
+
+    #[must_use]
+    fn count_dyn(&self, level: Level, matcher: &dyn PayloadMatcher) -> usize {
+        self.records
+            .lock_expect()
+            .iter()
+            .filter(|(record, _)| record.level() == level && matcher.matches(record.payload()))
+            .count()
+    }
+
+    /// Asserts that at least one record with the given level and a payload
+    /// matched by the matcher has been captured.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no such record is captured. The panic message lists all
+    /// captured records.
+    #[track_caller]
+    pub fn assert_logged(&self, level: Level, matcher: impl PayloadMatcher) {
+        if self.count_dyn(level, &matcher) == 0 {
+            panic!(
+                "expected a `{}` record with payload {}, but none was captured.\n{}",
+                level,
+                matcher.describe(),
+                self.dump()
+            );
+        }
+    }
+
+    /// Asserts that no record with the given level and a payload matched by
+    /// the matcher has been captured.
+    ///
+    /// # Panics
+    ///
+    /// Panics if such a record is captured. The panic message lists all
+    /// captured records.
+    #[track_caller]
+    pub fn assert_not_logged(&self, level: Level, matcher: impl PayloadMatcher) {
+        if self.count_dyn(level, &matcher) != 0 {
+            panic!(
+                "expected no `{}` record with payload {}, but some were captured.\n{}",
+                level,
+                matcher.describe(),
+                self.dump()
+            );
+        }
+    }
+
+    #[must_use]
+    fn dump(&self) -> String {
+        let records = self.records.lock_expect();
+        if records.is_empty() {
+            return "captured records: (none)".to_string();
+        }
+        records
+            .iter()
+            .fold("captured records:".to_string(), |mut dump, (record, _)| {
+                dump.push_str(&format!("\n  [{}] {:?}", record.level(), record.payload()));
+                dump
+            })
+    }
+}
+
+impl Default for CaptureSink {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Sink for CaptureSink {
+    fn log(&self, record: &Record) -> Result<()> {
+        let mut string_buf = StringBuf::new();
+        let mut ctx = FormatterContext::new();
+        self.formatter
+            .read()
+            .format(record, &mut string_buf, &mut ctx)?;
+        self.records
+            .lock_expect()
+            .push((record.to_owned(), string_buf.to_string()));
+        Ok(())
+    }
+
+    fn flush(&self) -> Result<()> {
+        self.flush_count.fetch_add(1, Ordering::Relaxed);
+        Ok(())
+    }
+
+    fn level_filter(&self) -> LevelFilter {
+        self.level_filter.load(Ordering::Relaxed)
+    }
+
+    fn set_level_filter(&self, level_filter: LevelFilter) {
+        self.level_filter.store(level_filter, Ordering::Relaxed);
+    }
+
+    fn set_formatter(&self, formatter: Box<dyn Formatter>) {
+        *self.formatter.write() = formatter;
+    }
+
+    fn set_error_handler(&self, _handler: Option<ErrorHandler>) {
+        // Errors are only returned from `log`, no error is handled by this
+        // sink.
+    }
+}
+
+/// Represents a matcher for payloads of records.
+///
+/// Matchers are usually constructed by functions in this module, such as
+/// [`contains`]. Closures `Fn(&str) -> bool` are also matchers.
+pub trait PayloadMatcher {
+    /// Determines if the payload is matched.
+    #[must_use]
+    fn matches(&self, payload: &str) -> bool;
+
+    /// Describes the matcher for assertion messages.
+    #[must_use]
+    fn describe(&self) -> String;
+}
+
+impl<F> PayloadMatcher for F
+where
+    F: Fn(&str) -> bool,
+{
+    fn matches(&self, payload: &str) -> bool {
+        self(payload)
+    }
+
+    fn describe(&self) -> String {
+        "matching a custom predicate".to_string()
+    }
+}
+
+/// A payload matcher returned by functions in this module.
+#[derive(Clone)]
+pub struct Matcher {
+    kind: MatcherKind,
+}
+
+#[derive(Clone)]
+enum MatcherKind {
+    Any,
+    Eq(String),
+    Contains(String),
+    StartsWith(String),
+    EndsWith(String),
+    #[cfg(feature = "regex")]
+    Regex(regex::Regex),
+}
+
+impl PayloadMatcher for Matcher {
+    fn matches(&self, payload: &str) -> bool {
+        match &self.kind {
+            MatcherKind::Any => true,
+            MatcherKind::Eq(text) => payload == text,
+            MatcherKind::Contains(text) => payload.contains(text.as_str()),
+            MatcherKind::StartsWith(text) => payload.starts_with(text.as_str()),
+            MatcherKind::EndsWith(text) => payload.ends_with(text.as_str()),
+            #[cfg(feature = "regex")]
+            MatcherKind::Regex(regex) => regex.is_match(payload),
+        }
+    }
+
+    fn describe(&self) -> String {
+        match &self.kind {
+            MatcherKind::Any => "of any content".to_string(),
+            MatcherKind::Eq(text) => format!("equal to {:?}", text),
+            MatcherKind::Contains(text) => format!("containing {:?}", text),
+            MatcherKind::StartsWith(text) => format!("starting with {:?}", text),
+            MatcherKind::EndsWith(text) => format!("ending with {:?}", text),
+            #[cfg(feature = "regex")]
+            MatcherKind::Regex(regex) => format!("matching regex {:?}", regex.as_str()),
+        }
+    }
+}
+
+impl fmt::Debug for Matcher {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.describe())
+    }
+}
+
+/// Matches any payload.
+#[must_use]
+pub fn any() -> Matcher {
+    Matcher {
+        kind: MatcherKind::Any,
+    }
+}
+
+/// Matches payloads equal to the given text.
+#[must_use]
+pub fn eq(text: impl Into<String>) -> Matcher {
+    Matcher {
+        kind: MatcherKind::Eq(text.into()),
+    }
+}
+
+/// Matches payloads containing the given text.
+#[must_use]
+pub fn contains(text: impl Into<String>) -> Matcher {
+    Matcher {
+        kind: MatcherKind::Contains(text.into()),
+    }
+}
+
+/// Matches payloads starting with the given text.
+#[must_use]
+pub fn starts_with(text: impl Into<String>) -> Matcher {
+    Matcher {
+        kind: MatcherKind::StartsWith(text.into()),
+    }
+}
+
+/// Matches payloads ending with the given text.
+#[must_use]
+pub fn ends_with(text: impl Into<String>) -> Matcher {
+    Matcher {
+        kind: MatcherKind::EndsWith(text.into()),
+    }
+}
+
+/// Matches payloads in which the given regular expression matches.
+///
+/// This function is only available if crate feature `regex` is enabled.
+///
+/// # Panics
+///
+/// Panics if the regular expression is invalid.
+#[cfg(feature = "regex")]
+#[must_use]
+#[track_caller]
+pub fn matches_regex(regex: &str) -> Matcher {
+    Matcher {
+        kind: MatcherKind::Regex(regex::Regex::new(regex).expect("invalid regex")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::panic::{self, AssertUnwindSafe};
+
+    use super::*;
+    use crate::{prelude::*, test_utils::*};
+
+    #[test]
+    fn capture_and_assert() {
+        let sink = Arc::new(CaptureSink::new());
+        let logger = build_test_logger(|b| b.sink(sink.clone()).level_filter(LevelFilter::All));
+
+        warn!(logger: logger, "request timeout after {} ms", 500);
+        info!(logger: logger, "request ok");
+        logger.flush();
+
+        assert_eq!(sink.len(), 2);
+        assert_eq!(sink.flush_count(), 1);
+        assert_eq!(
+            sink.payloads(),
+            vec!["request timeout after 500 ms", "request ok"]
+        );
+
+        sink.assert_logged(Level::Warn, contains("timeout"));
+        sink.assert_logged(Level::Info, eq("request ok"));
+        sink.assert_logged(Level::Info, |payload: &str| payload.len() == 10);
+        sink.assert_not_logged(Level::Info, contains("timeout"));
+        sink.assert_not_logged(Level::Error, any());
+        assert_eq!(sink.count(Level::Warn, starts_with("request")), 1);
+        assert_eq!(sink.count(Level::Info, ends_with("ok")), 1);
+
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            sink.assert_logged(Level::Error, contains("timeout"));
+        }));
+        let panic_message = result.unwrap_err().downcast::<String>().unwrap();
+        assert_eq!(
+            *panic_message,
+            "expected a `error` record with payload containing \"timeout\", but none was captured.
+captured records:
+  [warn] \"request timeout after 500 ms\"
+  [info] \"request ok\""
+        );
+
+        assert!(sink.contains(Level::Warn, "500 ms"));
+        assert!(!sink.contains(Level::Info, "500 ms"));
+
+        sink.set_formatter(Box::new(NoModFormatter::new()));
+        error!(logger: logger, "formatted");
+        assert_eq!(sink.formatted()[2], "formatted");
+        let formatted = &sink.formatted()[0];
+        assert!(formatted.contains("[warn]"));
+        assert!(formatted.contains("request timeout after 500 ms"));
+
+        sink.clear();
+        assert!(sink.is_empty());
+        assert_eq!(sink.flush_count(), 0);
+    }
+}
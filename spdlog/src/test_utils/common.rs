@@ -8,6 +8,7 @@
 
 use std::{
     fmt::Write,
+    io,
     marker::PhantomData,
     sync::{atomic::*, Arc, Mutex},
     thread::sleep,
@@ -28,6 +29,9 @@ pub struct TestSink {
     level_filter: Atomic<LevelFilter>,
     log_counter: AtomicUsize,
     flush_counter: AtomicUsize,
+    open_counter: AtomicUsize,
+    close_counter: AtomicUsize,
+    fail_open: AtomicBool,
     records: Mutex<Vec<RecordOwned>>,
     delay_duration: Option<Duration>,
 }
@@ -44,6 +48,9 @@ impl TestSink {
             level_filter: Atomic::new(LevelFilter::All),
             log_counter: AtomicUsize::new(0),
             flush_counter: AtomicUsize::new(0),
+            open_counter: AtomicUsize::new(0),
+            close_counter: AtomicUsize::new(0),
+            fail_open: AtomicBool::new(false),
             records: Mutex::new(vec![]),
             delay_duration: duration,
         }
@@ -59,6 +66,16 @@ impl TestSink {
         self.flush_counter.load(Ordering::Relaxed)
     }
 
+    #[must_use]
+    pub fn open_count(&self) -> usize {
+        self.open_counter.load(Ordering::Relaxed)
+    }
+
+    #[must_use]
+    pub fn close_count(&self) -> usize {
+        self.close_counter.load(Ordering::Relaxed)
+    }
+
     #[must_use]
     pub fn records(&self) -> Vec<RecordOwned> {
         self.records.lock().unwrap().clone()
@@ -74,9 +91,15 @@ impl TestSink {
             .collect()
     }
 
+    pub fn set_fail_open(&self, fail: bool) {
+        self.fail_open.store(fail, Ordering::Relaxed);
+    }
+
     pub fn reset(&self) {
         self.log_counter.store(0, Ordering::Relaxed);
         self.flush_counter.store(0, Ordering::Relaxed);
+        self.open_counter.store(0, Ordering::Relaxed);
+        self.close_counter.store(0, Ordering::Relaxed);
         self.records.lock().unwrap().clear();
     }
 }
@@ -102,6 +125,22 @@ impl Sink for TestSink {
         Ok(())
     }
 
+    fn open(&self) -> Result<()> {
+        self.open_counter.fetch_add(1, Ordering::Relaxed);
+        if self.fail_open.load(Ordering::Relaxed) {
+            return Err(Error::Connect(io::Error::new(
+                io::ErrorKind::Other,
+                "test sink fails to open",
+            )));
+        }
+        Ok(())
+    }
+
+    fn close(&self) -> Result<()> {
+        self.close_counter.fetch_add(1, Ordering::Relaxed);
+        Ok(())
+    }
+
     fn level_filter(&self) -> LevelFilter {
         self.level_filter.load(Ordering::Relaxed)
     }
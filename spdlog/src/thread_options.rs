@@ -0,0 +1,274 @@
+use std::{
+    io,
+    thread::{self, JoinHandle},
+};
+
+use crate::{diagnostics, sync::*, Level};
+
+/// Options of threads spawned by `spdlog-rs` internally.
+///
+/// `spdlog-rs` spawns worker threads for [periodic flushing], [idle flushing],
+/// cleaning up [rotated files], and processing records of asynchronous sinks
+/// such as [`AsyncPoolSink`]. By default, they are named after what they do,
+/// e.g. `spdlog-flush` and `spdlog-pool`, and use the default stack size and
+/// scheduling of the system.
+///
+/// Options are set for all worker threads with [`set_worker_thread_options`],
+/// or for the threads of a thread pool with
+/// [`ThreadPoolBuilder::thread_options`]. They apply to threads spawned
+/// afterwards.
+///
+/// # Examples
+///
+/// ```
+/// use spdlog::ThreadOptions;
+///
+/// spdlog::set_worker_thread_options(
+///     ThreadOptions::new()
+///         .name("app-logging")
+///         .stack_size(256 * 1024)
+///         .priority(10)
+///         .affinity([0]),
+/// );
+/// ```
+///
+/// [periodic flushing]: crate::Logger::set_flush_period
+/// [idle flushing]: crate::Logger::set_idle_flush
+/// [rotated files]: crate::sink::RotatingFileSinkBuilder::cleanup_interval
+/// [`AsyncPoolSink`]: crate::sink::AsyncPoolSink
+/// [`ThreadPoolBuilder::thread_options`]: crate::ThreadPoolBuilder::thread_options
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct ThreadOptions {
+    name: Option<String>,
+    stack_size: Option<usize>,
+    priority: Option<i32>,
+    affinity: Option<Vec<usize>>,
+}
+
+impl ThreadOptions {
+    /// Constructs a `ThreadOptions` with the default options of each thread.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Specifies the name of threads, overriding the default names.
+    ///
+    /// Some platforms limit the length of thread names, e.g. Linux truncates
+    /// them to 15 bytes.
+    #[must_use]
+    pub fn name<S>(mut self, name: S) -> Self
+    where
+        S: Into<String>,
+    {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Specifies the stack size of threads in bytes.
+    ///
+    /// See [`std::thread::Builder::stack_size`] for the details.
+    #[must_use]
+    pub fn stack_size(mut self, size: usize) -> Self {
+        self.stack_size = Some(size);
+        self
+    }
+
+    /// Specifies the scheduling priority of threads.
+    ///
+    /// - On Linux, it is the nice value of the thread, in the range from `-20`
+    ///   (the highest priority) to `19` (the lowest priority). Raising the
+    ///   priority usually requires privileges.
+    ///
+    /// - On Windows, it is the value passed to `SetThreadPriority`, e.g. `-2`
+    ///   (`THREAD_PRIORITY_LOWEST`) to `2` (`THREAD_PRIORITY_HIGHEST`).
+    ///
+    /// It is ignored on other platforms. Failures in setting the priority are
+    /// reported to the [diagnostics] logger, and the thread runs anyway.
+    ///
+    /// [diagnostics]: crate::diagnostics
+    #[must_use]
+    pub fn priority(mut self, priority: i32) -> Self {
+        self.priority = Some(priority);
+        self
+    }
+
+    /// Specifies the CPUs that threads are allowed to run on, by their
+    /// indexes starting from `0`.
+    ///
+    /// It is supported on Linux and Windows (the first 64 CPUs), and ignored
+    /// on other platforms. Failures in setting the affinity are reported to
+    /// the [diagnostics] logger, and the thread runs anyway.
+    ///
+    /// [diagnostics]: crate::diagnostics
+    #[must_use]
+    pub fn affinity<I>(mut self, cpus: I) -> Self
+    where
+        I: IntoIterator<Item = usize>,
+    {
+        self.affinity = Some(cpus.into_iter().collect());
+        self
+    }
+
+    // Spawns a thread with the options, panics if the thread fails to spawn
+    // like `std::thread::spawn`.
+    pub(crate) fn spawn<F, T>(&self, default_name: &str, f: F) -> JoinHandle<T>
+    where
+        F: FnOnce() -> T + Send + 'static,
+        T: Send + 'static,
+    {
+        let mut builder = thread::Builder::new()
+            .name(self.name.clone().unwrap_or_else(|| default_name.to_owned()));
+        if let Some(stack_size) = self.stack_size {
+            builder = builder.stack_size(stack_size);
+        }
+
+        let (priority, affinity) = (self.priority, self.affinity.clone());
+        builder
+            .spawn(move || {
+                if let Some(priority) = priority {
+                    if let Err(err) = platform::set_priority(priority) {
+                        report_error("priority", err);
+                    }
+                }
+                if let Some(affinity) = affinity {
+                    if let Err(err) = platform::set_affinity(&affinity) {
+                        report_error("affinity", err);
+                    }
+                }
+                f()
+            })
+            .expect("failed to spawn thread")
+    }
+}
+
+static WORKER_THREAD_OPTIONS: Lazy<SpinRwLock<ThreadOptions>> =
+    Lazy::new(|| SpinRwLock::new(ThreadOptions::new()));
+
+/// Sets the options of worker threads spawned by `spdlog-rs` internally from
+/// now on.
+///
+/// See [`ThreadOptions`] for the details.
+pub fn set_worker_thread_options(options: ThreadOptions) {
+    *WORKER_THREAD_OPTIONS.write() = options;
+}
+
+#[must_use]
+pub(crate) fn worker_thread_options() -> ThreadOptions {
+    WORKER_THREAD_OPTIONS.read().clone()
+}
+
+// Spawns a worker thread with the options set by `set_worker_thread_options`.
+pub(crate) fn spawn_worker<F, T>(default_name: &str, f: F) -> JoinHandle<T>
+where
+    F: FnOnce() -> T + Send + 'static,
+    T: Send + 'static,
+{
+    worker_thread_options().spawn(default_name, f)
+}
+
+fn report_error(option: &str, err: io::Error) {
+    diagnostics::report(
+        Level::Warn,
+        "ThreadOptions",
+        format_args!("failed to set thread {}: {}", option, err),
+    );
+}
+
+#[cfg(target_os = "linux")]
+mod platform {
+    use std::{io, mem};
+
+    pub(super) fn set_priority(priority: i32) -> io::Result<()> {
+        // On Linux, the nice value is a per-thread attribute.
+        let tid = unsafe { libc::syscall(libc::SYS_gettid) } as libc::id_t;
+        if unsafe { libc::setpriority(libc::PRIO_PROCESS, tid, priority) } != 0 {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(())
+    }
+
+    pub(super) fn set_affinity(cpus: &[usize]) -> io::Result<()> {
+        unsafe {
+            let mut set: libc::cpu_set_t = mem::zeroed();
+            libc::CPU_ZERO(&mut set);
+            for &cpu in cpus {
+                if cpu < libc::CPU_SETSIZE as usize {
+                    libc::CPU_SET(cpu, &mut set);
+                }
+            }
+            if libc::sched_setaffinity(0, mem::size_of::<libc::cpu_set_t>(), &set) != 0 {
+                return Err(io::Error::last_os_error());
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(windows)]
+mod platform {
+    use std::io;
+
+    use winapi::{
+        shared::basetsd::DWORD_PTR,
+        um::{
+            processthreadsapi::{GetCurrentThread, SetThreadPriority},
+            winbase::SetThreadAffinityMask,
+        },
+    };
+
+    pub(super) fn set_priority(priority: i32) -> io::Result<()> {
+        if unsafe { SetThreadPriority(GetCurrentThread(), priority) } == 0 {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(())
+    }
+
+    pub(super) fn set_affinity(cpus: &[usize]) -> io::Result<()> {
+        let mask = cpus
+            .iter()
+            .filter(|&&cpu| cpu < DWORD_PTR::BITS as usize)
+            .fold(0 as DWORD_PTR, |mask, &cpu| mask | (1 << cpu));
+        if unsafe { SetThreadAffinityMask(GetCurrentThread(), mask) } == 0 {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(())
+    }
+}
+
+#[cfg(not(any(target_os = "linux", windows)))]
+mod platform {
+    use std::io;
+
+    pub(super) fn set_priority(_priority: i32) -> io::Result<()> {
+        Ok(())
+    }
+
+    pub(super) fn set_affinity(_cpus: &[usize]) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn spawn() {
+        let options = ThreadOptions::new()
+            .name("spdlog-test")
+            .stack_size(128 * 1024)
+            .affinity([0]);
+        let name = options
+            .spawn("default", || thread::current().name().map(str::to_owned))
+            .join()
+            .unwrap();
+        assert_eq!(name.as_deref(), Some("spdlog-test"));
+
+        let name = ThreadOptions::new()
+            .spawn("default", || thread::current().name().map(str::to_owned))
+            .join()
+            .unwrap();
+        assert_eq!(name.as_deref(), Some("default"));
+    }
+}
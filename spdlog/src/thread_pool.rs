@@ -1,4 +1,4 @@
-use std::thread::{self, JoinHandle};
+use std::thread::JoinHandle;
 
 use crossbeam::channel::{self as mpmc, Receiver, Sender};
 use once_cell::sync::Lazy;
@@ -7,7 +7,7 @@ use crate::{
     error::{Error, InvalidArgumentError},
     sink::{OverflowPolicy, Task},
     sync::*,
-    Result,
+    thread_options, Result, ThreadOptions,
 };
 
 /// A thread pool for processing operations asynchronously.
@@ -46,6 +46,7 @@ pub struct ThreadPoolBuilder {
     threads: usize,
     on_thread_spawn: Option<Callback>,
     on_thread_finish: Option<Callback>,
+    thread_options: Option<ThreadOptions>,
 }
 
 struct Worker {
@@ -60,10 +61,13 @@ impl ThreadPool {
     /// | [capacity]         | `8192` (may change in the future) |
     /// | [on_thread_spawn]  | `None`                            |
     /// | [on_thread_finish] | `None`                            |
+    /// | [thread_options]   | [worker thread options]           |
     ///
     /// [capacity]: ThreadPoolBuilder::capacity
     /// [on_thread_spawn]: ThreadPoolBuilder::on_thread_spawn
     /// [on_thread_finish]: ThreadPoolBuilder::on_thread_finish
+    /// [thread_options]: ThreadPoolBuilder::thread_options
+    /// [worker thread options]: crate::set_worker_thread_options
     #[must_use]
     pub fn builder() -> ThreadPoolBuilder {
         ThreadPoolBuilder {
@@ -71,6 +75,7 @@ impl ThreadPool {
             threads: 1,
             on_thread_spawn: None,
             on_thread_finish: None,
+            thread_options: None,
         }
     }
 
@@ -82,16 +87,51 @@ impl ThreadPool {
 
     pub(super) fn assign_task(&self, task: Task, overflow_policy: OverflowPolicy) -> Result<()> {
         let inner = self.0.load();
-        let sender = inner.as_ref().unwrap().sender.as_ref().unwrap();
+        let inner = inner.as_ref().unwrap();
+        let sender = inner.sender.as_ref().unwrap();
 
         match overflow_policy {
-            OverflowPolicy::Block => sender.send(task).map_err(Error::from_crossbeam_send),
+            // The channel is shared by all sinks on the pool, tasks in it must not be
+            // dropped for another sink. Records of `DropOldest` sinks are queued by the
+            // sinks themselves (see `AsyncPoolSink`), and their other tasks are never
+            // dropped.
+            OverflowPolicy::Block | OverflowPolicy::DropOldest => {
+                sender.send(task).map_err(Error::from_crossbeam_send)
+            }
             OverflowPolicy::DropIncoming => sender
                 .try_send(task)
                 .map_err(Error::from_crossbeam_try_send),
         }
     }
 
+    // Assigns a task whose failure is handled by the caller, returns `false` if
+    // the channel is full and `block` is `false`, or if it is disconnected.
+    #[must_use]
+    pub(super) fn try_assign_task(&self, task: Task, block: bool) -> bool {
+        let inner = self.0.load();
+        let sender = inner.as_ref().unwrap().sender.as_ref().unwrap();
+
+        if block {
+            sender.send(task).is_ok()
+        } else {
+            sender.try_send(task).is_ok()
+        }
+    }
+
+    #[must_use]
+    pub(super) fn capacity(&self) -> usize {
+        let inner = self.0.load();
+        let sender = inner.as_ref().unwrap().sender.as_ref().unwrap();
+        sender.capacity().unwrap()
+    }
+
+    #[cfg(feature = "log-async")]
+    #[must_use]
+    pub(super) fn is_full(&self) -> bool {
+        let inner = self.0.load();
+        inner.as_ref().unwrap().sender.as_ref().unwrap().is_full()
+    }
+
     pub(super) fn destroy(&self) {
         if let Some(mut inner) = self.0.swap(None) {
             // Or use `Arc::into_inner`, but it requires us to bump MSRV.
@@ -168,6 +208,20 @@ impl ThreadPoolBuilder {
         self
     }
 
+    /// Specifies the options of the threads of the thread pool, e.g. the name
+    /// and the priority.
+    ///
+    /// If it is not specified, the options set by
+    /// [`set_worker_thread_options`] are used, and the threads are named
+    /// `spdlog-pool` by default.
+    ///
+    /// [`set_worker_thread_options`]: crate::set_worker_thread_options
+    #[must_use]
+    pub fn thread_options(&mut self, options: ThreadOptions) -> &mut Self {
+        self.thread_options = Some(options);
+        self
+    }
+
     /// Builds a [`ThreadPool`].
     pub fn build(&self) -> Result<ThreadPool> {
         if self.capacity < 1 {
@@ -183,6 +237,10 @@ impl ThreadPoolBuilder {
         }
 
         let (sender, receiver) = mpmc::bounded(self.capacity);
+        let thread_options = self
+            .thread_options
+            .clone()
+            .unwrap_or_else(thread_options::worker_thread_options);
 
         let mut threads = Vec::new();
         threads.resize_with(self.threads, || {
@@ -190,7 +248,7 @@ impl ThreadPoolBuilder {
             let on_thread_spawn = self.on_thread_spawn.clone();
             let on_thread_finish = self.on_thread_finish.clone();
 
-            Some(thread::spawn(move || {
+            Some(thread_options.spawn("spdlog-pool", move || {
                 if let Some(f) = on_thread_spawn {
                     f();
                 }
@@ -0,0 +1,329 @@
+// Calendar calculations shared by formatters and time-based rotation.
+//
+// The date library used to look up the local UTC offset is selected by crate
+// features: `chrono` (the default) takes precedence over `time`, and without
+// either of them, the offset is looked up via the C library or the Windows API.
+
+use std::time::{Duration, SystemTime};
+
+const SECS_PER_DAY: i64 = 86_400;
+
+/// Gets the offset of the local time zone from UTC at the given time, in
+/// seconds.
+#[must_use]
+pub(crate) fn local_offset(time: SystemTime) -> i32 {
+    cfg_if::cfg_if! {
+        if #[cfg(feature = "chrono")] {
+            use chrono::{Local, Offset, TimeZone, Utc};
+
+            let utc = chrono::DateTime::<Utc>::from(time).naive_utc();
+            Local.offset_from_utc_datetime(&utc).fix().local_minus_utc()
+        } else if #[cfg(feature = "time")] {
+            // The `time` crate refuses to read the local offset in a process with
+            // multiple threads on some platforms, fall back to UTC in that case.
+            time::UtcOffset::local_offset_at(time.into())
+                .map(|offset| offset.whole_seconds())
+                .unwrap_or(0)
+        } else {
+            os_local_offset(time)
+        }
+    }
+}
+
+#[cfg(not(any(feature = "chrono", feature = "time")))]
+#[must_use]
+fn os_local_offset(time: SystemTime) -> i32 {
+    cfg_if::cfg_if! {
+        if #[cfg(any(
+            target_os = "linux",
+            target_os = "android",
+            target_os = "macos",
+            target_os = "ios",
+            target_os = "freebsd",
+            target_os = "netbsd",
+            target_os = "openbsd",
+            target_os = "dragonfly"
+        ))] {
+            let timestamp = unix_timestamp(time) as libc::time_t;
+            let mut tm: libc::tm = unsafe { std::mem::zeroed() };
+            if unsafe { libc::localtime_r(&timestamp, &mut tm) }.is_null() {
+                return 0;
+            }
+            tm.tm_gmtoff as i32
+        } else if #[cfg(windows)] {
+            use winapi::{
+                shared::minwindef::FILETIME,
+                um::{minwinbase::SYSTEMTIME, timezoneapi},
+            };
+
+            // `FILETIME` counts 100-nanosecond intervals since 1601-01-01.
+            const TICKS_PER_SEC: i64 = 10_000_000;
+            const SECS_FROM_1601_TO_1970: i64 = 11_644_473_600;
+
+            let ticks = (unix_timestamp(time) + SECS_FROM_1601_TO_1970) * TICKS_PER_SEC;
+            if ticks < 0 {
+                return 0;
+            }
+            let utc_file_time = FILETIME {
+                dwLowDateTime: ticks as u32,
+                dwHighDateTime: (ticks >> 32) as u32,
+            };
+            let mut utc: SYSTEMTIME = unsafe { std::mem::zeroed() };
+            let mut local: SYSTEMTIME = unsafe { std::mem::zeroed() };
+            let mut local_file_time: FILETIME = unsafe { std::mem::zeroed() };
+            let succeeded = unsafe {
+                timezoneapi::FileTimeToSystemTime(&utc_file_time, &mut utc) != 0
+                    && timezoneapi::SystemTimeToTzSpecificLocalTime(
+                        std::ptr::null(),
+                        &utc,
+                        &mut local,
+                    ) != 0
+                    && timezoneapi::SystemTimeToFileTime(&local, &mut local_file_time) != 0
+            };
+            if !succeeded {
+                return 0;
+            }
+            let local_ticks = ((local_file_time.dwHighDateTime as i64) << 32)
+                | local_file_time.dwLowDateTime as i64;
+            ((local_ticks - ticks) / TICKS_PER_SEC) as i32
+        } else {
+            let _ = time;
+            0
+        }
+    }
+}
+
+/// Gets the number of seconds elapsed since the Unix epoch, negative for times
+/// before it.
+#[must_use]
+pub(crate) fn unix_timestamp(time: SystemTime) -> i64 {
+    match time.duration_since(SystemTime::UNIX_EPOCH) {
+        Ok(since) => since.as_secs() as i64,
+        Err(err) => {
+            let before = err.duration();
+            -(before.as_secs() as i64) - (before.subsec_nanos() > 0) as i64
+        }
+    }
+}
+
+#[must_use]
+pub(crate) fn from_unix_timestamp(timestamp: i64) -> SystemTime {
+    if timestamp >= 0 {
+        SystemTime::UNIX_EPOCH + Duration::from_secs(timestamp as u64)
+    } else {
+        SystemTime::UNIX_EPOCH - Duration::from_secs(timestamp.unsigned_abs())
+    }
+}
+
+/// A date and time with a fixed offset from UTC, accurate to the second.
+#[derive(Clone, Copy, Eq, PartialEq, Hash, Debug)]
+pub(crate) struct DateTime {
+    timestamp: i64,
+    offset: i32,
+    year: i32,
+    month: u32,
+    day: u32,
+    hour: u32,
+    minute: u32,
+    second: u32,
+    weekday_from_monday: u32,
+}
+
+impl DateTime {
+    #[must_use]
+    pub(crate) fn new(time: SystemTime, offset: i32) -> Self {
+        let timestamp = unix_timestamp(time);
+        let local_secs = timestamp + offset as i64;
+        let days = local_secs.div_euclid(SECS_PER_DAY);
+        let secs_of_day = local_secs.rem_euclid(SECS_PER_DAY) as u32;
+        let (year, month, day) = civil_from_days(days);
+
+        Self {
+            timestamp,
+            offset,
+            year,
+            month,
+            day,
+            hour: secs_of_day / 3600,
+            minute: secs_of_day % 3600 / 60,
+            second: secs_of_day % 60,
+            // 1970-01-01 is a Thursday
+            weekday_from_monday: (days + 3).rem_euclid(7) as u32,
+        }
+    }
+
+    #[must_use]
+    pub(crate) fn timestamp(&self) -> i64 {
+        self.timestamp
+    }
+
+    /// Gets the offset from UTC in seconds.
+    #[must_use]
+    pub(crate) fn offset(&self) -> i32 {
+        self.offset
+    }
+
+    #[must_use]
+    pub(crate) fn year(&self) -> i32 {
+        self.year
+    }
+
+    #[must_use]
+    pub(crate) fn month(&self) -> u32 {
+        self.month
+    }
+
+    #[must_use]
+    pub(crate) fn day(&self) -> u32 {
+        self.day
+    }
+
+    #[must_use]
+    pub(crate) fn hour(&self) -> u32 {
+        self.hour
+    }
+
+    /// Gets the hour on a 12-hour clock, along with whether it is PM.
+    #[must_use]
+    pub(crate) fn hour12(&self) -> (bool, u32) {
+        let hour12 = match self.hour % 12 {
+            0 => 12,
+            hour => hour,
+        };
+        (self.hour >= 12, hour12)
+    }
+
+    #[must_use]
+    pub(crate) fn minute(&self) -> u32 {
+        self.minute
+    }
+
+    #[must_use]
+    pub(crate) fn second(&self) -> u32 {
+        self.second
+    }
+
+    #[must_use]
+    pub(crate) fn weekday_from_monday(&self) -> u32 {
+        self.weekday_from_monday
+    }
+
+    /// Gets the number of seconds elapsed since the Unix epoch, if the time of
+    /// day of this date were replaced and the offset were ignored.
+    #[must_use]
+    pub(crate) fn local_timestamp_at(&self, hour: u32, minute: u32, second: u32) -> i64 {
+        local_timestamp(self.year, self.month, self.day, hour, minute, second)
+    }
+}
+
+/// Gets the number of seconds elapsed since the Unix epoch for the given date
+/// and time, as if they were in UTC.
+#[must_use]
+pub(crate) fn local_timestamp(
+    year: i32,
+    month: u32,
+    day: u32,
+    hour: u32,
+    minute: u32,
+    second: u32,
+) -> i64 {
+    days_from_civil(year, month, day) * SECS_PER_DAY + (hour * 3600 + minute * 60 + second) as i64
+}
+
+// Credits: http://howardhinnant.github.io/date_algorithms.html
+#[must_use]
+fn civil_from_days(days: i64) -> (i32, u32, u32) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_from_march = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_from_march + 2) / 5 + 1;
+    let month = if month_from_march < 10 {
+        month_from_march + 3
+    } else {
+        month_from_march - 9
+    };
+    let year = year_of_era + era * 400 + (month <= 2) as i64;
+
+    (year as i32, month as u32, day as u32)
+}
+
+#[must_use]
+fn days_from_civil(year: i32, month: u32, day: u32) -> i64 {
+    let year = year as i64 - (month <= 2) as i64;
+    let era = year.div_euclid(400);
+    let year_of_era = year.rem_euclid(400);
+    let month_from_march = (month as i64 + 9) % 12;
+    let day_of_year = (153 * month_from_march + 2) / 5 + day as i64 - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+
+    era * 146_097 + day_of_era - 719_468
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn date_time() {
+        let date_time = DateTime::new(from_unix_timestamp(1_709_182_800), 8 * 3600);
+
+        // 2024-02-29 13:00:00 +08:00, Thursday
+        assert_eq!(date_time.timestamp(), 1_709_182_800);
+        assert_eq!(date_time.offset(), 8 * 3600);
+        assert_eq!(
+            (date_time.year(), date_time.month(), date_time.day()),
+            (2024, 2, 29)
+        );
+        assert_eq!(
+            (date_time.hour(), date_time.minute(), date_time.second()),
+            (13, 0, 0)
+        );
+        assert_eq!(date_time.hour12(), (true, 1));
+        assert_eq!(date_time.weekday_from_monday(), 3);
+        assert_eq!(date_time.local_timestamp_at(0, 0, 0), 1_709_164_800);
+
+        let before_epoch = DateTime::new(from_unix_timestamp(-1), 0);
+        assert_eq!(
+            (
+                before_epoch.year(),
+                before_epoch.month(),
+                before_epoch.day()
+            ),
+            (1969, 12, 31)
+        );
+        assert_eq!(before_epoch.second(), 59);
+        assert_eq!(before_epoch.weekday_from_monday(), 2);
+        assert_eq!(before_epoch.hour12(), (true, 11));
+    }
+
+    #[test]
+    fn civil_days_round_trip() {
+        for days in (-800_000..800_000).step_by(97) {
+            let (year, month, day) = civil_from_days(days);
+            assert_eq!(days_from_civil(year, month, day), days);
+        }
+        assert_eq!(civil_from_days(0), (1970, 1, 1));
+        assert_eq!(civil_from_days(11_016), (2000, 2, 29));
+    }
+
+    // The `time` crate may fall back to UTC in multi-threaded processes.
+    #[cfg(any(feature = "chrono", not(feature = "time")))]
+    #[test]
+    fn local_offset_matches_chrono() {
+        use chrono::{Local, Offset, TimeZone};
+
+        for timestamp in [0, 1_709_182_800, 1_719_792_000] {
+            let expected = Local
+                .timestamp_opt(timestamp, 0)
+                .unwrap()
+                .offset()
+                .fix()
+                .local_minus_utc();
+            assert_eq!(local_offset(from_unix_timestamp(timestamp)), expected);
+        }
+    }
+}
@@ -0,0 +1,267 @@
+use std::{
+    cell::Cell,
+    time::{Duration, Instant},
+};
+
+use crate::sync::*;
+
+/// Latency statistics of an operation, accumulated since the logger was
+/// built.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash)]
+pub struct LatencyStats {
+    count: u64,
+    total: Duration,
+    min: Duration,
+    max: Duration,
+}
+
+impl LatencyStats {
+    /// Gets the number of measured operations.
+    #[must_use]
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// Gets the total time of measured operations.
+    #[must_use]
+    pub fn total(&self) -> Duration {
+        self.total
+    }
+
+    /// Gets the shortest time of an operation, or zero if none is measured.
+    #[must_use]
+    pub fn min(&self) -> Duration {
+        self.min
+    }
+
+    /// Gets the longest time of an operation, or zero if none is measured.
+    #[must_use]
+    pub fn max(&self) -> Duration {
+        self.max
+    }
+
+    /// Gets the mean time of an operation, or zero if none is measured.
+    #[must_use]
+    pub fn mean(&self) -> Duration {
+        match self.count {
+            0 => Duration::ZERO,
+            count => Duration::from_nanos(nanos(self.total) / count),
+        }
+    }
+}
+
+/// Timing statistics of a sink of a logger.
+///
+/// Formatting is measured for the built-in sinks only, the whole time spent in
+/// [`Sink::log`] of other sinks is counted as writing.
+///
+/// [`Sink::log`]: crate::sink::Sink::log
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash)]
+pub struct SinkStats {
+    format: LatencyStats,
+    write: LatencyStats,
+    flush: LatencyStats,
+}
+
+impl SinkStats {
+    /// Gets the statistics of formatting records.
+    #[must_use]
+    pub fn format(&self) -> LatencyStats {
+        self.format
+    }
+
+    /// Gets the statistics of writing records, i.e. the time spent in
+    /// [`Sink::log`] except formatting.
+    ///
+    /// For asynchronous sinks, this is the time of sending records to the
+    /// worker threads.
+    ///
+    /// [`Sink::log`]: crate::sink::Sink::log
+    #[must_use]
+    pub fn write(&self) -> LatencyStats {
+        self.write
+    }
+
+    /// Gets the statistics of flushing, or synchronizing in [audit mode].
+    ///
+    /// [audit mode]: crate::LoggerBuilder::audit
+    #[must_use]
+    pub fn flush(&self) -> LatencyStats {
+        self.flush
+    }
+}
+
+/// Timing statistics of a logger.
+///
+/// See [`LoggerBuilder::timing`] for details.
+///
+/// [`LoggerBuilder::timing`]: crate::LoggerBuilder::timing
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct LoggerStats {
+    sinks: Vec<SinkStats>,
+}
+
+impl LoggerStats {
+    /// Gets the statistics of sinks, in the same order as [`Logger::sinks`].
+    ///
+    /// [`Logger::sinks`]: crate::Logger::sinks
+    #[must_use]
+    pub fn sinks(&self) -> &[SinkStats] {
+        &self.sinks
+    }
+}
+
+// Measures the pipeline of a logger, with an entry for each sink.
+pub(crate) struct Timings {
+    sinks: Vec<SinkTimings>,
+}
+
+#[derive(Default)]
+struct SinkTimings {
+    format: LatencyRecorder,
+    write: LatencyRecorder,
+    flush: LatencyRecorder,
+}
+
+struct LatencyRecorder {
+    count: AtomicU64,
+    total_nanos: AtomicU64,
+    min_nanos: AtomicU64,
+    max_nanos: AtomicU64,
+}
+
+thread_local! {
+    // `Some` with the time spent in formatting so far while a sink is measured.
+    static FORMAT_TIME: Cell<Option<Duration>> = const { Cell::new(None) };
+}
+
+impl Timings {
+    #[must_use]
+    pub(crate) fn new(sinks: usize) -> Self {
+        Self {
+            sinks: (0..sinks).map(|_| SinkTimings::default()).collect(),
+        }
+    }
+
+    // Calls `Sink::log` of the sink at the index, measuring formatting and
+    // writing separately.
+    pub(crate) fn log<R>(&self, index: usize, log: impl FnOnce() -> R) -> R {
+        let outer = FORMAT_TIME.with(|time| time.replace(Some(Duration::ZERO)));
+        let start = Instant::now();
+        let result = log();
+        let elapsed = start.elapsed();
+        let format = FORMAT_TIME.with(|time| time.replace(outer));
+
+        let timings = &self.sinks[index];
+        match format {
+            Some(format) if !format.is_zero() => {
+                timings.format.record(format);
+                timings.write.record(elapsed.saturating_sub(format));
+            }
+            _ => timings.write.record(elapsed),
+        }
+        result
+    }
+
+    pub(crate) fn flush<R>(&self, index: usize, flush: impl FnOnce() -> R) -> R {
+        let start = Instant::now();
+        let result = flush();
+        self.sinks[index].flush.record(start.elapsed());
+        result
+    }
+
+    #[must_use]
+    pub(crate) fn stats(&self) -> LoggerStats {
+        LoggerStats {
+            sinks: self
+                .sinks
+                .iter()
+                .map(|timings| SinkStats {
+                    format: timings.format.stats(),
+                    write: timings.write.stats(),
+                    flush: timings.flush.stats(),
+                })
+                .collect(),
+        }
+    }
+}
+
+// Formats a record, adding the time to the sink being measured if any.
+pub(crate) fn measure_format<R>(format: impl FnOnce() -> R) -> R {
+    if FORMAT_TIME.with(|time| time.get().is_none()) {
+        return format();
+    }
+    let start = Instant::now();
+    let result = format();
+    let elapsed = start.elapsed();
+    FORMAT_TIME.with(|time| time.set(time.get().map(|total| total + elapsed)));
+    result
+}
+
+impl LatencyRecorder {
+    fn record(&self, elapsed: Duration) {
+        let elapsed = nanos(elapsed);
+        self.count.fetch_add(1, Ordering::Relaxed);
+        self.total_nanos.fetch_add(elapsed, Ordering::Relaxed);
+        self.min_nanos.fetch_min(elapsed, Ordering::Relaxed);
+        self.max_nanos.fetch_max(elapsed, Ordering::Relaxed);
+    }
+
+    #[must_use]
+    fn stats(&self) -> LatencyStats {
+        match self.count.load(Ordering::Relaxed) {
+            0 => LatencyStats::default(),
+            count => LatencyStats {
+                count,
+                total: Duration::from_nanos(self.total_nanos.load(Ordering::Relaxed)),
+                min: Duration::from_nanos(self.min_nanos.load(Ordering::Relaxed)),
+                max: Duration::from_nanos(self.max_nanos.load(Ordering::Relaxed)),
+            },
+        }
+    }
+}
+
+impl Default for LatencyRecorder {
+    fn default() -> Self {
+        Self {
+            count: AtomicU64::new(0),
+            total_nanos: AtomicU64::new(0),
+            min_nanos: AtomicU64::new(u64::MAX),
+            max_nanos: AtomicU64::new(0),
+        }
+    }
+}
+
+#[must_use]
+fn nanos(duration: Duration) -> u64 {
+    u64::try_from(duration.as_nanos()).unwrap_or(u64::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{prelude::*, test_utils::*};
+
+    #[test]
+    fn logger_stats() {
+        let slow_sink = Arc::new(TestSink::with_delay(Some(Duration::from_millis(5))));
+        let string_sink = Arc::new(StringSink::new());
+        let logger = build_test_logger(|b| b.sink(slow_sink).sink(string_sink).timing(true));
+
+        info!(logger: logger, "hello");
+        info!(logger: logger, "world");
+        logger.flush();
+
+        let stats = logger.stats().unwrap();
+        let [slow, string] = [stats.sinks()[0], stats.sinks()[1]];
+        assert_eq!(slow.write().count(), 2);
+        assert!(slow.write().min() >= Duration::from_millis(5));
+        assert!(slow.write().mean() >= slow.write().min());
+        assert_eq!(slow.flush().count(), 1);
+        assert_eq!(string.format().count(), 2);
+        assert_eq!(string.write().count(), 2);
+
+        let logger = build_test_logger(|b| b);
+        assert!(logger.stats().is_none());
+    }
+}
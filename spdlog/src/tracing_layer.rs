@@ -0,0 +1,304 @@
+use std::{
+    borrow::Cow,
+    fmt::{self, Write},
+};
+
+use tracing::{
+    field::{Field, Visit},
+    span::{Attributes, Id, Record as SpanRecord},
+    Event, Subscriber,
+};
+use tracing_subscriber::{
+    layer::{Context, Layer},
+    registry::LookupSpan,
+};
+
+use crate::{default_logger, sync::*, Level, Logger, Record, SourceLocation};
+
+/// A [`tracing_subscriber::Layer`] forwarding events from [tracing crate] to
+/// `spdlog-rs`.
+///
+/// Events are converted to records and passed to the global default logger,
+/// or the logger given by [`TracingLayer::with_logger`]. Fields of events are
+/// attached to records as structured key-value fields (see
+/// [`Record::key_values`]), string values as they are and other values
+/// formatted with [`Debug`].
+///
+/// [`Debug`]: std::fmt::Debug
+///
+/// Events inside spans are prefixed with the span context, i.e. the names and
+/// fields of the spans from the root, in the form of
+/// `outer{key=value}:inner: payload`.
+///
+/// This layer does not filter events by itself, it only filters them with the
+/// level filter of the target logger when forwarding, so that other layers
+/// are not affected.
+///
+/// ## Examples
+///
+/// ```
+/// use spdlog::{
+///     re_export::tracing_subscriber::{self, prelude::*},
+///     TracingLayer,
+/// };
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let subscriber = tracing_subscriber::registry().with(TracingLayer::new());
+/// tracing::subscriber::set_global_default(subscriber)?;
+///
+/// tracing::info!(user_id = 42, "login ok");
+/// # Ok(()) }
+/// ```
+///
+/// [tracing crate]: https://crates.io/crates/tracing
+#[derive(Default)]
+pub struct TracingLayer {
+    logger: Option<Arc<Logger>>,
+}
+
+impl TracingLayer {
+    /// Constructs a `TracingLayer` forwarding events to the global default
+    /// logger.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Constructs a `TracingLayer` forwarding events to the given logger.
+    #[must_use]
+    pub fn with_logger(logger: Arc<Logger>) -> Self {
+        Self {
+            logger: Some(logger),
+        }
+    }
+
+    #[must_use]
+    fn logger(&self) -> Arc<Logger> {
+        self.logger.clone().unwrap_or_else(default_logger)
+    }
+}
+
+impl<S> Layer<S> for TracingLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        if let Some(span) = ctx.span(id) {
+            let mut visitor = PayloadVisitor::default();
+            attrs.record(&mut visitor);
+            span.extensions_mut()
+                .insert(SpanFields(visitor.into_payload()));
+        }
+    }
+
+    fn on_record(&self, id: &Id, values: &SpanRecord<'_>, ctx: Context<'_, S>) {
+        if let Some(span) = ctx.span(id) {
+            let mut extensions = span.extensions_mut();
+            if let Some(SpanFields(fields)) = extensions.get_mut::<SpanFields>() {
+                let mut visitor = PayloadVisitor {
+                    fields: std::mem::take(fields),
+                    ..PayloadVisitor::default()
+                };
+                values.record(&mut visitor);
+                *fields = visitor.into_payload();
+            }
+        }
+    }
+
+    fn on_event(&self, event: &Event<'_>, ctx: Context<'_, S>) {
+        let metadata = event.metadata();
+        let level = level_from_tracing(*metadata.level());
+
+        let logger = self.logger();
+        if !logger.should_log(level) {
+            return;
+        }
+
+        let mut visitor = EventVisitor::default();
+        event.record(&mut visitor);
+
+        let mut payload = String::new();
+        if let Some(scope) = ctx.event_scope(event) {
+            for span in scope.from_root() {
+                payload.push_str(span.name());
+                if let Some(SpanFields(fields)) = span.extensions().get::<SpanFields>() {
+                    if !fields.is_empty() {
+                        _ = write!(payload, "{{{}}}", fields);
+                    }
+                }
+                payload.push(':');
+            }
+            payload.push(' ');
+        }
+        payload.push_str(&visitor.message);
+
+        // If the logger has a name configured, use that name. Otherwise, the name can
+        // also be given by the target of the event.
+        let logger_name = logger.name().or_else(|| {
+            let target = metadata.target();
+            if target.is_empty() {
+                None
+            } else {
+                Some(target)
+            }
+        });
+
+        let mut record = Record::new(
+            level,
+            payload,
+            SourceLocation::from_tracing_metadata(metadata),
+            logger_name,
+        );
+        record.set_key_values(visitor.key_values);
+        logger.log(&record);
+    }
+}
+
+#[must_use]
+fn level_from_tracing(level: tracing::Level) -> Level {
+    match level {
+        tracing::Level::ERROR => Level::Error,
+        tracing::Level::WARN => Level::Warn,
+        tracing::Level::INFO => Level::Info,
+        tracing::Level::DEBUG => Level::Debug,
+        tracing::Level::TRACE => Level::Trace,
+    }
+}
+
+// Formatted fields of a span, stored in the extensions of the span.
+struct SpanFields(String);
+
+#[derive(Default)]
+struct PayloadVisitor {
+    message: String,
+    fields: String,
+}
+
+impl PayloadVisitor {
+    #[must_use]
+    fn into_payload(mut self) -> String {
+        if self.message.is_empty() {
+            self.fields
+        } else {
+            if !self.fields.is_empty() {
+                self.message.push(' ');
+                self.message.push_str(&self.fields);
+            }
+            self.message
+        }
+    }
+}
+
+impl Visit for PayloadVisitor {
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        // Writing to a `String` never fails.
+        if field.name() == "message" {
+            _ = write!(self.message, "{:?}", value);
+        } else {
+            if !self.fields.is_empty() {
+                self.fields.push(' ');
+            }
+            _ = write!(self.fields, "{}={:?}", field.name(), value);
+        }
+    }
+}
+
+#[derive(Default)]
+struct EventVisitor {
+    message: String,
+    key_values: Vec<(Cow<'static, str>, Cow<'static, str>)>,
+}
+
+impl Visit for EventVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "message" {
+            self.message.push_str(value);
+        } else {
+            self.key_values
+                .push((Cow::Borrowed(field.name()), Cow::Owned(value.to_string())));
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        if field.name() == "message" {
+            // Writing to a `String` never fails.
+            _ = write!(self.message, "{:?}", value);
+        } else {
+            self.key_values.push((
+                Cow::Borrowed(field.name()),
+                Cow::Owned(format!("{:?}", value)),
+            ));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tracing_subscriber::prelude::*;
+
+    use super::*;
+    use crate::{test_utils::*, LevelFilter};
+
+    #[test]
+    fn forward_events() {
+        let sink = Arc::new(TestSink::new());
+        let logger = Arc::new(build_test_logger(|b| {
+            b.sink(sink.clone())
+                .level_filter(LevelFilter::MoreSevereEqual(Level::Debug))
+        }));
+        let subscriber = tracing_subscriber::registry().with(TracingLayer::with_logger(logger));
+
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::info!("hello");
+            tracing::warn!(user_id = 42, name = "spdlog", "login {}", "ok");
+            tracing::error!(target: "app::db", code = 1);
+            tracing::trace!("filtered");
+        });
+
+        assert_eq!(sink.payloads(), ["hello", "login ok", ""]);
+
+        let records = sink.records();
+        assert_eq!(records[0].key_values().count(), 0);
+        assert_eq!(
+            records[1].key_values().collect::<Vec<_>>(),
+            [("user_id", "42"), ("name", "spdlog")]
+        );
+        assert_eq!(records[2].key_values().collect::<Vec<_>>(), [("code", "1")]);
+        assert_eq!(records[0].level(), Level::Info);
+        assert_eq!(records[1].level(), Level::Warn);
+        assert_eq!(records[2].level(), Level::Error);
+        assert_eq!(records[2].logger_name(), Some("app::db"));
+        assert_eq!(
+            records[0].source_location().map(|srcloc| srcloc.file()),
+            Some(file!())
+        );
+    }
+
+    #[test]
+    fn span_context() {
+        let sink = Arc::new(TestSink::new());
+        let logger = Arc::new(build_test_logger(|b| b.sink(sink.clone())));
+        let subscriber = tracing_subscriber::registry().with(TracingLayer::with_logger(logger));
+
+        tracing::subscriber::with_default(subscriber, || {
+            let request = tracing::info_span!("request", id = 7, user = tracing::field::Empty);
+            let _request = request.enter();
+            request.record("user", "alice");
+            tracing::info_span!("db").in_scope(|| tracing::info!(rows = 3, "query"));
+            tracing::info!("done");
+        });
+        tracing::info!("outside");
+
+        assert_eq!(
+            sink.payloads(),
+            vec![
+                "request{id=7 user=\"alice\"}:db: query".to_string(),
+                "request{id=7 user=\"alice\"}: done".to_string(),
+            ]
+        );
+        assert_eq!(
+            sink.records()[0].key_values().collect::<Vec<_>>(),
+            [("rows", "3")]
+        );
+    }
+}
@@ -1,14 +1,30 @@
 use std::{
+    collections::hash_map::RandomState,
     fs::{self, File, OpenOptions},
+    hash::{BuildHasher, Hasher},
     path::Path,
 };
 
 use crate::{Error, Result};
 
 pub fn open_file(path: impl AsRef<Path>, truncate: bool) -> Result<File> {
-    if let Some(parent) = path.as_ref().parent() {
-        if !parent.exists() {
-            fs::create_dir_all(parent).map_err(Error::CreateDirectory)?;
+    open_file_with(path, truncate, true, |_| {})
+}
+
+// Opens a file like `open_file`, optionally without creating missing parent
+// directories, and with extra options set by `configure`, which can override
+// the default ones.
+pub fn open_file_with(
+    path: impl AsRef<Path>,
+    truncate: bool,
+    create_dirs: bool,
+    configure: impl FnOnce(&mut OpenOptions),
+) -> Result<File> {
+    if create_dirs {
+        if let Some(parent) = path.as_ref().parent() {
+            if !parent.exists() {
+                fs::create_dir_all(parent).map_err(Error::CreateDirectory)?;
+            }
         }
     }
 
@@ -19,11 +35,18 @@ pub fn open_file(path: impl AsRef<Path>, truncate: bool) -> Result<File> {
     } else {
         open_options.append(true);
     }
+    open_options.create(true);
+    configure(&mut open_options);
+
+    open_options.open(path).map_err(Error::OpenFile)
+}
 
-    open_options
-        .create(true)
-        .open(path)
-        .map_err(Error::OpenFile)
+// Returns a pseudo-random number, good enough for spreading events over time,
+// but not for anything security related.
+#[must_use]
+pub fn random_u64() -> u64 {
+    // Each `RandomState` is seeded with different keys.
+    RandomState::new().build_hasher().finish()
 }
 
 // Credits `static_assertions` crate
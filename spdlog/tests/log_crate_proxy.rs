@@ -11,6 +11,7 @@ include!(concat!(
 ));
 use test_utils::*;
 
+#[allow(clippy::incompatible_msrv)] // MSRV does not apply to tests
 static GLOBAL_LOG_CRATE_PROXY_MUTEX: Mutex<()> = Mutex::new(());
 
 #[cfg(feature = "log")]
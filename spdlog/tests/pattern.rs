@@ -6,15 +6,18 @@ use std::{
 
 use cfg_if::cfg_if;
 use regex::Regex;
-#[cfg(feature = "runtime-pattern")]
-use spdlog::formatter::runtime_pattern;
 use spdlog::{
     error,
-    formatter::{pattern, Formatter, FormatterContext, Pattern, PatternFormatter},
+    formatter::{
+        pattern, FieldQuoting, FieldsStyle, Formatter, FormatterContext, MultiLine, Pattern,
+        PatternFormatter, StaticFields,
+    },
     prelude::*,
     sink::Sink,
-    Error, StringBuf, __EOL,
+    StringBuf, __EOL,
 };
+#[cfg(feature = "runtime-pattern")]
+use spdlog::{formatter::runtime_pattern, Error};
 
 include!(concat!(
     env!("OUT_DIR"),
@@ -50,6 +53,7 @@ fn test_builtin_formatters() {
 
 #[test]
 fn test_custom_formatters() {
+    #[cfg_attr(not(feature = "runtime-pattern"), allow(unused_mut))]
     let mut patterns = vec![Box::new(
         pattern!("{logger}: [{level}] hello {payload} - {$mock1} / {$mock2}",
             {$mock1} => MockPattern1::default,
@@ -91,6 +95,134 @@ fn test_style_range() {
     );
 }
 
+#[test]
+fn test_code_and_bytes() {
+    let sink = Arc::new(MockSink::new());
+    sink.set_formatter(Box::new(PatternFormatter::new(pattern!(
+        "[{code}] {payload} {bytes} {bytes_base64}"
+    ))));
+    let logger = Logger::builder().sink(sink.clone()).build().unwrap();
+
+    error!(logger: logger, code = "E1042", bytes = &[0x7e, 0xff, 0x00], "bad frame");
+    assert_eq!(
+        sink.get_last_msg().unwrap().0,
+        "[E1042] bad frame 7eff00 fv8A"
+    );
+
+    error!(logger: logger, "no frame");
+    assert_eq!(sink.get_last_msg().unwrap().0, "[] no frame  ");
+}
+
+#[test]
+fn test_seq() {
+    let sink = Arc::new(MockSink::new());
+    sink.set_formatter(Box::new(PatternFormatter::new(pattern!("{seq} {payload}"))));
+    let logger = Logger::builder().sink(sink.clone()).build().unwrap();
+
+    info!(logger: logger, "first");
+    assert_eq!(sink.get_last_msg().unwrap().0, "0 first");
+    debug!(logger: logger, "filtered out by the logger");
+    info!(logger: logger, "second");
+    assert_eq!(sink.get_last_msg().unwrap().0, "1 second");
+
+    let forked = Arc::new(logger).fork_with_name(Some("forked")).unwrap();
+    info!(logger: forked, "forked");
+    assert_eq!(sink.get_last_msg().unwrap().0, "0 forked");
+}
+
+#[test]
+fn test_multi_line() {
+    let sink = Arc::new(MockSink::new());
+    let logger = Logger::builder().sink(sink.clone()).build().unwrap();
+
+    sink.set_formatter(Box::new(
+        PatternFormatter::new(pattern!("[{level}] {payload}")).multi_line(MultiLine::prefix("> ")),
+    ));
+    info!(logger: logger, "first\nsecond\nthird");
+    assert_eq!(
+        sink.get_last_msg().unwrap().0,
+        "[info] first\n> second\n> third"
+    );
+
+    sink.set_formatter(Box::new(
+        PatternFormatter::new(pattern!("[{level}] {payload}")).multi_line(MultiLine::Escape),
+    ));
+    info!(logger: logger, "first\nsecond");
+    assert_eq!(sink.get_last_msg().unwrap().0, "[info] first\\nsecond");
+}
+
+#[test]
+fn test_static_fields() {
+    let sink = Arc::new(MockSink::new());
+    let fields = StaticFields::new()
+        .field("pod", "web-1")
+        .field("note", "a b");
+    sink.set_formatter(Box::new(
+        PatternFormatter::new(pattern!("[{level}] {payload} {fields}")).static_fields(fields),
+    ));
+    let logger = Arc::new(Logger::builder().sink(sink.clone()).build().unwrap());
+
+    info!(logger: logger, "msg");
+    assert_eq!(
+        sink.get_last_msg().unwrap().0,
+        r#"[info] msg pod=web-1 note="a b""#
+    );
+
+    sink.set_formatter(Box::new(
+        PatternFormatter::new(pattern!("[{level}] {payload} {{{fields}}}"))
+            .static_fields(StaticFields::new().field("pod", "web-1").field("az", "b"))
+            .fields_style(
+                FieldsStyle::new()
+                    .separator(", ")
+                    .key_value_separator(": ")
+                    .quoting(FieldQuoting::Always)
+                    .exclude("az"),
+            ),
+    ));
+    info!(logger: logger, "msg");
+    assert_eq!(
+        sink.get_last_msg().unwrap().0,
+        r#"[info] msg {pod: "web-1"}"#
+    );
+}
+
+#[test]
+fn test_thread_name() {
+    let sink = Arc::new(MockSink::new());
+    sink.set_formatter(Box::new(PatternFormatter::new(pattern!(
+        "[{thread_name}] {payload}"
+    ))));
+    let logger = Arc::new(Logger::builder().sink(sink.clone()).build().unwrap());
+
+    let log_on_thread = |builder: std::thread::Builder, set_name: Option<&'static str>| {
+        let logger = logger.clone();
+        builder
+            .spawn(move || {
+                if let Some(name) = set_name {
+                    spdlog::set_thread_name(name);
+                }
+                info!(logger: logger, "msg");
+            })
+            .unwrap()
+            .join()
+            .unwrap();
+        sink.get_last_msg().unwrap().0
+    };
+
+    assert_eq!(log_on_thread(std::thread::Builder::new(), None), "[] msg");
+    assert_eq!(
+        log_on_thread(std::thread::Builder::new().name("std-name".into()), None),
+        "[std-name] msg"
+    );
+    assert_eq!(
+        log_on_thread(
+            std::thread::Builder::new().name("std-name".into()),
+            Some("worker-3")
+        ),
+        "[worker-3] msg"
+    );
+}
+
 #[track_caller]
 fn test_pattern_inner<P, F>(pat: P, expect_formatted: F, expect_style_range: Option<Range<usize>>)
 where
@@ -406,6 +538,24 @@ fn test_builtin_patterns() {
         Some(["{begin_sign}00:00"]),
         vec![HOUR_RANGE, MINUTE_RANGE],
     );
+    check!(
+        "{rfc3339}",
+        Some([
+            "0000-00-00T00:00:00.000+00:00",
+            "0000-00-00T00:00:00.000-00:00"
+        ]),
+        vec![
+            YEAR_RANGE,
+            MONTH_RANGE,
+            DAY_RANGE,
+            HOUR_RANGE,
+            MINUTE_RANGE,
+            SECOND_RANGE,
+            MILLISECOND_RANGE,
+            HOUR_RANGE,
+            MINUTE_RANGE,
+        ],
+    );
     check!(
         "{unix_timestamp}",
         None as Option<Vec<&str>>,
@@ -466,8 +616,23 @@ fn test_builtin_patterns() {
     }
     check!("{logger}", Some(["logger-name"]), vec![]);
     check!("{payload}", Some(["test payload"]), vec![]);
+    check!("{code}", Some([""]), vec![]);
+    check!("{bytes}", Some([""]), vec![]);
+    check!("{bytes_base64}", Some([""]), vec![]);
+    check!("{seq}", None as Option<Vec<&str>>, vec![0..=u64::MAX]);
+    check!(
+        "{global_seq}",
+        None as Option<Vec<&str>>,
+        vec![0..=u64::MAX]
+    );
     check!("{pid}", None as Option<Vec<&str>>, vec![OS_ID_RANGE]);
     check!("{tid}", None as Option<Vec<&str>>, vec![OS_ID_RANGE]);
+    check!(
+        "{thread_name}",
+        Some(["test_builtin_patterns", "main"]),
+        vec![],
+    );
+    check!("{fields}", Some([""]), vec![]);
     check!("{eol}", Some(["{eol}"]), vec![]);
 }
 
@@ -0,0 +1,57 @@
+// Checks that logging a record to a sink performs no heap allocation in the
+// steady state, since formatting buffers are reused.
+
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+};
+
+use spdlog::{prelude::*, sink::WriteSink};
+
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+fn main() {
+    // Emitting metrics allocates labels for each record.
+    if cfg!(feature = "metrics") {
+        return;
+    }
+
+    let sink = Arc::new(
+        WriteSink::builder()
+            .target(std::io::sink())
+            .build()
+            .unwrap(),
+    );
+    let logger = Logger::builder().sink(sink).build().unwrap();
+
+    // Warms up the reused buffers and caches.
+    for _ in 0..16 {
+        info!(logger: logger, "hello zero allocation {}", 42);
+    }
+
+    let before = ALLOCATIONS.load(Ordering::Relaxed);
+    for _ in 0..1000 {
+        info!(logger: logger, "hello zero allocation {}", 42);
+    }
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed) - before;
+    assert_eq!(allocations, 0);
+}